@@ -55,3 +55,28 @@ class SwiftMethodCallbackTester {
         callback(5)
     }
 }
+
+func swift_calls_rust_fn_callback_three_times(arg: (UInt8) -> UInt8) -> UInt8 {
+    arg(1) + arg(2) + arg(3)
+}
+
+func swift_calls_rust_fnmut_callback_three_times(arg: () -> UInt32) -> UInt32 {
+    let _ = arg()
+    let _ = arg()
+    return arg()
+}
+
+var storedRustFnCallback: (() -> ())? = nil
+
+func swift_stores_rust_fn_callback(arg: @escaping () -> ()) {
+    storedRustFnCallback = arg
+}
+
+func swift_calls_stored_rust_fn_callback() {
+    storedRustFnCallback!()
+}
+
+/// Releasing the last reference to the callback frees the Rust closure.
+func swift_drops_stored_rust_fn_callback() {
+    storedRustFnCallback = nil
+}
@@ -0,0 +1,14 @@
+import Foundation
+
+func swift_arg_hash_map_u8_u16(map: RustMap<UInt8, UInt16>) {
+    assert(map.len() == 2)
+    assert(map.get(key: 1) == 10)
+    assert(map.get(key: 2) == 20)
+}
+
+func swift_return_hash_map_u8_u16() -> RustMap<UInt8, UInt16> {
+    let map = RustMap<UInt8, UInt16>(vtable: .hashMap)
+    map.insert(key: 1, value: 10)
+    map.insert(key: 2, value: 20)
+    return map
+}
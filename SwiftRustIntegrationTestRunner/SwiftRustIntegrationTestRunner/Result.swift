@@ -5,6 +5,8 @@
 //  Created by Frankie Nwafili on 9/20/22.
 //
 
+import Foundation
+
 func swift_func_takes_callback_with_result_arg(
     arg: (RustResult<CallbackTestOpaqueRustType, String>) -> ()
 ) {
@@ -23,6 +25,32 @@ public class ResultTestOpaqueSwiftType {
     }
 }
 
+struct SwiftThrowingFunctionError: LocalizedError {
+    var errorDescription: String? {
+        "Swift function failed"
+    }
+}
+
+func swift_func_throws_string(succeed: Bool) throws -> String {
+    if succeed {
+        return "Success"
+    }
+    throw SwiftThrowingFunctionError()
+}
+
+func swift_func_throws_u32(succeed: Bool) throws -> UInt32 {
+    if succeed {
+        return 123
+    }
+    throw SwiftThrowingFunctionError()
+}
+
+func swift_func_throws_swift_error(succeed: Bool) throws {
+    if !succeed {
+        throw SwiftThrowingFunctionError()
+    }
+}
+
 extension ResultTestOpaqueRustType: @unchecked Sendable {}
 extension ResultTestOpaqueRustType: Error {}
 
@@ -40,3 +68,6 @@ extension SameEnum: Error {}
 
 extension AsyncResultErrEnum: @unchecked Sendable {}
 extension AsyncResultErrEnum: Error {}
+
+extension CatchPanicsError: @unchecked Sendable {}
+extension CatchPanicsError: Error {}
@@ -0,0 +1,9 @@
+import Foundation
+
+func swift_arg_hash_set_u8(set: RustSet<UInt8>) {
+    assert(set.toSet() == [1, 2, 3])
+}
+
+func swift_return_hash_set_u8() -> RustSet<UInt8> {
+    RustSet([1, 2, 3], vtable: .hashSet)
+}
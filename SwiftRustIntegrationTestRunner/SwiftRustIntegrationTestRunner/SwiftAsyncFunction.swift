@@ -0,0 +1,47 @@
+//
+//  SwiftAsyncFunction.swift
+//  SwiftRustIntegrationTestRunner
+//
+
+import Foundation
+
+func swift_async_return_null() async {
+    await Task.yield()
+}
+
+func swift_async_reflect_u8(arg: UInt8) async -> UInt8 {
+    await Task.yield()
+    return arg
+}
+
+func swift_async_reflect_str(arg: String) async -> String {
+    await Task.yield()
+    return arg
+}
+
+func swift_async_return_result(succeed: Bool) async -> RustResult<String, String> {
+    await Task.yield()
+    if succeed {
+        return .Ok("success")
+    } else {
+        return .Err("failure")
+    }
+}
+
+/// We expose this to the `swift_async_function.rs` test.
+public class SwiftAsyncCounter {
+    var count: UInt32 = 0
+    let lock = NSLock()
+
+    init() {}
+
+    func increment() async -> UInt32 {
+        await Task.yield()
+
+        lock.lock()
+        defer { lock.unlock() }
+
+        count += 1
+        return count
+    }
+}
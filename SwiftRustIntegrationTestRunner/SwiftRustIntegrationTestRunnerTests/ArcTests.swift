@@ -0,0 +1,59 @@
+//
+//  ArcTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for passing `Arc<SomeOpaqueRustType>` between Rust and Swift.
+class ArcTests: XCTestCase {
+
+    /// Verify that we can call `&self` methods on an `Arc` that was returned from Rust.
+    func testCallMethodsOnArc() throws {
+        let counter = make_arc_counter()
+
+        XCTAssertEqual(counter.increment(), 1)
+        XCTAssertEqual(counter.increment(), 2)
+        XCTAssertEqual(counter.value(), 2)
+        XCTAssertEqual(arc_counter_value_by_ref(counter), 2)
+    }
+
+    /// Verify that passing an `Arc` to Rust clones it instead of transferring ownership.
+    func testPassingArcToRustClonesIt() throws {
+        let counter = make_arc_counter()
+
+        // Swift's reference and the clone that Rust received.
+        XCTAssertEqual(arc_counter_strong_count(counter), 2)
+        XCTAssertEqual(arc_counter_strong_count(counter), 2)
+    }
+
+    /// Verify that Rust and Swift can share the same value, and that Swift's `deinit`
+    /// decrements the strong count.
+    func testArcIsSharedBetweenRustAndSwift() throws {
+        let counter = make_arc_counter()
+        var holder: ArcCounterHolder? = ArcCounterHolder(counter)
+
+        XCTAssertEqual(arc_counter_strong_count(counter), 3)
+
+        holder!.increment_from_rust()
+        XCTAssertEqual(counter.value(), 1)
+
+        do {
+            let sameCounter = holder!.counter()
+            XCTAssertEqual(sameCounter.increment(), 2)
+            XCTAssertEqual(arc_counter_strong_count(counter), 4)
+        }
+        XCTAssertEqual(arc_counter_strong_count(counter), 3)
+
+        holder = nil
+        XCTAssertEqual(arc_counter_strong_count(counter), 2)
+        XCTAssertEqual(counter.value(), 2)
+    }
+
+    /// Verify that we can return an `Option<Arc<T>>` from Rust.
+    func testOptionalArc() throws {
+        XCTAssertEqual(make_optional_arc_counter(true)!.value(), 0)
+        XCTAssertNil(make_optional_arc_counter(false))
+    }
+}
@@ -18,4 +18,19 @@ final class ArgumentAttributesTest: XCTestCase {
         XCTAssertEqual(test_argument_label(someArg: 10, 100), 110)
     }
 
+    /// Verify that the `swift_bridge(default = ...)` attribute works.
+    func testArgumentDefault() throws {
+        XCTAssertEqual(test_argument_default(2), 20)
+        XCTAssertEqual(test_argument_default(2, 3), 6)
+        XCTAssertEqual(test_argument_default(2, offset: 5), 25)
+        XCTAssertEqual(test_argument_default(2, 3, offset: 5), 11)
+    }
+
+    /// Verify that string literal defaults work for `&str` and `String` arguments.
+    func testArgumentDefaultString() throws {
+        XCTAssertEqual(test_argument_default_string().toString(), "Hello, world")
+        XCTAssertEqual(test_argument_default_string("Hi").toString(), "Hi, world")
+        XCTAssertEqual(test_argument_default_string("Hi", "there").toString(), "Hi, there")
+    }
+
 }
@@ -0,0 +1,77 @@
+//
+//  AsyncStreamTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for Rust streams that are consumed in Swift as an `AsyncThrowingStream`.
+class AsyncStreamTests: XCTestCase {
+
+    /// Verify that we can iterate over every item of a Rust stream.
+    func testIterateRustStream() async throws {
+        var items: [UInt8] = []
+        for try await item in rust_stream_count_to(3) {
+            items.append(item)
+        }
+
+        XCTAssertEqual(items, [1, 2, 3])
+    }
+
+    /// Verify that we can iterate over a Rust stream of Strings.
+    func testIterateRustStreamOfStrings() async throws {
+        var items: [String] = []
+        for try await item in rust_stream_strings() {
+            items.append(item.toString())
+        }
+
+        XCTAssertEqual(items, ["hello", "world"])
+    }
+
+    /// Verify that we can iterate over a stream returned by a Rust method.
+    func testIterateRustStreamFromMethod() async throws {
+        let source = RustStreamSource(2)
+
+        var items: [UInt8] = []
+        for try await item in source.items() {
+            items.append(item)
+        }
+
+        XCTAssertEqual(items, [1, 2])
+    }
+
+    /// Verify that the Rust stream gets dropped when Swift stops iterating over it.
+    func testRustStreamDroppedWhenIterationEnds() async throws {
+        do {
+            var items: [UInt32] = []
+            for try await item in rust_stream_endless() {
+                items.append(item)
+                if items.count == 3 {
+                    break
+                }
+            }
+
+            XCTAssertEqual(items, [1, 2, 3])
+        }
+
+        XCTAssertTrue(rust_stream_endless_was_dropped())
+    }
+
+    /// Verify that cancelling the Swift Task that is iterating over a Rust stream stops waiting
+    /// for the next item.
+    func testCancelRustStreamIteration() async throws {
+        let task = Task {
+            for try await _ in rust_stream_pending() {
+                XCTFail()
+            }
+        }
+        task.cancel()
+
+        do {
+            try await task.value
+            XCTFail()
+        } catch is CancellationError {
+        }
+    }
+}
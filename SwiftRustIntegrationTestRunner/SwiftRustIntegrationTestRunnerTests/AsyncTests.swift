@@ -17,18 +17,18 @@ class AsyncTests: XCTestCase {
     }
 
     func testSwiftCallsRustAsyncFn() async throws {
-        await rust_async_return_null()
+        try await rust_async_return_null()
     }
    
     /// Verify that we can pass and return a u8 to an async Rust function
     func testSwiftCallsRustAsyncFnReflectU8() async throws {
-        let num = await rust_async_reflect_u8(123)
+        let num = try await rust_async_reflect_u8(123)
         XCTAssertEqual(num, 123)
     }
     
      /// Verify that we can pass and return a String to an async Rust function
     func testSwiftCallsRustAsyncFnReflectString() async throws {
-        let string = await rust_async_reflect_string("hello world")
+        let string = try await rust_async_reflect_string("hello world")
         XCTAssertEqual(string.toString(), "hello world")
     }
     
@@ -36,7 +36,7 @@ class AsyncTests: XCTestCase {
     func testSwiftCallsRustAsyncMethodReflectU16() async throws {
         let test = TestRustAsyncSelf()
 
-        let num = await test.reflect_u16(567)
+        let num = try await test.reflect_u16(567)
         XCTAssertEqual(num, 567)
     }
 
@@ -46,14 +46,14 @@ class AsyncTests: XCTestCase {
         
         // Should return an AsyncResultOpaqueRustType1 type.
         do {
-            let _ = try await rust_async_func_reflect_result_opaque_rust(.Ok(AsyncResultOpaqueRustType1(10)))
+            let _ = try try await rust_async_func_reflect_result_opaque_rust(.Ok(AsyncResultOpaqueRustType1(10)))
         } catch {
             XCTFail()
         }
         
         // Should throw an AsyncResultOpaqueRustType2 type that conforms to Error protocol.
         do {
-            let _ = try await rust_async_func_reflect_result_opaque_rust(.Err(AsyncResultOpaqueRustType2(100)))
+            let _ = try try await rust_async_func_reflect_result_opaque_rust(.Err(AsyncResultOpaqueRustType2(100)))
             XCTFail()
         } catch let error as AsyncResultOpaqueRustType2 {
             XCTAssertEqual(error.val(), 100)
@@ -67,7 +67,7 @@ class AsyncTests: XCTestCase {
         
         //Should return an AsyncResultOkEnum
         do {
-            let value: AsyncResultOkEnum = try await rust_async_func_return_result_transparent_enum_and_transparent_enum(true)
+            let value: AsyncResultOkEnum = try try await rust_async_func_return_result_transparent_enum_and_transparent_enum(true)
             switch value {
             case .NoFields:
                 XCTFail()
@@ -83,7 +83,7 @@ class AsyncTests: XCTestCase {
         
         //Should throw an AsyncResultErrEnum
         do {
-            let _ = try await rust_async_func_return_result_transparent_enum_and_transparent_enum(false)
+            let _ = try try await rust_async_func_return_result_transparent_enum_and_transparent_enum(false)
             XCTFail()
         } catch let error as AsyncResultErrEnum {
             switch error {
@@ -101,7 +101,7 @@ class AsyncTests: XCTestCase {
     func testSwiftCallsRustAsyncFnReturnResultOpaqueRustTransparentEnum() async throws {
         //Should return an AsyncResultOpaqueRustType1
         do {
-            let value: AsyncResultOpaqueRustType1 = try await rust_async_func_return_result_opaque_rust_and_transparent_enum(true)
+            let value: AsyncResultOpaqueRustType1 = try try await rust_async_func_return_result_opaque_rust_and_transparent_enum(true)
             XCTAssertEqual(value.val(), 10)
         } catch {
             XCTFail()
@@ -109,7 +109,7 @@ class AsyncTests: XCTestCase {
         
         //Should throw an AsyncResultErrEnum
         do {
-            let _: AsyncResultOpaqueRustType1 = try await rust_async_func_return_result_opaque_rust_and_transparent_enum(false)
+            let _: AsyncResultOpaqueRustType1 = try try await rust_async_func_return_result_opaque_rust_and_transparent_enum(false)
         } catch let error as AsyncResultErrEnum {
             switch error {
             case .UnnamedFields(_, _):
@@ -126,7 +126,7 @@ class AsyncTests: XCTestCase {
     func testSwiftCallsRustAsyncFnReturnResultTransparentEnumOpaqueRust() async throws {
         //Should return an AsyncResultOkEnum
         do {
-            let value: AsyncResultOkEnum = try await rust_async_func_return_result_transparent_enum_and_opaque_rust(true)
+            let value: AsyncResultOkEnum = try try await rust_async_func_return_result_transparent_enum_and_opaque_rust(true)
             switch value {
             case .NoFields:
                 break
@@ -141,7 +141,7 @@ class AsyncTests: XCTestCase {
         
         //Should throw an AsyncResultOpaqueRustType1
         do {
-            let _ = try await rust_async_func_return_result_transparent_enum_and_opaque_rust(false)
+            let _ = try try await rust_async_func_return_result_transparent_enum_and_opaque_rust(false)
             XCTFail()
         } catch let error as AsyncResultOpaqueRustType1 {
             XCTAssertEqual(error.val(), 1000)
@@ -155,14 +155,14 @@ class AsyncTests: XCTestCase {
     func testSwiftCallsRustAsyncFnReturnResultNullTransparentEnum() async throws {
         //Should return an Unit type
         do {
-            let _: () = try await rust_async_func_return_result_null_and_transparent_enum(true)
+            let _: () = try try await rust_async_func_return_result_null_and_transparent_enum(true)
         } catch {
             XCTFail()
         }
         
         //Should throw an AsyncResultErrEnum
         do {
-            let _ = try await rust_async_func_return_result_null_and_transparent_enum(false)
+            let _ = try try await rust_async_func_return_result_null_and_transparent_enum(false)
             XCTFail()
         } catch let error as AsyncResultErrEnum {
             switch error {
@@ -178,18 +178,82 @@ class AsyncTests: XCTestCase {
     }
     
     func testSwiftCallsRustAsyncFnRetStruct() async throws {
-        let _: AsyncRustFnReturnStruct = await rust_async_return_struct()
+        let _: AsyncRustFnReturnStruct = try await rust_async_return_struct()
     }
     
     func testSwiftCallsRustAsyncFnReturnResultNullOpaqueRust() async throws {
-        try await rust_async_func_return_result_null_opaque_rust(true)
+        try try await rust_async_func_return_result_null_opaque_rust(true)
         
         do {
-            try await rust_async_func_return_result_null_opaque_rust(false)
+            try try await rust_async_func_return_result_null_opaque_rust(false)
             XCTFail()
         } catch let error as AsyncResultOpaqueRustType2 {
             XCTAssertEqual(error.val(), 111)
         }
     }
-}
+    
+    /// Verify that we can call a `#[swift_bridge(local)]` async Rust function whose future is not
+    /// `Send`.
+    func testSwiftCallsRustLocalAsyncFn() async throws {
+        let string = try await rust_local_async_reflect_string("hello world")
+        XCTAssertEqual(string.toString(), "hello world")
+    }
+
+    /// Verify that we can call a `#[swift_bridge(local)]` async Rust method on a type that holds
+    /// `Rc<RefCell<_>>` state.
+    func testSwiftCallsRustLocalAsyncMethod() async throws {
+        let counter = AsyncRustLocalCounter()
+
+        XCTAssertEqual(try await counter.increment(2), 2)
+        XCTAssertEqual(try await counter.increment(3), 5)
+    }
 
+    /// Verify that cancelling the Swift Task that awaits an async Rust function drops the Rust
+    /// future and throws a CancellationError.
+    func testSwiftCallsRustAsyncFnCancelled() async throws {
+        let task = Task {
+            try await rust_async_pending_until_cancelled()
+        }
+        task.cancel()
+
+        do {
+            try await task.value
+            XCTFail()
+        } catch is CancellationError {
+        }
+
+        XCTAssertTrue(rust_async_pending_future_was_dropped())
+    }
+    
+    /// Verify that Rust can await an async Swift function that doesn't return anything.
+    func testRustCallsSwiftAsyncFnReturnNull() async throws {
+        try await rust_calls_swift_async_return_null()
+    }
+    
+    /// Verify that Rust can pass and receive a u8 from an async Swift function.
+    func testRustCallsSwiftAsyncFnReflectU8() async throws {
+        let num = try await rust_calls_swift_async_reflect_u8(123)
+        XCTAssertEqual(num, 123)
+    }
+    
+    /// Verify that Rust can pass a &str to an async Swift function and receive a String.
+    func testRustCallsSwiftAsyncFnReflectStr() async throws {
+        let string = try await rust_calls_swift_async_reflect_str("hello world")
+        XCTAssertEqual(string.toString(), "hello world")
+    }
+    
+    /// Verify that Rust can receive a Result from an async Swift function.
+    func testRustCallsSwiftAsyncFnReturnResult() async throws {
+        let ok = try await rust_calls_swift_async_return_result(true)
+        XCTAssertEqual(ok.toString(), "ok: success")
+        
+        let err = try await rust_calls_swift_async_return_result(false)
+        XCTAssertEqual(err.toString(), "err: failure")
+    }
+    
+    /// Verify that Rust can call async methods on a Swift class.
+    func testRustCallsSwiftAsyncMethod() async throws {
+        let sum = try await rust_calls_swift_async_method()
+        XCTAssertEqual(sum, 3)
+    }
+}
@@ -14,4 +14,54 @@ class CallbackTests: XCTestCase {
     func testRustCallsSwift() throws {
         test_callbacks_rust_calls_swift()
     }
+    
+    /// Verify that we can pass closures from Swift to Rust.
+    func testSwiftCallsRust() throws {
+        var called = false
+        rust_takes_callback_fnonce_no_args_no_return({ called = true })
+        XCTAssert(called)
+        
+        rust_takes_callback_fnonce_primitive({ num in num * 2 })
+        
+        rust_takes_callback_fnonce_opaque_rust({ rustTy in
+            rustTy.double()
+            return rustTy
+        })
+        
+        var twoParams: (Int16, UInt32)? = nil
+        rust_takes_callback_fnonce_two_params({ num, rustTy in twoParams = (num, rustTy.val()) })
+        XCTAssertEqual(twoParams?.0, 123)
+        XCTAssertEqual(twoParams?.1, 222)
+        
+        var callCount = 0
+        rust_takes_two_callbacks_fnonce_noop({ callCount += 1 }, { callCount += 1 })
+        XCTAssertEqual(callCount, 2)
+        
+        XCTAssertEqual(rust_calls_callback_fn_three_times({ num in num * 2 }), 2 + 4 + 6)
+    }
+    
+    /// Verify that Rust can hold on to a Swift closure, call it from any thread, and release
+    /// it once it gets dropped.
+    func testRustStoresSwiftClosure() throws {
+        class Counter {
+            var values: [UInt32] = []
+        }
+        
+        let notifier = CallbackTestChangeNotifier()
+        
+        weak var weakCounter: Counter?
+        do {
+            let counter = Counter()
+            weakCounter = counter
+            notifier.set_on_change({ value in counter.values.append(value) })
+        }
+        
+        notifier.notify_change(1)
+        notifier.notify_change_from_another_thread(2)
+        notifier.notify_change(3)
+        XCTAssertEqual(weakCounter?.values, [1, 2, 3])
+        
+        notifier.clear_on_change()
+        XCTAssertNil(weakCounter)
+    }
 }
@@ -0,0 +1,40 @@
+//
+//  CatchPanicsTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for bridge modules that use `#[swift_bridge::bridge(catch_panics)]`.
+class CatchPanicsTests: XCTestCase {
+    /// Verify that a panic in a Rust function that returns a Result gets thrown as a RustPanic.
+    func testRustFnResultPanicThrowsRustPanic() throws {
+        XCTAssertNoThrow(try rust_func_result_may_panic(false))
+
+        do {
+            try rust_func_result_may_panic(true)
+            XCTFail()
+        } catch let error as RustPanic {
+            XCTAssertEqual(error.message, "rust_func_result_may_panic panicked")
+        }
+    }
+
+    /// Verify that a panic in an async Rust function gets thrown as a RustPanic.
+    func testRustAsyncFnPanicThrowsRustPanic() async throws {
+        let value = try await rust_async_func_may_panic(false)
+        XCTAssertEqual(value, 123)
+
+        do {
+            let _ = try await rust_async_func_may_panic(true)
+            XCTFail()
+        } catch let error as RustPanic {
+            XCTAssertEqual(error.message, "rust_async_func_may_panic panicked")
+        }
+    }
+
+    /// Verify that functions that can't throw still work normally when catching panics.
+    func testRustFnCatchingPanicsReturnsValue() throws {
+        XCTAssertEqual(rust_func_reflect_u8_catching_panics(5), 5)
+    }
+}
@@ -0,0 +1,110 @@
+//
+//  CodableTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for the `Codable` conformances that we generate for shared types that derive serde's
+/// `Serialize` and `Deserialize`.
+///
+/// We encode values in Swift and decode them in Rust, and vice versa.
+///
+/// See crates/swift-integration-tests/src/serde_codable.rs
+class CodableTests: XCTestCase {
+    /// Verify that a struct round trips between Swift's JSONEncoder and Rust's serde_json.
+    func testCodableStruct() throws {
+        let value = CodableStruct(some_number: 123, text: "hello".intoRustString(), maybe_number: nil)
+
+        let fromRust = rust_codable_struct_from_json(try encodeToJson(value))
+        XCTAssertEqual(fromRust.some_number, 123)
+        XCTAssertEqual(fromRust.text.toString(), "hello")
+        XCTAssertNil(fromRust.maybe_number)
+
+        let json = rust_codable_struct_to_json(
+            CodableStruct(some_number: 5, text: "world".intoRustString(), maybe_number: 7)
+        ).toString()
+        XCTAssertEqual(json, #"{"someNumber":5,"label":"world","maybeNumber":7}"#)
+
+        let fromJson = try decodeFromJson(CodableStruct.self, json)
+        XCTAssertEqual(fromJson.some_number, 5)
+        XCTAssertEqual(fromJson.text.toString(), "world")
+        XCTAssertEqual(fromJson.maybe_number, 7)
+    }
+
+    /// Verify that an enum round trips using serde's externally tagged representation.
+    func testCodableExternallyTaggedEnum() throws {
+        let unitVariant = rust_codable_externally_tagged_enum_from_json(
+            try encodeToJson(CodableExternallyTaggedEnum.UnitVariant)
+        )
+        guard case .UnitVariant = unitVariant else {
+            return XCTFail()
+        }
+
+        let tupleVariant = rust_codable_externally_tagged_enum_from_json(
+            try encodeToJson(CodableExternallyTaggedEnum.TupleVariant(1, "two".intoRustString()))
+        )
+        guard case let .TupleVariant(first, second) = tupleVariant else {
+            return XCTFail()
+        }
+        XCTAssertEqual(first, 1)
+        XCTAssertEqual(second.toString(), "two")
+
+        let json = rust_codable_externally_tagged_enum_to_json(.StructVariant(field: -3)).toString()
+        XCTAssertEqual(json, #"{"struct_variant":{"renamed":-3}}"#)
+        guard case let .StructVariant(field) = try decodeFromJson(CodableExternallyTaggedEnum.self, json) else {
+            return XCTFail()
+        }
+        XCTAssertEqual(field, -3)
+
+        let newtypeJson = rust_codable_externally_tagged_enum_to_json(.NewtypeVariant(9)).toString()
+        guard case let .NewtypeVariant(value) = try decodeFromJson(CodableExternallyTaggedEnum.self, newtypeJson) else {
+            return XCTFail()
+        }
+        XCTAssertEqual(value, 9)
+    }
+
+    /// Verify that an enum round trips using serde's internally tagged representation.
+    func testCodableInternallyTaggedEnum() throws {
+        let fromRust = rust_codable_internally_tagged_enum_from_json(
+            try encodeToJson(CodableInternallyTaggedEnum.StructVariant(field: 4))
+        )
+        guard case let .StructVariant(field) = fromRust else {
+            return XCTFail()
+        }
+        XCTAssertEqual(field, 4)
+
+        let json = rust_codable_internally_tagged_enum_to_json(.UnitVariant).toString()
+        XCTAssertEqual(json, #"{"type":"UnitVariant"}"#)
+        guard case .UnitVariant = try decodeFromJson(CodableInternallyTaggedEnum.self, json) else {
+            return XCTFail()
+        }
+    }
+
+    /// Verify that an enum round trips using serde's adjacently tagged representation.
+    func testCodableAdjacentlyTaggedEnum() throws {
+        let fromRust = rust_codable_adjacently_tagged_enum_from_json(
+            try encodeToJson(CodableAdjacentlyTaggedEnum.NewtypeVariant("content".intoRustString()))
+        )
+        guard case let .NewtypeVariant(value) = fromRust else {
+            return XCTFail()
+        }
+        XCTAssertEqual(value.toString(), "content")
+
+        let json = rust_codable_adjacently_tagged_enum_to_json(.NewtypeVariant("hi".intoRustString())).toString()
+        XCTAssertEqual(json, #"{"t":"NewtypeVariant","c":"hi"}"#)
+        guard case let .NewtypeVariant(decoded) = try decodeFromJson(CodableAdjacentlyTaggedEnum.self, json) else {
+            return XCTFail()
+        }
+        XCTAssertEqual(decoded.toString(), "hi")
+    }
+
+    private func encodeToJson<T: Encodable>(_ value: T) throws -> String {
+        String(data: try JSONEncoder().encode(value), encoding: .utf8)!
+    }
+
+    private func decodeFromJson<T: Decodable>(_ type: T.Type, _ json: String) throws -> T {
+        try JSONDecoder().decode(type, from: json.data(using: .utf8)!)
+    }
+}
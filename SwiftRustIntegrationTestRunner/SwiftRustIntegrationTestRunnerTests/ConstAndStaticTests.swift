@@ -0,0 +1,26 @@
+//
+//  ConstAndStaticTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for `const` and `static` items in bridge modules.
+/// See crates/swift-integration-tests/src/const_and_static.rs
+class ConstAndStaticTests: XCTestCase {
+    /// Verify that primitive literals are available as Swift constants.
+    func testPrimitiveLiterals() throws {
+        XCTAssertEqual(CONST_MAX_ITEMS, 100)
+        XCTAssertEqual(CONST_MIN_OFFSET, -5)
+        XCTAssertEqual(CONST_RATIO, 0.25)
+        XCTAssertEqual(STATIC_ENABLED, true)
+    }
+
+    /// Verify that we read values that aren't primitive literals from Rust.
+    func testValuesReadFromRust() throws {
+        XCTAssertEqual(CONST_BUFFER_SIZE, 4096)
+        XCTAssertEqual(STATIC_VERSION.toString(), "1.2.3")
+        XCTAssertEqual(CONST_MAYBE_NUMBER, 7)
+    }
+}
@@ -0,0 +1,41 @@
+//
+//  FunctionAttributeSetTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests the #[swift_bridge(set(...))], #[swift_bridge(set_with(...))] and
+/// #[swift_bridge(get_mut(...))] attributes.
+class FunctionAttributeSetTests: XCTestCase {
+    /// Verify that the `swift_bridge(set(...))` attribute sets a field.
+    func testSet() throws {
+        let val = SomeTypeSet()
+
+        val.set_my_u8(5)
+        XCTAssertEqual(val.my_u8(), 5)
+
+        val.set_my_string("world")
+        XCTAssertEqual(val.my_string().toString(), "world")
+    }
+
+    /// Verify that the `swift_bridge(set_with(...))` attribute passes the value through a
+    /// function before setting the field.
+    func testSetWith() throws {
+        let val = SomeTypeSet()
+
+        val.set_my_u16_from_u8(5)
+        XCTAssertEqual(val.my_u16(), 10)
+    }
+
+    /// Verify that the `swift_bridge(get_mut(...))` attribute returns a mutable reference to a
+    /// field.
+    func testGetMut() throws {
+        let val = SomeTypeSet()
+
+        val.my_nested_mut().increment()
+        val.my_nested_mut().increment()
+        XCTAssertEqual(val.my_nested_mut().value(), 2)
+    }
+}
@@ -0,0 +1,50 @@
+//
+//  FunctionAttributeSwiftPropertyTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests the #[swift_bridge(swift_property)] attribute.
+class FunctionAttributeSwiftPropertyTests: XCTestCase {
+    /// Verify that we can get and set a computed property.
+    func testGetAndSetProperty() throws {
+        let val = SomeTypeSwiftProperty()
+        XCTAssertEqual(val.count, 0)
+
+        val.count = 5
+        XCTAssertEqual(val.count, 5)
+
+        val.count += 1
+        XCTAssertEqual(val.count, 6)
+    }
+
+    /// Verify that we can get and set a computed property that holds a String.
+    func testGetAndSetStringProperty() throws {
+        let val = SomeTypeSwiftProperty()
+        XCTAssertEqual(val.name.toString(), "Hello")
+
+        val.name = "Hello, world".intoRustString()
+        XCTAssertEqual(val.name.toString(), "Hello, world")
+        XCTAssertEqual(val.name_length, 12)
+    }
+
+    /// Verify that a property without a setter can be read through an immutable reference.
+    func testReadOnlyProperty() throws {
+        let val = SomeTypeSwiftProperty()
+        let valRef: SomeTypeSwiftPropertyRef = val
+
+        XCTAssertEqual(valRef.name_length, 5)
+    }
+
+    /// Verify that a property with a setter can be read through an immutable reference.
+    func testReadPropertyWithSetterThroughRef() throws {
+        let val = SomeTypeSwiftProperty()
+        val.count = 3
+
+        let valRef: SomeTypeSwiftPropertyRef = val
+        XCTAssertEqual(valRef.count, 3)
+        XCTAssertEqual(valRef.name.toString(), "Hello")
+    }
+}
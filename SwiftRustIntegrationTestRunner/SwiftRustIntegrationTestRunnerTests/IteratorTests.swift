@@ -0,0 +1,46 @@
+//
+//  IteratorTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for Rust iterators that are consumed in Swift as a `Sequence`.
+class IteratorTests: XCTestCase {
+
+    /// Verify that we can iterate over every item of a Rust iterator.
+    func testIterateRustIterator() throws {
+        var items: [UInt8] = []
+        for item in rust_iterator_count_to(3) {
+            items.append(item)
+        }
+
+        XCTAssertEqual(items, [1, 2, 3])
+    }
+
+    /// Verify that we can use `Sequence` methods on a Rust iterator of Strings.
+    func testMapRustIteratorOfStrings() throws {
+        let items = rust_iterator_strings().map { $0.toString() }
+
+        XCTAssertEqual(items, ["hello", "world"])
+    }
+
+    /// Verify that we can iterate over an iterator returned by a Rust method.
+    func testIterateRustIteratorFromMethod() throws {
+        let source = RustIteratorSource(2)
+
+        XCTAssertEqual(Array(source.items()), [1, 2])
+    }
+
+    /// Verify that the Rust iterator gets dropped when Swift stops iterating over it.
+    func testRustIteratorDroppedWhenIterationEnds() throws {
+        do {
+            let items = Array(rust_iterator_endless().prefix(3))
+
+            XCTAssertEqual(items, [1, 2, 3])
+        }
+
+        XCTAssertTrue(rust_iterator_endless_was_dropped())
+    }
+}
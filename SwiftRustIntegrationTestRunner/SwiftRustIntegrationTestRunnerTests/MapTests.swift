@@ -0,0 +1,112 @@
+//
+//  MapTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+class MapTests: XCTestCase {
+    func testRustMapInsertGetRemove() throws {
+        let map = RustMap<UInt32, RustString>(vtable: .hashMap)
+        XCTAssertEqual(map.len(), 0)
+
+        map.insert(key: 1, value: "one".intoRustString())
+        map.insert(key: 2, value: "two".intoRustString())
+        XCTAssertEqual(map.len(), 2)
+        XCTAssertTrue(map.contains(key: 1))
+        XCTAssertEqual(map.get(key: 2)!.as_str().toString(), "two")
+        XCTAssertNil(map.get(key: 3))
+
+        XCTAssertEqual(map.remove(key: 1)!.toString(), "one")
+        XCTAssertNil(map.remove(key: 1))
+        XCTAssertEqual(map.len(), 1)
+    }
+
+    func testRustMapIterator() throws {
+        let map = RustMap<UInt32, RustString>(vtable: .hashMap)
+        map.insert(key: 1, value: "one".intoRustString())
+        map.insert(key: 2, value: "two".intoRustString())
+
+        var iterations = 0
+        for (key, value) in map {
+            XCTAssertEqual(map.get(key: key)!.as_str().toString(), value.as_str().toString())
+            iterations += 1
+        }
+        XCTAssertEqual(iterations, 2)
+        XCTAssertEqual(map.toArray().count, 2)
+    }
+
+    func testRustMapToDictionary() throws {
+        let map = RustMap<UInt8, UInt16>(vtable: .btreeMap)
+        map.insert(key: 1, value: 10)
+        map.insert(key: 2, value: 20)
+
+        XCTAssertEqual(rust_reflect_btree_map_u8_u16(map).toDictionary(), [1: 10, 2: 20])
+    }
+
+    /// Verify that a map with `String` keys can be converted into a Swift `Dictionary`.
+    func testRustMapWithStringKeysToDictionary() throws {
+        let map = RustMap<RustString, ARustTypeInsideMap>(vtable: .hashMap)
+        map.insert(key: "hello".intoRustString(), value: ARustTypeInsideMap("world"))
+
+        let reflected = rust_reflect_hash_map_string_opaque_rust_type(map)
+        let dictionary = reflected.toDictionary()
+        XCTAssertEqual(dictionary["hello"]!.text().toString(), "world")
+    }
+
+    /// Verify that a HashMap<K, V> can be used as an argument and return type for
+    /// extern "Rust" functions.
+    func testReflectHashMap() throws {
+        let map = RustMap<UInt32, RustString>(vtable: .hashMap)
+        map.insert(key: 5, value: "five".intoRustString())
+
+        let reflected = rust_reflect_hash_map_u32_string(map)
+        XCTAssertEqual(reflected.len(), 1)
+        XCTAssertEqual(reflected.get(key: 5)!.as_str().toString(), "five")
+    }
+
+    /// Verify that two bridge modules can use the same map.
+    func testMapUsedInTwoBridgeModules() throws {
+        let map = RustMap<UInt32, RustString>(vtable: .hashMap)
+        map.insert(key: 1, value: "one".intoRustString())
+
+        let reflected = rust_reflect_hash_map_u32_string(
+            rust_reflect_hash_map_u32_string_from_second_module(map)
+        )
+        XCTAssertEqual(reflected.get(key: 1)!.as_str().toString(), "one")
+    }
+
+    /// Verify that a transparent struct that derives `Clone` can be used as a map value.
+    func testRustMapOfTransparentStructs() throws {
+        let map = RustMap<UInt8, StructInsideMap>(vtable: .hashMap)
+        map.insert(key: 1, value: StructInsideMap(value: 10))
+
+        let reflected = rust_reflect_hash_map_u8_transparent_struct(map)
+        XCTAssertEqual(reflected.get(key: 1)!.value, 10)
+        XCTAssertNil(reflected.get(key: 2))
+    }
+
+    /// Verify that a map can be nested in an `Option`.
+    func testReflectOptionalRustMap() throws {
+        let map = RustMap<UInt8, UInt16>(vtable: .btreeMap)
+        map.insert(key: 1, value: 10)
+
+        XCTAssertEqual(rust_reflect_optional_btree_map_u8_u16(map)!.toDictionary(), [1: 10])
+        XCTAssertNil(rust_reflect_optional_btree_map_u8_u16(nil))
+    }
+
+    /// Verify that a map can be used as a shared struct's field.
+    func testStructWithRustMapField() throws {
+        let map = RustMap<UInt8, UInt16>(vtable: .hashMap)
+        map.insert(key: 1, value: 10)
+
+        let reflected = rust_reflect_struct_with_map_field(StructWithMapField(map: map))
+        XCTAssertEqual(reflected.map.get(key: 1), 10)
+    }
+
+    /// Verify that Rust can pass `RustMap`s to and receive `RustMap`s from Swift.
+    func testRustCallsSwiftRustMapFunctions() {
+        run_map_tests()
+    }
+}
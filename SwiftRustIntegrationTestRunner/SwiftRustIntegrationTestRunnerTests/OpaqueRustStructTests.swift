@@ -130,5 +130,91 @@ class OpaqueRustStructTests: XCTestCase {
             }
         }
     }
-}
 
+    func testOpaqueRustTypeImplComparable() throws {
+        let val1 = RustComparableType(1)
+        let val2 = RustComparableType(2)
+
+        XCTAssert(val1 < val2)
+        XCTAssertFalse(val2 < val1)
+        XCTAssertFalse(val1 < RustComparableType(1))
+        XCTAssert(val1 <= RustComparableType(1))
+        XCTAssertEqual(val1, RustComparableType(1))
+
+        let sorted = [RustComparableType(3), val2, val1].sorted()
+        XCTAssertEqual(sorted.map { $0.value() }, [1, 2, 3])
+
+        let ref1: RustComparableTypeRef = val1
+        let refMut2: RustComparableTypeRefMut = val2
+        XCTAssert(ref1 < refMut2)
+    }
+
+    /// Verify that every comparison operator is false when `PartialOrd::partial_cmp`
+    /// returns `None`.
+    func testOpaqueRustTypeImplComparableWithIncomparableValues() throws {
+        let one = RustPartiallyComparableType(1.0)
+        let two = RustPartiallyComparableType(2.0)
+        let nan = RustPartiallyComparableType(Double.nan)
+
+        XCTAssert(one < two)
+        XCTAssert(one <= two)
+        XCTAssert(two > one)
+        XCTAssert(two >= one)
+        XCTAssert(one <= RustPartiallyComparableType(1.0))
+        XCTAssert(one >= RustPartiallyComparableType(1.0))
+
+        XCTAssertFalse(one < nan)
+        XCTAssertFalse(one <= nan)
+        XCTAssertFalse(one > nan)
+        XCTAssertFalse(one >= nan)
+        XCTAssertFalse(nan < one)
+        XCTAssertFalse(nan <= one)
+        XCTAssertFalse(nan > one)
+        XCTAssertFalse(nan >= one)
+    }
+
+    func testOpaqueRustTypeImplOperators() throws {
+        func values(_ nums: [Int32]) -> RustOperatorsType {
+            let vec = RustVec<Int32>()
+            for num in nums {
+                vec.push(value: num)
+            }
+            return RustOperatorsType(vec)
+        }
+
+        let lhs = values([1, 2, 3])
+        let rhs = values([10, 20, 30])
+
+        let sum = lhs + rhs
+        XCTAssertEqual(sum.len(), 3)
+        XCTAssertEqual(sum[0], 11)
+        XCTAssertEqual(sum[2], 33)
+
+        let difference = rhs - lhs
+        XCTAssertEqual(difference[1], 18)
+
+        let negated = -lhs
+        XCTAssertEqual(negated[2], -3)
+
+        // The operands are cloned, so they can still be used.
+        XCTAssertEqual(lhs[0], 1)
+        XCTAssertEqual(rhs[0], 10)
+    }
+
+    func testOpaqueRustTypeImplDisplay() throws {
+        let val = RustDisplayType(10)
+
+        XCTAssertEqual(val.description, "RustDisplayType with value 10")
+        XCTAssertEqual("\(val)", "RustDisplayType with value 10")
+
+        let valRef: RustDisplayTypeRef = val
+        XCTAssertEqual(String(describing: valRef), "RustDisplayType with value 10")
+    }
+
+    func testOpaqueRustTypeImplDebug() throws {
+        let val = RustDebugType(10)
+
+        XCTAssertEqual(val.debugDescription, "RustDebugType { num: 10 }")
+        XCTAssertEqual(String(reflecting: val), "RustDebugType { num: 10 }")
+    }
+}
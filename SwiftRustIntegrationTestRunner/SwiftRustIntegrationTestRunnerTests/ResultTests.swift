@@ -209,4 +209,9 @@ class ResultTests: XCTestCase {
             XCTAssertEqual(UInt32(i), value.val())
         }
     }
+
+    /// Verify that Rust can call Swift functions that throw.
+    func testRustCallsThrowingSwiftFunctions() throws {
+        rust_func_calls_throwing_swift_functions()
+    }
 }
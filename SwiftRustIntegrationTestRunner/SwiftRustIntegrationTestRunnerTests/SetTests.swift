@@ -0,0 +1,79 @@
+//
+//  SetTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+class SetTests: XCTestCase {
+    func testRustSetInsertContainsRemove() throws {
+        let set = RustSet<UInt32>(vtable: .hashSet)
+        XCTAssertEqual(set.len(), 0)
+
+        XCTAssertTrue(set.insert(5))
+        XCTAssertFalse(set.insert(5))
+        XCTAssertEqual(set.len(), 1)
+        XCTAssertTrue(set.contains(5))
+
+        XCTAssertTrue(set.remove(5))
+        XCTAssertFalse(set.remove(5))
+        XCTAssertEqual(set.len(), 0)
+    }
+
+    func testRustSetIterator() throws {
+        let set = RustSet<Int64>([3, 1, 2], vtable: .btreeSet)
+
+        // A BTreeSet iterates in sorted order.
+        XCTAssertEqual(set.toArray(), [1, 2, 3])
+    }
+
+    /// Verify that a HashSet<T> can be used as an argument and return type for extern "Rust"
+    /// functions, and converted into a Swift `Set`.
+    func testReflectHashSet() throws {
+        let set = RustSet<UInt32>([1, 2, 2, 3], vtable: .hashSet)
+
+        let reflected = rust_reflect_hash_set_u32(set)
+        XCTAssertEqual(reflected.toSet(), [1, 2, 3])
+    }
+
+    /// Verify that a set of `String`s can be converted into a Swift `Set<String>`.
+    /// Verify that a set that is used in two bridge modules can be passed to both of them.
+    func testSetUsedInTwoBridgeModules() throws {
+        let set = RustSet<UInt32>([1, 2], vtable: .hashSet)
+
+        let reflected = rust_reflect_hash_set_u32(
+            rust_reflect_hash_set_u32_from_second_module(set)
+        )
+        XCTAssertEqual(reflected.toSet(), [1, 2])
+    }
+
+    func testReflectHashSetOfStrings() throws {
+        let set = RustSet<RustString>(vtable: .hashSet)
+        set.insert("hello".intoRustString())
+        set.insert("world".intoRustString())
+
+        let reflected = rust_reflect_hash_set_string(set)
+        XCTAssertEqual(reflected.toSet(), ["hello", "world"])
+    }
+
+    func testReflectBTreeSet() throws {
+        let set = RustSet<Int64>([-1, 1], vtable: .btreeSet)
+
+        XCTAssertEqual(rust_reflect_btree_set_i64(set).toArray(), [-1, 1])
+    }
+
+    /// Verify that a set can be nested in a `Result`.
+    func testRustSetInResult() throws {
+        let set = RustSet<UInt32>([1, 2], vtable: .hashSet)
+        XCTAssertEqual(try rust_hash_set_u32_or_error(set, true).toSet(), [1, 2])
+
+        let otherSet = RustSet<UInt32>([3], vtable: .hashSet)
+        XCTAssertThrowsError(try rust_hash_set_u32_or_error(otherSet, false))
+    }
+
+    /// Verify that Rust can pass `RustSet`s to and receive `RustSet`s from Swift.
+    func testRustCallsSwiftRustSetFunctions() {
+        run_set_tests()
+    }
+}
@@ -42,5 +42,32 @@ class SharedEnumAttributeTests: XCTestCase {
         XCTAssertEqual(debugString, "Variant")
     }
 
-}
+    /// Verify that the generated `Equatable`, `Hashable` and `Comparable` conformances agree with
+    /// the enum's derived `PartialEq`, `Hash` and `Ord` implementations.
+    /// See crates/swift-integration-tests/src/enum_attributes/derive.rs
+    func testSharedEnumDeriveOrd() throws {
+        // Passing a value to Rust moves its `RustString`, so we create fresh values for every call.
+        func values() -> [EnumDeriveOrd] {
+            [
+                EnumDeriveOrd.Unit,
+                EnumDeriveOrd.Unnamed(1, "b".intoRustString()),
+                EnumDeriveOrd.Unnamed(1, "c".intoRustString()),
+                EnumDeriveOrd.Unnamed(2, "a".intoRustString()),
+                EnumDeriveOrd.Named(flag: false),
+                EnumDeriveOrd.Named(flag: true),
+            ]
+        }
+
+        for lhsIdx in 0..<values().count {
+            for rhsIdx in 0..<values().count {
+                let ordering = rust_cmp_enum_derive_ord(values()[lhsIdx], values()[rhsIdx])
+
+                XCTAssertEqual(values()[lhsIdx] == values()[rhsIdx], ordering == 0)
+                XCTAssertEqual(values()[lhsIdx] < values()[rhsIdx], ordering < 0)
+            }
 
+            XCTAssertEqual(values()[lhsIdx].hashValue, values()[lhsIdx].hashValue)
+        }
+        XCTAssertEqual(Set(values() + values()).count, values().count)
+    }
+}
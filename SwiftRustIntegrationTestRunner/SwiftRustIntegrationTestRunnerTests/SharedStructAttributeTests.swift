@@ -34,5 +34,55 @@ class SharedStructAttributeTests: XCTestCase {
     func testSharedStructAlreadyDeclared() throws {
         test_rust_calls_swift_already_declared_struct()
     }
-}
 
+    /// Verify that the generated `Equatable`, `Hashable` and `Comparable` conformances agree with
+    /// the struct's derived `PartialEq`, `Hash` and `Ord` implementations.
+    /// See crates/swift-integration-tests/src/struct_attributes/derive.rs
+    func testSharedStructDeriveOrd() throws {
+        // Passing a value to Rust moves its `RustString`, so we create fresh values for every call.
+        func values() -> [StructDeriveOrd] {
+            [
+                StructDeriveOrd(number: 1, flag: false, text: "b".intoRustString(), maybe_number: nil),
+                StructDeriveOrd(number: 1, flag: false, text: "b".intoRustString(), maybe_number: 0),
+                StructDeriveOrd(number: 1, flag: false, text: "é".intoRustString(), maybe_number: nil),
+                StructDeriveOrd(number: 1, flag: false, text: "e\u{301}".intoRustString(), maybe_number: nil),
+                StructDeriveOrd(number: 1, flag: true, text: "a".intoRustString(), maybe_number: nil),
+                StructDeriveOrd(number: 2, flag: false, text: "a".intoRustString(), maybe_number: nil),
+            ]
+        }
+
+        for lhsIdx in 0..<values().count {
+            for rhsIdx in 0..<values().count {
+                let ordering = rust_cmp_struct_derive_ord(values()[lhsIdx], values()[rhsIdx])
+
+                XCTAssertEqual(values()[lhsIdx] == values()[rhsIdx], ordering == 0)
+                XCTAssertEqual(values()[lhsIdx] < values()[rhsIdx], ordering < 0)
+            }
+
+            XCTAssertEqual(values()[lhsIdx].hashValue, values()[lhsIdx].hashValue)
+        }
+    }
+
+    /// Verify that a shared struct's operator attributes call its Rust operator trait impls.
+    /// See crates/swift-integration-tests/src/struct_attributes/operators.rs
+    func testSharedStructOperators() throws {
+        let lhs = StructOperators(x: 6, y: 8)
+        let rhs = StructOperators(x: 2, y: 4)
+
+        let sum = lhs + rhs
+        XCTAssertEqual(sum.x, 8)
+        XCTAssertEqual(sum.y, 12)
+
+        let difference = lhs - rhs
+        XCTAssertEqual(difference.x, 4)
+        XCTAssertEqual(difference.y, 4)
+
+        let product = lhs * rhs
+        XCTAssertEqual(product.x, 12)
+        XCTAssertEqual(product.y, 32)
+
+        let quotient = lhs / rhs
+        XCTAssertEqual(quotient.x, 3)
+        XCTAssertEqual(quotient.y, 2)
+    }
+}
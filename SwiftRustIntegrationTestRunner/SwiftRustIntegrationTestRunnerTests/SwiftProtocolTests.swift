@@ -0,0 +1,63 @@
+//
+//  SwiftProtocolTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for `#[swift_bridge(protocol)]` types.
+class SwiftProtocolTests: XCTestCase {
+
+    /// Verify that Rust can call methods on a Swift class that conforms to a generated protocol.
+    func testRustReadsFromSwiftProtocol() throws {
+        let store = InMemoryKeyValueStore()
+        store.values["hello"] = "world"
+
+        XCTAssertEqual(rust_reads_from_swift_key_value_store(store, "hello")!.toString(), "world")
+        XCTAssertNil(rust_reads_from_swift_key_value_store(store, "missing"))
+    }
+
+    /// Verify that Rust can call `&mut self` methods on a Swift class that conforms to a
+    /// generated protocol.
+    func testRustWritesToSwiftProtocol() throws {
+        let store = InMemoryKeyValueStore()
+
+        XCTAssertEqual(rust_writes_to_swift_key_value_store(store, "a", "1"), 1)
+        XCTAssertEqual(rust_writes_to_swift_key_value_store(store, "b", "2"), 2)
+        XCTAssertEqual(store.values["b"], "2")
+    }
+
+    /// Verify that Rust releases the Swift instance when the `Box<dyn Trait>` is dropped.
+    func testRustReleasesSwiftProtocolInstance() throws {
+        weak var weakStore: InMemoryKeyValueStore? = nil
+
+        do {
+            let store = InMemoryKeyValueStore()
+            weakStore = store
+            let _ = rust_reads_from_swift_key_value_store(store, "hello")
+        }
+
+        XCTAssertNil(weakStore)
+    }
+}
+
+class InMemoryKeyValueStore: KeyValueStore {
+    var values: [String: String] = [:]
+
+    func contains(key: RustStr) -> Bool {
+        values[key.toString()] != nil
+    }
+
+    func get(key: RustStr) -> RustString {
+        values[key.toString()]!.intoRustString()
+    }
+
+    func set(key: RustString, value: RustString) {
+        values[key.toString()] = value.toString()
+    }
+
+    func count() -> UInt32 {
+        UInt32(values.count)
+    }
+}
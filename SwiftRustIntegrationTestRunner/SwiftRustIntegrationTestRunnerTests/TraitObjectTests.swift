@@ -0,0 +1,60 @@
+//
+//  TraitObjectTests.swift
+//  SwiftRustIntegrationTestRunnerTests
+//
+
+import XCTest
+@testable import SwiftRustIntegrationTestRunner
+
+/// Tests for `#[swift_bridge(trait_object)]` types.
+class TraitObjectTests: XCTestCase {
+
+    /// Verify that we can call trait methods on a `Box<dyn Trait>` that was returned from Rust.
+    func testCallMethodsOnBoxedTraitObject() throws {
+        let renderer = make_boxed_renderer("boxed")
+
+        XCTAssertEqual(renderer.name().toString(), "boxed")
+        XCTAssertEqual(renderer.render(2, 5), 10)
+
+        renderer.resize(3)
+        XCTAssertEqual(renderer.render(2, 5), 30)
+        XCTAssertEqual(renderer.render_count(), 2)
+    }
+
+    /// Verify that we can call trait methods on an `Arc<dyn Trait>` that was returned from Rust.
+    func testCallMethodsOnArcTraitObject() throws {
+        let renderer = make_arc_renderer("arc")
+
+        XCTAssertEqual(renderer.name().toString(), "arc")
+        XCTAssertEqual(renderer.render(2, 5), 10)
+
+        XCTAssertEqual(renderer.render_count(), 1)
+    }
+
+    /// Verify that we can return an `Option<Box<dyn Trait>>` from Rust.
+    func testOptionalTraitObject() throws {
+        XCTAssertEqual(make_optional_renderer(true)!.name().toString(), "optional")
+        XCTAssertNil(make_optional_renderer(false))
+    }
+
+    /// Verify that we can pass trait objects back to Rust by reference.
+    func testPassTraitObjectReferenceToRust() throws {
+        let renderer = make_boxed_renderer("boxed")
+
+        rust_resizes_with_ref_mut(renderer, 4)
+        XCTAssertEqual(rust_renders_with_ref(renderer), 24)
+        XCTAssertEqual(renderer.render_count(), 1)
+    }
+
+    /// Verify that we can pass owned trait objects back to Rust.
+    func testPassOwnedTraitObjectToRust() throws {
+        XCTAssertEqual(rust_takes_boxed_renderer(make_boxed_renderer("boxed")).toString(), "boxed")
+
+        let renderer = make_arc_renderer("arc")
+        let _ = renderer.render(1, 1)
+        XCTAssertEqual(rust_takes_arc_shared_renderer(renderer), 1)
+
+        // A `Box<dyn Trait>` can be passed to a Rust function that takes an `Arc<dyn Trait>`.
+        XCTAssertEqual(rust_takes_arc_renderer(make_boxed_renderer("boxed")), 0)
+    }
+}
@@ -1,9 +1,11 @@
+use crate::generate_core::async_support::C_ASYNC_TASK_HANDLE_SUPPORT;
 use crate::generate_core::boxed_fn_support::{
     C_CALLBACK_SUPPORT_NO_ARGS_NO_RETURN, SWIFT_CALLBACK_SUPPORT_NO_ARGS_NO_RETURN,
 };
 use crate::generate_core::option_support::{
     swift_option_primitive_support, C_OPTION_PRIMITIVE_SUPPORT,
 };
+use crate::generate_core::panic_support::{C_RUST_PANIC_SUPPORT, SWIFT_RUST_PANIC};
 use crate::generate_core::result_support::{C_RESULT_SUPPORT, SWIFT_RUST_RESULT};
 use std::path::Path;
 
@@ -12,9 +14,13 @@ const RUST_STRING_C: &'static str = include_str!("./generate_core/rust_string.c.
 
 const STRING_SWIFT: &'static str = include_str!("./generate_core/string.swift");
 const RUST_VEC_SWIFT: &'static str = include_str!("./generate_core/rust_vec.swift");
+const RUST_MAP_SWIFT: &str = include_str!("./generate_core/rust_map.swift");
+const RUST_SET_SWIFT: &str = include_str!("./generate_core/rust_set.swift");
 
+mod async_support;
 mod boxed_fn_support;
 mod option_support;
+mod panic_support;
 mod result_support;
 
 pub(super) fn write_core_swift_and_c(out_dir: &Path) {
@@ -27,6 +33,8 @@ pub(super) fn write_core_swift_and_c(out_dir: &Path) {
     swift += "\n";
     swift += &SWIFT_RUST_RESULT;
     swift += "\n";
+    swift += SWIFT_RUST_PANIC;
+    swift += "\n";
     swift += &swift_option_primitive_support();
 
     std::fs::write(core_swift_out, swift).unwrap();
@@ -39,6 +47,10 @@ pub(super) fn write_core_swift_and_c(out_dir: &Path) {
     c_header += &C_CALLBACK_SUPPORT_NO_ARGS_NO_RETURN;
     c_header += "\n";
     c_header += &C_RESULT_SUPPORT;
+    c_header += "\n";
+    c_header += C_ASYNC_TASK_HANDLE_SUPPORT;
+    c_header += "\n";
+    c_header += C_RUST_PANIC_SUPPORT;
 
     std::fs::write(core_c_header_out, c_header).unwrap();
 }
@@ -48,6 +60,8 @@ fn core_swift() -> String {
 
     core_swift += STRING_SWIFT;
     core_swift += RUST_VEC_SWIFT;
+    core_swift += RUST_MAP_SWIFT;
+    core_swift += RUST_SET_SWIFT;
 
     for (swift_ty, rust_ty) in vec![
         ("UInt8", "u8"),
@@ -0,0 +1,6 @@
+/// Declares the handle that Swift uses to cancel the task running an async Rust function.
+pub const C_ASYNC_TASK_HANDLE_SUPPORT: &str = r#"
+void* __swift_bridge__$AsyncTaskHandle$new(void);
+void __swift_bridge__$AsyncTaskHandle$cancel(void* handle);
+void __swift_bridge__$AsyncTaskHandle$_free(void* handle);
+"#;
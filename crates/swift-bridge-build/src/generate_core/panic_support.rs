@@ -0,0 +1,51 @@
+/// Surfaces panics that were caught in `#[swift_bridge::bridge(catch_panics)]` modules.
+pub const SWIFT_RUST_PANIC: &str = r#"
+/// A panic in a Rust function from a bridge module that uses
+/// `#[swift_bridge::bridge(catch_panics)]`.
+public struct RustPanic: Error {
+    public let message: String
+
+    public init(message: String) {
+        self.message = message
+    }
+}
+
+extension RustPanic: LocalizedError {
+    public var errorDescription: String? {
+        message
+    }
+}
+
+extension RustPanic {
+    /// Register a handler for panics in Rust functions that can't throw.
+    ///
+    /// The Rust process aborts once the handler returns.
+    public static func setHandler(_ handler: @escaping (String) -> ()) {
+        rustPanicHandler = handler
+        __swift_bridge__$RustPanic$set_handler({ message in
+            rustPanicHandler?(RustString(ptr: message!).toString())
+        })
+    }
+
+    static func take() -> RustPanic? {
+        guard let message = __swift_bridge__$RustPanic$take() else {
+            return nil
+        }
+        return RustPanic(message: RustString(ptr: message).toString())
+    }
+
+    static func check<T>(_ val: T) throws -> T {
+        if let rustPanic = take() {
+            throw rustPanic
+        }
+        return val
+    }
+}
+
+fileprivate var rustPanicHandler: ((String) -> ())?
+"#;
+
+pub const C_RUST_PANIC_SUPPORT: &str = r#"
+void* __swift_bridge__$RustPanic$take(void);
+void __swift_bridge__$RustPanic$set_handler(void (*handler)(void* message));
+"#;
@@ -0,0 +1,168 @@
+public class RustMap<K: Vectorizable, V: Vectorizable> {
+    var ptr: UnsafeMutableRawPointer
+    var isOwned: Bool = true
+    let vtable: RustMapVtable<K, V>
+
+    public init(ptr: UnsafeMutableRawPointer, vtable: RustMapVtable<K, V>) {
+        self.ptr = ptr
+        self.vtable = vtable
+    }
+
+    /// Create an empty map.
+    ///
+    /// For example: `RustMap<UInt32, RustString>(vtable: .hashMap)`
+    public init(vtable: RustMapVtable<K, V>) {
+        self.ptr = vtable.new()
+        self.vtable = vtable
+    }
+
+    /// Create a map that holds all of the given entries.
+    ///
+    /// For example: `RustMap(["a".intoRustString(): UInt8(1)], vtable: .hashMap)`
+    public convenience init<S: Sequence>(_ entries: S, vtable: RustMapVtable<K, V>) where S.Element == (key: K, value: V) {
+        self.init(vtable: vtable)
+        self.extend(entries)
+    }
+
+    /// Rust returns a UInt, but we cast to an Int because many Swift APIs such as
+    /// `ForEach(0..rustMap.len())` expect Int.
+    public func len() -> Int {
+        Int(vtable.len(ptr))
+    }
+
+    public func contains(key: K.SelfRef) -> Bool {
+        vtable.containsKey(ptr, key)
+    }
+
+    public func get(key: K.SelfRef) -> Optional<V.SelfRef> {
+        vtable.get(ptr, key)
+    }
+
+    public func insert(key: K, value: V) {
+        vtable.insert(ptr, key, value)
+    }
+
+    public func remove(key: K.SelfRef) -> Optional<V> {
+        vtable.remove(ptr, key)
+    }
+
+    public func extend<S: Sequence>(_ entries: S) where S.Element == (key: K, value: V) {
+        for (key, value) in entries {
+            self.insert(key: key, value: value)
+        }
+    }
+
+    public func toArray() -> Array<(key: K.SelfRef, value: V.SelfRef)> {
+        Array(self)
+    }
+
+    deinit {
+        if isOwned {
+            vtable.free(ptr)
+        }
+    }
+}
+
+extension RustMap where K.SelfRef: Hashable {
+    public func toDictionary() -> Dictionary<K.SelfRef, V.SelfRef> {
+        var dictionary = Dictionary<K.SelfRef, V.SelfRef>(minimumCapacity: self.len())
+        for (key, value) in self {
+            dictionary[key] = value
+        }
+        return dictionary
+    }
+}
+
+extension RustMap where K == RustString {
+    public func toDictionary() -> Dictionary<String, V.SelfRef> {
+        var dictionary = Dictionary<String, V.SelfRef>(minimumCapacity: self.len())
+        for (key, value) in self {
+            dictionary[key.as_str().toString()] = value
+        }
+        return dictionary
+    }
+}
+
+extension RustMap: Sequence {
+    public func makeIterator() -> RustMapIterator<K, V> {
+        return RustMapIterator(self)
+    }
+}
+
+/// Iterates over a snapshot of the map's entries.
+///
+/// The map must not be mutated while it is being iterated over.
+public class RustMapIterator<K: Vectorizable, V: Vectorizable>: IteratorProtocol {
+    var rustMap: RustMap<K, V>
+    var entries: UnsafeMutableRawPointer
+    var index: UInt = 0
+    let count: UInt
+
+    init (_ rustMap: RustMap<K, V>) {
+        self.rustMap = rustMap
+        self.entries = rustMap.vtable.entries(rustMap.ptr)
+        self.count = rustMap.vtable.len(rustMap.ptr)
+    }
+
+    public func next() -> (key: K.SelfRef, value: V.SelfRef)? {
+        if index >= count {
+            return nil
+        }
+
+        let entry = (
+            key: rustMap.vtable.entriesKey(entries, index),
+            value: rustMap.vtable.entriesValue(entries, index)
+        )
+        index += 1
+        return entry
+    }
+
+    deinit {
+        rustMap.vtable.entriesFree(entries)
+    }
+}
+
+/// The functions that a `RustMap` uses to call into the Rust map for a particular key and value
+/// type.
+///
+/// These are generated once for every `HashMap<K, V>` and `BTreeMap<K, V>` used in the crate's
+/// bridge modules, and exposed as `RustMapVtable.hashMap` and `RustMapVtable.btreeMap`.
+public struct RustMapVtable<K: Vectorizable, V: Vectorizable> {
+    let new: () -> UnsafeMutableRawPointer
+    let free: (UnsafeMutableRawPointer) -> ()
+    let len: (UnsafeMutableRawPointer) -> UInt
+    let containsKey: (UnsafeMutableRawPointer, K.SelfRef) -> Bool
+    let get: (UnsafeMutableRawPointer, K.SelfRef) -> Optional<V.SelfRef>
+    let insert: (UnsafeMutableRawPointer, K, V) -> ()
+    let remove: (UnsafeMutableRawPointer, K.SelfRef) -> Optional<V>
+    let entries: (UnsafeMutableRawPointer) -> UnsafeMutableRawPointer
+    let entriesKey: (UnsafeMutableRawPointer, UInt) -> K.SelfRef
+    let entriesValue: (UnsafeMutableRawPointer, UInt) -> V.SelfRef
+    let entriesFree: (UnsafeMutableRawPointer) -> ()
+
+    public init(
+        new: @escaping () -> UnsafeMutableRawPointer,
+        free: @escaping (UnsafeMutableRawPointer) -> (),
+        len: @escaping (UnsafeMutableRawPointer) -> UInt,
+        containsKey: @escaping (UnsafeMutableRawPointer, K.SelfRef) -> Bool,
+        get: @escaping (UnsafeMutableRawPointer, K.SelfRef) -> Optional<V.SelfRef>,
+        insert: @escaping (UnsafeMutableRawPointer, K, V) -> (),
+        remove: @escaping (UnsafeMutableRawPointer, K.SelfRef) -> Optional<V>,
+        entries: @escaping (UnsafeMutableRawPointer) -> UnsafeMutableRawPointer,
+        entriesKey: @escaping (UnsafeMutableRawPointer, UInt) -> K.SelfRef,
+        entriesValue: @escaping (UnsafeMutableRawPointer, UInt) -> V.SelfRef,
+        entriesFree: @escaping (UnsafeMutableRawPointer) -> ()
+    ) {
+        self.new = new
+        self.free = free
+        self.len = len
+        self.containsKey = containsKey
+        self.get = get
+        self.insert = insert
+        self.remove = remove
+        self.entries = entries
+        self.entriesKey = entriesKey
+        self.entriesValue = entriesValue
+        self.entriesFree = entriesFree
+    }
+}
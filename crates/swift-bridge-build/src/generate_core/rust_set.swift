@@ -0,0 +1,146 @@
+public class RustSet<T: Vectorizable> {
+    var ptr: UnsafeMutableRawPointer
+    var isOwned: Bool = true
+    let vtable: RustSetVtable<T>
+
+    public init(ptr: UnsafeMutableRawPointer, vtable: RustSetVtable<T>) {
+        self.ptr = ptr
+        self.vtable = vtable
+    }
+
+    /// Create an empty set.
+    ///
+    /// For example: `RustSet<UInt32>(vtable: .hashSet)`
+    public init(vtable: RustSetVtable<T>) {
+        self.ptr = vtable.new()
+        self.vtable = vtable
+    }
+
+    /// Create a set that holds all of the given values.
+    ///
+    /// For example: `RustSet([UInt32(1), 2, 3], vtable: .btreeSet)`
+    public convenience init<S: Sequence>(_ values: S, vtable: RustSetVtable<T>) where S.Element == T {
+        self.init(vtable: vtable)
+        for value in values {
+            self.insert(value)
+        }
+    }
+
+    /// Rust returns a UInt, but we cast to an Int because many Swift APIs such as
+    /// `ForEach(0..rustSet.len())` expect Int.
+    public func len() -> Int {
+        Int(vtable.len(ptr))
+    }
+
+    public func contains(_ value: T.SelfRef) -> Bool {
+        vtable.contains(ptr, value)
+    }
+
+    /// Returns `true` if the value was not already in the set.
+    @discardableResult
+    public func insert(_ value: T) -> Bool {
+        vtable.insert(ptr, value)
+    }
+
+    /// Returns `true` if the value was in the set.
+    @discardableResult
+    public func remove(_ value: T.SelfRef) -> Bool {
+        vtable.remove(ptr, value)
+    }
+
+    public func toArray() -> Array<T.SelfRef> {
+        Array(self)
+    }
+
+    deinit {
+        if isOwned {
+            vtable.free(ptr)
+        }
+    }
+}
+
+extension RustSet where T.SelfRef: Hashable {
+    public func toSet() -> Set<T.SelfRef> {
+        Set(self)
+    }
+}
+
+extension RustSet where T == RustString {
+    public func toSet() -> Set<String> {
+        Set(self.map { $0.as_str().toString() })
+    }
+}
+
+extension RustSet: Sequence {
+    public func makeIterator() -> RustSetIterator<T> {
+        return RustSetIterator(self)
+    }
+}
+
+/// Iterates over a snapshot of the set's values.
+///
+/// The set must not be mutated while it is being iterated over.
+public class RustSetIterator<T: Vectorizable>: IteratorProtocol {
+    var rustSet: RustSet<T>
+    var items: UnsafeMutableRawPointer
+    var index: UInt = 0
+    let count: UInt
+
+    init (_ rustSet: RustSet<T>) {
+        self.rustSet = rustSet
+        self.items = rustSet.vtable.items(rustSet.ptr)
+        self.count = rustSet.vtable.len(rustSet.ptr)
+    }
+
+    public func next() -> T.SelfRef? {
+        if index >= count {
+            return nil
+        }
+
+        let item = rustSet.vtable.itemsGet(items, index)
+        index += 1
+        return item
+    }
+
+    deinit {
+        rustSet.vtable.itemsFree(items)
+    }
+}
+
+/// The functions that a `RustSet` uses to call into the Rust set for a particular item type.
+///
+/// These are generated once for every `HashSet<T>` and `BTreeSet<T>` used in the crate's
+/// bridge modules, and exposed as `RustSetVtable.hashSet` and `RustSetVtable.btreeSet`.
+public struct RustSetVtable<T: Vectorizable> {
+    let new: () -> UnsafeMutableRawPointer
+    let free: (UnsafeMutableRawPointer) -> ()
+    let len: (UnsafeMutableRawPointer) -> UInt
+    let contains: (UnsafeMutableRawPointer, T.SelfRef) -> Bool
+    let insert: (UnsafeMutableRawPointer, T) -> Bool
+    let remove: (UnsafeMutableRawPointer, T.SelfRef) -> Bool
+    let items: (UnsafeMutableRawPointer) -> UnsafeMutableRawPointer
+    let itemsGet: (UnsafeMutableRawPointer, UInt) -> T.SelfRef
+    let itemsFree: (UnsafeMutableRawPointer) -> ()
+
+    public init(
+        new: @escaping () -> UnsafeMutableRawPointer,
+        free: @escaping (UnsafeMutableRawPointer) -> (),
+        len: @escaping (UnsafeMutableRawPointer) -> UInt,
+        contains: @escaping (UnsafeMutableRawPointer, T.SelfRef) -> Bool,
+        insert: @escaping (UnsafeMutableRawPointer, T) -> Bool,
+        remove: @escaping (UnsafeMutableRawPointer, T.SelfRef) -> Bool,
+        items: @escaping (UnsafeMutableRawPointer) -> UnsafeMutableRawPointer,
+        itemsGet: @escaping (UnsafeMutableRawPointer, UInt) -> T.SelfRef,
+        itemsFree: @escaping (UnsafeMutableRawPointer) -> ()
+    ) {
+        self.new = new
+        self.free = free
+        self.len = len
+        self.contains = contains
+        self.insert = insert
+        self.remove = remove
+        self.items = items
+        self.itemsGet = itemsGet
+        self.itemsFree = itemsFree
+    }
+}
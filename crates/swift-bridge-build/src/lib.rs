@@ -6,6 +6,7 @@
 mod package;
 use crate::generate_core::write_core_swift_and_c;
 pub use package::*;
+use std::collections::BTreeMap;
 use std::path::Path;
 use swift_bridge_ir::{CodegenConfig, SwiftBridgeModule};
 use syn::__private::ToTokens;
@@ -61,13 +62,8 @@ impl GeneratedCode {
     pub fn write_all_concatenated(&self, swift_bridge_out_dir: impl AsRef<Path>, crate_name: &str) {
         let swift_bridge_out_dir = swift_bridge_out_dir.as_ref();
 
-        let mut concatenated_swift = "".to_string();
-        let mut concatenated_c = "".to_string();
-
-        for gen in &self.generated {
-            concatenated_swift += &gen.swift;
-            concatenated_c += &gen.c_header;
-        }
+        let concatenated_swift = self.concat_swift();
+        let concatenated_c = self.concat_c();
 
         let out = swift_bridge_out_dir.join(&crate_name);
         match std::fs::create_dir_all(&out) {
@@ -88,9 +84,21 @@ impl GeneratedCode {
     /// Concatenate all of the generated Swift code into one file.
     pub fn concat_swift(&self) -> String {
         let mut swift = "".to_string();
+        let mut collection_vtables = BTreeMap::new();
 
         for gen in &self.generated {
             swift += &gen.swift;
+
+            for (key, vtable) in &gen.collection_vtables {
+                collection_vtables.entry(key).or_insert(vtable);
+            }
+        }
+
+        // Many bridge modules can use the same collection, but Swift only allows each vtable to
+        // be declared once.
+        for vtable in collection_vtables.values() {
+            swift += vtable;
+            swift += "\n";
         }
 
         swift
@@ -114,6 +122,7 @@ fn parse_file_contents(file: &str) -> syn::Result<GeneratedFromSwiftBridgeModule
     let mut generated = GeneratedFromSwiftBridgeModule {
         c_header: "".to_string(),
         swift: "".to_string(),
+        collection_vtables: BTreeMap::new(),
     };
 
     for item in file.items {
@@ -144,6 +153,10 @@ fn parse_file_contents(file: &str) -> syn::Result<GeneratedFromSwiftBridgeModule
                     let swift = &swift_and_c.swift;
                     generated.swift += &swift;
                     generated.swift += "\n\n";
+
+                    for (key, vtable) in swift_and_c.collection_vtables {
+                        generated.collection_vtables.entry(key).or_insert(vtable);
+                    }
                 }
             }
             _ => {}
@@ -157,4 +170,6 @@ fn parse_file_contents(file: &str) -> syn::Result<GeneratedFromSwiftBridgeModule
 struct GeneratedFromSwiftBridgeModule {
     c_header: String,
     swift: String,
+    /// The `RustMapVtable`s and `RustSetVtable`s used by the file's bridge modules.
+    collection_vtables: BTreeMap<String, String>,
 }
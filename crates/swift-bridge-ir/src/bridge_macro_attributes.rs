@@ -15,6 +15,9 @@ pub enum SwiftBridgeModuleAttr {
     /// as `RustString`.
     /// `#\[swift_bridge::bridge(swift_bridge_path = swift_bridge)\]`
     SwiftBridgePath(Path),
+    /// Catch panics in the module's Rust functions instead of letting them unwind into Swift.
+    /// `#\[swift_bridge::bridge(catch_panics)\]`
+    CatchPanics,
 }
 
 impl Parse for SwiftBridgeModuleAttrs {
@@ -34,10 +37,13 @@ impl Parse for SwiftBridgeModuleAttrs {
 impl Parse for SwiftBridgeModuleAttr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let key: Ident = input.parse()?;
-        let _equals = input.parse::<Token![=]>()?;
 
         let attr = match key.to_string().as_str() {
-            "swift_bridge_path" => SwiftBridgeModuleAttr::SwiftBridgePath(input.parse()?),
+            "swift_bridge_path" => {
+                input.parse::<Token![=]>()?;
+                SwiftBridgeModuleAttr::SwiftBridgePath(input.parse()?)
+            }
+            "catch_panics" => SwiftBridgeModuleAttr::CatchPanics,
             _ => {
                 return Err(syn::Error::new(input.span(), "Unknown attribute."));
             }
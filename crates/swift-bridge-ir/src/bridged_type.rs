@@ -7,16 +7,20 @@ use quote::ToTokens;
 use quote::{quote, quote_spanned};
 use syn::{FnArg, Pat, PatType, Path, ReturnType, Type};
 
-pub(crate) use self::bridged_opaque_type::OpaqueForeignType;
-use crate::bridged_type::boxed_fn::BridgeableBoxedFnOnce;
+pub(crate) use self::bridged_opaque_type::{OpaqueForeignType, TraitObjectPointer};
+use crate::bridged_type::boxed_fn::BridgeableBoxedFn;
 use crate::bridged_type::bridgeable_pointer::{BuiltInPointer, Pointee, PointerKind};
 use crate::bridged_type::bridgeable_result::BuiltInResult;
 use crate::bridged_type::bridgeable_string::BridgedString;
+use crate::bridged_type::bridgeable_swift_error::BridgedSwiftError;
+use crate::bridged_type::built_in_map::BuiltInMap;
+use crate::bridged_type::built_in_set::BuiltInSet;
 use crate::bridged_type::built_in_tuple::BuiltInTuple;
 
 use crate::parse::{HostLang, TypeDeclaration, TypeDeclarations};
 
 use self::bridged_option::BridgedOption;
+pub(crate) use self::collection_item::collection_item_ref;
 pub(crate) use self::shared_enum::{DeriveAttrs, EnumVariant, SharedEnum};
 pub(crate) use self::shared_struct::{SharedStruct, StructFields, StructSwiftRepr};
 
@@ -25,10 +29,14 @@ mod bridgeable_pointer;
 mod bridgeable_result;
 pub mod bridgeable_str;
 pub mod bridgeable_string;
+mod bridgeable_swift_error;
 pub mod bridged_opaque_type;
 mod bridged_option;
+mod built_in_map;
 mod built_in_primitive;
+mod built_in_set;
 mod built_in_tuple;
+mod collection_item;
 mod shared_enum;
 pub(crate) mod shared_struct;
 
@@ -271,6 +279,9 @@ pub(crate) trait BridgeableType: Debug {
     ///  of checking the type.
     fn is_str(&self) -> bool;
 
+    /// Whether or not this is a `String`.
+    fn is_string(&self) -> bool;
+
     /// Whether or not the type is a `String`, or a type that contains an owned String such as
     /// `Option<String>` or `struct Foo { field: String }`
     /// TODO: This is temporary as we move towards using this trait.. We should look at how
@@ -285,6 +296,15 @@ pub(crate) trait BridgeableType: Debug {
     ///  of checking the type.
     fn contains_ref_string_recursive(&self) -> bool;
 
+    /// Whether or not the type is an `Arc<SomeType>` of the given opaque Rust type, or a type that
+    /// contains one such as `Option<Arc<SomeType>>` or `struct Foo { field: Arc<SomeType> }`.
+    fn contains_arc_of_opaque_type(&self, opaque_ty: &Ident, types: &TypeDeclarations) -> bool;
+
+    /// Call `visit` with every `HashMap`, `BTreeMap`, `HashSet` and `BTreeSet` within the type.
+    /// Most types can't contain one.
+    fn visit_maps_and_sets(&self, _types: &TypeDeclarations, _visit: &mut dyn FnMut(&BridgedType)) {
+    }
+
     /// Parse the type from a `FnArg`.
     fn from_fn_arg(
         fn_arg: &FnArg,
@@ -327,6 +347,12 @@ pub(crate) fn bridgeable_type_from_token_stream_str(
         return BridgedString::parse_token_stream_str(tokens, types).map(|o| Box::new(o) as _);
     }
 
+    // A type that was declared in the bridge module takes precedence over the built-in
+    // `SwiftError`.
+    if BridgedSwiftError::can_parse_token_stream_str(tokens) && types.get(tokens).is_none() {
+        return BridgedSwiftError::parse_token_stream_str(tokens, types).map(|o| Box::new(o) as _);
+    }
+
     OpaqueForeignType::parse_token_stream_str(tokens, types).map(|o| Box::new(o) as _)
 }
 
@@ -386,7 +412,11 @@ pub(crate) enum StdLibType {
     /// &str
     Str,
     Vec(BuiltInVec),
-    BoxedFnOnce(BridgeableBoxedFnOnce),
+    /// `HashMap<K, V>` or `BTreeMap<K, V>`
+    Map(BuiltInMap),
+    /// `HashSet<T>` or `BTreeSet<T>`
+    Set(BuiltInSet),
+    BoxedFn(BridgeableBoxedFn),
     Option(BridgedOption),
     Result(BuiltInResult),
     Tuple(BuiltInTuple),
@@ -488,6 +518,8 @@ impl BridgeableType for BridgedType {
     fn is_passed_via_pointer(&self) -> bool {
         match self {
             BridgedType::StdLib(StdLibType::Vec(_)) => true,
+            BridgedType::StdLib(StdLibType::Map(_)) => true,
+            BridgedType::StdLib(StdLibType::Set(_)) => true,
             BridgedType::StdLib(_) => false,
             BridgedType::Foreign(_) => false,
             BridgedType::Bridgeable(ty) => ty.is_passed_via_pointer(),
@@ -507,6 +539,8 @@ impl BridgeableType for BridgedType {
                 StdLibType::Tuple(ty) => {
                     ty.generate_custom_rust_ffi_types(swift_bridge_path, types)
                 }
+                StdLibType::Map(ty) => ty.generate_custom_rust_ffi_types(swift_bridge_path, types),
+                StdLibType::Set(ty) => ty.generate_custom_rust_ffi_types(swift_bridge_path, types),
                 _ => None,
             },
             BridgedType::Foreign(_) => None,
@@ -521,6 +555,8 @@ impl BridgeableType for BridgedType {
             BridgedType::StdLib(ty) => match ty {
                 StdLibType::Result(ty) => ty.generate_custom_c_ffi_types(types),
                 StdLibType::Tuple(ty) => ty.generate_custom_c_ffi_types(types),
+                StdLibType::Map(ty) => ty.generate_custom_c_ffi_types(types),
+                StdLibType::Set(ty) => ty.generate_custom_c_ffi_types(types),
                 _ => None,
             },
             BridgedType::Foreign(_) => None,
@@ -609,12 +645,12 @@ impl BridgeableType for BridgedType {
 
     fn convert_ffi_expression_to_rust_type(
         &self,
-        _expression: &TokenStream,
-        _span: Span,
-        _swift_bridge_path: &Path,
-        _types: &TypeDeclarations,
+        expression: &TokenStream,
+        span: Span,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
     ) -> TokenStream {
-        todo!()
+        self.convert_ffi_expression_to_rust_type(expression, span, swift_bridge_path, types)
     }
 
     fn convert_ffi_option_expression_to_rust_type(&self, _expression: &TokenStream) -> TokenStream {
@@ -688,6 +724,13 @@ impl BridgeableType for BridgedType {
         }
     }
 
+    fn is_string(&self) -> bool {
+        match self {
+            BridgedType::Bridgeable(b) => b.is_string(),
+            _ => false,
+        }
+    }
+
     fn contains_owned_string_recursive(&self, types: &TypeDeclarations) -> bool {
         self.contains_owned_string_recursive(types)
     }
@@ -696,6 +739,14 @@ impl BridgeableType for BridgedType {
         todo!()
     }
 
+    fn contains_arc_of_opaque_type(&self, opaque_ty: &Ident, types: &TypeDeclarations) -> bool {
+        self.contains_arc_of_opaque_type(opaque_ty, types)
+    }
+
+    fn visit_maps_and_sets(&self, types: &TypeDeclarations, visit: &mut dyn FnMut(&BridgedType)) {
+        self.visit_maps_and_sets(types, visit)
+    }
+
     fn has_swift_bridge_copy_annotation(&self) -> bool {
         match self {
             BridgedType::Bridgeable(b) => b.has_swift_bridge_copy_annotation(),
@@ -742,6 +793,10 @@ impl BridgedType {
                 Some(ty)
             }
             Type::Reference(ty_ref) => match ty_ref.elem.deref() {
+                Type::TraitObject(_) => {
+                    OpaqueForeignType::trait_object_from_type_reference(ty_ref, types)
+                        .map(|ty| BridgedType::Bridgeable(Box::new(ty)))
+                }
                 Type::Path(p) => {
                     if let Some(ty) = types.get_with_type_path(p) {
                         Some(ty.to_bridged_type(true, ty_ref.mutability.is_some()))
@@ -804,6 +859,10 @@ impl BridgedType {
             return Some(BridgedType::StdLib(StdLibType::Vec(BuiltInVec {
                 ty: Box::new(inner),
             })));
+        } else if let Some(map) = BuiltInMap::from_str_tokens(tokens, types) {
+            return Some(BridgedType::StdLib(StdLibType::Map(map)));
+        } else if let Some(set) = BuiltInSet::from_str_tokens(tokens, types) {
+            return Some(BridgedType::StdLib(StdLibType::Set(set)));
         } else if tokens.starts_with("Option < ") {
             let last_bracket = tokens.rfind(">")?;
 
@@ -811,7 +870,8 @@ impl BridgedType {
             let inner = inner.trim_start_matches("Option < ");
 
             // Remove spaces from generics. i.e. "SomeType < u32 > " -> "SomeType<u32>"
-            let inner = if inner.contains("<") {
+            // We keep the spaces in trait objects, since "Box<dynSomeTrait>" would not parse.
+            let inner = if inner.contains("<") && !inner.contains("dyn ") {
                 inner.replace(" ", "")
             } else {
                 inner.to_string()
@@ -827,9 +887,13 @@ impl BridgedType {
             return Some(BridgedType::StdLib(StdLibType::Result(
                 BuiltInResult::from_str_tokens(&tokens, types)?,
             )));
-        } else if tokens.starts_with("Box < dyn FnOnce") {
-            return Some(BridgedType::StdLib(StdLibType::BoxedFnOnce(
-                BridgeableBoxedFnOnce::from_str_tokens(&tokens, types)?,
+        } else if let Some(ty) = OpaqueForeignType::trait_object_from_str_tokens(tokens, types) {
+            return Some(BridgedType::Bridgeable(Box::new(ty)));
+        } else if let Some(ty) = OpaqueForeignType::arc_from_str_tokens(tokens, types) {
+            return Some(BridgedType::Bridgeable(Box::new(ty)));
+        } else if tokens.starts_with("Box < dyn ") {
+            return Some(BridgedType::StdLib(StdLibType::BoxedFn(
+                BridgeableBoxedFn::from_str_tokens(&tokens, types)?,
             )));
         } else if tokens.starts_with("(") {
             let tuple: Type = syn::parse2(TokenStream::from_str(&tokens).unwrap()).unwrap();
@@ -898,12 +962,14 @@ impl BridgedType {
                     let ty = v.ty.to_rust_type_path(types);
                     quote! { Vec<#ty> }
                 }
+                StdLibType::Map(map) => map.to_rust_type_path(types),
+                StdLibType::Set(set) => set.to_rust_type_path(types),
                 StdLibType::Option(opt) => {
                     let ty = opt.ty.to_rust_type_path(types);
                     quote! { Option<#ty> }
                 }
                 StdLibType::Result(result) => result.to_rust_type_path(types),
-                StdLibType::BoxedFnOnce(fn_once) => fn_once.to_rust_type_path(types),
+                StdLibType::BoxedFn(fn_once) => fn_once.to_rust_type_path(types),
                 StdLibType::Tuple(tuple) => tuple.to_rust_type_path(types),
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
@@ -978,6 +1044,14 @@ impl BridgedType {
                     let ty = ty.ty.to_rust_type_path(types);
                     quote! { *mut Vec<#ty> }
                 }
+                StdLibType::Map(map) => {
+                    let ty = map.to_rust_type_path(types);
+                    quote! { *mut #ty }
+                }
+                StdLibType::Set(set) => {
+                    let ty = set.to_rust_type_path(types);
+                    quote! { *mut #ty }
+                }
                 StdLibType::Option(opt) => match opt.ty.deref() {
                     BridgedType::Bridgeable(b) => {
                         b.to_ffi_compatible_option_rust_type(swift_bridge_path, types)
@@ -1038,13 +1112,21 @@ impl BridgedType {
                             let ty = ty.ty.to_rust_type_path(types);
                             quote! { *mut Vec<#ty> }
                         }
+                        StdLibType::Map(map) => {
+                            let ty = map.to_rust_type_path(types);
+                            quote! { *mut #ty }
+                        }
+                        StdLibType::Set(set) => {
+                            let ty = set.to_rust_type_path(types);
+                            quote! { *mut #ty }
+                        }
                         StdLibType::Option(_) => {
                             todo!("Option<Option<T>> is not yet supported")
                         }
                         StdLibType::Result(_) => {
                             todo!("Option<Result<T, E>> is not yet supported")
                         }
-                        StdLibType::BoxedFnOnce(_) => {
+                        StdLibType::BoxedFn(_) => {
                             todo!("Support Box<dyn FnOnce(A, B) -> C>")
                         }
                         StdLibType::Tuple(_) => todo!(),
@@ -1065,7 +1147,7 @@ impl BridgedType {
                 StdLibType::Result(result) => {
                     result.to_ffi_compatible_rust_type(swift_bridge_path, types)
                 }
-                StdLibType::BoxedFnOnce(fn_once) => fn_once.to_ffi_compatible_rust_type(types),
+                StdLibType::BoxedFn(fn_once) => fn_once.to_ffi_compatible_rust_type(types),
                 StdLibType::Tuple(tuple) => {
                     tuple.to_ffi_compatible_rust_type(swift_bridge_path, types)
                 }
@@ -1183,9 +1265,27 @@ impl BridgedType {
                         format!("RustVec<{}>", ty.ty.to_swift_type(type_pos, types))
                     }
                 },
+                StdLibType::Map(map) => match type_pos {
+                    TypePosition::FnArg(func_host_lang, _)
+                    | TypePosition::FnReturn(func_host_lang)
+                        if func_host_lang.is_swift() =>
+                    {
+                        "UnsafeMutableRawPointer".to_string()
+                    }
+                    _ => map.to_swift_type(types),
+                },
+                StdLibType::Set(set) => match type_pos {
+                    TypePosition::FnArg(func_host_lang, _)
+                    | TypePosition::FnReturn(func_host_lang)
+                        if func_host_lang.is_swift() =>
+                    {
+                        "UnsafeMutableRawPointer".to_string()
+                    }
+                    _ => set.to_swift_type(types),
+                },
                 StdLibType::Option(opt) => opt.to_swift_type(type_pos, types),
                 StdLibType::Result(result) => result.to_swift_type(type_pos, types),
-                StdLibType::BoxedFnOnce(boxed_fn) => boxed_fn.to_swift_type().to_string(),
+                StdLibType::BoxedFn(boxed_fn) => boxed_fn.to_swift_type(type_pos, types),
                 StdLibType::Tuple(tuple) => tuple.to_swift_type(type_pos, types),
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
@@ -1257,9 +1357,11 @@ impl BridgedType {
                 StdLibType::Str => "struct RustStr".to_string(),
                 StdLibType::Null => "void".to_string(),
                 StdLibType::Vec(_) => "void*".to_string(),
+                StdLibType::Map(_) => "void*".to_string(),
+                StdLibType::Set(_) => "void*".to_string(),
                 StdLibType::Option(opt) => opt.to_c(),
                 StdLibType::Result(result) => result.to_c(types).to_string(),
-                StdLibType::BoxedFnOnce(_) => "void*".to_string(),
+                StdLibType::BoxedFn(_) => "void*".to_string(),
                 StdLibType::Tuple(tuple) => tuple.to_c_type(types),
             },
             BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
@@ -1350,7 +1452,7 @@ impl BridgedType {
                         #swift_bridge_path::string::RustStr::from_str( #expression )
                     }
                 }
-                StdLibType::Vec(_) => {
+                StdLibType::Vec(_) | StdLibType::Map(_) | StdLibType::Set(_) => {
                     quote! { Box::into_raw(Box::new( #expression )) }
                 }
                 StdLibType::Option(opt) => {
@@ -1362,7 +1464,7 @@ impl BridgedType {
                     types,
                     span,
                 ),
-                StdLibType::BoxedFnOnce(fn_once) => {
+                StdLibType::BoxedFn(fn_once) => {
                     fn_once.convert_rust_value_to_ffi_compatible_value(expression, types)
                 }
                 StdLibType::Tuple(tuple) => tuple.convert_rust_expression_to_ffi_type(
@@ -1434,7 +1536,7 @@ impl BridgedType {
                 StdLibType::Str => {
                     quote_spanned! {span=> #value.to_str() }
                 }
-                StdLibType::Vec(_) => {
+                StdLibType::Vec(_) | StdLibType::Map(_) | StdLibType::Set(_) => {
                     quote_spanned! {span=>
                         unsafe { * Box::from_raw(#value) }
                     }
@@ -1445,7 +1547,7 @@ impl BridgedType {
                 StdLibType::Result(result) => {
                     result.convert_ffi_value_to_rust_value(value, span, swift_bridge_path, types)
                 }
-                StdLibType::BoxedFnOnce(_) => {
+                StdLibType::BoxedFn(_) => {
                     todo!("Support Box<dyn FnOnce(A, B) -> C>")
                 }
                 StdLibType::Tuple(tuple) => {
@@ -1537,13 +1639,15 @@ impl BridgedType {
                 StdLibType::Vec(_ty) => {
                     format!("RustVec(ptr: {})", expression)
                 }
-                StdLibType::Option(opt) => opt.convert_ffi_expression_to_swift_type(expression),
+                StdLibType::Map(map) => map.convert_ffi_value_to_swift_value(expression, types),
+                StdLibType::Set(set) => set.convert_ffi_value_to_swift_value(expression, types),
+                StdLibType::Option(opt) => {
+                    opt.convert_ffi_expression_to_swift_type(expression, types)
+                }
                 StdLibType::Result(result) => {
                     result.convert_ffi_value_to_swift_value(expression, type_pos, types)
                 }
-                StdLibType::BoxedFnOnce(fn_once) => {
-                    fn_once.convert_ffi_value_to_swift_value(type_pos)
-                }
+                StdLibType::BoxedFn(fn_once) => fn_once.convert_ffi_value_to_swift_value(type_pos),
                 StdLibType::Tuple(tuple) => {
                     tuple.convert_ffi_expression_to_swift_type(expression, type_pos, types)
                 }
@@ -1627,7 +1731,7 @@ impl BridgedType {
                         unimplemented!()
                     }
                 },
-                StdLibType::Vec(_) => {
+                StdLibType::Vec(_) | StdLibType::Map(_) | StdLibType::Set(_) => {
                     format!(
                         "{{ let val = {value}; val.isOwned = false; return val.ptr }}()",
                         value = expression
@@ -1639,7 +1743,7 @@ impl BridgedType {
                 StdLibType::Result(result) => {
                     result.convert_swift_expression_to_ffi_compatible(expression, types, type_pos)
                 }
-                StdLibType::BoxedFnOnce(_) => {
+                StdLibType::BoxedFn(_) => {
                     todo!("Support Box<dyn FnOnce(A, B) -> C>")
                 }
                 StdLibType::Tuple(tuple) => {
@@ -1706,6 +1810,8 @@ impl BridgedType {
                 },
                 StdLibType::RefSlice(slice) => slice.ty.to_c_include(types),
                 StdLibType::Vec(_vec) => Some(vec!["stdint.h"]),
+                StdLibType::Map(_map) => Some(vec!["stdint.h", "stdbool.h"]),
+                StdLibType::Set(_set) => Some(vec!["stdint.h", "stdbool.h"]),
                 StdLibType::Tuple(tuple) => tuple.to_c_include(types),
                 _ => None,
             },
@@ -1771,13 +1877,18 @@ impl BridgedType {
                 StdLibType::Vec(_) => {
                     todo!("Support Option<Vec<T>>")
                 }
+                // Maps and sets are passed as pointers, so a null pointer means `None`.
+                StdLibType::Map(_) | StdLibType::Set(_) => UnusedOptionNoneValue {
+                    rust: quote! { std::ptr::null_mut() },
+                    swift: "nil".to_string(),
+                },
                 StdLibType::Option(_) => {
                     todo!("Support nested Option<Option<T>>")
                 }
                 StdLibType::Result(_) => {
                     todo!("Result<T, E> is not yet supported")
                 }
-                StdLibType::BoxedFnOnce(_) => {
+                StdLibType::BoxedFn(_) => {
                     todo!("Support Box<dyn FnOnce(A, B) -> C>")
                 }
                 StdLibType::Tuple(_tuple) => todo!(),
@@ -1806,6 +1917,9 @@ impl BridgedType {
             BridgedType::Bridgeable(b) => b.contains_owned_string_recursive(types),
             BridgedType::StdLib(stdlib_type) => match stdlib_type {
                 StdLibType::Vec(inner) => inner.ty.contains_owned_string_recursive(types),
+                // A `RustMap<RustString, V>` or `RustSet<RustString>` is passed as is, so it
+                // doesn't need any generics.
+                StdLibType::Map(_) | StdLibType::Set(_) => false,
                 StdLibType::Option(inner) => inner.ty.contains_owned_string_recursive(types),
                 StdLibType::Result(inner) => {
                     inner.ok_ty.contains_owned_string_recursive(types)
@@ -1847,6 +1961,84 @@ impl BridgedType {
         }
     }
 
+    /// Whether or not the type is an `Arc<SomeType>` of the given opaque Rust type, or a type that
+    /// contains one such as `Option<Arc<SomeType>>` or `struct Foo { field: Arc<SomeType> }`.
+    pub fn contains_arc_of_opaque_type(&self, opaque_ty: &Ident, types: &TypeDeclarations) -> bool {
+        match self {
+            BridgedType::Bridgeable(b) => b.contains_arc_of_opaque_type(opaque_ty, types),
+            BridgedType::StdLib(stdlib_type) => match stdlib_type {
+                StdLibType::Vec(inner) => inner.ty.contains_arc_of_opaque_type(opaque_ty, types),
+                StdLibType::RefSlice(inner) => {
+                    inner.ty.contains_arc_of_opaque_type(opaque_ty, types)
+                }
+                StdLibType::Map(map) => {
+                    map.key.contains_arc_of_opaque_type(opaque_ty, types)
+                        || map.value.contains_arc_of_opaque_type(opaque_ty, types)
+                }
+                StdLibType::Set(set) => set.ty.contains_arc_of_opaque_type(opaque_ty, types),
+                StdLibType::BoxedFn(boxed_fn) => {
+                    boxed_fn
+                        .params
+                        .iter()
+                        .any(|param| param.contains_arc_of_opaque_type(opaque_ty, types))
+                        || boxed_fn.ret.contains_arc_of_opaque_type(opaque_ty, types)
+                }
+                StdLibType::Option(inner) => inner.ty.contains_arc_of_opaque_type(opaque_ty, types),
+                StdLibType::Result(inner) => {
+                    inner.ok_ty.contains_arc_of_opaque_type(opaque_ty, types)
+                        || inner.err_ty.contains_arc_of_opaque_type(opaque_ty, types)
+                }
+                StdLibType::Tuple(ty) => ty.contains_arc_of_opaque_type(opaque_ty, types),
+                _ => false,
+            },
+            BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
+                shared_struct
+                    .fields
+                    .contains_arc_of_opaque_type(opaque_ty, types)
+            }
+            BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Enum(shared_enum))) => {
+                shared_enum
+                    .variants
+                    .iter()
+                    .any(|variant| variant.fields.contains_arc_of_opaque_type(opaque_ty, types))
+            }
+        }
+    }
+
+    /// Call `visit` with every `HashMap`, `BTreeMap`, `HashSet` and `BTreeSet` within the type,
+    /// including ones that are nested in an `Option`, a `Result`, a tuple or a shared type's
+    /// fields.
+    pub fn visit_maps_and_sets(
+        &self,
+        types: &TypeDeclarations,
+        visit: &mut dyn FnMut(&BridgedType),
+    ) {
+        if let Some(result) = self.as_result() {
+            result.ok_ty.visit_maps_and_sets(types, visit);
+            result.err_ty.visit_maps_and_sets(types, visit);
+            return;
+        }
+
+        match self {
+            BridgedType::StdLib(StdLibType::Map(_) | StdLibType::Set(_)) => visit(self),
+            BridgedType::StdLib(StdLibType::Option(inner)) => {
+                inner.ty.visit_maps_and_sets(types, visit)
+            }
+            BridgedType::StdLib(StdLibType::Tuple(tuple)) => {
+                tuple.visit_maps_and_sets(types, visit)
+            }
+            BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
+                shared_struct.fields.visit_maps_and_sets(types, visit)
+            }
+            BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Enum(shared_enum))) => {
+                for variant in shared_enum.variants.iter() {
+                    variant.fields.visit_maps_and_sets(types, visit);
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Convert a rust expression into this type using
     pub fn rust_expression_into(&self, expression: &TokenStream) -> TokenStream {
         match self {
@@ -1892,15 +2084,19 @@ impl BridgedType {
                 StdLibType::U8 => "U8".to_string(),
                 StdLibType::U16 => "U16".to_string(),
                 StdLibType::U32 => "U32".to_string(),
+                StdLibType::U64 => "U64".to_string(),
                 StdLibType::Usize => "UInt".to_string(),
                 StdLibType::I8 => "I8".to_string(),
                 StdLibType::I16 => "I16".to_string(),
                 StdLibType::I32 => "I32".to_string(),
+                StdLibType::I64 => "I64".to_string(),
                 StdLibType::Isize => "Int".to_string(),
                 StdLibType::Bool => "Bool".to_string(),
                 StdLibType::F32 => "F32".to_string(),
                 StdLibType::F64 => "F64".to_string(),
                 StdLibType::Tuple(ty) => ty.to_alpha_numeric_underscore_name(types),
+                StdLibType::Map(ty) => ty.to_alpha_numeric_underscore_name(types),
+                StdLibType::Set(ty) => ty.to_alpha_numeric_underscore_name(types),
                 _ => todo!(),
             },
             BridgedType::Foreign(ty) => match ty {
@@ -1942,7 +2138,7 @@ mod tests {
 
         let parsed = BridgedType::new_with_str(tokens, &TypeDeclarations::default()).unwrap();
         match parsed {
-            BridgedType::StdLib(StdLibType::BoxedFnOnce(_)) => {}
+            BridgedType::StdLib(StdLibType::BoxedFn(_)) => {}
             _ => panic!(),
         };
     }
@@ -6,43 +6,95 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens};
 use std::collections::HashSet;
 use std::str::FromStr;
-use syn::parse::{Parse, ParseStream};
-use syn::punctuated::Punctuated;
-use syn::{Path, Type};
+use syn::{GenericArgument, Path, PathArguments, ReturnType, Type, TypeParamBound};
 
-/// Box<dyn FnOnce(A, B, C) -> ()>
+/// Box<dyn FnOnce(A, B, C) -> ()>, Box<dyn Fn(A) -> B + Send>, Box<dyn FnMut()>, etc.
 #[derive(Debug)]
-pub(crate) struct BridgeableBoxedFnOnce {
+pub(crate) struct BridgeableBoxedFn {
+    /// Whether this is an `FnOnce`, `Fn` or `FnMut`.
+    pub kind: BoxedFnKind,
     /// The functions parameters.
     pub params: Vec<BridgedType>,
     /// The functions return type.
     pub ret: Box<BridgedType>,
+    /// Auto trait bounds such as `Send` or `Sync`.
+    pub auto_traits: Vec<Ident>,
 }
 
-/// example: Vec<SomeType, AnotherType, u32>
-pub(crate) struct FunctionArguments(pub Vec<Type>);
-impl Parse for FunctionArguments {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let args: Punctuated<Type, syn::Token![,]> = Punctuated::parse_terminated(input)?;
-        Ok(Self(args.into_iter().collect()))
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum BoxedFnKind {
+    /// Can only be called once, after which the Rust closure is freed.
+    FnOnce,
+    /// Can be called any number of times.
+    Fn,
+    /// Can be called any number of times, but never concurrently.
+    FnMut,
+}
+
+impl BoxedFnKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BoxedFnKind::FnOnce => "FnOnce",
+            BoxedFnKind::Fn => "Fn",
+            BoxedFnKind::FnMut => "FnMut",
+        }
     }
 }
 
-impl BridgeableBoxedFnOnce {
+impl BridgeableBoxedFn {
     pub fn does_not_have_params_or_return(&self) -> bool {
         self.params.is_empty() && self.ret.is_null()
     }
 
+    /// Whether or not we use the pre-defined `__private__RustFnOnceCallbackNoArgsNoRet` Swift
+    /// class for this function, instead of generating a class for it.
+    pub fn uses_no_args_no_return_support(&self) -> bool {
+        self.kind == BoxedFnKind::FnOnce && self.does_not_have_params_or_return()
+    }
+
+    /// The name of the Swift class that wraps this function.
+    ///
+    /// __private__RustFnOnceCallback, __private__RustFnCallback or __private__RustFnMutCallback
+    pub fn swift_callback_class_prefix(&self) -> String {
+        format!("__private__Rust{}Callback", self.kind.as_str())
+    }
+
     /// Box<dyn FnOnce(A, B) -> C>
     pub fn to_rust_type_path(&self, types: &TypeDeclarations) -> TokenStream {
+        let dyn_fn = self.dyn_fn_tokens(types);
+        quote! {
+            Box<#dyn_fn>
+        }
+    }
+
+    /// dyn Fn(A, B) -> C + Send
+    fn dyn_fn_tokens(&self, types: &TypeDeclarations) -> TokenStream {
+        let kind = Ident::new(self.kind.as_str(), Span::call_site());
         let args: Vec<TokenStream> = self
             .params
             .iter()
             .map(|a| a.to_rust_type_path(types))
             .collect();
         let ret = &self.ret.to_rust_type_path(types);
+        let auto_traits = &self.auto_traits;
+
         quote! {
-            Box<dyn FnOnce(#(#args),*) -> #ret>
+            dyn #kind(#(#args),*) -> #ret #(+ #auto_traits)*
+        }
+    }
+
+    /// Call the boxed function that the `expression` pointer points to.
+    ///
+    /// An `FnOnce` is consumed by the call, while an `Fn` or `FnMut` is borrowed so that it
+    /// can be called again.
+    pub fn call_boxed_fn_ptr(&self, expression: &TokenStream, args: &[TokenStream]) -> TokenStream {
+        match self.kind {
+            BoxedFnKind::FnOnce => quote! {
+                unsafe { Box::from_raw(#expression)(#(#args),*) }
+            },
+            BoxedFnKind::Fn | BoxedFnKind::FnMut => quote! {
+                unsafe { (*#expression)(#(#args),*) }
+            },
         }
     }
 
@@ -51,27 +103,17 @@ impl BridgeableBoxedFnOnce {
         expression: &TokenStream,
         types: &TypeDeclarations,
     ) -> TokenStream {
-        let args: Vec<TokenStream> = self
-            .params
-            .iter()
-            .map(|a| a.to_rust_type_path(types))
-            .collect();
-        let ret = &self.ret.to_rust_type_path(types);
+        let dyn_fn = self.dyn_fn_tokens(types);
 
         quote! {
-            Box::into_raw(Box::new(#expression)) as *mut Box<dyn FnOnce(#(#args),*) -> #ret>
+            Box::into_raw(Box::new(#expression)) as *mut Box<#dyn_fn>
         }
     }
 
     pub fn to_ffi_compatible_rust_type(&self, types: &TypeDeclarations) -> TokenStream {
-        let params: Vec<TokenStream> = self
-            .params
-            .iter()
-            .map(|a| a.to_rust_type_path(types))
-            .collect();
-        let ret = &self.ret.to_rust_type_path(types);
+        let dyn_fn = self.dyn_fn_tokens(types);
         quote! {
-            *mut Box<dyn FnOnce(#(#params),*) -> #ret>
+            *mut Box<#dyn_fn>
         }
     }
 
@@ -188,14 +230,145 @@ impl BridgeableBoxedFnOnce {
         args
     }
 
-    pub fn to_swift_type(&self) -> &'static str {
-        "UnsafeMutableRawPointer"
+    /// A Swift closure when Swift passes the function to Rust, otherwise a pointer to the boxed
+    /// Rust function.
+    pub fn to_swift_type(&self, type_pos: TypePosition, types: &TypeDeclarations) -> String {
+        match type_pos {
+            TypePosition::FnArg(HostLang::Rust, _) => {
+                format!("@escaping {}", self.to_swift_closure_type(types))
+            }
+            _ => "UnsafeMutableRawPointer".to_string(),
+        }
+    }
+
+    /// Whether or not the function has a `Send` or `Sync` bound, meaning that Rust can call it
+    /// from other threads.
+    pub fn is_sendable(&self) -> bool {
+        !self.auto_traits.is_empty()
+    }
+
+    /// The type of a Swift closure that Swift passes to Rust.
+    ///
+    /// For example, `Box<dyn Fn(u8, SomeType) -> u16>` would become:
+    /// "(UInt8, SomeType) -> UInt16"
+    ///
+    /// Functions with a `Send` or `Sync` bound become `@Sendable` closures, such as
+    /// "@Sendable (UInt8, SomeType) -> UInt16".
+    pub fn to_swift_closure_type(&self, types: &TypeDeclarations) -> String {
+        let params = self
+            .params
+            .iter()
+            .map(|ty| ty.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let ret = self
+            .ret
+            .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+
+        let maybe_sendable = if self.is_sendable() { "@Sendable " } else { "" };
+
+        format!("{maybe_sendable}({params}) -> {ret}")
+    }
+
+    /// Convert a pointer to a retained Swift closure into a boxed Rust function that calls the
+    /// closure.
+    ///
+    /// The Swift closure gets released when the boxed function is dropped.
+    ///
+    /// For example, `Box<dyn Fn(u8) -> u16>` would become:
+    ///
+    /// ```no_run,ignore
+    /// {
+    ///     extern "C" {
+    ///         #[link_name = "__swift_bridge__$some_function$param0"]
+    ///         fn call_swift_callback(callback: *mut std::ffi::c_void, arg0: u8) -> u16;
+    ///         #[link_name = "__swift_bridge__$some_function$_free$param0"]
+    ///         fn free_swift_callback(callback: *mut std::ffi::c_void);
+    ///     }
+    ///     let callback = unsafe {
+    ///         swift_bridge::boxed_fn_support::SwiftCallback::new(callback, free_swift_callback)
+    ///     };
+    ///     Box::new(move |arg0: u8| unsafe { call_swift_callback(callback.as_ptr(), arg0) })
+    /// }
+    /// ```
+    ///
+    /// Functions with a `Send` or `Sync` bound use a `SendableSwiftCallback` instead, since
+    /// Swift passes them as `@Sendable` closures.
+    pub fn convert_swift_callback_to_rust_boxed_fn(
+        &self,
+        expression: &TokenStream,
+        call_link_name: &str,
+        free_link_name: &str,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        let ffi_params = self.params_to_ffi_compatible_rust_types(swift_bridge_path, types);
+
+        let mut closure_params = vec![];
+        let mut call_args = vec![];
+        for (idx, ty) in self.params.iter().enumerate() {
+            let arg_name = Ident::new(&format!("arg{}", idx), Span::call_site());
+            let arg_ty = ty.to_rust_type_path(types);
+
+            closure_params.push(quote! { #arg_name: #arg_ty });
+            call_args.push(ty.convert_rust_expression_to_ffi_type(
+                &arg_name.to_token_stream(),
+                swift_bridge_path,
+                types,
+                Span::call_site(),
+            ));
+        }
+
+        let maybe_ret = if self.ret.is_null() {
+            quote! {}
+        } else {
+            let ret = self
+                .ret
+                .to_ffi_compatible_rust_type(swift_bridge_path, types);
+            quote! { -> #ret }
+        };
+
+        let callback_ty = if self.is_sendable() {
+            quote! { SendableSwiftCallback }
+        } else {
+            quote! { SwiftCallback }
+        };
+
+        let call = quote! {
+            unsafe { call_swift_callback(callback.as_ptr() #(, #call_args)*) }
+        };
+        let call = self.ret.convert_ffi_expression_to_rust_type(
+            &call,
+            Span::call_site(),
+            swift_bridge_path,
+            types,
+        );
+
+        quote! {
+            {
+                extern "C" {
+                    #[link_name = #call_link_name]
+                    fn call_swift_callback(callback: *mut std::ffi::c_void #(, #ffi_params)*) #maybe_ret;
+
+                    #[link_name = #free_link_name]
+                    fn free_swift_callback(callback: *mut std::ffi::c_void);
+                }
+
+                let callback = unsafe {
+                    #swift_bridge_path::boxed_fn_support::#callback_ty::new(
+                        #expression,
+                        free_swift_callback
+                    )
+                };
+                Box::new(move |#(#closure_params),*| #call)
+            }
+        }
     }
 
     pub fn convert_ffi_value_to_swift_value(&self, type_pos: TypePosition) -> String {
         match type_pos {
             TypePosition::FnArg(_, param_idx) => {
-                if self.does_not_have_params_or_return() {
+                if self.uses_no_args_no_return_support() {
                     format!("{{ cb{param_idx}.call() }}")
                 } else if self.params.len() > 0 {
                     let args = self.to_swift_call_args();
@@ -240,45 +413,74 @@ impl BridgeableBoxedFnOnce {
     }
 }
 
-impl BridgeableBoxedFnOnce {
+impl BridgeableBoxedFn {
+    /// Parse a boxed function such as `Box < dyn FnOnce (A , B) -> C >` or
+    /// `Box < dyn Fn (A) + Send >`.
     pub fn from_str_tokens(string: &str, types: &TypeDeclarations) -> Option<Self> {
-        // ( A , B , C ) -> D >
-        //   OR
-        // ( A , B , C ) >
-        let signature = string.trim_start_matches("Box < dyn FnOnce");
+        let ty: Type = syn::parse2(TokenStream::from_str(string).ok()?).ok()?;
 
-        let open_parens = signature.find("(").unwrap();
-        let closing_parens = signature.find(")").unwrap();
-        // A, B, C
-        let args = &signature[open_parens + 1..closing_parens];
-
-        let return_idx = signature.rfind("->");
-
-        // D
-        let ret = return_idx.map(|idx| &signature[(idx + 3)..signature.len() - 2]);
+        let dyn_fn = match ty {
+            Type::Path(path) => {
+                let boxed = path.path.segments.last()?;
+                if boxed.ident != "Box" {
+                    return None;
+                }
+                match &boxed.arguments {
+                    PathArguments::AngleBracketed(args) => match args.args.first()? {
+                        GenericArgument::Type(Type::TraitObject(dyn_fn)) => dyn_fn.clone(),
+                        _ => return None,
+                    },
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
 
-        let args = TokenStream::from_str(args).unwrap();
-        let args: FunctionArguments = syn::parse2(args).unwrap();
+        let mut function = None;
+        let mut auto_traits = vec![];
+
+        for bound in dyn_fn.bounds {
+            let bound = match bound {
+                TypeParamBound::Trait(bound) => bound,
+                _ => return None,
+            };
+            let segment = bound.path.segments.last()?;
+
+            match segment.ident.to_string().as_str() {
+                "FnOnce" | "Fn" | "FnMut" => {
+                    let kind = match segment.ident.to_string().as_str() {
+                        "FnOnce" => BoxedFnKind::FnOnce,
+                        "Fn" => BoxedFnKind::Fn,
+                        _ => BoxedFnKind::FnMut,
+                    };
+                    let sig = match &segment.arguments {
+                        PathArguments::Parenthesized(sig) => sig.clone(),
+                        _ => return None,
+                    };
+                    function = Some((kind, sig));
+                }
+                "Send" | "Sync" => auto_traits.push(segment.ident.clone()),
+                _ => return None,
+            }
+        }
 
-        let ret = if let Some(ret) = ret {
-            // Parse out the comma in:
-            //   Box<dyn FnOnce() -> (),>
-            let ret = ret.trim_end_matches(",");
+        let (kind, sig) = function?;
 
-            let ret = syn::parse2::<Type>(TokenStream::from_str(ret).unwrap()).unwrap();
-            BridgedType::new_with_type(&ret, types)?
-        } else {
-            BridgedType::StdLib(StdLibType::Null)
+        let ret = match sig.output {
+            ReturnType::Default => BridgedType::StdLib(StdLibType::Null),
+            ReturnType::Type(_, ret) => BridgedType::new_with_type(&ret, types)?,
         };
 
-        let mut args_bridged_tys = Vec::with_capacity(args.0.len());
-        for arg in args.0 {
+        let mut args_bridged_tys = Vec::with_capacity(sig.inputs.len());
+        for arg in sig.inputs {
             args_bridged_tys.push(BridgedType::new_with_type(&arg, types)?);
         }
 
-        return Some(BridgeableBoxedFnOnce {
+        return Some(BridgeableBoxedFn {
+            kind,
             params: args_bridged_tys,
             ret: Box::new(ret),
+            auto_traits,
         });
     }
 }
@@ -293,7 +495,7 @@ mod tests {
         let tokens = quote! {Box<dyn FnOnce()>}.to_token_stream().to_string();
 
         assert!(
-            BridgeableBoxedFnOnce::from_str_tokens(&tokens, &TypeDeclarations::default())
+            BridgeableBoxedFn::from_str_tokens(&tokens, &TypeDeclarations::default())
                 .unwrap()
                 .ret
                 .is_null()
@@ -308,7 +510,7 @@ mod tests {
             .to_string();
 
         assert!(matches!(
-            *BridgeableBoxedFnOnce::from_str_tokens(&tokens, &TypeDeclarations::default())
+            *BridgeableBoxedFn::from_str_tokens(&tokens, &TypeDeclarations::default())
                 .unwrap()
                 .ret,
             BridgedType::StdLib(StdLibType::U8)
@@ -323,7 +525,7 @@ mod tests {
             .to_string();
 
         assert!(
-            BridgeableBoxedFnOnce::from_str_tokens(&tokens, &TypeDeclarations::default())
+            BridgeableBoxedFn::from_str_tokens(&tokens, &TypeDeclarations::default())
                 .unwrap()
                 .ret
                 .is_null(),
@@ -339,7 +541,7 @@ mod tests {
         let tokens = "Box < dyn FnOnce() -> () >";
 
         assert!(
-            BridgeableBoxedFnOnce::from_str_tokens(tokens, &TypeDeclarations::default())
+            BridgeableBoxedFn::from_str_tokens(tokens, &TypeDeclarations::default())
                 .unwrap()
                 .ret
                 .is_null(),
@@ -364,11 +566,58 @@ mod tests {
             let tokens = test.to_token_stream().to_string();
 
             assert!(
-                BridgeableBoxedFnOnce::from_str_tokens(&tokens, &TypeDeclarations::default())
+                BridgeableBoxedFn::from_str_tokens(&tokens, &TypeDeclarations::default())
                     .unwrap()
                     .ret
                     .is_null(),
             );
         }
     }
+
+    /// Verify that we can parse `Fn` and `FnMut` boxed fns along with their auto trait bounds.
+    #[test]
+    fn boxed_fn_kinds_and_auto_traits() {
+        let tests = vec![
+            (quote! {Box<dyn Fn(u8)>}, BoxedFnKind::Fn, vec![]),
+            (quote! {Box<dyn FnMut() -> u8>}, BoxedFnKind::FnMut, vec![]),
+            (
+                quote! {Box<dyn Fn(u8) -> u8 + Send>},
+                BoxedFnKind::Fn,
+                vec!["Send"],
+            ),
+            (
+                quote! {Box<dyn FnOnce() + Send + Sync>},
+                BoxedFnKind::FnOnce,
+                vec!["Send", "Sync"],
+            ),
+        ];
+
+        for (tokens, expected_kind, expected_auto_traits) in tests {
+            let boxed_fn = BridgeableBoxedFn::from_str_tokens(
+                &tokens.to_string(),
+                &TypeDeclarations::default(),
+            )
+            .unwrap();
+
+            assert_eq!(boxed_fn.kind, expected_kind);
+            assert_eq!(
+                boxed_fn
+                    .auto_traits
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>(),
+                expected_auto_traits
+            );
+        }
+    }
+
+    /// Verify that we do not treat a boxed trait object that isn't a function as a boxed fn.
+    #[test]
+    fn boxed_non_fn_trait_object_is_not_a_boxed_fn() {
+        let tokens = quote! {Box<dyn Debug + Send>}.to_string();
+
+        assert!(
+            BridgeableBoxedFn::from_str_tokens(&tokens, &TypeDeclarations::default()).is_none()
+        );
+    }
 }
@@ -3,7 +3,7 @@ use crate::bridged_type::{
 };
 use crate::parse::TypeDeclarations;
 use crate::Path;
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens};
 use std::fmt::{Debug, Formatter};
 use syn::Type;
@@ -235,6 +235,10 @@ impl BridgeableType for BuiltInPointer {
         todo!()
     }
 
+    fn is_string(&self) -> bool {
+        false
+    }
+
     fn contains_owned_string_recursive(&self, _types: &TypeDeclarations) -> bool {
         todo!()
     }
@@ -243,6 +247,10 @@ impl BridgeableType for BuiltInPointer {
         todo!()
     }
 
+    fn contains_arc_of_opaque_type(&self, _opaque_ty: &Ident, _types: &TypeDeclarations) -> bool {
+        false
+    }
+
     fn has_swift_bridge_copy_annotation(&self) -> bool {
         todo!()
     }
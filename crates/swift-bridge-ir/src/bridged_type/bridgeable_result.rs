@@ -145,6 +145,47 @@ impl BuiltInResult {
         swift_bridge_path: &Path,
         types: &TypeDeclarations,
     ) -> TokenStream {
+        if self.is_custom_result_type() {
+            let ffi_ty = self.to_ffi_compatible_rust_type(swift_bridge_path, types);
+            let convert_ok = self.ok_ty.convert_ffi_expression_to_rust_type(
+                &quote! { ok },
+                span,
+                swift_bridge_path,
+                types,
+            );
+            let convert_err = self.err_ty.convert_ffi_expression_to_rust_type(
+                &quote! { err },
+                span,
+                swift_bridge_path,
+                types,
+            );
+
+            return quote_spanned! {span=>
+                match #expression {
+                    #ffi_ty::Ok(ok) => std::result::Result::Ok(#convert_ok),
+                    #ffi_ty::Err(err) => std::result::Result::Err(#convert_err),
+                }
+            };
+        }
+
+        if self.ok_ty.can_be_encoded_with_zero_bytes() {
+            let ok = self.ok_ty.only_encoding().unwrap().rust;
+            let convert_err = self.err_ty.convert_ffi_expression_to_rust_type(
+                expression,
+                span,
+                swift_bridge_path,
+                types,
+            );
+
+            return quote_spanned! {span=>
+                if #expression.is_null() {
+                    std::result::Result::Ok(#ok)
+                } else {
+                    std::result::Result::Err(#convert_err)
+                }
+            };
+        }
+
         let convert_ok = self.ok_ty.convert_ffi_result_ok_value_to_rust_value(
             expression,
             swift_bridge_path,
@@ -187,21 +228,26 @@ impl BuiltInResult {
                 if self.err_ty.can_be_encoded_with_zero_bytes() {
                     todo!()
                 }
-                if self.is_custom_result_type() {
-                    return format!(
-                        "{}${}",
-                        SWIFT_BRIDGE_PREFIX,
-                        self.custom_c_struct_name(types)
-                    );
-                }
-                if self.ok_ty.can_be_encoded_with_zero_bytes() {
-                    return "UnsafeMutableRawPointer?".to_string();
-                }
-                "__private__ResultPtrAndPtr".to_string()
+                self.to_ffi_compatible_swift_type(types)
             }
         }
     }
 
+    /// The Swift type that this `Result` is passed over FFI as.
+    pub fn to_ffi_compatible_swift_type(&self, types: &TypeDeclarations) -> String {
+        if self.is_custom_result_type() {
+            return format!(
+                "{}${}",
+                SWIFT_BRIDGE_PREFIX,
+                self.custom_c_struct_name(types)
+            );
+        }
+        if self.ok_ty.can_be_encoded_with_zero_bytes() {
+            return "UnsafeMutableRawPointer?".to_string();
+        }
+        "__private__ResultPtrAndPtr".to_string()
+    }
+
     pub fn convert_ffi_value_to_swift_value(
         &self,
         expression: &str,
@@ -292,23 +338,126 @@ impl BuiltInResult {
         types: &TypeDeclarations,
         type_pos: TypePosition,
     ) -> String {
-        let convert_ok = self
-            .ok_ty
-            .convert_swift_expression_to_ffi_type("ok", types, type_pos);
+        let (ok_pattern, ok_ffi, err_ffi) = self.swift_ffi_ok_and_err(types, type_pos);
+        let ok_pattern = if ok_pattern.is_some() {
+            ".Ok(let ok)"
+        } else {
+            ".Ok"
+        };
+
+        format!(
+            "{{ switch {expression} {{ case {ok_pattern}: return {ok_ffi} case .Err(let err): return {err_ffi} }} }}()"
+        )
+    }
+
+    /// Convert a call to a Swift function that `throws` into the `Result`'s FFI representation.
+    ///
+    /// Used when Swift implements an `extern "Swift"` function that returns a `Result`.
+    /// Swift functions can throw any `Error`, so we check while parsing that the error type is
+    /// `SwiftError`.
+    pub fn convert_swift_throwing_expression_to_ffi_type(
+        &self,
+        expression: &str,
+        types: &TypeDeclarations,
+        type_pos: TypePosition,
+    ) -> String {
+        let (call, ok_ffi, err_ffi) =
+            self.swift_throwing_call_and_ffi_values(expression, types, type_pos);
+
+        format!("{{ do {{ {call}; return {ok_ffi} }} catch let err {{ return {err_ffi} }} }}()")
+    }
+
+    /// Call a Swift function that `throws` and hand the `Result`'s FFI representation to a
+    /// callback, such as the callback that Rust passes to an async `extern "Swift"` function.
+    pub fn call_swift_throwing_expression_with_ffi_value(
+        &self,
+        expression: &str,
+        callback: impl Fn(&str) -> String,
+        types: &TypeDeclarations,
+        type_pos: TypePosition,
+    ) -> String {
+        let (call, ok_ffi, err_ffi) =
+            self.swift_throwing_call_and_ffi_values(expression, types, type_pos);
+        let ok_callback = callback(&ok_ffi);
+        let err_callback = callback(&err_ffi);
+
+        format!(
+            r#"do {{
+            {call}
+            {ok_callback}
+        }} catch let err {{
+            {err_callback}
+        }}"#
+        )
+    }
+
+    /// A statement that calls a throwing Swift expression and binds its value to `ok`, along with
+    /// the FFI representations of the `Result` built from `ok` or from the thrown `err`.
+    fn swift_throwing_call_and_ffi_values(
+        &self,
+        expression: &str,
+        types: &TypeDeclarations,
+        type_pos: TypePosition,
+    ) -> (String, String, String) {
+        let (ok_pattern, ok_ffi, err_ffi) = self.swift_ffi_ok_and_err(types, type_pos);
+        let call = match ok_pattern {
+            Some(ok) => format!("let {ok} = try {expression}"),
+            None => format!("let _ = try {expression}"),
+        };
+
+        (call, ok_ffi, err_ffi)
+    }
+
+    /// The Swift expressions that build this `Result`'s FFI representation from a Swift `ok` or
+    /// `err` value, along with the name that the `ok` value needs to be bound to. There is no
+    /// `ok` binding when the `Ok` type is passed without any bytes, such as `()`.
+    fn swift_ffi_ok_and_err(
+        &self,
+        types: &TypeDeclarations,
+        type_pos: TypePosition,
+    ) -> (Option<&'static str>, String, String) {
         let convert_err = self
             .err_ty
             .convert_swift_expression_to_ffi_type("err", types, type_pos);
-
-        if self.ok_ty.can_be_encoded_with_zero_bytes() {
-            format!(
-                "{{ switch {val} {{ case .Ok(let ok): return __private__ResultPtrAndPtr(is_ok: true, ok_or_err: {convert_ok}) case .Err(let err): return __private__ResultPtrAndPtr(is_ok: false, ok_or_err: {convert_err}) }} }}()",
-                val = expression
-            )
+        let ok_binding = if self.ok_ty.can_be_encoded_with_zero_bytes() {
+            None
         } else {
-            format!(
-                "{{ switch {val} {{ case .Ok(let ok): return __private__ResultPtrAndPtr(is_ok: true, ok_or_err: {convert_ok}) case .Err(let err): return __private__ResultPtrAndPtr(is_ok: false, ok_or_err: {convert_err}) }} }}()",
-                val = expression
-            )
+            Some("ok")
+        };
+
+        if self.is_custom_result_type() {
+            let ffi_ty = self.to_ffi_compatible_swift_type(types);
+            let ok_tag = self.c_ok_tag_name(types);
+            let err_tag = self.c_err_tag_name(types);
+            let ok_payload = match ok_binding {
+                Some(ok) => {
+                    let convert_ok = self
+                        .ok_ty
+                        .convert_swift_expression_to_ffi_type(ok, types, type_pos);
+                    format!("{ffi_ty}$Fields(ok: {convert_ok})")
+                }
+                None => format!("{ffi_ty}$Fields()"),
+            };
+
+            return (
+                ok_binding,
+                format!("{ffi_ty}(tag: {ok_tag}, payload: {ok_payload})"),
+                format!("{ffi_ty}(tag: {err_tag}, payload: {ffi_ty}$Fields(err: {convert_err}))"),
+            );
+        }
+
+        match ok_binding {
+            None => (None, "nil".to_string(), convert_err),
+            Some(ok) => {
+                let convert_ok = self
+                    .ok_ty
+                    .convert_swift_expression_to_ffi_type(ok, types, type_pos);
+                (
+                    ok_binding,
+                    format!("__private__ResultPtrAndPtr(is_ok: true, ok_or_err: {convert_ok})"),
+                    format!("__private__ResultPtrAndPtr(is_ok: false, ok_or_err: {convert_err})"),
+                )
+            }
         }
     }
 
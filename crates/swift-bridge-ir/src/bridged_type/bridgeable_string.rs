@@ -2,7 +2,7 @@ use crate::bridged_type::{
     BridgeableType, CFfiStruct, OnlyEncoding, TypePosition, UnusedOptionNoneValue,
 };
 use crate::TypeDeclarations;
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, quote_spanned, ToTokens};
 use syn::{Path, Type};
 
@@ -99,7 +99,7 @@ impl BridgeableType for BridgedString {
         _swift_bridge_path: &Path,
         _types: &TypeDeclarations,
     ) -> String {
-        todo!()
+        "UnsafeMutableRawPointer?".to_string()
     }
 
     fn to_ffi_compatible_option_c_type(&self) -> String {
@@ -287,6 +287,10 @@ impl BridgeableType for BridgedString {
         false
     }
 
+    fn is_string(&self) -> bool {
+        true
+    }
+
     fn contains_owned_string_recursive(&self, _types: &TypeDeclarations) -> bool {
         true
     }
@@ -295,6 +299,10 @@ impl BridgeableType for BridgedString {
         false
     }
 
+    fn contains_arc_of_opaque_type(&self, _opaque_ty: &Ident, _types: &TypeDeclarations) -> bool {
+        false
+    }
+
     fn has_swift_bridge_copy_annotation(&self) -> bool {
         false
     }
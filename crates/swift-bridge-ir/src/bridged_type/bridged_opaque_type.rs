@@ -7,7 +7,7 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, quote_spanned, ToTokens};
 use std::fmt::{Debug, Formatter};
 use std::ops::Deref;
-use syn::{Path, Type};
+use syn::{Path, Type, TypeParamBound, TypeReference};
 
 #[derive(Clone)]
 pub(crate) struct OpaqueForeignType {
@@ -16,9 +16,26 @@ pub(crate) struct OpaqueForeignType {
     pub reference: bool,
     pub mutable: bool,
     pub has_swift_bridge_copy_annotation: bool,
+    /// Whether this is an `extern "Swift"` type with a `#[swift_bridge(protocol)]` attribute.
+    pub is_swift_protocol: bool,
+    /// Whether this is an `extern "Rust"` type with a `#[swift_bridge(trait_object)]` attribute.
+    pub is_trait_object: bool,
+    /// Set when this is a `Box<dyn Trait>`, `Arc<dyn Trait>` or `&dyn Trait`.
+    pub trait_object_pointer: Option<TraitObjectPointer>,
+    /// Set when this is an `Arc<SomeType>`, which Swift holds a strong reference to.
+    pub arc: bool,
     pub generics: OpaqueRustTypeGenerics,
 }
 
+/// The pointer that holds a `dyn Trait`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum TraitObjectPointer {
+    Box,
+    Arc,
+    /// `&dyn Trait` or `&mut dyn Trait`
+    Ref,
+}
+
 impl BridgeableType for OpaqueForeignType {
     fn is_built_in_type(&self) -> bool {
         false
@@ -59,8 +76,18 @@ impl BridgeableType for OpaqueForeignType {
             .angle_bracketed_concrete_generics_tokens(types);
 
         if self.host_lang.is_rust() {
-            quote! {
-                super:: #ty_name #generics
+            match self.trait_object_pointer {
+                Some(TraitObjectPointer::Box) => quote! { Box<dyn super::#ty_name> },
+                Some(TraitObjectPointer::Arc) => quote! { std::sync::Arc<dyn super::#ty_name> },
+                Some(TraitObjectPointer::Ref) => {
+                    if self.mutable {
+                        quote! { &mut dyn super::#ty_name }
+                    } else {
+                        quote! { &dyn super::#ty_name }
+                    }
+                }
+                None if self.arc => quote! { std::sync::Arc<super::#ty_name> },
+                None => self.rust_pointee_type(&generics),
             }
         } else {
             quote! {
@@ -84,6 +111,10 @@ impl BridgeableType for OpaqueForeignType {
                             if self.mutable {
                                 class_name += "Mut";
                             }
+
+                            if self.arc {
+                                class_name += "Arc";
+                            }
                         }
 
                         format!(
@@ -161,6 +192,8 @@ impl BridgeableType for OpaqueForeignType {
         if self.has_swift_bridge_copy_annotation {
             let ty = self.copy_rust_repr_type();
             quote! { #ty }
+        } else if self.arc {
+            quote! { *const super::#ty_name }
         } else {
             if self.host_lang.is_rust() {
                 let generics = self
@@ -174,9 +207,11 @@ impl BridgeableType for OpaqueForeignType {
                         quote! { *const }
                     };
 
-                    quote_spanned! {ty_name.span()=> #ptr super::#ty_name }
+                    let pointee = self.rust_pointee_type(&quote! {});
+                    quote_spanned! {ty_name.span()=> #ptr #pointee }
                 } else {
-                    quote! { *mut super::#ty_name #generics }
+                    let pointee = self.rust_pointee_type(&generics);
+                    quote! { *mut #pointee }
                 }
             } else {
                 quote! { #ty_name }
@@ -189,16 +224,18 @@ impl BridgeableType for OpaqueForeignType {
         _swift_bridge_path: &Path,
         types: &TypeDeclarations,
     ) -> TokenStream {
-        let type_name = &self.ty;
-
         if self.has_swift_bridge_copy_annotation {
             let option_ty = self.option_copy_rust_repr_type();
             quote! { #option_ty }
+        } else if self.arc {
+            let ty_name = &self.ty;
+            quote! { *const super::#ty_name }
         } else {
             let generics = self
                 .generics
                 .angle_bracketed_concrete_generics_tokens(types);
-            quote! { *mut super::#type_name #generics }
+            let pointee = self.rust_pointee_type(&generics);
+            quote! { *mut #pointee }
         }
     }
 
@@ -207,7 +244,11 @@ impl BridgeableType for OpaqueForeignType {
         _swift_bridge_path: &Path,
         _types: &TypeDeclarations,
     ) -> String {
-        todo!()
+        if self.has_swift_bridge_copy_annotation {
+            self.option_copy_ffi_repr_type_string()
+        } else {
+            "UnsafeMutableRawPointer?".to_string()
+        }
     }
 
     fn to_ffi_compatible_option_c_type(&self) -> String {
@@ -225,33 +266,38 @@ impl BridgeableType for OpaqueForeignType {
         types: &TypeDeclarations,
         span: Span,
     ) -> TokenStream {
-        let ty_name = &self.ty;
-
         if self.host_lang.is_rust() {
             if self.has_swift_bridge_copy_annotation {
                 let copy_ty = self.copy_rust_repr_type();
                 quote! {
                     #copy_ty::from_rust_repr(#expression)
                 }
+            } else if self.arc {
+                quote_spanned! {span=>
+                    std::sync::Arc::into_raw(#expression)
+                }
             } else if self.reference {
                 let ptr = if self.mutable {
                     quote! { *mut }
                 } else {
                     quote! { *const }
                 };
+                let pointee = self.rust_pointee_type(&quote! {});
 
                 quote! {
-                    #expression as #ptr super::#ty_name
+                    #expression as #ptr #pointee
                 }
             } else {
                 let generics = self
                     .generics
                     .angle_bracketed_concrete_generics_tokens(types);
+                let pointee = self.rust_pointee_type(&generics);
+                let expression = self.wrap_trait_object(expression);
                 quote_spanned! {span=>
                     Box::into_raw(Box::new({
-                        let val: super::#ty_name #generics = #expression;
+                        let val: #pointee = #expression;
                         val
-                    })) as *mut super::#ty_name #generics
+                    })) as *mut #pointee
                 }
             }
         } else {
@@ -283,10 +329,19 @@ impl BridgeableType for OpaqueForeignType {
                     }
                 }
             }
+        } else if self.arc {
+            quote! {
+                if let Some(val) = #expression {
+                    std::sync::Arc::into_raw(val)
+                } else {
+                    std::ptr::null()
+                }
+            }
         } else {
+            let val = self.wrap_trait_object(&quote! { val });
             quote! {
                 if let Some(val) = #expression {
-                    Box::into_raw(Box::new(val))
+                    Box::into_raw(Box::new(#val))
                 } else {
                     std::ptr::null_mut()
                 }
@@ -305,7 +360,8 @@ impl BridgeableType for OpaqueForeignType {
         if self.host_lang.is_rust() {
             if self.has_swift_bridge_copy_annotation {
                 format!("{}.intoFfiRepr()", expression)
-            } else if self.reference {
+            } else if self.reference || self.arc {
+                // Rust clones the `Arc`, so Swift keeps its strong reference.
                 format!("{}.ptr", expression)
             } else {
                 match type_pos {
@@ -338,18 +394,12 @@ impl BridgeableType for OpaqueForeignType {
             match type_pos {
                 TypePosition::FnArg(func_host_lang, _) => {
                     if func_host_lang.is_rust() {
-                        format!("Unmanaged.passRetained({}).toOpaque()", expression)
+                        self.swift_pass_retained(expression)
                     } else {
-                        format!(
-                            "Unmanaged<{type_name}>.fromOpaque({value}).takeRetainedValue()",
-                            type_name = ty_name,
-                            value = expression
-                        )
+                        self.swift_take_value(&ty_name.to_string(), expression, "takeRetainedValue")
                     }
                 }
-                TypePosition::FnReturn(_func_host_lang) => {
-                    format!("Unmanaged.passRetained({}).toOpaque()", expression)
-                }
+                TypePosition::FnReturn(_func_host_lang) => self.swift_pass_retained(expression),
                 TypePosition::SharedStructField => {
                     todo!("Opaque types in shared struct fields are not yet supported")
                 }
@@ -373,6 +423,8 @@ impl BridgeableType for OpaqueForeignType {
                         option_ffi_repr = option_ffi_repr,
                         ffi_repr = ffi_repr
                     )
+        } else if self.arc {
+            format!("{{ if let val = {expression} {{ return val.ptr }} else {{ return nil }} }}()")
         } else {
             format!("{{ if let val = {expression} {{ val.isOwned = false; return val.ptr }} else {{ return nil }} }}()", expression = expression,)
         }
@@ -395,6 +447,8 @@ impl BridgeableType for OpaqueForeignType {
                 quote! {
                     #maybe_ref #expression.into_rust_repr()
                 }
+            } else if self.arc {
+                self.clone_arc(expression)
             } else if self.reference {
                 let maybe_mut = if self.mutable {
                     quote! { mut }
@@ -402,13 +456,21 @@ impl BridgeableType for OpaqueForeignType {
                     quote! {}
                 };
 
-                quote! {
-                    unsafe {  & #maybe_mut * #expression }
+                // `&TraitObject<dyn Trait>` would get unsized instead of dereferenced into a
+                // `&dyn Trait`, so we dereference it ourselves.
+                if self.trait_object_pointer == Some(TraitObjectPointer::Ref) {
+                    quote! {
+                        unsafe {  & #maybe_mut ** #expression }
+                    }
+                } else {
+                    quote! {
+                        unsafe {  & #maybe_mut * #expression }
+                    }
                 }
             } else {
-                quote! {
+                self.unwrap_trait_object(&quote! {
                     unsafe { * Box::from_raw(  #expression ) }
-                }
+                })
             }
         } else {
             if self.reference {
@@ -430,12 +492,24 @@ impl BridgeableType for OpaqueForeignType {
                     None
                 }
             }
+        } else if self.arc {
+            let val = self.clone_arc(expression);
+            quote! {
+                if #expression.is_null() {
+                    None
+                } else {
+                    Some(#val)
+                }
+            }
         } else {
+            let val = self.unwrap_trait_object(&quote! {
+                unsafe { * Box::from_raw(#expression) }
+            });
             quote! {
                 if #expression.is_null() {
                     None
                 } else {
-                    Some(unsafe { * Box::from_raw(#expression) } )
+                    Some(#val)
                 }
             }
         }
@@ -455,6 +529,9 @@ impl BridgeableType for OpaqueForeignType {
         if self.mutable {
             ty_name += "Mut";
         }
+        if self.arc {
+            ty_name += "Arc";
+        }
 
         if self.host_lang.is_rust() {
             if self.has_swift_bridge_copy_annotation {
@@ -478,11 +555,7 @@ impl BridgeableType for OpaqueForeignType {
                 }
             }
         } else {
-            format!(
-                "Unmanaged<{ty_name}>.fromOpaque({value}).takeRetainedValue()",
-                ty_name = ty_name,
-                value = expression
-            )
+            self.swift_take_value(&ty_name, expression, "takeRetainedValue")
         }
     }
 
@@ -495,7 +568,10 @@ impl BridgeableType for OpaqueForeignType {
                 type_name = type_name
             )
         } else {
-            let type_name = self.swift_name();
+            let mut type_name = self.swift_name();
+            if self.arc {
+                type_name += "Arc";
+            }
             format!(
                 "{{ let val = {expression}; if val != nil {{ return {type_name}(ptr: val!) }} else {{ return nil }} }}()",
                 expression = expression,
@@ -514,9 +590,10 @@ impl BridgeableType for OpaqueForeignType {
 
         match self.host_lang {
             HostLang::Rust => {
-                quote! {
-                    unsafe { *Box::from_raw(#result.ok_or_err as *mut super::#ty) }
-                }
+                let pointee = self.rust_pointee_type(&quote! {});
+                self.unwrap_trait_object(&quote! {
+                    unsafe { *Box::from_raw(#result.ok_or_err as *mut #pointee) }
+                })
             }
             HostLang::Swift => {
                 quote! {
@@ -536,9 +613,10 @@ impl BridgeableType for OpaqueForeignType {
 
         match self.host_lang {
             HostLang::Rust => {
-                quote! {
-                    unsafe { *Box::from_raw(#result.ok_or_err as *mut super::#ty) }
-                }
+                let pointee = self.rust_pointee_type(&quote! {});
+                self.unwrap_trait_object(&quote! {
+                    unsafe { *Box::from_raw(#result.ok_or_err as *mut #pointee) }
+                })
             }
             HostLang::Swift => {
                 quote! {
@@ -553,9 +631,17 @@ impl BridgeableType for OpaqueForeignType {
 
         if self.reference {
             todo!("Support returning Option<&T> where T is an opaque type")
+        } else if self.arc {
+            UnusedOptionNoneValue {
+                rust: quote! { std::ptr::null::<super::#ty_name>() },
+                swift: "TODO..Support Swift Option<T>::None value".into(),
+            }
         } else {
             UnusedOptionNoneValue {
-                rust: quote! { std::ptr::null::<#ty_name>() as *mut super::#ty_name },
+                rust: {
+                    let pointee = self.rust_pointee_type(&quote! {});
+                    quote! { std::ptr::null::<#ty_name>() as *mut #pointee }
+                },
                 swift: "TODO..Support Swift Option<T>::None value".into(),
             }
         }
@@ -616,6 +702,10 @@ impl BridgeableType for OpaqueForeignType {
         false
     }
 
+    fn is_string(&self) -> bool {
+        false
+    }
+
     fn contains_owned_string_recursive(&self, _types: &TypeDeclarations) -> bool {
         false
     }
@@ -624,6 +714,10 @@ impl BridgeableType for OpaqueForeignType {
         false
     }
 
+    fn contains_arc_of_opaque_type(&self, opaque_ty: &Ident, _types: &TypeDeclarations) -> bool {
+        self.arc && self.ty == *opaque_ty
+    }
+
     fn has_swift_bridge_copy_annotation(&self) -> bool {
         self.has_swift_bridge_copy_annotation
     }
@@ -641,6 +735,149 @@ impl OpaqueForeignType {
         format!("{}", self.ty)
     }
 
+    /// Parse a `Box<dyn SomeTrait>` or `Arc<dyn SomeTrait>` from a stringified token stream,
+    /// where `SomeTrait` is a `#[swift_bridge(trait_object)]` type.
+    pub fn trait_object_from_str_tokens(tokens: &str, types: &TypeDeclarations) -> Option<Self> {
+        let (pointer, tokens) = if let Some(tokens) = tokens.strip_prefix("Box < dyn ") {
+            (TraitObjectPointer::Box, tokens)
+        } else if let Some(tokens) = tokens
+            .trim_start_matches("std :: sync :: ")
+            .strip_prefix("Arc < dyn ")
+        {
+            (TraitObjectPointer::Arc, tokens)
+        } else {
+            return None;
+        };
+
+        let trait_name = tokens.strip_suffix(" >")?;
+        let declaration = types.get(trait_name)?.as_opaque()?;
+        if !declaration.attributes.trait_object {
+            return None;
+        }
+
+        let mut ty = declaration.to_opaque_type(false, false);
+        ty.trait_object_pointer = Some(pointer);
+        Some(ty)
+    }
+
+    /// Parse an `Arc<SomeType>` from a stringified token stream, where `SomeType` is an opaque
+    /// Rust type.
+    pub fn arc_from_str_tokens(tokens: &str, types: &TypeDeclarations) -> Option<Self> {
+        let ty_name = tokens
+            .trim_start_matches("std :: sync :: ")
+            .strip_prefix("Arc < ")?
+            .strip_suffix(" >")?;
+
+        let declaration = types.get(ty_name)?.as_opaque()?;
+        if declaration.host_lang.is_swift()
+            || declaration.attributes.trait_object
+            || declaration.attributes.copy.is_some()
+            || !declaration.generics.is_empty()
+        {
+            return None;
+        }
+
+        let mut ty = declaration.to_opaque_type(false, false);
+        ty.arc = true;
+        Some(ty)
+    }
+
+    /// Parse a `&dyn SomeTrait` or `&mut dyn SomeTrait`, where `SomeTrait` is a
+    /// `#[swift_bridge(trait_object)]` type.
+    pub fn trait_object_from_type_reference(
+        ty_ref: &TypeReference,
+        types: &TypeDeclarations,
+    ) -> Option<Self> {
+        let trait_object = match ty_ref.elem.deref() {
+            Type::TraitObject(trait_object) => trait_object,
+            _ => return None,
+        };
+        let trait_name = match trait_object.bounds.first()? {
+            TypeParamBound::Trait(bound) => bound.path.to_token_stream().to_string(),
+            _ => return None,
+        };
+
+        let declaration = types.get(&trait_name)?.as_opaque()?;
+        if !declaration.attributes.trait_object {
+            return None;
+        }
+
+        let mut ty = declaration.to_opaque_type(true, ty_ref.mutability.is_some());
+        ty.trait_object_pointer = Some(TraitObjectPointer::Ref);
+        Some(ty)
+    }
+
+    /// The Rust type that Swift holds a pointer to.
+    ///
+    /// `super::SomeType<u32>`, or `SomeTrait` for a `#[swift_bridge(trait_object)]` type.
+    /// `SomeTrait` is a `TraitObject<dyn super::SomeTrait>` alias that we generate in the bridge
+    /// module.
+    pub fn rust_pointee_type(&self, generics: &TokenStream) -> TokenStream {
+        let ty_name = &self.ty;
+
+        if self.is_trait_object {
+            quote! { #ty_name }
+        } else {
+            quote! { super::#ty_name #generics }
+        }
+    }
+
+    /// Clone the `Arc` that Swift holds a pointer to. Swift keeps its own strong reference.
+    fn clone_arc(&self, expression: &TokenStream) -> TokenStream {
+        quote! {
+            {
+                let ptr = #expression;
+                unsafe {
+                    std::sync::Arc::increment_strong_count(ptr);
+                    std::sync::Arc::from_raw(ptr)
+                }
+            }
+        }
+    }
+
+    /// Box<dyn SomeTrait> -> SomeTrait::from(Box<dyn SomeTrait>)
+    fn wrap_trait_object(&self, expression: &TokenStream) -> TokenStream {
+        let ty_name = &self.ty;
+
+        match self.trait_object_pointer {
+            Some(TraitObjectPointer::Box) | Some(TraitObjectPointer::Arc) => {
+                quote! { #ty_name::from(#expression) }
+            }
+            Some(TraitObjectPointer::Ref) | None => expression.clone(),
+        }
+    }
+
+    /// SomeTrait -> Box<dyn SomeTrait>
+    fn unwrap_trait_object(&self, expression: &TokenStream) -> TokenStream {
+        match self.trait_object_pointer {
+            Some(TraitObjectPointer::Box) => quote! { (#expression).into_box() },
+            Some(TraitObjectPointer::Arc) => quote! { (#expression).into_arc() },
+            Some(TraitObjectPointer::Ref) | None => expression.clone(),
+        }
+    }
+
+    /// Retain an instance of an `extern "Swift"` type and get a pointer to it.
+    ///
+    /// Swift protocols are passed as `AnyObject`s, since `Unmanaged` only works with classes.
+    pub fn swift_pass_retained(&self, expression: &str) -> String {
+        if self.is_swift_protocol {
+            format!("Unmanaged.passRetained({expression} as AnyObject).toOpaque()")
+        } else {
+            format!("Unmanaged.passRetained({expression}).toOpaque()")
+        }
+    }
+
+    /// Get the instance of an `extern "Swift"` type that a pointer points to.
+    ///
+    /// `take` is either "takeRetainedValue" or "takeUnretainedValue".
+    pub fn swift_take_value(&self, ty_name: &str, pointer: &str, take: &str) -> String {
+        if self.is_swift_protocol {
+            format!("(Unmanaged<AnyObject>.fromOpaque({pointer}).{take}() as! {ty_name})")
+        } else {
+            format!("Unmanaged<{ty_name}>.fromOpaque({pointer}).{take}()")
+        }
+    }
+
     /// The name of the type used to pass a `#[swift_bridge(Copy(...))]` type over FFI
     ///
     /// __swift_bridge__SomeType
@@ -709,6 +946,7 @@ impl PartialEq for OpaqueForeignType {
             && self.host_lang == other.host_lang
             && self.reference == other.reference
             && self.mutable == other.mutable
+            && self.arc == other.arc
     }
 }
 
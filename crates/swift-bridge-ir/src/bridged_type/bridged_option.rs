@@ -91,7 +91,7 @@ impl BridgedOption {
                         }
                     }
                 }
-                StdLibType::Vec(_) => {
+                StdLibType::Vec(_) | StdLibType::Map(_) | StdLibType::Set(_) => {
                     quote! {
                         if let Some(value) = #expression {
                             Box::into_raw(Box::new(value))
@@ -106,7 +106,7 @@ impl BridgedOption {
                 StdLibType::Result(_) => {
                     todo!("Support Option<Result<T, E>>")
                 }
-                StdLibType::BoxedFnOnce(_) => {
+                StdLibType::BoxedFn(_) => {
                     todo!("Option<Box<dyn FnOnce(A, B) -> C>> is not yet supported")
                 }
                 StdLibType::Tuple(_) => todo!(),
@@ -171,7 +171,7 @@ impl BridgedOption {
                         if #expression.start.is_null() { None } else { Some(#expression.to_str()) }
                     }
                 }
-                StdLibType::Vec(_) => {
+                StdLibType::Vec(_) | StdLibType::Map(_) | StdLibType::Set(_) => {
                     quote! {
                         if #expression.is_null() { None } else { Some( unsafe { * Box::from_raw(#expression) } ) }
                     }
@@ -182,7 +182,7 @@ impl BridgedOption {
                 StdLibType::Result(_) => {
                     todo!("Option<Result<T, E>> is not yet supported")
                 }
-                StdLibType::BoxedFnOnce(_) => {
+                StdLibType::BoxedFn(_) => {
                     todo!("Option<Box<dyn FnOnce(A, B) -> C>> is not yet supported")
                 }
                 StdLibType::Tuple(_) => todo!(),
@@ -200,7 +200,11 @@ impl BridgedOption {
         }
     }
 
-    pub(super) fn convert_ffi_expression_to_swift_type(&self, expression: &str) -> String {
+    pub(super) fn convert_ffi_expression_to_swift_type(
+        &self,
+        expression: &str,
+        types: &TypeDeclarations,
+    ) -> String {
         match self.ty.deref() {
             BridgedType::Bridgeable(b) => b.convert_ffi_option_expression_to_swift_type(expression),
             BridgedType::StdLib(stdlib_type) => match stdlib_type {
@@ -241,13 +245,25 @@ impl BridgedOption {
                     expression = expression
                     )
                 }
+                StdLibType::Map(map) => {
+                    let map = map.convert_ffi_value_to_swift_value("val!", types);
+                    format!(
+                        "{{ let val = {expression}; if val != nil {{ return {map} }} else {{ return nil }} }}()"
+                    )
+                }
+                StdLibType::Set(set) => {
+                    let set = set.convert_ffi_value_to_swift_value("val!", types);
+                    format!(
+                        "{{ let val = {expression}; if val != nil {{ return {set} }} else {{ return nil }} }}()"
+                    )
+                }
                 StdLibType::Option(_) => {
                     todo!("Support Option<Option<T>>")
                 }
                 StdLibType::Result(_) => {
                     todo!("Option<Result<T, E>> is not yet supported")
                 }
-                StdLibType::BoxedFnOnce(_) => {
+                StdLibType::BoxedFn(_) => {
                     todo!("Option<Box<dyn FnOnce(A, B) -> C>> is not yet supported")
                 }
                 StdLibType::Tuple(_) => todo!(),
@@ -298,7 +314,7 @@ impl BridgedOption {
                 StdLibType::Str => {
                     format!("{expression}AsRustStr", expression = expression)
                 }
-                StdLibType::Vec(_) => {
+                StdLibType::Vec(_) | StdLibType::Map(_) | StdLibType::Set(_) => {
                     format!(
                         "{{ if let val = {expression} {{ val.isOwned = false; return val.ptr }} else {{ return nil }} }}()"
                     , expression = expression
@@ -310,7 +326,7 @@ impl BridgedOption {
                 StdLibType::Result(_) => {
                     todo!("Option<Result<T, E>> is not yet supported")
                 }
-                StdLibType::BoxedFnOnce(_) => {
+                StdLibType::BoxedFn(_) => {
                     todo!("Option<Box<dyn FnOnce(A, B) -> C>> is not yet supported")
                 }
                 StdLibType::Tuple(_) => todo!(),
@@ -354,12 +370,12 @@ impl BridgedOption {
                 format!("Optional<{}>", self.ty.to_swift_type(type_pos, types))
             }
             TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy => {
-                unimplemented!()
+                self.to_ffi_compatible_swift_type(types)
             }
         }
     }
 
-    fn to_ffi_compatible_swift_type(&self, _types: &TypeDeclarations) -> String {
+    fn to_ffi_compatible_swift_type(&self, types: &TypeDeclarations) -> String {
         match self.ty.deref() {
             BridgedType::StdLib(stdlib_type) => match stdlib_type {
                 StdLibType::Null => {
@@ -393,7 +409,10 @@ impl BridgedOption {
                 StdLibType::Vec(_) => {
                     todo!()
                 }
-                StdLibType::BoxedFnOnce(_) => {
+                StdLibType::Map(_) | StdLibType::Set(_) => {
+                    todo!()
+                }
+                StdLibType::BoxedFn(_) => {
                     todo!()
                 }
                 StdLibType::Option(_) => {
@@ -406,11 +425,14 @@ impl BridgedOption {
                     todo!()
                 }
             },
-            BridgedType::Foreign(_) => {
-                todo!()
+            BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct))) => {
+                shared_struct.ffi_option_name_string()
+            }
+            BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Enum(shared_enum))) => {
+                shared_enum.ffi_option_name_string()
             }
-            BridgedType::Bridgeable(_) => {
-                todo!()
+            BridgedType::Bridgeable(b) => {
+                b.to_ffi_compatible_option_swift_type(&syn::parse_quote!(swift_bridge), types)
             }
         }
     }
@@ -444,14 +466,14 @@ impl BridgedOption {
                     todo!("Option<&[T]> is not yet supported")
                 }
                 StdLibType::Str => "struct RustStr".to_string(),
-                StdLibType::Vec(_) => "void*".to_string(),
+                StdLibType::Vec(_) | StdLibType::Map(_) | StdLibType::Set(_) => "void*".to_string(),
                 StdLibType::Option(_) => {
                     todo!("Option<Option<T>> is not yet supported")
                 }
                 StdLibType::Result(_) => {
                     todo!("Option<Result<T, E>> is not yet supported")
                 }
-                StdLibType::BoxedFnOnce(_) => {
+                StdLibType::BoxedFn(_) => {
                     todo!("Option<Box<dyn FnOnce(A, B) -> C>> is not yet supported")
                 }
                 StdLibType::Tuple(_) => todo!(),
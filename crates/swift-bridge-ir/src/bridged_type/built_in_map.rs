@@ -0,0 +1,450 @@
+use crate::bridged_type::collection_item::{collection_item_ref, CollectionItemRef};
+use crate::bridged_type::{BridgedType, CFfiStruct, TypePosition};
+use crate::parse::{HostLang, TypeDeclarations};
+use crate::SWIFT_BRIDGE_PREFIX;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use std::str::FromStr;
+use syn::{GenericArgument, Path, PathArguments, Type};
+
+/// HashMap<K, V> or BTreeMap<K, V>
+#[derive(Debug)]
+pub(crate) struct BuiltInMap {
+    pub kind: MapKind,
+    pub key: Box<BridgedType>,
+    pub value: Box<BridgedType>,
+    /// `Option<V>`, used when removing a value from the map.
+    pub optional_value: Box<BridgedType>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum MapKind {
+    HashMap,
+    BTreeMap,
+}
+
+impl MapKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MapKind::HashMap => "HashMap",
+            MapKind::BTreeMap => "BTreeMap",
+        }
+    }
+
+    /// The name of the static `RustMapVtable` property that Swift uses for this kind of map.
+    fn swift_vtable_name(&self) -> &'static str {
+        match self {
+            MapKind::HashMap => "hashMap",
+            MapKind::BTreeMap => "btreeMap",
+        }
+    }
+}
+
+impl BuiltInMap {
+    /// Parse a map from a stringified token stream such as `HashMap < u32 , String >`.
+    pub fn from_str_tokens(tokens: &str, types: &TypeDeclarations) -> Option<Self> {
+        let tokens = tokens.trim_start_matches("std :: collections :: ");
+        let kind = if tokens.starts_with("HashMap < ") {
+            MapKind::HashMap
+        } else if tokens.starts_with("BTreeMap < ") {
+            MapKind::BTreeMap
+        } else {
+            return None;
+        };
+
+        let ty: Type = syn::parse2(TokenStream::from_str(tokens).ok()?).ok()?;
+        let args = match ty {
+            Type::Path(path) => match path.path.segments.last()?.arguments.clone() {
+                PathArguments::AngleBracketed(args) => args.args,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let mut generics = args.into_iter().filter_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        });
+        let key = generics.next()?;
+        let value = generics.next()?;
+
+        let optional_value: Type = syn::parse_quote! { Option<#value> };
+
+        Some(BuiltInMap {
+            kind,
+            key: Box::new(BridgedType::new_with_type(&key, types)?),
+            value: Box::new(BridgedType::new_with_type(&value, types)?),
+            optional_value: Box::new(BridgedType::new_with_type(&optional_value, types)?),
+        })
+    }
+
+    /// HashMap<K, V> -> std::collections::HashMap<K, V>
+    pub fn to_rust_type_path(&self, types: &TypeDeclarations) -> TokenStream {
+        let kind = format_ident!("{}", self.kind.as_str());
+        let key = self.key.to_rust_type_path(types);
+        let value = self.value.to_rust_type_path(types);
+
+        quote! { std::collections::#kind<#key, #value> }
+    }
+
+    /// RustMap<UInt32, RustString>
+    pub fn to_swift_type(&self, types: &TypeDeclarations) -> String {
+        format!(
+            "RustMap<{}, {}>",
+            self.key
+                .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types),
+            self.value
+                .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types)
+        )
+    }
+
+    /// Convert a pointer to a Rust map into a Swift `RustMap`.
+    pub fn convert_ffi_value_to_swift_value(
+        &self,
+        expression: &str,
+        types: &TypeDeclarations,
+    ) -> String {
+        format!(
+            "{}(ptr: {}, vtable: .{})",
+            self.to_swift_type(types),
+            expression,
+            self.kind.swift_vtable_name()
+        )
+    }
+
+    /// HashMap<u32, String> -> __swift_bridge__$some_function$HashMap_U32_String
+    ///
+    /// See [`TypeDeclarations::collection_symbol_scope`] for why the module's first function is
+    /// part of the name.
+    fn ffi_name_prefix(&self, types: &TypeDeclarations) -> String {
+        format!(
+            "{}${}",
+            types
+                .collection_symbol_scope()
+                .unwrap_or(SWIFT_BRIDGE_PREFIX),
+            self.to_alpha_numeric_underscore_name(types)
+        )
+    }
+
+    /// Keys and values are validated while parsing, so we know that they can be stored in a map.
+    fn key_and_value_items(
+        &self,
+        types: &TypeDeclarations,
+    ) -> (CollectionItemRef, CollectionItemRef) {
+        (
+            collection_item_ref(&self.key, types).unwrap(),
+            collection_item_ref(&self.value, types).unwrap(),
+        )
+    }
+
+    /// Generate the functions that Swift uses to work with this map.
+    ///
+    /// These get deduplicated alongside our other custom FFI types, so a module that uses the same
+    /// map in many signatures only exports them once.
+    pub fn generate_custom_rust_ffi_types(
+        &self,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> Option<Vec<TokenStream>> {
+        let prefix = self.ffi_name_prefix(types);
+        let export_name = |func: &str| format!("{}${}", prefix, func);
+
+        let map_ty = self.to_rust_type_path(types);
+        let key_ty = self.key.to_rust_type_path(types);
+        let value_ty = self.value.to_rust_type_path(types);
+        let entries_ty = quote! { Vec<(*const #key_ty, *const #value_ty)> };
+
+        let (key_item, value_item) = self.key_and_value_items(types);
+
+        let key_ref_ffi_ty =
+            key_item.to_ffi_compatible_rust_type(&self.key, swift_bridge_path, types);
+        let value_ref_ffi_ty =
+            value_item.to_ffi_compatible_rust_type(&self.value, swift_bridge_path, types);
+        let optional_value_ref_ffi_ty = value_item.to_optional_ffi_compatible_rust_type(
+            &self.value,
+            &self.optional_value,
+            swift_bridge_path,
+            types,
+        );
+        let key_ffi_ty = self
+            .key
+            .to_ffi_compatible_rust_type(swift_bridge_path, types);
+        let value_ffi_ty = self
+            .value
+            .to_ffi_compatible_rust_type(swift_bridge_path, types);
+        let optional_value_ffi_ty = self
+            .optional_value
+            .to_ffi_compatible_rust_type(swift_bridge_path, types);
+
+        let lookup_key = key_item.convert_ffi_expression_to_rust_ref(&quote! { key });
+        let optional_value_ref = value_item.convert_optional_rust_ref_to_ffi_type(
+            &quote! { val },
+            &value_ty,
+            &self.optional_value,
+            swift_bridge_path,
+            types,
+        );
+        let entry_key_ref = key_item.convert_rust_ref_to_ffi_type(&quote! { key }, &key_ty);
+        let entry_value_ref = value_item.convert_rust_ref_to_ffi_type(&quote! { val }, &value_ty);
+
+        let owned_key = self.key.convert_ffi_expression_to_rust_type(
+            &quote! { key },
+            Span::call_site(),
+            swift_bridge_path,
+            types,
+        );
+        let owned_value = self.value.convert_ffi_expression_to_rust_type(
+            &quote! { value },
+            Span::call_site(),
+            swift_bridge_path,
+            types,
+        );
+        let removed_value = self.optional_value.convert_rust_expression_to_ffi_type(
+            &quote! { val },
+            swift_bridge_path,
+            types,
+            Span::call_site(),
+        );
+
+        let new = export_name("new");
+        let drop = export_name("drop");
+        let len = export_name("len");
+        let contains_key = export_name("contains_key");
+        let get = export_name("get");
+        let insert = export_name("insert");
+        let remove = export_name("remove");
+        let entries = export_name("entries");
+        let entries_key = export_name("entries_key");
+        let entries_value = export_name("entries_value");
+        let entries_drop = export_name("entries_drop");
+
+        Some(vec![quote! {
+            const _: () = {
+                #[doc(hidden)]
+                #[export_name = #new]
+                pub extern "C" fn _new() -> *mut #map_ty {
+                    Box::into_raw(Box::new(<#map_ty>::new()))
+                }
+
+                #[doc(hidden)]
+                #[export_name = #drop]
+                pub extern "C" fn _drop(map: *mut #map_ty) {
+                    let map = unsafe { Box::from_raw(map) };
+                    drop(map)
+                }
+
+                #[doc(hidden)]
+                #[export_name = #len]
+                pub extern "C" fn _len(map: *const #map_ty) -> usize {
+                    unsafe { &*map }.len()
+                }
+
+                #[doc(hidden)]
+                #[export_name = #contains_key]
+                pub extern "C" fn _contains_key(map: *const #map_ty, key: #key_ref_ffi_ty) -> bool {
+                    unsafe { &*map }.contains_key(#lookup_key)
+                }
+
+                #[doc(hidden)]
+                #[export_name = #get]
+                pub extern "C" fn _get(map: *const #map_ty, key: #key_ref_ffi_ty) -> #optional_value_ref_ffi_ty {
+                    let val = unsafe { &*map }.get(#lookup_key);
+                    #optional_value_ref
+                }
+
+                #[doc(hidden)]
+                #[export_name = #insert]
+                pub extern "C" fn _insert(map: *mut #map_ty, key: #key_ffi_ty, value: #value_ffi_ty) {
+                    unsafe { &mut *map }.insert(#owned_key, #owned_value);
+                }
+
+                #[doc(hidden)]
+                #[export_name = #remove]
+                pub extern "C" fn _remove(map: *mut #map_ty, key: #key_ref_ffi_ty) -> #optional_value_ffi_ty {
+                    let val = unsafe { &mut *map }.remove(#lookup_key);
+                    #removed_value
+                }
+
+                #[doc(hidden)]
+                #[export_name = #entries]
+                pub extern "C" fn _entries(map: *const #map_ty) -> *mut #entries_ty {
+                    let entries: #entries_ty = unsafe { &*map }
+                        .iter()
+                        .map(|(key, val)| (key as *const #key_ty, val as *const #value_ty))
+                        .collect();
+                    Box::into_raw(Box::new(entries))
+                }
+
+                #[doc(hidden)]
+                #[export_name = #entries_key]
+                pub extern "C" fn _entries_key(entries: *const #entries_ty, index: usize) -> #key_ref_ffi_ty {
+                    let key = unsafe { &*(&*entries)[index].0 };
+                    #entry_key_ref
+                }
+
+                #[doc(hidden)]
+                #[export_name = #entries_value]
+                pub extern "C" fn _entries_value(entries: *const #entries_ty, index: usize) -> #value_ref_ffi_ty {
+                    let val = unsafe { &*(&*entries)[index].1 };
+                    #entry_value_ref
+                }
+
+                #[doc(hidden)]
+                #[export_name = #entries_drop]
+                pub extern "C" fn _entries_drop(entries: *mut #entries_ty) {
+                    let entries = unsafe { Box::from_raw(entries) };
+                    drop(entries)
+                }
+            };
+        }])
+    }
+
+    /// Declare the functions from [`BuiltInMap::generate_custom_rust_ffi_types`] in the C header.
+    pub fn generate_custom_c_ffi_types(&self, types: &TypeDeclarations) -> Option<CFfiStruct> {
+        let prefix = self.ffi_name_prefix(types);
+
+        let (key_item, value_item) = self.key_and_value_items(types);
+        let key_ref = key_item.to_c(&self.key, types);
+        let value_ref = value_item.to_c(&self.value, types);
+        let optional_value_ref = value_item.to_optional_c(&self.value, &self.optional_value, types);
+        let key = self.key.to_c(types);
+        let value = self.value.to_c(types);
+        let optional_value = self.optional_value.to_c(types);
+
+        let c_ffi_type = format!(
+            r#"void* {prefix}$new(void);
+void {prefix}$drop(void* map);
+uintptr_t {prefix}$len(void* map);
+bool {prefix}$contains_key(void* map, {key_ref} key);
+{optional_value_ref} {prefix}$get(void* map, {key_ref} key);
+void {prefix}$insert(void* map, {key} key, {value} value);
+{optional_value} {prefix}$remove(void* map, {key_ref} key);
+void* {prefix}$entries(void* map);
+{key_ref} {prefix}$entries_key(void* entries, uintptr_t index);
+{value_ref} {prefix}$entries_value(void* entries, uintptr_t index);
+void {prefix}$entries_drop(void* entries);"#
+        );
+
+        Some(CFfiStruct {
+            c_ffi_type,
+            fields: vec![],
+        })
+    }
+
+    /// The `RustMapVtable` property that Swift uses for this map, such as
+    /// `RustMapVtable<UInt32, RustString>.hashMap`.
+    ///
+    /// Every bridge module that uses the same map generates this property, so it should only be
+    /// written to the Swift module once.
+    pub fn swift_vtable_key(&self, types: &TypeDeclarations) -> String {
+        format!(
+            "RustMapVtable<{}, {}>.{}",
+            self.key
+                .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types),
+            self.value
+                .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types),
+            self.kind.swift_vtable_name()
+        )
+    }
+
+    /// Generate the `RustMapVtable` that lets the generic Swift `RustMap` call into the
+    /// functions that are specific to this key and value type.
+    pub fn generate_swift_vtable(&self, types: &TypeDeclarations) -> String {
+        let prefix = self.ffi_name_prefix(types);
+        let swift_key = self
+            .key
+            .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+        let swift_value = self
+            .value
+            .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+
+        let (key_item, value_item) = self.key_and_value_items(types);
+
+        let lookup_key = key_item.convert_swift_ref_to_ffi_type("key");
+        let get = value_item.convert_optional_ffi_ref_to_swift_type(
+            &format!("{prefix}$get(map, {lookup_key})"),
+            &self.optional_value,
+            types,
+        );
+        let entries_key = key_item
+            .convert_ffi_ref_to_swift_type(&format!("{prefix}$entries_key(entries, index)"));
+        let entries_value = value_item
+            .convert_ffi_ref_to_swift_type(&format!("{prefix}$entries_value(entries, index)"));
+
+        let owned_key = self.key.convert_swift_expression_to_ffi_type(
+            "key",
+            types,
+            TypePosition::FnArg(HostLang::Rust, 0),
+        );
+        let owned_value = self.value.convert_swift_expression_to_ffi_type(
+            "value",
+            types,
+            TypePosition::FnArg(HostLang::Rust, 1),
+        );
+        let removed_value = self.optional_value.convert_ffi_value_to_swift_value(
+            &format!("{prefix}$remove(map, {lookup_key})"),
+            TypePosition::FnReturn(HostLang::Rust),
+            types,
+        );
+
+        let vtable_name = self.kind.swift_vtable_name();
+
+        format!(
+            r#"extension RustMapVtable where K == {swift_key}, V == {swift_value} {{
+    public static var {vtable_name}: RustMapVtable<{swift_key}, {swift_value}> {{
+        RustMapVtable(
+            new: {{ {prefix}$new() }},
+            free: {{ map in {prefix}$drop(map) }},
+            len: {{ map in {prefix}$len(map) }},
+            containsKey: {{ map, key in {prefix}$contains_key(map, {lookup_key}) }},
+            get: {{ map, key in {get} }},
+            insert: {{ map, key, value in {prefix}$insert(map, {owned_key}, {owned_value}) }},
+            remove: {{ map, key in {removed_value} }},
+            entries: {{ map in {prefix}$entries(map) }},
+            entriesKey: {{ entries, index in {entries_key} }},
+            entriesValue: {{ entries, index in {entries_value} }},
+            entriesFree: {{ entries in {prefix}$entries_drop(entries) }}
+        )
+    }}
+}}
+"#
+        )
+    }
+
+    pub fn to_alpha_numeric_underscore_name(&self, types: &TypeDeclarations) -> String {
+        format!(
+            "{}_{}_{}",
+            self.kind.as_str(),
+            self.key.to_alpha_numeric_underscore_name(types),
+            self.value.to_alpha_numeric_underscore_name(types)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bridged_type::StdLibType;
+
+    /// Verify that we can parse maps with and without a `std::collections` prefix.
+    #[test]
+    fn parse_map_from_str_tokens() {
+        let types = TypeDeclarations::default();
+
+        let tests = vec![
+            ("HashMap < u8 , u16 >", MapKind::HashMap),
+            ("BTreeMap < u8 , u16 >", MapKind::BTreeMap),
+            (
+                "std :: collections :: HashMap < u8 , u16 >",
+                MapKind::HashMap,
+            ),
+        ];
+
+        for (tokens, expected_kind) in tests {
+            let map = BuiltInMap::from_str_tokens(tokens, &types).unwrap();
+            assert_eq!(map.kind, expected_kind);
+            assert!(matches!(*map.key, BridgedType::StdLib(StdLibType::U8)));
+            assert!(matches!(*map.value, BridgedType::StdLib(StdLibType::U16)));
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use crate::bridged_type::collection_item::{collection_item_ref, CollectionItemRef};
+use crate::bridged_type::{BridgedType, CFfiStruct, TypePosition};
+use crate::parse::{HostLang, TypeDeclarations};
+use crate::SWIFT_BRIDGE_PREFIX;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use std::str::FromStr;
+use syn::{GenericArgument, Path, PathArguments, Type};
+
+/// HashSet<T> or BTreeSet<T>
+#[derive(Debug)]
+pub(crate) struct BuiltInSet {
+    pub kind: SetKind,
+    pub ty: Box<BridgedType>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum SetKind {
+    HashSet,
+    BTreeSet,
+}
+
+impl SetKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SetKind::HashSet => "HashSet",
+            SetKind::BTreeSet => "BTreeSet",
+        }
+    }
+
+    /// The name of the static `RustSetVtable` property that Swift uses for this kind of set.
+    fn swift_vtable_name(&self) -> &'static str {
+        match self {
+            SetKind::HashSet => "hashSet",
+            SetKind::BTreeSet => "btreeSet",
+        }
+    }
+}
+
+impl BuiltInSet {
+    /// Parse a set from a stringified token stream such as `HashSet < u32 >`.
+    pub fn from_str_tokens(tokens: &str, types: &TypeDeclarations) -> Option<Self> {
+        let tokens = tokens.trim_start_matches("std :: collections :: ");
+        let kind = if tokens.starts_with("HashSet < ") {
+            SetKind::HashSet
+        } else if tokens.starts_with("BTreeSet < ") {
+            SetKind::BTreeSet
+        } else {
+            return None;
+        };
+
+        let ty: Type = syn::parse2(TokenStream::from_str(tokens).ok()?).ok()?;
+        let args = match ty {
+            Type::Path(path) => match path.path.segments.last()?.arguments.clone() {
+                PathArguments::AngleBracketed(args) => args.args,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let ty = args.into_iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })?;
+
+        Some(BuiltInSet {
+            kind,
+            ty: Box::new(BridgedType::new_with_type(&ty, types)?),
+        })
+    }
+
+    /// HashSet<T> -> std::collections::HashSet<T>
+    pub fn to_rust_type_path(&self, types: &TypeDeclarations) -> TokenStream {
+        let kind = format_ident!("{}", self.kind.as_str());
+        let ty = self.ty.to_rust_type_path(types);
+
+        quote! { std::collections::#kind<#ty> }
+    }
+
+    /// RustSet<UInt32>
+    pub fn to_swift_type(&self, types: &TypeDeclarations) -> String {
+        format!(
+            "RustSet<{}>",
+            self.ty
+                .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types)
+        )
+    }
+
+    /// Convert a pointer to a Rust set into a Swift `RustSet`.
+    pub fn convert_ffi_value_to_swift_value(
+        &self,
+        expression: &str,
+        types: &TypeDeclarations,
+    ) -> String {
+        format!(
+            "{}(ptr: {}, vtable: .{})",
+            self.to_swift_type(types),
+            expression,
+            self.kind.swift_vtable_name()
+        )
+    }
+
+    /// HashSet<u32> -> __swift_bridge__$some_function$HashSet_U32
+    ///
+    /// See [`TypeDeclarations::collection_symbol_scope`] for why the module's first function is
+    /// part of the name.
+    fn ffi_name_prefix(&self, types: &TypeDeclarations) -> String {
+        format!(
+            "{}${}",
+            types
+                .collection_symbol_scope()
+                .unwrap_or(SWIFT_BRIDGE_PREFIX),
+            self.to_alpha_numeric_underscore_name(types)
+        )
+    }
+
+    /// Items are validated while parsing, so we know that they can be stored in a set.
+    fn item(&self, types: &TypeDeclarations) -> CollectionItemRef {
+        collection_item_ref(&self.ty, types).unwrap()
+    }
+
+    /// Generate the functions that Swift uses to work with this set.
+    ///
+    /// Like the `vec_externs!` in `swift_bridge::std_bridge::rust_vec`, but only emitted for the
+    /// sets that a module uses.
+    pub fn generate_custom_rust_ffi_types(
+        &self,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> Option<Vec<TokenStream>> {
+        let prefix = self.ffi_name_prefix(types);
+        let export_name = |func: &str| format!("{}${}", prefix, func);
+
+        let set_ty = self.to_rust_type_path(types);
+        let ty = self.ty.to_rust_type_path(types);
+        let items_ty = quote! { Vec<*const #ty> };
+
+        let item = self.item(types);
+
+        let ref_ffi_ty = item.to_ffi_compatible_rust_type(&self.ty, swift_bridge_path, types);
+        let ffi_ty = self
+            .ty
+            .to_ffi_compatible_rust_type(swift_bridge_path, types);
+
+        let lookup_value = item.convert_ffi_expression_to_rust_ref(&quote! { value });
+        let item_ref = item.convert_rust_ref_to_ffi_type(&quote! { item }, &ty);
+        let owned_value = self.ty.convert_ffi_expression_to_rust_type(
+            &quote! { value },
+            Span::call_site(),
+            swift_bridge_path,
+            types,
+        );
+
+        let new = export_name("new");
+        let drop = export_name("drop");
+        let len = export_name("len");
+        let contains = export_name("contains");
+        let insert = export_name("insert");
+        let remove = export_name("remove");
+        let items = export_name("items");
+        let items_get = export_name("items_get");
+        let items_drop = export_name("items_drop");
+
+        Some(vec![quote! {
+            const _: () = {
+                #[doc(hidden)]
+                #[export_name = #new]
+                pub extern "C" fn _new() -> *mut #set_ty {
+                    Box::into_raw(Box::new(<#set_ty>::new()))
+                }
+
+                #[doc(hidden)]
+                #[export_name = #drop]
+                pub extern "C" fn _drop(set: *mut #set_ty) {
+                    let set = unsafe { Box::from_raw(set) };
+                    drop(set)
+                }
+
+                #[doc(hidden)]
+                #[export_name = #len]
+                pub extern "C" fn _len(set: *const #set_ty) -> usize {
+                    unsafe { &*set }.len()
+                }
+
+                #[doc(hidden)]
+                #[export_name = #contains]
+                pub extern "C" fn _contains(set: *const #set_ty, value: #ref_ffi_ty) -> bool {
+                    unsafe { &*set }.contains(#lookup_value)
+                }
+
+                #[doc(hidden)]
+                #[export_name = #insert]
+                pub extern "C" fn _insert(set: *mut #set_ty, value: #ffi_ty) -> bool {
+                    unsafe { &mut *set }.insert(#owned_value)
+                }
+
+                #[doc(hidden)]
+                #[export_name = #remove]
+                pub extern "C" fn _remove(set: *mut #set_ty, value: #ref_ffi_ty) -> bool {
+                    unsafe { &mut *set }.remove(#lookup_value)
+                }
+
+                #[doc(hidden)]
+                #[export_name = #items]
+                pub extern "C" fn _items(set: *const #set_ty) -> *mut #items_ty {
+                    let items: #items_ty = unsafe { &*set }
+                        .iter()
+                        .map(|item| item as *const #ty)
+                        .collect();
+                    Box::into_raw(Box::new(items))
+                }
+
+                #[doc(hidden)]
+                #[export_name = #items_get]
+                pub extern "C" fn _items_get(items: *const #items_ty, index: usize) -> #ref_ffi_ty {
+                    let item = unsafe { &*(&*items)[index] };
+                    #item_ref
+                }
+
+                #[doc(hidden)]
+                #[export_name = #items_drop]
+                pub extern "C" fn _items_drop(items: *mut #items_ty) {
+                    let items = unsafe { Box::from_raw(items) };
+                    drop(items)
+                }
+            };
+        }])
+    }
+
+    /// Declare the functions from [`BuiltInSet::generate_custom_rust_ffi_types`] in the C header.
+    pub fn generate_custom_c_ffi_types(&self, types: &TypeDeclarations) -> Option<CFfiStruct> {
+        let prefix = self.ffi_name_prefix(types);
+
+        let item_ref = self.item(types).to_c(&self.ty, types);
+        let item = self.ty.to_c(types);
+
+        let c_ffi_type = format!(
+            r#"void* {prefix}$new(void);
+void {prefix}$drop(void* set);
+uintptr_t {prefix}$len(void* set);
+bool {prefix}$contains(void* set, {item_ref} value);
+bool {prefix}$insert(void* set, {item} value);
+bool {prefix}$remove(void* set, {item_ref} value);
+void* {prefix}$items(void* set);
+{item_ref} {prefix}$items_get(void* items, uintptr_t index);
+void {prefix}$items_drop(void* items);"#
+        );
+
+        Some(CFfiStruct {
+            c_ffi_type,
+            fields: vec![],
+        })
+    }
+
+    /// The `RustSetVtable` property that Swift uses for this set, such as
+    /// `RustSetVtable<UInt32>.hashSet`.
+    ///
+    /// Every bridge module that uses the same set generates this property, so it should only be
+    /// written to the Swift module once.
+    pub fn swift_vtable_key(&self, types: &TypeDeclarations) -> String {
+        format!(
+            "RustSetVtable<{}>.{}",
+            self.ty
+                .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types),
+            self.kind.swift_vtable_name()
+        )
+    }
+
+    /// Generate the `RustSetVtable` that lets the generic Swift `RustSet` call into the
+    /// functions that are specific to this item type.
+    pub fn generate_swift_vtable(&self, types: &TypeDeclarations) -> String {
+        let prefix = self.ffi_name_prefix(types);
+        let swift_ty = self
+            .ty
+            .to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+
+        let item = self.item(types);
+
+        let lookup_value = item.convert_swift_ref_to_ffi_type("value");
+        let items_get =
+            item.convert_ffi_ref_to_swift_type(&format!("{prefix}$items_get(items, index)"));
+        let owned_value = self.ty.convert_swift_expression_to_ffi_type(
+            "value",
+            types,
+            TypePosition::FnArg(HostLang::Rust, 0),
+        );
+
+        let vtable_name = self.kind.swift_vtable_name();
+
+        format!(
+            r#"extension RustSetVtable where T == {swift_ty} {{
+    public static var {vtable_name}: RustSetVtable<{swift_ty}> {{
+        RustSetVtable(
+            new: {{ {prefix}$new() }},
+            free: {{ set in {prefix}$drop(set) }},
+            len: {{ set in {prefix}$len(set) }},
+            contains: {{ set, value in {prefix}$contains(set, {lookup_value}) }},
+            insert: {{ set, value in {prefix}$insert(set, {owned_value}) }},
+            remove: {{ set, value in {prefix}$remove(set, {lookup_value}) }},
+            items: {{ set in {prefix}$items(set) }},
+            itemsGet: {{ items, index in {items_get} }},
+            itemsFree: {{ items in {prefix}$items_drop(items) }}
+        )
+    }}
+}}
+"#
+        )
+    }
+
+    pub fn to_alpha_numeric_underscore_name(&self, types: &TypeDeclarations) -> String {
+        format!(
+            "{}_{}",
+            self.kind.as_str(),
+            self.ty.to_alpha_numeric_underscore_name(types),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bridged_type::StdLibType;
+
+    /// Verify that we can parse sets with and without a `std::collections` prefix.
+    #[test]
+    fn parse_set_from_str_tokens() {
+        let types = TypeDeclarations::default();
+
+        let tests = vec![
+            ("HashSet < u8 >", SetKind::HashSet),
+            ("BTreeSet < u8 >", SetKind::BTreeSet),
+            ("std :: collections :: HashSet < u8 >", SetKind::HashSet),
+        ];
+
+        for (tokens, expected_kind) in tests {
+            let set = BuiltInSet::from_str_tokens(tokens, &types).unwrap();
+            assert_eq!(set.kind, expected_kind);
+            assert!(matches!(*set.ty, BridgedType::StdLib(StdLibType::U8)));
+        }
+    }
+}
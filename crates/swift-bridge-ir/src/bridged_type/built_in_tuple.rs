@@ -1,6 +1,7 @@
 use crate::bridged_type::shared_struct::UnnamedStructFields;
 use crate::bridged_type::{
-    BridgeableType, BuiltInResult, CFfiStruct, OnlyEncoding, TypePosition, UnusedOptionNoneValue,
+    BridgeableType, BridgedType, BuiltInResult, CFfiStruct, OnlyEncoding, TypePosition,
+    UnusedOptionNoneValue,
 };
 use crate::parse::TypeDeclarations;
 use crate::SWIFT_BRIDGE_PREFIX;
@@ -323,6 +324,10 @@ impl BridgeableType for BuiltInTuple {
         todo!();
     }
 
+    fn is_string(&self) -> bool {
+        false
+    }
+
     fn contains_owned_string_recursive(&self, types: &TypeDeclarations) -> bool {
         self.0.contains_owned_string_recursive(types)
     }
@@ -331,6 +336,14 @@ impl BridgeableType for BuiltInTuple {
         todo!()
     }
 
+    fn contains_arc_of_opaque_type(&self, opaque_ty: &Ident, types: &TypeDeclarations) -> bool {
+        self.0.contains_arc_of_opaque_type(opaque_ty, types)
+    }
+
+    fn visit_maps_and_sets(&self, types: &TypeDeclarations, visit: &mut dyn FnMut(&BridgedType)) {
+        self.0.visit_maps_and_sets(types, visit)
+    }
+
     fn has_swift_bridge_copy_annotation(&self) -> bool {
         todo!();
     }
@@ -0,0 +1,264 @@
+use crate::bridged_type::{BridgedType, CustomBridgedType, SharedType, StdLibType, TypePosition};
+use crate::parse::{HostLang, TypeDeclaration, TypeDeclarations};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::Path;
+
+/// How a map key or value, or a set item, gets exposed to Swift while it is still owned by the
+/// Rust collection.
+///
+/// This mirrors the `Vectorizable.SelfRef` associated type on the Swift side.
+pub(crate) enum CollectionItemRef {
+    /// u8, i32, bool, etc. Copied across the boundary.
+    Primitive,
+    /// String, exposed as a `RustStringRef`.
+    String,
+    /// An opaque Rust type, exposed as a `SomeTypeRef`.
+    OpaqueRustType(String),
+    /// A transparent enum with no data carrying variants. Copied across the boundary.
+    TransparentEnum,
+    /// A transparent struct that derives `Clone`. Cloned across the boundary.
+    TransparentStruct,
+}
+
+/// Returns `None` if the type can't be used as a map key or value, or a set item.
+/// These get reported as a `ParseError::UnsupportedCollectionItem`.
+pub(crate) fn collection_item_ref(
+    ty: &BridgedType,
+    types: &TypeDeclarations,
+) -> Option<CollectionItemRef> {
+    match ty {
+        BridgedType::StdLib(stdlib_ty) => match stdlib_ty {
+            StdLibType::U8
+            | StdLibType::I8
+            | StdLibType::U16
+            | StdLibType::I16
+            | StdLibType::U32
+            | StdLibType::I32
+            | StdLibType::U64
+            | StdLibType::I64
+            | StdLibType::Usize
+            | StdLibType::Isize
+            | StdLibType::F32
+            | StdLibType::F64
+            | StdLibType::Bool => Some(CollectionItemRef::Primitive),
+            _ => None,
+        },
+        BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Enum(shared_enum)))
+            if !shared_enum.has_one_or_more_variants_with_data() =>
+        {
+            Some(CollectionItemRef::TransparentEnum)
+        }
+        BridgedType::Foreign(CustomBridgedType::Shared(SharedType::Struct(shared_struct)))
+            if shared_struct.is_vectorizable() =>
+        {
+            Some(CollectionItemRef::TransparentStruct)
+        }
+        BridgedType::Foreign(_) => None,
+        BridgedType::Bridgeable(b) => {
+            let name = b.to_alpha_numeric_underscore_name(types);
+            if b.is_built_in_type() && name == "String" {
+                return Some(CollectionItemRef::String);
+            }
+
+            match types.get(&name) {
+                Some(TypeDeclaration::Opaque(opaque))
+                    if opaque.host_lang.is_rust() && opaque.attributes.copy.is_none() =>
+                {
+                    Some(CollectionItemRef::OpaqueRustType(name))
+                }
+                _ => None,
+            }
+        }
+    }
+}
+
+impl CollectionItemRef {
+    /// The FFI type used to pass a reference to an item.
+    pub(super) fn to_ffi_compatible_rust_type(
+        &self,
+        ty: &BridgedType,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        match self {
+            CollectionItemRef::Primitive
+            | CollectionItemRef::TransparentEnum
+            | CollectionItemRef::TransparentStruct => {
+                ty.to_ffi_compatible_rust_type(swift_bridge_path, types)
+            }
+            CollectionItemRef::String => quote! { *const #swift_bridge_path::string::RustString },
+            CollectionItemRef::OpaqueRustType(_) => {
+                let ty = ty.to_rust_type_path(types);
+                quote! { *const #ty }
+            }
+        }
+    }
+
+    /// The FFI type used to pass an `Option<&T>`, such as the result of looking up a key.
+    ///
+    /// `optional_ty` is the `Option<T>` for the item.
+    pub(super) fn to_optional_ffi_compatible_rust_type(
+        &self,
+        ty: &BridgedType,
+        optional_ty: &BridgedType,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        match self {
+            CollectionItemRef::Primitive
+            | CollectionItemRef::TransparentEnum
+            | CollectionItemRef::TransparentStruct => {
+                optional_ty.to_ffi_compatible_rust_type(swift_bridge_path, types)
+            }
+            // A null pointer means `None`.
+            CollectionItemRef::String | CollectionItemRef::OpaqueRustType(_) => {
+                self.to_ffi_compatible_rust_type(ty, swift_bridge_path, types)
+            }
+        }
+    }
+
+    pub(super) fn to_c(&self, ty: &BridgedType, types: &TypeDeclarations) -> String {
+        match self {
+            CollectionItemRef::Primitive
+            | CollectionItemRef::TransparentEnum
+            | CollectionItemRef::TransparentStruct => ty.to_c(types),
+            CollectionItemRef::String | CollectionItemRef::OpaqueRustType(_) => "void*".to_string(),
+        }
+    }
+
+    /// The C type used to pass an `Option<&T>`.
+    pub(super) fn to_optional_c(
+        &self,
+        ty: &BridgedType,
+        optional_ty: &BridgedType,
+        types: &TypeDeclarations,
+    ) -> String {
+        match self {
+            CollectionItemRef::Primitive
+            | CollectionItemRef::TransparentEnum
+            | CollectionItemRef::TransparentStruct => optional_ty.to_c(types),
+            CollectionItemRef::String | CollectionItemRef::OpaqueRustType(_) => {
+                self.to_c(ty, types)
+            }
+        }
+    }
+
+    /// Convert a `&T` that points into the collection into its FFI representation.
+    pub(super) fn convert_rust_ref_to_ffi_type(
+        &self,
+        expression: &TokenStream,
+        ty: &TokenStream,
+    ) -> TokenStream {
+        match self {
+            CollectionItemRef::Primitive => quote! { *#expression },
+            // `RustString` is `#[repr(transparent)]`, so a `&String` can be viewed as a
+            // `&RustString`.
+            CollectionItemRef::String => quote! { #expression as *const String as *const _ },
+            CollectionItemRef::OpaqueRustType(_) => quote! { #expression as *const #ty },
+            CollectionItemRef::TransparentEnum => quote! { (*#expression).into_ffi_repr() },
+            CollectionItemRef::TransparentStruct => {
+                quote! { Clone::clone(#expression).into_ffi_repr() }
+            }
+        }
+    }
+
+    /// Convert an `Option<&T>` that points into the collection into its FFI representation.
+    pub(super) fn convert_optional_rust_ref_to_ffi_type(
+        &self,
+        expression: &TokenStream,
+        ty: &TokenStream,
+        optional_ty: &BridgedType,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        let copied = match self {
+            CollectionItemRef::Primitive | CollectionItemRef::TransparentEnum => {
+                quote! { #expression.copied() }
+            }
+            CollectionItemRef::TransparentStruct => quote! { #expression.cloned() },
+            CollectionItemRef::String | CollectionItemRef::OpaqueRustType(_) => {
+                let val = self.convert_rust_ref_to_ffi_type(&quote! { val }, ty);
+                return quote! {
+                    if let Some(val) = #expression {
+                        #val
+                    } else {
+                        std::ptr::null()
+                    }
+                };
+            }
+        };
+
+        optional_ty.convert_rust_expression_to_ffi_type(
+            &copied,
+            swift_bridge_path,
+            types,
+            Span::call_site(),
+        )
+    }
+
+    /// Convert the FFI representation of an item into a `&T` that can be used for lookups.
+    pub(super) fn convert_ffi_expression_to_rust_ref(
+        &self,
+        expression: &TokenStream,
+    ) -> TokenStream {
+        match self {
+            CollectionItemRef::Primitive => quote! { &#expression },
+            CollectionItemRef::String => quote! { unsafe { &(*#expression).0 } },
+            CollectionItemRef::OpaqueRustType(_) => quote! { unsafe { &*#expression } },
+            CollectionItemRef::TransparentEnum | CollectionItemRef::TransparentStruct => {
+                quote! { &#expression.into_rust_repr() }
+            }
+        }
+    }
+
+    /// Convert a Swift `Vectorizable.SelfRef` into its FFI representation.
+    pub(super) fn convert_swift_ref_to_ffi_type(&self, expression: &str) -> String {
+        match self {
+            CollectionItemRef::Primitive => expression.to_string(),
+            CollectionItemRef::String | CollectionItemRef::OpaqueRustType(_) => {
+                format!("{expression}.ptr")
+            }
+            CollectionItemRef::TransparentEnum | CollectionItemRef::TransparentStruct => {
+                format!("{expression}.intoFfiRepr()")
+            }
+        }
+    }
+
+    /// Convert the FFI representation of a reference into a Swift `Vectorizable.SelfRef`.
+    pub(super) fn convert_ffi_ref_to_swift_type(&self, expression: &str) -> String {
+        match self {
+            CollectionItemRef::Primitive => expression.to_string(),
+            CollectionItemRef::String => format!("RustStringRef(ptr: {expression})"),
+            CollectionItemRef::OpaqueRustType(ty) => format!("{ty}Ref(ptr: {expression})"),
+            CollectionItemRef::TransparentEnum | CollectionItemRef::TransparentStruct => {
+                format!("{expression}.intoSwiftRepr()")
+            }
+        }
+    }
+
+    /// Convert the FFI representation of an `Option<&T>` into a Swift
+    /// `Optional<Vectorizable.SelfRef>`.
+    pub(super) fn convert_optional_ffi_ref_to_swift_type(
+        &self,
+        expression: &str,
+        optional_ty: &BridgedType,
+        types: &TypeDeclarations,
+    ) -> String {
+        match self {
+            CollectionItemRef::Primitive
+            | CollectionItemRef::TransparentEnum
+            | CollectionItemRef::TransparentStruct => optional_ty.convert_ffi_value_to_swift_value(
+                expression,
+                TypePosition::FnReturn(HostLang::Rust),
+                types,
+            ),
+            CollectionItemRef::String | CollectionItemRef::OpaqueRustType(_) => {
+                let val = self.convert_ffi_ref_to_swift_type("val!");
+                format!(
+                    "{{ let val = {expression}; if val != nil {{ return {val} }} else {{ return nil }} }}()"
+                )
+            }
+        }
+    }
+}
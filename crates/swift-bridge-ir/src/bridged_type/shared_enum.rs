@@ -1,3 +1,4 @@
+use crate::parse::{Deprecation, PlatformVersion, SerdeAttributes};
 use crate::SWIFT_BRIDGE_PREFIX;
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
@@ -12,6 +13,13 @@ use super::StructFields;
 #[derive(Default, Clone)]
 pub(crate) struct DeriveAttrs {
     pub debug: bool,
+    pub partial_eq: bool,
+    pub eq: bool,
+    pub hash: bool,
+    pub partial_ord: bool,
+    pub ord: bool,
+    pub serialize: bool,
+    pub deserialize: bool,
 }
 
 #[derive(Clone)]
@@ -21,6 +29,12 @@ pub(crate) struct SharedEnum {
     pub already_declared: bool,
     pub swift_name: Option<LitStr>,
     pub derive: DeriveAttrs,
+    pub serde: SerdeAttributes,
+    pub doc_comment: Option<String>,
+    /// `#[swift_bridge(available(iOS = "15"))]`
+    pub available: Vec<PlatformVersion>,
+    /// `#[deprecated]`
+    pub deprecated: Option<Deprecation>,
 }
 
 impl SharedEnum {
@@ -1,5 +1,5 @@
 use crate::bridged_type::{BridgedType, StructFields, TypePosition};
-use crate::parse::TypeDeclarations;
+use crate::parse::{Deprecation, SerdeAttributes, TypeDeclarations};
 use proc_macro2::Ident;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
@@ -12,6 +12,10 @@ pub(crate) struct EnumVariant {
     pub name: Ident,
     #[allow(unused)]
     pub fields: StructFields,
+    pub serde: SerdeAttributes,
+    pub doc_comment: Option<String>,
+    /// `#[deprecated]`
+    pub deprecated: Option<Deprecation>,
 }
 
 impl EnumVariant {
@@ -1,8 +1,11 @@
+pub(crate) use self::struct_field::NormalizedStructField;
 pub(crate) use self::struct_field::StructField;
 pub(crate) use self::struct_field::StructFields;
 use self::struct_field::UnnamedStructField;
 use crate::bridged_type::{BridgedType, OnlyEncoding, TypePosition};
-use crate::parse::TypeDeclarations;
+use crate::parse::{
+    Deprecation, OperatorTrait, PlatformVersion, SerdeAttributes, TypeDeclarations,
+};
 use crate::SWIFT_BRIDGE_PREFIX;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
@@ -22,7 +25,12 @@ impl UnnamedStructFields {
         let unnamed_fields = types
             .into_iter()
             .enumerate()
-            .map(|(idx, ty)| UnnamedStructField { ty: ty, idx: idx })
+            .map(|(idx, ty)| UnnamedStructField {
+                ty: ty,
+                idx: idx,
+                serde: SerdeAttributes::default(),
+                doc_comment: None,
+            })
             .collect();
         Self(unnamed_fields)
     }
@@ -198,6 +206,23 @@ impl UnnamedStructFields {
             })
             .any(|ty| ty.contains_owned_string_recursive(types))
     }
+    pub fn contains_arc_of_opaque_type(&self, opaque_ty: &Ident, types: &TypeDeclarations) -> bool {
+        self.0.iter().any(|field| {
+            BridgedType::new_with_type(&field.ty, types)
+                .is_some_and(|ty| ty.contains_arc_of_opaque_type(opaque_ty, types))
+        })
+    }
+    pub fn visit_maps_and_sets(
+        &self,
+        types: &TypeDeclarations,
+        visit: &mut dyn FnMut(&BridgedType),
+    ) {
+        for field in self.0.iter() {
+            if let Some(ty) = BridgedType::new_with_type(&field.ty, types) {
+                ty.visit_maps_and_sets(types, visit);
+            }
+        }
+    }
     pub fn to_rust_type_path_tokens(&self, types: &TypeDeclarations) -> Vec<TokenStream> {
         self.0
             .iter()
@@ -251,12 +276,27 @@ pub(crate) struct SharedStruct {
     pub swift_name: Option<LitStr>,
     pub already_declared: bool,
     pub derives: StructDerives,
+    pub serde: SerdeAttributes,
+    pub doc_comment: Option<String>,
+    /// `#[swift_bridge(available(iOS = "15"))]`
+    pub available: Vec<PlatformVersion>,
+    /// `#[deprecated]`
+    pub deprecated: Option<Deprecation>,
+    /// `#[swift_bridge(Add, Sub, Index(usize, Output = f64))]`
+    pub operators: Vec<OperatorTrait>,
 }
 
 #[derive(Clone)]
 pub(crate) struct StructDerives {
     pub copy: bool,
     pub clone: bool,
+    pub partial_eq: bool,
+    pub eq: bool,
+    pub hash: bool,
+    pub partial_ord: bool,
+    pub ord: bool,
+    pub serialize: bool,
+    pub deserialize: bool,
 }
 
 impl SharedStruct {
@@ -267,6 +307,12 @@ impl SharedStruct {
         }
     }
 
+    /// Transparent structs that derive `Clone` can be stored in a `RustVec`, `RustMap` or
+    /// `RustSet`. Swift gets a clone of an item whenever it reads one.
+    pub(crate) fn is_vectorizable(&self) -> bool {
+        self.swift_repr == StructSwiftRepr::Structure && self.derives.clone
+    }
+
     pub(crate) fn ffi_name_string(&self) -> String {
         let name = self.swift_name_string();
 
@@ -1,5 +1,8 @@
 use std::fmt::{Debug, Formatter};
 
+use crate::bridged_type::BridgedType;
+use crate::errors::ParseErrors;
+use crate::parse::{doc_comment_from_attributes, SerdeAttributes, TypeDeclarations};
 use proc_macro2::{Ident, TokenStream};
 use quote::{quote, ToTokens};
 use syn::{Fields, Type};
@@ -32,6 +35,7 @@ impl StructFields {
                 .map(|n| NormalizedStructField {
                     accessor: NormalizedStructFieldAccessor::Named(n.name.clone()),
                     ty: n.ty.clone(),
+                    serde: n.serde.clone(),
                 })
                 .collect(),
             StructFields::Unnamed(unnamed) => unnamed
@@ -39,12 +43,35 @@ impl StructFields {
                 .map(|u| NormalizedStructField {
                     accessor: NormalizedStructFieldAccessor::Unnamed(u.idx),
                     ty: u.ty.clone(),
+                    serde: u.serde.clone(),
                 })
                 .collect(),
             StructFields::Unit => Vec::new(),
         }
     }
 
+    /// Whether or not any of the fields is an `Arc<SomeType>` of the given opaque Rust type, or a
+    /// type that contains one.
+    pub fn contains_arc_of_opaque_type(&self, opaque_ty: &Ident, types: &TypeDeclarations) -> bool {
+        self.normalized_fields().iter().any(|field| {
+            BridgedType::new_with_type(&field.ty, types)
+                .is_some_and(|ty| ty.contains_arc_of_opaque_type(opaque_ty, types))
+        })
+    }
+
+    /// Call `visit` with every `HashMap`, `BTreeMap`, `HashSet` and `BTreeSet` within the fields.
+    pub fn visit_maps_and_sets(
+        &self,
+        types: &TypeDeclarations,
+        visit: &mut dyn FnMut(&BridgedType),
+    ) {
+        for field in self.normalized_fields() {
+            if let Some(ty) = BridgedType::new_with_type(&field.ty, types) {
+                ty.visit_maps_and_sets(types, visit);
+            }
+        }
+    }
+
     pub fn wrap_declaration_fields(&self, struct_fields: &[TokenStream]) -> TokenStream {
         match &self {
             StructFields::Named(_) => {
@@ -82,14 +109,18 @@ impl StructFields {
         }
     }
 
-    pub fn from_syn_fields(fields: Fields) -> Self {
-        match fields {
+    pub fn from_syn_fields(fields: Fields, errors: &mut ParseErrors) -> syn::Result<Self> {
+        let mut serde_errors = vec![];
+
+        let fields = match fields {
             Fields::Named(f) => {
                 let mut fields = vec![];
                 for field in f.named.iter() {
                     let field = NamedStructField {
                         name: field.ident.clone().unwrap(),
                         ty: field.ty.clone(),
+                        serde: SerdeAttributes::from_attributes(&field.attrs, &mut serde_errors)?,
+                        doc_comment: doc_comment_from_attributes(&field.attrs)?,
                     };
                     fields.push(field);
                 }
@@ -102,6 +133,8 @@ impl StructFields {
                     let field = UnnamedStructField {
                         ty: field.ty.clone(),
                         idx,
+                        serde: SerdeAttributes::from_attributes(&field.attrs, &mut serde_errors)?,
+                        doc_comment: doc_comment_from_attributes(&field.attrs)?,
                     };
                     fields.push(field);
                 }
@@ -109,7 +142,11 @@ impl StructFields {
                 StructFields::Unnamed(fields)
             }
             Fields::Unit => StructFields::Unit,
-        }
+        };
+
+        errors.append(serde_errors);
+
+        Ok(fields)
     }
 }
 
@@ -117,17 +154,22 @@ impl StructFields {
 pub(crate) struct NamedStructField {
     pub name: Ident,
     pub ty: Type,
+    pub serde: SerdeAttributes,
+    pub doc_comment: Option<String>,
 }
 
 #[derive(Clone)]
 pub(crate) struct UnnamedStructField {
     pub ty: Type,
     pub idx: usize,
+    pub serde: SerdeAttributes,
+    pub doc_comment: Option<String>,
 }
 
 pub(crate) trait StructField {
     fn field_type(&self) -> &Type;
     fn swift_name_string(&self) -> String;
+    fn doc_comment(&self) -> Option<&String>;
 }
 
 impl StructField for NamedStructField {
@@ -138,6 +180,10 @@ impl StructField for NamedStructField {
     fn swift_name_string(&self) -> String {
         self.name.to_string()
     }
+
+    fn doc_comment(&self) -> Option<&String> {
+        self.doc_comment.as_ref()
+    }
 }
 
 impl StructField for UnnamedStructField {
@@ -148,6 +194,10 @@ impl StructField for UnnamedStructField {
     fn swift_name_string(&self) -> String {
         format!("_{}", self.idx)
     }
+
+    fn doc_comment(&self) -> Option<&String> {
+        self.doc_comment.as_ref()
+    }
 }
 
 impl PartialEq for NamedStructField {
@@ -1,3 +1,4 @@
+use crate::parse::SerdeAttributes;
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 use std::str::FromStr;
@@ -6,6 +7,7 @@ use syn::Type;
 pub(crate) struct NormalizedStructField {
     pub accessor: NormalizedStructFieldAccessor,
     pub ty: Type,
+    pub serde: SerdeAttributes,
 }
 
 pub(crate) enum NormalizedStructFieldAccessor {
@@ -1,5 +1,9 @@
 use crate::bridge_module_attributes::CfgAttr;
+use crate::bridged_type::BridgedType;
+use crate::parse::{OpaqueForeignTypeDeclaration, SharedTypeDeclaration, TypeDeclaration};
+use crate::parsed_extern_fn::{impl_stream_item_ty, iterator_item_ty};
 use crate::SwiftBridgeModule;
+use std::collections::BTreeMap;
 
 mod generate_c_header;
 mod generate_rust_tokens;
@@ -14,6 +18,12 @@ pub struct SwiftCodeAndCHeader {
     pub swift: String,
     /// The generated C header.
     pub c_header: String,
+    /// The `RustMapVtable` and `RustSetVtable` extensions for the collections that the module
+    /// uses, keyed by the Swift property that each one declares.
+    ///
+    /// Other bridge modules might use the same collections, so when writing the Swift code for
+    /// many modules each key should only be written once.
+    pub collection_vtables: BTreeMap<String, String>,
 }
 
 /// Configuration for how we will generate our Swift code.
@@ -39,6 +49,7 @@ impl SwiftBridgeModule {
         SwiftCodeAndCHeader {
             swift: self.generate_swift(&config),
             c_header: self.generate_c_header(&config),
+            collection_vtables: self.generate_swift_collection_vtables(&config),
         }
     }
 
@@ -58,4 +69,104 @@ impl SwiftBridgeModule {
 
         true
     }
+
+    /// Whether or not any of the module's functions or shared types use an `Arc<SomeType>` for
+    /// this opaque Rust type, such as `fn f(arg: Option<Arc<SomeType>>)` or
+    /// `struct Foo { field: Arc<SomeType> }`. If so we generate the code that Swift uses to hold on
+    /// to the `Arc`.
+    fn opaque_type_is_used_in_arc(&self, ty: &OpaqueForeignTypeDeclaration) -> bool {
+        if ty.host_lang.is_swift() || !ty.generics.is_empty() || ty.attributes.copy.is_some() {
+            return false;
+        }
+
+        let contains_arc = |bridged: Option<BridgedType>| {
+            bridged.is_some_and(|bridged| bridged.contains_arc_of_opaque_type(&ty.ty, &self.types))
+        };
+
+        let used_in_function = self.functions.iter().any(|function| {
+            let sig = &function.func.sig;
+
+            let used_in_args = sig
+                .inputs
+                .iter()
+                .any(|arg| contains_arc(BridgedType::new_with_fn_arg(arg, &self.types)));
+
+            let used_in_return = match impl_stream_item_ty(&sig.output)
+                .or_else(|| iterator_item_ty(&sig.output))
+            {
+                Some(item_ty) => contains_arc(BridgedType::new_with_type(item_ty, &self.types)),
+                None => contains_arc(BridgedType::new_with_return_type(&sig.output, &self.types)),
+            };
+
+            used_in_args || used_in_return
+        });
+
+        let used_in_shared_type =
+            self.types
+                .types()
+                .into_iter()
+                .any(|declaration| match declaration {
+                    TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct)) => {
+                        shared_struct
+                            .fields
+                            .contains_arc_of_opaque_type(&ty.ty, &self.types)
+                    }
+                    TypeDeclaration::Shared(SharedTypeDeclaration::Enum(shared_enum)) => {
+                        shared_enum.variants.iter().any(|variant| {
+                            variant
+                                .fields
+                                .contains_arc_of_opaque_type(&ty.ty, &self.types)
+                        })
+                    }
+                    TypeDeclaration::Opaque(_) => false,
+                });
+
+        used_in_function || used_in_shared_type
+    }
+
+    /// Call `visit` with every `HashMap`, `BTreeMap`, `HashSet` and `BTreeSet` that the module
+    /// passes over FFI, whether it's used directly in a function signature, nested in another
+    /// type such as `Option<HashMap<K, V>>`, or in one of the module's shared types' fields.
+    ///
+    /// Each of these needs its own exported Rust functions, C declarations and Swift vtable.
+    fn visit_maps_and_sets(&self, visit: &mut dyn FnMut(&BridgedType)) {
+        for function in self.functions.iter() {
+            let sig = &function.func.sig;
+
+            for arg in sig.inputs.iter() {
+                if let Some(ty) = BridgedType::new_with_fn_arg(arg, &self.types) {
+                    ty.visit_maps_and_sets(&self.types, visit);
+                }
+            }
+
+            let ret =
+                match impl_stream_item_ty(&sig.output).or_else(|| iterator_item_ty(&sig.output)) {
+                    Some(item_ty) => BridgedType::new_with_type(item_ty, &self.types),
+                    None => BridgedType::new_with_return_type(&sig.output, &self.types),
+                };
+            if let Some(ret) = ret {
+                ret.visit_maps_and_sets(&self.types, visit);
+            }
+        }
+
+        // Shared types that were declared in another bridge module get their collections from
+        // that module.
+        for declaration in self.types.types() {
+            match declaration {
+                TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct))
+                    if !shared_struct.already_declared =>
+                {
+                    shared_struct.fields.visit_maps_and_sets(&self.types, visit);
+                }
+                TypeDeclaration::Shared(SharedTypeDeclaration::Enum(shared_enum))
+                    if !shared_enum.already_declared =>
+                {
+                    for variant in shared_enum.variants.iter() {
+                        variant.fields.visit_maps_and_sets(&self.types, visit);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
 }
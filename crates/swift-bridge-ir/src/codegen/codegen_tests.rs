@@ -28,27 +28,42 @@ use crate::test_utils::{
 };
 
 mod already_declared_attribute_codegen_tests;
+mod arc_codegen_tests;
+mod argument_default_codegen_tests;
 mod argument_label_codegen_tests;
 mod async_function_codegen_tests;
+mod availability_codegen_tests;
+mod boxed_fn_codegen_tests;
 mod boxed_fnonce_codegen_tests;
 mod built_in_tuple_codegen_tests;
 mod c_header_declaration_order_codegen_tests;
+mod catch_panics_codegen_tests;
+mod codable_codegen_tests;
 mod conditional_compilation_codegen_tests;
+mod const_and_static_codegen_tests;
 mod derive_attribute_codegen_tests;
 mod derive_struct_attribute_codegen_tests;
+mod doc_comment_codegen_tests;
 mod extern_rust_function_opaque_rust_type_argument_codegen_tests;
 mod extern_rust_function_opaque_rust_type_return_codegen_tests;
 mod extern_rust_method_swift_class_placement_codegen_tests;
 mod extern_swift_function_opaque_swift_type_return_codegen_tests;
 mod function_attribute_codegen_tests;
 mod generic_opaque_rust_type_codegen_tests;
+mod iterator_codegen_tests;
+mod map_codegen_tests;
 mod opaque_rust_type_codegen_tests;
 mod opaque_swift_type_codegen_tests;
+mod operator_codegen_tests;
 mod option_codegen_tests;
 mod result_codegen_tests;
 mod return_into_attribute_codegen_tests;
+mod set_codegen_tests;
 mod single_representation_type_elision_codegen_tests;
+mod stream_codegen_tests;
 mod string_codegen_tests;
+mod swift_protocol_codegen_tests;
+mod trait_object_codegen_tests;
 mod transparent_enum_codegen_tests;
 mod transparent_struct_codegen_tests;
 mod vec_codegen_tests;
@@ -173,7 +188,13 @@ impl CodegenTest {
             crate_feature_lookup,
         };
 
-        let swift = module.generate_swift(&codegen_config);
+        let mut swift = module.generate_swift(&codegen_config);
+        for vtable in module
+            .generate_swift_collection_vtables(&codegen_config)
+            .values()
+        {
+            swift += vtable;
+        }
         match self.expected_swift_code {
             ExpectedSwiftCode::ExactAfterTrim(expected_swift) => {
                 assert_trimmed_generated_equals_trimmed_expected(&swift, expected_swift);
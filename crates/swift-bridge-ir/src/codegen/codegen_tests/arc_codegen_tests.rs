@@ -0,0 +1,290 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate the code for Swift to hold on to an `Arc<SomeType>` when a function
+/// returns one.
+mod extern_rust_fn_returns_arc_opaque_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+                    fn some_function() -> Arc<SomeType>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$some_function"]
+                pub extern "C" fn __swift_bridge__some_function() -> *const super::SomeType {
+                    std::sync::Arc::into_raw(super::some_function())
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_free_arc"]
+                pub extern "C" fn __swift_bridge__SomeType__free_arc (this: *const super::SomeType) {
+                    let this = unsafe { std::sync::Arc::from_raw(this) };
+                    drop(this);
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func some_function() -> SomeTypeArc {
+    SomeTypeArc(ptr: __swift_bridge__$some_function())
+}
+"#,
+            r#"
+public class SomeTypeArc: SomeTypeRef {
+    public override init(ptr: UnsafeMutableRawPointer) {
+        super.init(ptr: ptr)
+    }
+
+    deinit {
+        __swift_bridge__$SomeType$_free_arc(ptr)
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            r#"
+void __swift_bridge__$SomeType$_free_arc(void* self);
+"#,
+            r#"
+void* __swift_bridge__$some_function(void);
+"#,
+        ])
+    }
+
+    #[test]
+    fn extern_rust_fn_returns_arc_opaque_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that Rust clones the `Arc` that Swift passes to it, so that Swift keeps its strong
+/// reference.
+mod extern_rust_fn_takes_arc_opaque_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+                    fn some_function(arg: std::sync::Arc<SomeType>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(arg: *const super::SomeType) {
+                super::some_function({
+                    let ptr = arg;
+                    unsafe {
+                        std::sync::Arc::increment_strong_count(ptr);
+                        std::sync::Arc::from_raw(ptr)
+                    }
+                })
+            }
+        })
+    }
+
+    const EXPECTED_SWIFT_CODE: ExpectedSwiftCode = ExpectedSwiftCode::ContainsAfterTrim(
+        r#"
+public func some_function(_ arg: SomeTypeArc) {
+    __swift_bridge__$some_function(arg.ptr)
+}
+"#,
+    );
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+void __swift_bridge__$some_function(void* arg);
+"#,
+    );
+
+    #[test]
+    fn extern_rust_fn_takes_arc_opaque_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: EXPECTED_SWIFT_CODE,
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we can return an `Option<Arc<SomeType>>`.
+mod extern_rust_fn_returns_option_arc_opaque_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+                    fn some_function() -> Option<Arc<SomeType>>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function() -> *const super::SomeType {
+                if let Some(val) = super::some_function() {
+                    std::sync::Arc::into_raw(val)
+                } else {
+                    std::ptr::null()
+                }
+            }
+        })
+    }
+
+    const EXPECTED_SWIFT_CODE: ExpectedSwiftCode = ExpectedSwiftCode::ContainsAfterTrim(
+        r#"
+public func some_function() -> Optional<SomeTypeArc> {
+    { let val = __swift_bridge__$some_function(); if val != nil { return SomeTypeArc(ptr: val!) } else { return nil } }()
+}
+"#,
+    );
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+void* __swift_bridge__$some_function(void);
+"#,
+    );
+
+    #[test]
+    fn extern_rust_fn_returns_option_arc_opaque_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: EXPECTED_SWIFT_CODE,
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we don't generate any `Arc` code for opaque types that aren't used in an `Arc`.
+mod opaque_type_not_used_in_arc {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::DoesNotContain(quote! {
+            __swift_bridge__SomeType__free_arc
+        })
+    }
+
+    const EXPECTED_SWIFT_CODE: ExpectedSwiftCode = ExpectedSwiftCode::DoesNotContainAfterTrim(
+        r#"
+SomeTypeArc
+"#,
+    );
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::DoesNotContainAfterTrim(
+        r#"
+_free_arc
+"#,
+    );
+
+    #[test]
+    fn opaque_type_not_used_in_arc() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: EXPECTED_SWIFT_CODE,
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate the code for Swift to hold on to an `Arc<SomeType>` when only a shared
+/// struct's field uses one.
+mod shared_struct_field_uses_arc_opaque_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: Option<std::sync::Arc<SomeType>>,
+                }
+
+                extern "Rust" {
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$SomeType$_free_arc"]
+            pub extern "C" fn __swift_bridge__SomeType__free_arc (this: *const super::SomeType) {
+                let this = unsafe { std::sync::Arc::from_raw(this) };
+                drop(this);
+            }
+        })
+    }
+
+    const EXPECTED_SWIFT_CODE: ExpectedSwiftCode = ExpectedSwiftCode::ContainsAfterTrim(
+        r#"
+public class SomeTypeArc: SomeTypeRef {
+"#,
+    );
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+void __swift_bridge__$SomeType$_free_arc(void* self);
+"#,
+    );
+
+    #[test]
+    fn shared_struct_field_uses_arc_opaque_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: EXPECTED_SWIFT_CODE,
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
@@ -0,0 +1,175 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that `#[swift_bridge(default = ...)]` attributes become Swift default argument values.
+mod argument_default {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(
+                        required: u8,
+                        #[swift_bridge(default = 10)] count: u32,
+                        #[swift_bridge(default = -1.5)] offset: f64,
+                        #[swift_bridge(label = "isEnabled", default = true)] enabled: bool,
+                        #[swift_bridge(default = None)] limit: Option<i64>,
+                    );
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub extern "C" fn __swift_bridge__some_function(
+                required: u8,
+                count: u32,
+                offset: f64,
+                enabled: bool,
+                limit: swift_bridge::option::OptionI64
+            ) {
+                super::some_function(
+                    required,
+                    count,
+                    offset,
+                    enabled,
+                    {
+                        let val = limit;
+                        if val.is_some {
+                            Some(val.val)
+                        } else {
+                            None
+                        }
+                    }
+                )
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ required: UInt8, _ count: UInt32 = 10, _ offset: Double = -1.5, isEnabled enabled: Bool = true, _ limit: Optional<Int64> = nil) {
+    __swift_bridge__$some_function(required, count, offset, enabled, limit.intoFfiRepr())
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn argument_default() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that an initializer can have default argument values.
+mod argument_default_initializer {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(init)]
+                    fn new(#[swift_bridge(default = 3)] retries: u16) -> SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::SkipTest
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeType {
+    public convenience init(_ retries: UInt16 = 3) {
+        self.init(ptr: __swift_bridge__$SomeType$new(retries))
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn argument_default_initializer() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that string literal defaults become Swift string literals for `&str` and `String`
+/// arguments.
+mod argument_default_string {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(#[swift_bridge(default = "localhost")] host: &str);
+                    fn another_function(#[swift_bridge(default = "guest")] name: String);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::SkipTest
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func some_function<GenericToRustStr: ToRustStr>(_ host: GenericToRustStr = "localhost") {
+"#,
+            r#"
+public func another_function<GenericIntoRustString: IntoRustString>(_ name: GenericIntoRustString = "guest") {
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn argument_default_string() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
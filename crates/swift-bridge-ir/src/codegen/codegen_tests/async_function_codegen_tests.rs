@@ -21,20 +21,30 @@ mod extern_rust_async_function_no_return {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
             pub extern "C" fn __swift_bridge__some_function(
+                task_handle: *const std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let fut = super::some_function();
+                let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let task = async move {
                     fut.await;
 
-                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = task_callback_wrapper;
                     let callback_wrapper = callback_wrapper.0;
 
                     (callback)(callback_wrapper)
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                let task = unsafe {
+                    swift_bridge::async_support::CancellableTask::new(
+                        task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                        Box::pin(task),
+                        callback_wrapper,
+                        on_cancel
+                    )
+                };
+                swift_bridge::async_support::spawn_task(Box::pin(task))
             }
         })
     }
@@ -42,27 +52,38 @@ mod extern_rust_async_function_no_return {
     fn expected_swift_code() -> ExpectedSwiftCode {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
-func some_function() async {
+func some_function() async throws {
     func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?) {
         let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
         wrapper.cb(.success(()))
     }
+    func onCancel(cbWrapperPtr: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(CancellationError()))
+    }
 
-    return await withCheckedContinuation({ (continuation: CheckedContinuation<(), Never>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let taskHandle = __swift_bridge__$AsyncTaskHandle$new()
+    defer { __swift_bridge__$AsyncTaskHandle$_free(taskHandle) }
+
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<(), Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete)
+            __swift_bridge__$some_function(taskHandle, wrapperPtr, onComplete, onCancel)
+        })
+    }, onCancel: {
+        __swift_bridge__$AsyncTaskHandle$cancel(taskHandle)
     })
 }
 class CbWrapper$some_function {
-    var cb: (Result<(), Never>) -> ()
+    var cb: (Result<(), Error>) -> ()
 
-    public init(cb: @escaping (Result<(), Never>) -> ()) {
+    public init(cb: @escaping (Result<(), Error>) -> ()) {
         self.cb = cb
     }
 }
@@ -73,7 +94,7 @@ class CbWrapper$some_function {
     fn expected_c_header() -> ExpectedCHeader {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper));
+void __swift_bridge__$some_function(void* task_handle, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper), void __swift_bridge__$some_function$cancel(void* callback_wrapper));
     "#,
         )
     }
@@ -108,21 +129,31 @@ mod extern_rust_async_function_u32_arg {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__some_function(
+                task_handle: *const std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
                 arg: u32
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let fut = super::some_function(arg);
+                let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let task = async move {
                     fut.await;
 
-                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = task_callback_wrapper;
                     let callback_wrapper = callback_wrapper.0;
 
                     (callback)(callback_wrapper)
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                let task = unsafe {
+                    swift_bridge::async_support::CancellableTask::new(
+                        task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                        Box::pin(task),
+                        callback_wrapper,
+                        on_cancel
+                    )
+                };
+                swift_bridge::async_support::spawn_task(Box::pin(task))
             }
         })
     }
@@ -130,27 +161,38 @@ mod extern_rust_async_function_u32_arg {
     fn expected_swift_code() -> ExpectedSwiftCode {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
-func some_function(_ arg: UInt32) async {
+func some_function(_ arg: UInt32) async throws {
     func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?) {
         let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
         wrapper.cb(.success(()))
     }
+    func onCancel(cbWrapperPtr: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(CancellationError()))
+    }
 
-    return await withCheckedContinuation({ (continuation: CheckedContinuation<(), Never>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let taskHandle = __swift_bridge__$AsyncTaskHandle$new()
+    defer { __swift_bridge__$AsyncTaskHandle$_free(taskHandle) }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<(), Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
+
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete, arg)
+            __swift_bridge__$some_function(taskHandle, wrapperPtr, onComplete, onCancel, arg)
+        })
+    }, onCancel: {
+        __swift_bridge__$AsyncTaskHandle$cancel(taskHandle)
     })
 }
 class CbWrapper$some_function {
-    var cb: (Result<(), Never>) -> ()
+    var cb: (Result<(), Error>) -> ()
 
-    public init(cb: @escaping (Result<(), Never>) -> ()) {
+    public init(cb: @escaping (Result<(), Error>) -> ()) {
         self.cb = cb
     }
 }
@@ -162,7 +204,7 @@ class CbWrapper$some_function {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
 #include <stdint.h>
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper), uint32_t arg);
+void __swift_bridge__$some_function(void* task_handle, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper), void __swift_bridge__$some_function$cancel(void* callback_wrapper), uint32_t arg);
     "#,
         )
     }
@@ -197,20 +239,30 @@ mod extern_rust_async_function_returns_u8 {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__some_function(
+                task_handle: *const std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void, u8) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let fut = super::some_function();
+                let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let task = async move {
                     let val = fut.await;
 
-                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = task_callback_wrapper;
                     let callback_wrapper = callback_wrapper.0;
 
                     (callback)(callback_wrapper, val)
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                let task = unsafe {
+                    swift_bridge::async_support::CancellableTask::new(
+                        task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                        Box::pin(task),
+                        callback_wrapper,
+                        on_cancel
+                    )
+                };
+                swift_bridge::async_support::spawn_task(Box::pin(task))
             }
         })
     }
@@ -218,27 +270,38 @@ mod extern_rust_async_function_returns_u8 {
     fn expected_swift_code() -> ExpectedSwiftCode {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
-func some_function() async -> UInt8 {
+func some_function() async throws -> UInt8 {
     func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnRetVal: UInt8) {
         let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
         wrapper.cb(.success(rustFnRetVal))
     }
+    func onCancel(cbWrapperPtr: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(CancellationError()))
+    }
 
-    return await withCheckedContinuation({ (continuation: CheckedContinuation<UInt8, Never>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let taskHandle = __swift_bridge__$AsyncTaskHandle$new()
+    defer { __swift_bridge__$AsyncTaskHandle$_free(taskHandle) }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<UInt8, Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete)
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+
+            __swift_bridge__$some_function(taskHandle, wrapperPtr, onComplete, onCancel)
+        })
+    }, onCancel: {
+        __swift_bridge__$AsyncTaskHandle$cancel(taskHandle)
     })
 }
 class CbWrapper$some_function {
-    var cb: (Result<UInt8, Never>) -> ()
+    var cb: (Result<UInt8, Error>) -> ()
 
-    public init(cb: @escaping (Result<UInt8, Never>) -> ()) {
+    public init(cb: @escaping (Result<UInt8, Error>) -> ()) {
         self.cb = cb
     }
 }
@@ -250,7 +313,7 @@ class CbWrapper$some_function {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
 #include <stdint.h>
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, uint8_t ret));
+void __swift_bridge__$some_function(void* task_handle, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, uint8_t ret), void __swift_bridge__$some_function$cancel(void* callback_wrapper));
     "#,
         )
     }
@@ -285,20 +348,30 @@ mod extern_rust_async_function_returns_string {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__some_function(
+                task_handle: *const std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void, *mut swift_bridge::string::RustString) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let fut = super::some_function();
+                let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let task = async move {
                     let val = swift_bridge::string::RustString(fut.await).box_into_raw();
 
-                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = task_callback_wrapper;
                     let callback_wrapper = callback_wrapper.0;
 
                     (callback)(callback_wrapper, val)
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                let task = unsafe {
+                    swift_bridge::async_support::CancellableTask::new(
+                        task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                        Box::pin(task),
+                        callback_wrapper,
+                        on_cancel
+                    )
+                };
+                swift_bridge::async_support::spawn_task(Box::pin(task))
             }
         })
     }
@@ -306,27 +379,38 @@ mod extern_rust_async_function_returns_string {
     fn expected_swift_code() -> ExpectedSwiftCode {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
-func some_function() async -> RustString {
+func some_function() async throws -> RustString {
     func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnRetVal: UnsafeMutableRawPointer?) {
         let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
         wrapper.cb(.success(RustString(ptr: rustFnRetVal!)))
     }
+    func onCancel(cbWrapperPtr: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(CancellationError()))
+    }
 
-    return await withCheckedContinuation({ (continuation: CheckedContinuation<RustString, Never>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let taskHandle = __swift_bridge__$AsyncTaskHandle$new()
+    defer { __swift_bridge__$AsyncTaskHandle$_free(taskHandle) }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<RustString, Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete)
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+
+            __swift_bridge__$some_function(taskHandle, wrapperPtr, onComplete, onCancel)
+        })
+    }, onCancel: {
+        __swift_bridge__$AsyncTaskHandle$cancel(taskHandle)
     })
 }
 class CbWrapper$some_function {
-    var cb: (Result<RustString, Never>) -> ()
+    var cb: (Result<RustString, Error>) -> ()
 
-    public init(cb: @escaping (Result<RustString, Never>) -> ()) {
+    public init(cb: @escaping (Result<RustString, Error>) -> ()) {
         self.cb = cb
     }
 }
@@ -337,7 +421,7 @@ class CbWrapper$some_function {
     fn expected_c_header() -> ExpectedCHeader {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, void* ret));
+void __swift_bridge__$some_function(void* task_handle, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, void* ret), void __swift_bridge__$some_function$cancel(void* callback_wrapper));
     "#,
         )
     }
@@ -376,20 +460,30 @@ mod extern_rust_async_function_returns_struct {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__some_function(
+                task_handle: *const std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void, __swift_bridge__SomeStruct) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let fut = super::some_function();
+                let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let task = async move {
                     let val = fut.await.into_ffi_repr();
 
-                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = task_callback_wrapper;
                     let callback_wrapper = callback_wrapper.0;
 
                     (callback)(callback_wrapper, val)
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                let task = unsafe {
+                    swift_bridge::async_support::CancellableTask::new(
+                        task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                        Box::pin(task),
+                        callback_wrapper,
+                        on_cancel
+                    )
+                };
+                swift_bridge::async_support::spawn_task(Box::pin(task))
             }
         })
     }
@@ -397,27 +491,38 @@ mod extern_rust_async_function_returns_struct {
     fn expected_swift_code() -> ExpectedSwiftCode {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
-func some_function() async -> SomeStruct {
+func some_function() async throws -> SomeStruct {
     func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnRetVal: __swift_bridge__$SomeStruct) {
         let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
         wrapper.cb(.success(rustFnRetVal.intoSwiftRepr()))
     }
+    func onCancel(cbWrapperPtr: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(CancellationError()))
+    }
 
-    return await withCheckedContinuation({ (continuation: CheckedContinuation<SomeStruct, Never>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let taskHandle = __swift_bridge__$AsyncTaskHandle$new()
+    defer { __swift_bridge__$AsyncTaskHandle$_free(taskHandle) }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<SomeStruct, Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
+
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete)
+            __swift_bridge__$some_function(taskHandle, wrapperPtr, onComplete, onCancel)
+        })
+    }, onCancel: {
+        __swift_bridge__$AsyncTaskHandle$cancel(taskHandle)
     })
 }
 class CbWrapper$some_function {
-    var cb: (Result<SomeStruct, Never>) -> ()
+    var cb: (Result<SomeStruct, Error>) -> ()
 
-    public init(cb: @escaping (Result<SomeStruct, Never>) -> ()) {
+    public init(cb: @escaping (Result<SomeStruct, Error>) -> ()) {
         self.cb = cb
     }
 }
@@ -428,7 +533,7 @@ class CbWrapper$some_function {
     fn expected_c_header() -> ExpectedCHeader {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, struct __swift_bridge__$SomeStruct ret));
+void __swift_bridge__$some_function(void* task_handle, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, struct __swift_bridge__$SomeStruct ret), void __swift_bridge__$some_function$cancel(void* callback_wrapper));
     "#,
         )
     }
@@ -464,21 +569,31 @@ mod extern_rust_async_method {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__SomeType_some_method(
+                task_handle: *const std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
                 this: *mut super::SomeType
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let fut = (unsafe {&*this}).some_method();
+                let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let task = async move {
                     fut.await;
 
-                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = task_callback_wrapper;
                     let callback_wrapper = callback_wrapper.0;
 
                     (callback)(callback_wrapper)
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                let task = unsafe {
+                    swift_bridge::async_support::CancellableTask::new(
+                        task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                        Box::pin(task),
+                        callback_wrapper,
+                        on_cancel
+                    )
+                };
+                swift_bridge::async_support::spawn_task(Box::pin(task))
             }
         })
     }
@@ -487,27 +602,38 @@ mod extern_rust_async_method {
         ExpectedSwiftCode::ContainsAfterTrim(
             r#"
 extension SomeTypeRef {
-    public func some_method() async {
+    public func some_method() async throws {
         func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?) {
             let wrapper = Unmanaged<CbWrapper$SomeType$some_method>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
             wrapper.cb(.success(()))
         }
+        func onCancel(cbWrapperPtr: UnsafeMutableRawPointer?) {
+            let wrapper = Unmanaged<CbWrapper$SomeType$some_method>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+            wrapper.cb(.failure(CancellationError()))
+        }
 
-        return await withCheckedContinuation({ (continuation: CheckedContinuation<(), Never>) in
-            let callback = { rustFnRetVal in
-                continuation.resume(with: rustFnRetVal)
-            }
+        let taskHandle = __swift_bridge__$AsyncTaskHandle$new()
+        defer { __swift_bridge__$AsyncTaskHandle$_free(taskHandle) }
 
-            let wrapper = CbWrapper$SomeType$some_method(cb: callback)
-            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+        return try await withTaskCancellationHandler(operation: {
+            try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<(), Error>) in
+                let callback = { rustFnRetVal in
+                    continuation.resume(with: rustFnRetVal)
+                }
 
-            __swift_bridge__$SomeType$some_method(wrapperPtr, onComplete, ptr)
+                let wrapper = CbWrapper$SomeType$some_method(cb: callback)
+                let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+
+                __swift_bridge__$SomeType$some_method(taskHandle, wrapperPtr, onComplete, onCancel, ptr)
+            })
+        }, onCancel: {
+            __swift_bridge__$AsyncTaskHandle$cancel(taskHandle)
         })
     }
     class CbWrapper$SomeType$some_method {
-        var cb: (Result<(), Never>) -> ()
+        var cb: (Result<(), Error>) -> ()
     
-        public init(cb: @escaping (Result<(), Never>) -> ()) {
+        public init(cb: @escaping (Result<(), Error>) -> ()) {
             self.cb = cb
         }
     }
@@ -519,7 +645,7 @@ extension SomeTypeRef {
     fn expected_c_header() -> ExpectedCHeader {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
-void __swift_bridge__$SomeType$some_method(void* callback_wrapper, void __swift_bridge__$SomeType$some_method$async(void* callback_wrapper), void* self);
+void __swift_bridge__$SomeType$some_method(void* task_handle, void* callback_wrapper, void __swift_bridge__$SomeType$some_method$async(void* callback_wrapper), void __swift_bridge__$SomeType$some_method$cancel(void* callback_wrapper), void* self);
     "#,
         )
     }
@@ -556,11 +682,13 @@ mod extern_rust_async_function_returns_result_opaque {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__some_function(
+                task_handle: *const std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void, swift_bridge :: result :: ResultPtrAndPtr) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let fut = super::some_function();
+                let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let task = async move {
                 let val = match fut.await {
                     Ok(ok) => {
@@ -582,12 +710,20 @@ mod extern_rust_async_function_returns_result_opaque {
                         }
                     }
                 };
-                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = task_callback_wrapper;
                     let callback_wrapper = callback_wrapper.0;
 
                     (callback)(callback_wrapper, val)
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                let task = unsafe {
+                    swift_bridge::async_support::CancellableTask::new(
+                        task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                        Box::pin(task),
+                        callback_wrapper,
+                        on_cancel
+                    )
+                };
+                swift_bridge::async_support::spawn_task(Box::pin(task))
             }
         })
     }
@@ -608,16 +744,27 @@ public func some_function() async throws -> OkType {
             wrapper.cb(.failure(ErrorType(ptr: rustFnRetVal.ok_or_err!)))
         }
     }
+    func onCancel(cbWrapperPtr: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(CancellationError()))
+    }
 
-    return try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<OkType, Error>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let taskHandle = __swift_bridge__$AsyncTaskHandle$new()
+    defer { __swift_bridge__$AsyncTaskHandle$_free(taskHandle) }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<OkType, Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
+
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete)
+            __swift_bridge__$some_function(taskHandle, wrapperPtr, onComplete, onCancel)
+        })
+    }, onCancel: {
+        __swift_bridge__$AsyncTaskHandle$cancel(taskHandle)
     })
 }
 class CbWrapper$some_function {
@@ -634,7 +781,7 @@ class CbWrapper$some_function {
     fn expected_c_header() -> ExpectedCHeader {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, struct __private__ResultPtrAndPtr ret));
+void __swift_bridge__$some_function(void* task_handle, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, struct __private__ResultPtrAndPtr ret), void __swift_bridge__$some_function$cancel(void* callback_wrapper));
     "#,
         )
     }
@@ -677,22 +824,32 @@ mod extern_rust_async_function_returns_result_transparent_enum {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__some_function(
+                task_handle: *const std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void, ResultOkEnumAndErrEnum) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let fut = super::some_function();
+                let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let task = async move {
                 let val = match fut.await {
                     Ok(ok) => ResultOkEnumAndErrEnum::Ok(ok.into_ffi_repr()),
                     Err(err) => ResultOkEnumAndErrEnum::Err(err.into_ffi_repr()),
                 };
-                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = task_callback_wrapper;
                     let callback_wrapper = callback_wrapper.0;
 
                     (callback)(callback_wrapper, val)
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                let task = unsafe {
+                    swift_bridge::async_support::CancellableTask::new(
+                        task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                        Box::pin(task),
+                        callback_wrapper,
+                        on_cancel
+                    )
+                };
+                swift_bridge::async_support::spawn_task(Box::pin(task))
             }
         })
     }
@@ -709,16 +866,27 @@ public func some_function() async throws -> OkEnum {
         let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
         switch rustFnRetVal.tag { case __swift_bridge__$ResultOkEnumAndErrEnum$ResultOk: wrapper.cb(.success(rustFnRetVal.payload.ok.intoSwiftRepr())) case __swift_bridge__$ResultOkEnumAndErrEnum$ResultErr: wrapper.cb(.failure(rustFnRetVal.payload.err.intoSwiftRepr())) default: fatalError() }
     }
+    func onCancel(cbWrapperPtr: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(CancellationError()))
+    }
 
-    return try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<OkEnum, Error>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let taskHandle = __swift_bridge__$AsyncTaskHandle$new()
+    defer { __swift_bridge__$AsyncTaskHandle$_free(taskHandle) }
+
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<OkEnum, Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete)
+            __swift_bridge__$some_function(taskHandle, wrapperPtr, onComplete, onCancel)
+        })
+    }, onCancel: {
+        __swift_bridge__$AsyncTaskHandle$cancel(taskHandle)
     })
 }
 class CbWrapper$some_function {
@@ -735,7 +903,7 @@ class CbWrapper$some_function {
     fn expected_c_header() -> ExpectedCHeader {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, struct __swift_bridge__$ResultOkEnumAndErrEnum ret));
+void __swift_bridge__$some_function(void* task_handle, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, struct __swift_bridge__$ResultOkEnumAndErrEnum ret), void __swift_bridge__$some_function$cancel(void* callback_wrapper));
     "#,
         )
     }
@@ -775,11 +943,13 @@ mod extern_rust_async_function_returns_result_opaque_rust_transparent_enum {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__some_function(
+                task_handle: *const std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void, ResultSomeTypeAndErrEnum) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let fut = super::some_function();
+                let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let task = async move {
                 let val = match fut.await {
                     Ok(ok) => ResultSomeTypeAndErrEnum::Ok(Box::into_raw(Box::new({
@@ -788,12 +958,20 @@ mod extern_rust_async_function_returns_result_opaque_rust_transparent_enum {
                     })) as *mut super::SomeType),
                     Err(err) => ResultSomeTypeAndErrEnum::Err(err.into_ffi_repr()),
                 };
-                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = task_callback_wrapper;
                     let callback_wrapper = callback_wrapper.0;
 
                     (callback)(callback_wrapper, val)
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                let task = unsafe {
+                    swift_bridge::async_support::CancellableTask::new(
+                        task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                        Box::pin(task),
+                        callback_wrapper,
+                        on_cancel
+                    )
+                };
+                swift_bridge::async_support::spawn_task(Box::pin(task))
             }
         })
     }
@@ -810,16 +988,27 @@ public func some_function() async throws -> SomeType {
         let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
         switch rustFnRetVal.tag { case __swift_bridge__$ResultSomeTypeAndErrEnum$ResultOk: wrapper.cb(.success(SomeType(ptr: rustFnRetVal.payload.ok))) case __swift_bridge__$ResultSomeTypeAndErrEnum$ResultErr: wrapper.cb(.failure(rustFnRetVal.payload.err.intoSwiftRepr())) default: fatalError() }
     }
+    func onCancel(cbWrapperPtr: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(CancellationError()))
+    }
 
-    return try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<SomeType, Error>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let taskHandle = __swift_bridge__$AsyncTaskHandle$new()
+    defer { __swift_bridge__$AsyncTaskHandle$_free(taskHandle) }
+
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<SomeType, Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete)
+            __swift_bridge__$some_function(taskHandle, wrapperPtr, onComplete, onCancel)
+        })
+    }, onCancel: {
+        __swift_bridge__$AsyncTaskHandle$cancel(taskHandle)
     })
 }
 class CbWrapper$some_function {
@@ -836,7 +1025,7 @@ class CbWrapper$some_function {
     fn expected_c_header() -> ExpectedCHeader {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, struct __swift_bridge__$ResultSomeTypeAndErrEnum ret));
+void __swift_bridge__$some_function(void* task_handle, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, struct __swift_bridge__$ResultSomeTypeAndErrEnum ret), void __swift_bridge__$some_function$cancel(void* callback_wrapper));
     "#,
         )
     }
@@ -875,22 +1064,32 @@ mod extern_rust_async_function_returns_result_null_transparent_enum {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
              pub extern "C" fn __swift_bridge__some_function(
+                task_handle: *const std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void, ResultVoidAndErrEnum) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let fut = super::some_function();
+                let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let task = async move {
                 let val = match fut.await {
                     Ok(ok) => ResultVoidAndErrEnum::Ok,
                     Err(err) => ResultVoidAndErrEnum::Err(err.into_ffi_repr()),
                 };
-                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = task_callback_wrapper;
                     let callback_wrapper = callback_wrapper.0;
 
                     (callback)(callback_wrapper, val)
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                let task = unsafe {
+                    swift_bridge::async_support::CancellableTask::new(
+                        task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                        Box::pin(task),
+                        callback_wrapper,
+                        on_cancel
+                    )
+                };
+                swift_bridge::async_support::spawn_task(Box::pin(task))
             }
         })
     }
@@ -907,16 +1106,27 @@ public func some_function() async throws -> () {
         let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
         switch rustFnRetVal.tag { case __swift_bridge__$ResultVoidAndErrEnum$ResultOk: wrapper.cb(.success(())) case __swift_bridge__$ResultVoidAndErrEnum$ResultErr: wrapper.cb(.failure(rustFnRetVal.payload.err.intoSwiftRepr())) default: fatalError() }
     }
+    func onCancel(cbWrapperPtr: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(CancellationError()))
+    }
 
-    return try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<(), Error>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let taskHandle = __swift_bridge__$AsyncTaskHandle$new()
+    defer { __swift_bridge__$AsyncTaskHandle$_free(taskHandle) }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<(), Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete)
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+
+            __swift_bridge__$some_function(taskHandle, wrapperPtr, onComplete, onCancel)
+        })
+    }, onCancel: {
+        __swift_bridge__$AsyncTaskHandle$cancel(taskHandle)
     })
 }
 class CbWrapper$some_function {
@@ -933,7 +1143,7 @@ class CbWrapper$some_function {
     fn expected_c_header() -> ExpectedCHeader {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, struct __swift_bridge__$ResultVoidAndErrEnum ret));
+void __swift_bridge__$some_function(void* task_handle, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, struct __swift_bridge__$ResultVoidAndErrEnum ret), void __swift_bridge__$some_function$cancel(void* callback_wrapper));
     "#,
         )
     }
@@ -969,11 +1179,13 @@ mod extern_rust_async_function_returns_result_null_opaque {
     fn expected_rust_tokens() -> ExpectedRustTokens {
         ExpectedRustTokens::Contains(quote! {
             pub extern "C" fn __swift_bridge__some_function(
+                task_handle: *const std::ffi::c_void,
                 callback_wrapper: *mut std::ffi::c_void,
                 callback: extern "C" fn(*mut std::ffi::c_void, *mut super::ErrorType) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
             ) {
-                let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let fut = super::some_function();
+                let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
                 let task = async move {
                     let val = match fut.await {
                         Ok(ok) => std::ptr::null_mut(),
@@ -982,11 +1194,19 @@ mod extern_rust_async_function_returns_result_null_opaque {
                             val
                         })) as *mut super::ErrorType
                     };
-                    let callback_wrapper = callback_wrapper;
+                    let callback_wrapper = task_callback_wrapper;
                     let callback_wrapper = callback_wrapper.0;
                     (callback)(callback_wrapper, val)
                 };
-                swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                let task = unsafe {
+                    swift_bridge::async_support::CancellableTask::new(
+                        task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                        Box::pin(task),
+                        callback_wrapper,
+                        on_cancel
+                    )
+                };
+                swift_bridge::async_support::spawn_task(Box::pin(task))
             }
         })
     }
@@ -1007,16 +1227,27 @@ public func some_function() async throws -> () {
             wrapper.cb(.failure(ErrorType(ptr: rustFnRetVal!)))
         }
     }
+    func onCancel(cbWrapperPtr: UnsafeMutableRawPointer?) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        wrapper.cb(.failure(CancellationError()))
+    }
 
-    return try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<(), Error>) in
-        let callback = { rustFnRetVal in
-            continuation.resume(with: rustFnRetVal)
-        }
+    let taskHandle = __swift_bridge__$AsyncTaskHandle$new()
+    defer { __swift_bridge__$AsyncTaskHandle$_free(taskHandle) }
+
+    return try await withTaskCancellationHandler(operation: {
+        try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<(), Error>) in
+            let callback = { rustFnRetVal in
+                continuation.resume(with: rustFnRetVal)
+            }
 
-        let wrapper = CbWrapper$some_function(cb: callback)
-        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+            let wrapper = CbWrapper$some_function(cb: callback)
+            let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
 
-        __swift_bridge__$some_function(wrapperPtr, onComplete)
+            __swift_bridge__$some_function(taskHandle, wrapperPtr, onComplete, onCancel)
+        })
+    }, onCancel: {
+        __swift_bridge__$AsyncTaskHandle$cancel(taskHandle)
     })
 }
 class CbWrapper$some_function {
@@ -1033,7 +1264,7 @@ class CbWrapper$some_function {
     fn expected_c_header() -> ExpectedCHeader {
         ExpectedCHeader::ContainsAfterTrim(
             r#"
-void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, void* ret));
+void __swift_bridge__$some_function(void* task_handle, void* callback_wrapper, void __swift_bridge__$some_function$async(void* callback_wrapper, void* ret), void __swift_bridge__$some_function$cancel(void* callback_wrapper));
     "#,
         )
     }
@@ -1049,3 +1280,561 @@ void __swift_bridge__$some_function(void* callback_wrapper, void __swift_bridge_
         .test();
     }
 }
+
+/// Verify that we generate the correct code for a `#[swift_bridge(local)]` extern "Rust" async
+/// function.
+/// The arguments get converted before being moved to the local executor's thread, where the
+/// future gets created.
+mod extern_rust_local_async_function {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(local)]
+                    async fn some_function(arg: String) -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                task_handle: *const std::ffi::c_void,
+                callback_wrapper: *mut std::ffi::c_void,
+                callback: extern "C" fn(*mut std::ffi::c_void, u8) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
+                arg: *mut swift_bridge::string::RustString
+            ) {
+                let arg = unsafe { Box::from_raw(arg).0 };
+                let task_handle = swift_bridge::async_support::AssertSend(task_handle);
+                let callback_wrapper = swift_bridge::async_support::AssertSend(callback_wrapper);
+                swift_bridge::async_support::spawn_local_task(move || {
+                    let task_handle = task_handle.into_inner();
+                    let callback_wrapper = callback_wrapper.into_inner();
+                    let fut = super::some_function(arg);
+                    let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+                    let task = async move {
+                        let val = fut.await;
+
+                        let callback_wrapper = task_callback_wrapper;
+                        let callback_wrapper = callback_wrapper.0;
+
+                        (callback)(callback_wrapper, val)
+                    };
+                    let task = unsafe {
+                        swift_bridge::async_support::CancellableTask::new(
+                            task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                            Box::pin(task),
+                            callback_wrapper,
+                            on_cancel
+                        )
+                    };
+                    task
+                })
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function<GenericIntoRustString: IntoRustString>(_ arg: GenericIntoRustString) async throws -> UInt8 {
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_local_async_function() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that a `#[swift_bridge(local)]` extern "Rust" async method uses its `self` pointer on the
+/// local executor's thread.
+mod extern_rust_local_async_method {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(local)]
+                    async fn some_method(&self);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                swift_bridge::async_support::assert_self_is_sync(this);
+                let this = swift_bridge::async_support::AssertSend(this);
+                let task_handle = swift_bridge::async_support::AssertSend(task_handle);
+                let callback_wrapper = swift_bridge::async_support::AssertSend(callback_wrapper);
+            },
+            quote! {
+                let this = this.into_inner();
+                let task_handle = task_handle.into_inner();
+                let callback_wrapper = callback_wrapper.into_inner();
+                let fut = (unsafe { &*this }).some_method();
+            },
+        ])
+    }
+
+    #[test]
+    fn extern_rust_local_async_method() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that a `#[swift_bridge(local)]` extern "Rust" async method that takes `&mut self` asserts
+/// that its `self` type is `Send`, since it gets mutably borrowed on the local executor's thread.
+mod extern_rust_local_async_mut_method {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(local)]
+                    async fn some_method(&mut self);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            swift_bridge::async_support::assert_self_is_send(this);
+            let this = swift_bridge::async_support::AssertSend(this);
+        })
+    }
+
+    #[test]
+    fn extern_rust_local_async_mut_method() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate the correct code for extern "Swift" async functions that do not
+/// return a value.
+mod extern_swift_async_function_no_return {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Swift" {
+                    async fn some_function();
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub fn some_function() -> impl std::future::Future<Output = ()> {
+                    extern "C" fn on_complete(callback_wrapper: *mut std::ffi::c_void) {
+                        let val = ();
+                        unsafe { swift_bridge::async_support::SwiftFuture::<()>::complete(callback_wrapper, val) }
+                    }
+
+                    let (future, callback_wrapper) = swift_bridge::async_support::SwiftFuture::<()>::new();
+                    unsafe { __swift_bridge__some_function(callback_wrapper, on_complete, ) };
+                    future
+                }
+            },
+            quote! {
+                #[link_name = "__swift_bridge__$some_function"]
+                fn __swift_bridge__some_function(
+                    callback_wrapper: *mut std::ffi::c_void,
+                    callback: extern "C" fn(*mut std::ffi::c_void) -> (),
+                );
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function (_ callbackWrapper: UnsafeMutableRawPointer, _ callback: @escaping @convention(c) (UnsafeMutableRawPointer) -> ()) {
+    Task {
+        await some_function()
+        callback(callbackWrapper)
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::DoesNotContainAfterTrim("some_function")
+    }
+
+    #[test]
+    fn extern_swift_async_function_no_return() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate the correct code for extern "Swift" async functions that take a
+/// `&str` and return a `String`.
+/// Swift copies the `&str` before spawning its `Task`, since Rust's borrow ends as soon as the
+/// Swift function returns.
+mod extern_swift_async_function_str_arg_returns_string {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Swift" {
+                    async fn some_function(arg: &str) -> String;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub fn some_function(arg: &str) -> impl std::future::Future<Output = String> {
+                extern "C" fn on_complete(
+                    callback_wrapper: *mut std::ffi::c_void,
+                    val: *mut swift_bridge::string::RustString
+                ) {
+                    let val = unsafe { Box::from_raw(val).0 };
+                    unsafe { swift_bridge::async_support::SwiftFuture::<String>::complete(callback_wrapper, val) }
+                }
+
+                let (future, callback_wrapper) = swift_bridge::async_support::SwiftFuture::<String>::new();
+                unsafe {
+                    __swift_bridge__some_function(
+                        callback_wrapper,
+                        on_complete,
+                        swift_bridge::string::RustStr::from_str(arg)
+                    )
+                };
+                future
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function (_ callbackWrapper: UnsafeMutableRawPointer, _ callback: @escaping @convention(c) (UnsafeMutableRawPointer, UnsafeMutableRawPointer) -> (), _ arg: RustStr) {
+    let arg = arg.toString()
+    Task {
+        let val = await some_function(arg: arg)
+        callback(callbackWrapper, { let rustString = val.intoRustString(); rustString.isOwned = false; return rustString.ptr }())
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_swift_async_function_str_arg_returns_string() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate the correct code for extern "Swift" async functions that return a
+/// `Result`.
+mod extern_swift_async_function_returns_result {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Swift" {
+                    async fn some_function() -> Result<String, String>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                fn __swift_bridge__some_function(
+                    callback_wrapper: *mut std::ffi::c_void,
+                    callback: extern "C" fn(*mut std::ffi::c_void, swift_bridge::result::ResultPtrAndPtr) -> (),
+                );
+            },
+            quote! {
+                pub fn some_function() -> impl std::future::Future<Output = Result<String, String> > {
+                    extern "C" fn on_complete(
+                        callback_wrapper: *mut std::ffi::c_void,
+                        val: swift_bridge::result::ResultPtrAndPtr
+                    ) {
+                        let val = if val.is_ok {
+                            std::result::Result::Ok(unsafe {
+                                Box::from_raw(val.ok_or_err as *mut swift_bridge::string::RustString).0
+                            })
+                        } else {
+                            std::result::Result::Err(unsafe {
+                                Box::from_raw(val.ok_or_err as *mut swift_bridge::string::RustString).0
+                            })
+                        };
+                        unsafe { swift_bridge::async_support::SwiftFuture::<Result<String, String> >::complete(callback_wrapper, val) }
+                    }
+
+                    let (future, callback_wrapper) = swift_bridge::async_support::SwiftFuture::<Result<String, String> >::new();
+                    unsafe { __swift_bridge__some_function(callback_wrapper, on_complete, ) };
+                    future
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+func __swift_bridge__some_function (_ callbackWrapper: UnsafeMutableRawPointer, _ callback: @escaping @convention(c) (UnsafeMutableRawPointer, __private__ResultPtrAndPtr) -> ()) {
+    Task {
+        let val = await some_function()
+        callback(callbackWrapper, { switch val { case .Ok(let ok): return __private__ResultPtrAndPtr(is_ok: true, ok_or_err: { let rustString = ok.intoRustString(); rustString.isOwned = false; return rustString.ptr }()) case .Err(let err): return __private__ResultPtrAndPtr(is_ok: false, ok_or_err: { let rustString = err.intoRustString(); rustString.isOwned = false; return rustString.ptr }()) } }())
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_swift_async_function_returns_result() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate the correct code for extern "Swift" async functions that return a
+/// `Result` that is passed over FFI using a custom `Result` struct, such as when the `Ok` type is
+/// a primitive.
+mod extern_swift_async_function_returns_result_with_primitive_ok {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Swift" {
+                    async fn some_function() -> Result<u32, String>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            fn __swift_bridge__some_function(
+                callback_wrapper: *mut std::ffi::c_void,
+                callback: extern "C" fn(*mut std::ffi::c_void, ResultU32AndString) -> (),
+            );
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+func __swift_bridge__some_function (_ callbackWrapper: UnsafeMutableRawPointer, _ callback: @escaping @convention(c) (UnsafeMutableRawPointer, __swift_bridge__$ResultU32AndString) -> ()) {
+    Task {
+        let val = await some_function()
+        callback(callbackWrapper, { switch val { case .Ok(let ok): return __swift_bridge__$ResultU32AndString(tag: __swift_bridge__$ResultU32AndString$ResultOk, payload: __swift_bridge__$ResultU32AndString$Fields(ok: ok)) case .Err(let err): return __swift_bridge__$ResultU32AndString(tag: __swift_bridge__$ResultU32AndString$ResultErr, payload: __swift_bridge__$ResultU32AndString$Fields(err: { let rustString = err.intoRustString(); rustString.isOwned = false; return rustString.ptr }())) } }())
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_swift_async_function_returns_result_with_primitive_ok() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that Swift implements extern "Swift" async functions that return a
+/// `Result<T, SwiftError>` as `async throws` functions, and that we catch the error that they
+/// throw.
+mod extern_swift_async_function_returns_result_with_swift_error {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Swift" {
+                    async fn some_function() -> Result<u32, SwiftError>;
+                    async fn another_function() -> Result<(), SwiftError>;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+func __swift_bridge__some_function (_ callbackWrapper: UnsafeMutableRawPointer, _ callback: @escaping @convention(c) (UnsafeMutableRawPointer, __swift_bridge__$ResultU32AndSwiftError) -> ()) {
+    Task {
+        do {
+            let ok = try await some_function()
+            callback(callbackWrapper, __swift_bridge__$ResultU32AndSwiftError(tag: __swift_bridge__$ResultU32AndSwiftError$ResultOk, payload: __swift_bridge__$ResultU32AndSwiftError$Fields(ok: ok)))
+        } catch let err {
+            callback(callbackWrapper, __swift_bridge__$ResultU32AndSwiftError(tag: __swift_bridge__$ResultU32AndSwiftError$ResultErr, payload: __swift_bridge__$ResultU32AndSwiftError$Fields(err: { let rustString = err.localizedDescription.intoRustString(); rustString.isOwned = false; return rustString.ptr }())))
+        }
+    }
+}
+"#,
+            r#"
+func __swift_bridge__another_function (_ callbackWrapper: UnsafeMutableRawPointer, _ callback: @escaping @convention(c) (UnsafeMutableRawPointer, UnsafeMutableRawPointer?) -> ()) {
+    Task {
+        do {
+            let _ = try await another_function()
+            callback(callbackWrapper, nil)
+        } catch let err {
+            callback(callbackWrapper, { let rustString = err.localizedDescription.intoRustString(); rustString.isOwned = false; return rustString.ptr }())
+        }
+    }
+}
+"#,
+        ])
+    }
+
+    #[test]
+    fn extern_swift_async_function_returns_result_with_swift_error() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate the correct code for extern "Swift" async methods.
+/// Swift retains the instance before spawning its `Task`.
+mod extern_swift_async_method {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Swift" {
+                    type SomeType;
+
+                    async fn some_method(&self, arg: u8) -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            impl SomeType {
+                pub fn some_method(&self, arg: u8) -> impl std::future::Future<Output = u8> {
+                    extern "C" fn on_complete(callback_wrapper: *mut std::ffi::c_void, val: u8) {
+                        let val = val;
+                        unsafe { swift_bridge::async_support::SwiftFuture::<u8>::complete(callback_wrapper, val) }
+                    }
+
+                    let (future, callback_wrapper) = swift_bridge::async_support::SwiftFuture::<u8>::new();
+                    unsafe {
+                        __swift_bridge__SomeType_some_method(
+                            callback_wrapper,
+                            on_complete,
+                            swift_bridge::PointerToSwiftType(self.0),
+                            arg
+                        )
+                    };
+                    future
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$SomeType$some_method")
+func __swift_bridge__SomeType_some_method (_ callbackWrapper: UnsafeMutableRawPointer, _ callback: @escaping @convention(c) (UnsafeMutableRawPointer, UInt8) -> (), _ this: UnsafeMutableRawPointer, _ arg: UInt8) {
+    let this = Unmanaged<SomeType>.fromOpaque(this).takeUnretainedValue()
+    Task {
+        let val = await this.some_method(arg: arg)
+        callback(callbackWrapper, val)
+    }
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_swift_async_method() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
@@ -0,0 +1,368 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate `@available` attributes for a deprecated function that is only
+/// available on some platform versions, and that the exported Rust function allows calling the
+/// deprecated function.
+mod function_availability {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[deprecated(since = "0.2.0", note = "Use \"another_function\" instead.")]
+                    #[swift_bridge(available(iOS = "15", macOS = "12.3"))]
+                    fn some_function();
+
+                    #[deprecated]
+                    fn another_function();
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[allow(deprecated)]
+                #[export_name = "__swift_bridge__$some_function"]
+                pub extern "C" fn __swift_bridge__some_function() {
+                    super::some_function()
+                }
+            },
+            quote! {
+                #[allow(deprecated)]
+                #[export_name = "__swift_bridge__$another_function"]
+                pub extern "C" fn __swift_bridge__another_function() {
+                    super::another_function()
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+@available(iOS 15, macOS 12.3, *)
+@available(*, deprecated, message: "Deprecated since 0.2.0. Use \"another_function\" instead.")
+public func some_function() {
+"#,
+            r#"
+@available(*, deprecated)
+public func another_function() {
+"#,
+        ])
+    }
+
+    #[test]
+    fn function_availability() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate `@available` attributes for an opaque Rust type's classes and its
+/// methods, and that the exported Rust functions allow calling the deprecated type's methods.
+mod opaque_rust_type_availability {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[deprecated = "Use AnotherType instead."]
+                    #[swift_bridge(available(iOS = "16"))]
+                    type SomeType;
+
+                    #[swift_bridge(available(iOS = "17"))]
+                    fn some_method(&self);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[allow(deprecated)]
+            #[export_name = "__swift_bridge__$SomeType$some_method"]
+            pub extern "C" fn __swift_bridge__SomeType_some_method(this: *mut super::SomeType) {
+                (unsafe { &*this }).some_method()
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+@available(iOS 16, *)
+@available(*, deprecated, message: "Use AnotherType instead.")
+public class SomeType: SomeTypeRefMut {
+"#,
+            r#"
+@available(iOS 16, *)
+@available(*, deprecated, message: "Use AnotherType instead.")
+public class SomeTypeRefMut: SomeTypeRef {
+"#,
+            r#"
+@available(iOS 16, *)
+@available(*, deprecated, message: "Use AnotherType instead.")
+public class SomeTypeRef {
+"#,
+            r#"
+extension SomeTypeRef {
+    @available(iOS 17, *)
+    public func some_method() {
+"#,
+        ])
+    }
+
+    #[test]
+    fn opaque_rust_type_availability() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate `@available` attributes for a shared struct.
+mod shared_struct_availability {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[deprecated(note = "Use AnotherStruct instead.")]
+                #[swift_bridge(swift_repr = "struct", available(watchOS = "9"))]
+                struct SomeStruct {
+                    field: u8,
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@available(watchOS 9, *)
+@available(*, deprecated, message: "Use AnotherStruct instead.")
+public struct SomeStruct {
+"#,
+        )
+    }
+
+    #[test]
+    fn shared_struct_availability() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate `@available` attributes for a shared enum and its variants.
+mod shared_enum_availability {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(available(tvOS = "15"))]
+                enum SomeEnum {
+                    Variant1,
+                    #[deprecated(since = "1.0")]
+                    Variant2(u8),
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@available(tvOS 15, *)
+public enum SomeEnum {
+    case Variant1
+    @available(*, deprecated, message: "Deprecated since 1.0.")
+    case Variant2(UInt8)
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn shared_enum_availability() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that every extension of an opaque Rust type and every free function that mentions the
+/// type gets the type's `@available` attributes.
+mod opaque_rust_type_extensions_availability {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(available(iOS = "16"), Equatable, Hashable)]
+                    type SomeType;
+
+                    #[swift_bridge(init)]
+                    fn new() -> SomeType;
+                    fn consume(self);
+                    fn read(&self) -> u8;
+                    fn write(&mut self);
+
+                    #[swift_bridge(available(iOS = "17", macOS = "14"))]
+                    fn make_some_type() -> SomeType;
+                    fn take_some_types(arg: Vec<SomeType>);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+@available(iOS 17, macOS 14, *)
+public func make_some_type() -> SomeType {
+"#,
+            r#"
+@available(iOS 16, *)
+public func take_some_types(_ arg: RustVec<SomeType>) {
+"#,
+            r#"
+@available(iOS 16, *)
+extension SomeType {
+    public convenience init() {
+"#,
+            r#"
+@available(iOS 16, *)
+extension SomeType {
+    public func consume() {
+"#,
+            r#"
+@available(iOS 16, *)
+extension SomeTypeRefMut {
+    public func write() {
+"#,
+            r#"
+@available(iOS 16, *)
+extension SomeTypeRef {
+    public func read() -> UInt8 {
+"#,
+            r#"
+@available(iOS 16, *)
+extension SomeTypeRef: Equatable {
+"#,
+            r#"
+@available(iOS 16, *)
+extension SomeTypeRef: Hashable{
+"#,
+            r#"
+@available(iOS 16, *)
+extension SomeType: Vectorizable {
+"#,
+        ])
+    }
+
+    #[test]
+    fn opaque_rust_type_extensions_availability() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that every extension of a shared struct and every function that mentions the struct
+/// gets the struct's `@available` attributes.
+mod shared_struct_extensions_availability {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct", available(macOS = "13"))]
+                #[derive(PartialEq)]
+                struct SomeStruct {
+                    field: u8,
+                }
+
+                extern "Rust" {
+                    fn rust_returns_struct() -> Option<SomeStruct>;
+                }
+
+                extern "Swift" {
+                    fn swift_takes_struct(arg: SomeStruct);
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+@available(macOS 13, *)
+public func rust_returns_struct() -> Optional<SomeStruct> {
+"#,
+            r#"
+@available(macOS 13, *)
+@_cdecl("__swift_bridge__$swift_takes_struct")
+func __swift_bridge__swift_takes_struct (_ arg: __swift_bridge__$SomeStruct) {
+"#,
+            r#"
+@available(macOS 13, *)
+extension __swift_bridge__$SomeStruct {
+"#,
+            r#"
+@available(macOS 13, *)
+extension __swift_bridge__$Option$SomeStruct {
+"#,
+            r#"
+@available(macOS 13, *)
+extension SomeStruct: Equatable {
+"#,
+        ])
+    }
+
+    #[test]
+    fn shared_struct_extensions_availability() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
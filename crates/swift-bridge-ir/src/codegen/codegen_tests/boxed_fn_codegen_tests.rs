@@ -0,0 +1,403 @@
+//! Tests for passing `Box<dyn Fn(A) -> B>` and `Box<dyn FnMut(A) -> B>` between languages.
+//!
+//! Unlike a `Box<dyn FnOnce>`, these callbacks can be called any number of times, so Swift only
+//! frees them once the Swift closure that wraps them is deinitialized.
+//!
+//! Swift closures that are passed to Rust are retained until Rust drops the boxed function.
+//!
+//! See also: crates/swift-integration-tests/src/boxed_functions.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we can pass a `Box<dyn Fn>` with a primitive arg and return value from Rust to
+/// Swift, and that Swift calls it without taking ownership of it.
+mod test_swift_takes_fn_callback_primitive_arg_and_return {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    fn some_function(callback: Box<dyn Fn(u8) -> u16>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub fn some_function (callback: Box<dyn Fn(u8) -> u16>) {
+                    unsafe {
+                        __swift_bridge__some_function(
+                            Box::into_raw(Box::new(callback)) as *mut Box<dyn Fn(u8) -> u16>
+                        )
+                    }
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$some_function$param0"]
+                pub extern "C" fn some_function_param0(some_function_callback: *mut Box<dyn Fn(u8) -> u16>, arg0: u8) -> u16 {
+                    unsafe { (*some_function_callback)(arg0) }
+                }
+
+                #[export_name = "__swift_bridge__$some_function$_free$param0"]
+                pub extern "C" fn free_some_function_param0(some_function_callback: *mut Box<dyn Fn(u8) -> u16>) {
+                    let _ = unsafe { Box::from_raw(some_function_callback) };
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function (_ callback: UnsafeMutableRawPointer) {
+    { let cb0 = __private__RustFnCallback$some_function$param0(ptr: callback); let _ = some_function(callback: { arg0 in cb0.call(arg0) }) }()
+}
+"#,
+            r#"
+class __private__RustFnCallback$some_function$param0 {
+    var ptr: UnsafeMutableRawPointer
+
+    init(ptr: UnsafeMutableRawPointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        __swift_bridge__$some_function$_free$param0(ptr)
+    }
+
+    func call(_ arg0: UInt8) -> UInt16 {
+        return __swift_bridge__$some_function$param0(ptr, arg0)
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            r#"uint16_t __swift_bridge__$some_function$param0(void* some_function_callback, uint8_t arg0);"#,
+            r#"void __swift_bridge__$some_function$_free$param0(void* some_function_callback);"#,
+        ])
+    }
+
+    #[test]
+    fn test_swift_takes_fn_callback_primitive_arg_and_return() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that a `Box<dyn FnMut()>` with no args or return value gets its own callback class
+/// instead of reusing the `FnOnce` no args no return class, since it can be called many times.
+mod test_swift_takes_fnmut_callback_no_args_no_return {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    fn some_function(callback: Box<dyn FnMut()>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function$param0"]
+            pub extern "C" fn some_function_param0(some_function_callback: *mut Box<dyn FnMut() -> ()>) {
+                unsafe { (*some_function_callback)() }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function (_ callback: UnsafeMutableRawPointer) {
+    { let cb0 = __private__RustFnMutCallback$some_function$param0(ptr: callback); let _ = some_function(callback: { cb0.call() }) }()
+}
+"#,
+            r#"
+class __private__RustFnMutCallback$some_function$param0 {
+    var ptr: UnsafeMutableRawPointer
+
+    init(ptr: UnsafeMutableRawPointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        __swift_bridge__$some_function$_free$param0(ptr)
+    }
+
+    func call() {
+        return __swift_bridge__$some_function$param0(ptr)
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            r#"void __swift_bridge__$some_function$param0(void* some_function_callback);"#,
+            r#"void __swift_bridge__$some_function$_free$param0(void* some_function_callback);"#,
+        ])
+    }
+
+    #[test]
+    fn test_swift_takes_fnmut_callback_no_args_no_return() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that `Send` and `Sync` bounds on a boxed fn are kept in the generated Rust types.
+mod test_swift_takes_send_sync_fn_callback {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    fn some_function(callback: Box<dyn Fn(u8) + Send + Sync>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub fn some_function (callback: Box<dyn Fn(u8) -> () + Send + Sync>) {
+                    unsafe {
+                        __swift_bridge__some_function(
+                            Box::into_raw(Box::new(callback)) as *mut Box<dyn Fn(u8) -> () + Send + Sync>
+                        )
+                    }
+                }
+            },
+            quote! {
+                #[link_name = "__swift_bridge__$some_function"]
+                fn __swift_bridge__some_function(callback: *mut Box<dyn Fn(u8) -> () + Send + Sync>);
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+class __private__RustFnCallback$some_function$param0 {
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"void __swift_bridge__$some_function$param0(void* some_function_callback, uint8_t arg0);"#,
+        )
+    }
+
+    #[test]
+    fn test_swift_takes_send_sync_fn_callback() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that Swift can pass a closure to a Rust function, and that Rust can call the closure
+/// and release it once the boxed function is dropped.
+///
+/// The function is `Send`, so Swift must pass a `@Sendable` closure.
+mod test_rust_takes_swift_fn_callback_primitive_arg_and_return {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(callback: Box<dyn Fn(u8) -> u16 + Send>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(callback: *mut std::ffi::c_void) {
+                super::some_function({
+                    extern "C" {
+                        #[link_name = "__swift_bridge__$some_function$param0"]
+                        fn call_swift_callback(callback: *mut std::ffi::c_void, arg0: u8) -> u16;
+
+                        #[link_name = "__swift_bridge__$some_function$_free$param0"]
+                        fn free_swift_callback(callback: *mut std::ffi::c_void);
+                    }
+
+                    let callback = unsafe {
+                        swift_bridge::boxed_fn_support::SendableSwiftCallback::new(
+                            callback,
+                            free_swift_callback
+                        )
+                    };
+                    Box::new(move |arg0: u8| unsafe { call_swift_callback(callback.as_ptr(), arg0) })
+                })
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func some_function(_ callback: @escaping @Sendable (UInt8) -> UInt16) {
+    __swift_bridge__$some_function(Unmanaged.passRetained(__private__SwiftCallback$some_function$param0(callback)).toOpaque())
+}
+"#,
+            r#"
+class __private__SwiftCallback$some_function$param0 {
+    let callback: @Sendable (UInt8) -> UInt16
+
+    init(_ callback: @escaping @Sendable (UInt8) -> UInt16) {
+        self.callback = callback
+    }
+}
+
+@_cdecl("__swift_bridge__$some_function$param0")
+func __swift_bridge__some_function_param0 (_ callback: UnsafeMutableRawPointer, _ arg0: UInt8) -> UInt16 {
+    let ret = Unmanaged<__private__SwiftCallback$some_function$param0>.fromOpaque(callback).takeUnretainedValue().callback(arg0)
+    return ret
+}
+
+@_cdecl("__swift_bridge__$some_function$_free$param0")
+func __swift_bridge__some_function__free_param0 (_ callback: UnsafeMutableRawPointer) {
+    Unmanaged<__private__SwiftCallback$some_function$param0>.fromOpaque(callback).release()
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ExactAfterTrim(
+            r#"
+void __swift_bridge__$some_function(void* callback);
+"#,
+        )
+    }
+
+    #[test]
+    fn test_rust_takes_swift_fn_callback_primitive_arg_and_return() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that a method on an opaque Rust type can take a Swift closure that holds an opaque
+/// Rust type.
+///
+/// The function is not `Send`, so the closure does not need to be `@Sendable`.
+mod test_rust_method_takes_swift_callback_with_opaque_rust_arg {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn set_on_change(&mut self, callback: Box<dyn FnMut(SomeType)>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                let callback = unsafe {
+                    swift_bridge::boxed_fn_support::SwiftCallback::new(
+                        callback,
+                        free_swift_callback
+                    )
+                };
+            },
+            quote! {
+                #[link_name = "__swift_bridge__$SomeType$set_on_change$param1"]
+                fn call_swift_callback(callback: *mut std::ffi::c_void, arg0: *mut super::SomeType);
+            },
+            quote! {
+                Box::new(move |arg0: super::SomeType| unsafe {
+                    call_swift_callback(
+                        callback.as_ptr(),
+                        Box::into_raw(Box::new({
+                            let val: super::SomeType = arg0;
+                            val
+                        })) as *mut super::SomeType
+                    )
+                })
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+    public func set_on_change(_ callback: @escaping (SomeType) -> ()) {
+        __swift_bridge__$SomeType$set_on_change(ptr, Unmanaged.passRetained(__private__SwiftCallback$SomeType$set_on_change$param1(callback)).toOpaque())
+    }
+"#,
+            r#"
+@_cdecl("__swift_bridge__$SomeType$set_on_change$param1")
+func __swift_bridge__SomeType_set_on_change_param1 (_ callback: UnsafeMutableRawPointer, _ arg0: UnsafeMutableRawPointer) {
+    Unmanaged<__private__SwiftCallback$SomeType$set_on_change$param1>.fromOpaque(callback).takeUnretainedValue().callback(SomeType(ptr: arg0))
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __swift_bridge__$SomeType$set_on_change(void* self, void* callback);
+"#,
+        )
+    }
+
+    #[test]
+    fn test_rust_method_takes_swift_callback_with_opaque_rust_arg() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
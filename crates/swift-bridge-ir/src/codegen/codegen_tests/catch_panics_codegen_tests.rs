@@ -0,0 +1,394 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we abort after calling Swift's panic handler when a function that can't throw
+/// panics.
+mod catch_panics_non_throwing_function {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge(catch_panics)]
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(arg: u8) -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(arg: u8) -> u8 {
+                swift_bridge::panic_support::call_or_abort(|| { super::some_function(arg) })
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: UInt8) -> UInt8 {
+    __swift_bridge__$some_function(arg)
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn catch_panics_non_throwing_function() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that Swift throws a `RustPanic` when a function that returns a `Result` panics.
+mod catch_panics_result_function {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge(catch_panics)]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_function() -> Result<SomeType, SomeType>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function() -> swift_bridge::result::ResultPtrAndPtr {
+                unsafe {
+                    swift_bridge::panic_support::call_or_store_panic(|| {
+                        match super::some_function() {
+                            Ok(ok) => {
+                                swift_bridge::result::ResultPtrAndPtr {
+                                    is_ok: true,
+                                    ok_or_err: Box::into_raw(Box::new({
+                                        let val: super::SomeType = ok;
+                                        val
+                                    })) as *mut super::SomeType as *mut std::ffi::c_void
+                                }
+                            }
+                            Err(err) => {
+                                swift_bridge::result::ResultPtrAndPtr {
+                                    is_ok: false,
+                                    ok_or_err: Box::into_raw(Box::new({
+                                        let val: super::SomeType = err;
+                                        val
+                                    })) as *mut super::SomeType as *mut std::ffi::c_void
+                                }
+                            }
+                        }
+                    })
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function() throws -> SomeType {
+    try { let val = try RustPanic.check(__swift_bridge__$some_function()); if val.is_ok { return SomeType(ptr: val.ok_or_err!) } else { throw SomeType(ptr: val.ok_or_err!) } }()
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn catch_panics_result_function() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that Swift throws a `RustPanic` when an async function panics.
+mod catch_panics_async_function {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge(catch_panics)]
+            mod ffi {
+                extern "Rust" {
+                    async fn some_function() -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            let task = async move {
+                let val = match swift_bridge::async_support::CatchUnwind::new(fut).await {
+                    Ok(val) => val,
+                    Err(payload) => {
+                        swift_bridge::panic_support::store_panic(payload);
+
+                        let callback_wrapper = task_callback_wrapper;
+                        let callback_wrapper = callback_wrapper.0;
+
+                        return (callback)(callback_wrapper, unsafe { std::mem::zeroed() });
+                    }
+                };
+
+                let callback_wrapper = task_callback_wrapper;
+                let callback_wrapper = callback_wrapper.0;
+
+                (callback)(callback_wrapper, val)
+            };
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+    func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnRetVal: UInt8) {
+        let wrapper = Unmanaged<CbWrapper$some_function>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+        if let rustPanic = RustPanic.take() {
+            wrapper.cb(.failure(rustPanic))
+            return
+        }
+        wrapper.cb(.success(rustFnRetVal))
+    }
+"#,
+        )
+    }
+
+    #[test]
+    fn catch_panics_async_function() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we abort after calling Swift's panic handler when an opaque type's `Display` or
+/// `Debug` implementation panics.
+mod catch_panics_display_and_debug {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge(catch_panics)]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Display, Debug)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_display"]
+                pub extern "C" fn __swift_bridge__SomeType__display (
+                    this: *const super::SomeType,
+                ) -> *mut swift_bridge::string::RustString {
+                    swift_bridge::panic_support::call_or_abort(|| {
+                        swift_bridge::string::RustString(
+                            format!("{}", unsafe { &*this })
+                        ).box_into_raw()
+                    })
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_debug"]
+                pub extern "C" fn __swift_bridge__SomeType__debug (
+                    this: *const super::SomeType,
+                ) -> *mut swift_bridge::string::RustString {
+                    swift_bridge::panic_support::call_or_abort(|| {
+                        swift_bridge::string::RustString(
+                            format!("{:?}", unsafe { &*this })
+                        ).box_into_raw()
+                    })
+                }
+            },
+        ])
+    }
+
+    #[test]
+    fn catch_panics_display_and_debug() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we abort after calling Swift's panic handler when an opaque type's `PartialEq` or
+/// `PartialOrd` implementation panics.
+mod catch_panics_partial_eq_and_partial_cmp {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge(catch_panics)]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Comparable)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_partial_eq"]
+                pub extern "C" fn __swift_bridge__SomeType__partial_eq (
+                    lhs: *const super::SomeType,
+                    rhs: *const super::SomeType
+                ) -> bool {
+                    swift_bridge::panic_support::call_or_abort(|| {
+                        unsafe { &*lhs == &*rhs }
+                    })
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_partial_cmp"]
+                pub extern "C" fn __swift_bridge__SomeType__partial_cmp (
+                    lhs: *const super::SomeType,
+                    rhs: *const super::SomeType
+                ) -> i8 {
+                    swift_bridge::panic_support::call_or_abort(|| {
+                        match unsafe { (&*lhs).partial_cmp(&*rhs) } {
+                            Some(std::cmp::Ordering::Less) => -1,
+                            Some(std::cmp::Ordering::Equal) => 0,
+                            Some(std::cmp::Ordering::Greater) => 1,
+                            None => 2,
+                        }
+                    })
+                }
+            },
+        ])
+    }
+
+    #[test]
+    fn catch_panics_partial_eq_and_partial_cmp() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we abort after calling Swift's panic handler when an opaque type's operator trait
+/// implementation panics.
+mod catch_panics_operators {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge(catch_panics)]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Add, Index(usize, Output = u8))]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_add"]
+                pub extern "C" fn __swift_bridge__SomeType__add(
+                    lhs: *const super::SomeType,
+                    rhs: *const super::SomeType
+                ) -> *mut super::SomeType {
+                    swift_bridge::panic_support::call_or_abort(|| {
+                        Box::into_raw(Box::new({
+                            let val: super::SomeType = std::ops::Add::add(
+                                Clone::clone(unsafe { &*lhs }),
+                                Clone::clone(unsafe { &*rhs })
+                            );
+                            val
+                        })) as *mut super::SomeType
+                    })
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_index"]
+                pub extern "C" fn __swift_bridge__SomeType__index(
+                    this: *const super::SomeType,
+                    index: usize
+                ) -> u8 {
+                    swift_bridge::panic_support::call_or_abort(|| {
+                        Clone::clone(std::ops::Index::index(unsafe { &*this }, index))
+                    })
+                }
+            },
+        ])
+    }
+
+    #[test]
+    fn catch_panics_operators() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: ExpectedSwiftCode::SkipTest,
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we don't catch panics unless the bridge module uses `catch_panics`.
+mod does_not_catch_panics_by_default {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    fn some_function() -> Result<u8, u8>;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn does_not_catch_panics_by_default() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: ExpectedRustTokens::DoesNotContain(quote! { panic_support }),
+            expected_swift_code: ExpectedSwiftCode::DoesNotContainAfterTrim("RustPanic"),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
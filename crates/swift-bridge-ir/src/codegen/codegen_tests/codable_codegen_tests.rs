@@ -0,0 +1,541 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate a Codable conformance for a struct that derives serde's traits.
+mod serde_struct_codable {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                #[derive(Serialize, Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct SomeStruct {
+                    some_field: u8,
+                    #[serde(rename = "text")]
+                    string_field: String,
+                    optional_field: Option<u32>,
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[derive(::serde::Serialize, ::serde::Deserialize)]
+            #[serde(rename_all = "camelCase")]
+            pub struct SomeStruct {
+                pub some_field: u8,
+                #[serde(rename = "text")]
+                pub string_field: String,
+                pub optional_field: Option<u32>
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeStruct: Codable {
+    enum CodingKeys: String, CodingKey {
+        case some_field = "someField"
+        case string_field = "text"
+        case optional_field = "optionalField"
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.container(keyedBy: CodingKeys.self)
+        try container.encode(self.some_field, forKey: .some_field)
+        try container.encode(self.string_field.toString(), forKey: .string_field)
+        try container.encodeIfPresent(self.optional_field, forKey: .optional_field)
+    }
+
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        self.init(some_field: try container.decode(UInt8.self, forKey: .some_field), string_field: try container.decode(String.self, forKey: .string_field).intoRustString(), optional_field: try container.decodeIfPresent(UInt32.self, forKey: .optional_field))
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn serde_struct_codable() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that newtype structs are encoded as their inner value, tuple structs are encoded as
+/// arrays and unit structs are encoded as null.
+mod serde_newtype_tuple_and_unit_struct_codable {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                #[derive(Serialize, Deserialize)]
+                struct NewtypeStruct(String);
+
+                #[swift_bridge(swift_repr = "struct")]
+                #[derive(Serialize, Deserialize)]
+                struct TupleStruct(u8, Option<i16>);
+
+                #[derive(Serialize, Deserialize)]
+                struct UnitStruct;
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::SkipTest
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+extension NewtypeStruct: Codable {
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.singleValueContainer()
+        try container.encode(self._0.toString())
+    }
+
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.singleValueContainer()
+        self.init(_0: try container.decode(String.self).intoRustString())
+    }
+}
+"#,
+            r#"
+extension TupleStruct: Codable {
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.unkeyedContainer()
+        try container.encode(self._0)
+        try container.encode(self._1)
+    }
+
+    public init(from decoder: Decoder) throws {
+        var container = try decoder.unkeyedContainer()
+        self.init(_0: try container.decode(UInt8.self), _1: try container.decode(Optional<Int16>.self))
+    }
+}
+"#,
+            r#"
+extension UnitStruct: Codable {
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.singleValueContainer()
+        try container.encodeNil()
+    }
+
+    public init(from decoder: Decoder) throws {
+        _ = try decoder.singleValueContainer()
+        self.init()
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn serde_newtype_tuple_and_unit_struct_codable() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we only generate an Encodable conformance for a struct that only derives
+/// `Serialize`.
+mod serde_serialize_only_struct_encodable {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                #[derive(serde::Serialize)]
+                struct SomeStruct {
+                    field: u8,
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[derive(::serde::Serialize)]
+            pub struct SomeStruct {
+                pub field: u8
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+extension SomeStruct: Encodable {
+    enum CodingKeys: String, CodingKey {
+        case field = "field"
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.container(keyedBy: CodingKeys.self)
+        try container.encode(self.field, forKey: .field)
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn serde_serialize_only_struct_encodable() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate a Codable conformance that uses serde's default externally tagged
+/// enum representation.
+mod serde_externally_tagged_enum_codable {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[derive(Serialize, Deserialize)]
+                #[serde(rename_all = "snake_case")]
+                enum SomeEnum {
+                    UnitVariant,
+                    NewtypeVariant(String),
+                    TupleVariant(u8, u16),
+                    #[serde(rename_all = "camelCase")]
+                    StructVariant { some_field: i32 },
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[derive(::serde::Serialize, ::serde::Deserialize)]
+            #[serde(rename_all = "snake_case")]
+            pub enum SomeEnum {
+                UnitVariant,
+                NewtypeVariant(String),
+                TupleVariant(u8, u16),
+                #[serde(rename_all = "camelCase")]
+                StructVariant { some_field: i32 }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeEnum: Codable {
+    enum CodingKeys: String, CodingKey {
+        case UnitVariant = "unit_variant"
+        case NewtypeVariant = "newtype_variant"
+        case TupleVariant = "tuple_variant"
+        case StructVariant = "struct_variant"
+    }
+
+    enum StructVariantCodingKeys: String, CodingKey {
+        case some_field = "someField"
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        switch self {
+        case .UnitVariant:
+            var container = encoder.singleValueContainer()
+            try container.encode("unit_variant")
+        case let .NewtypeVariant(_0):
+            var container = encoder.container(keyedBy: CodingKeys.self)
+            try container.encode(_0.toString(), forKey: .NewtypeVariant)
+        case let .TupleVariant(_0, _1):
+            var container = encoder.container(keyedBy: CodingKeys.self)
+            var variantContainer = container.nestedUnkeyedContainer(forKey: .TupleVariant)
+            try variantContainer.encode(_0)
+            try variantContainer.encode(_1)
+        case let .StructVariant(some_field: some_field):
+            var container = encoder.container(keyedBy: CodingKeys.self)
+            var variantContainer = container.nestedContainer(keyedBy: StructVariantCodingKeys.self, forKey: .StructVariant)
+            try variantContainer.encode(some_field, forKey: .some_field)
+        }
+    }
+
+    public init(from decoder: Decoder) throws {
+        if let variant = try? decoder.singleValueContainer().decode(String.self) {
+            switch variant {
+            case "unit_variant":
+                self = .UnitVariant
+                return
+            default:
+                break
+            }
+        }
+        let container = try decoder.container(keyedBy: CodingKeys.self)
+        guard let key = container.allKeys.first else {
+            throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Expected an enum variant"))
+        }
+        switch key {
+        case .UnitVariant:
+            self = .UnitVariant
+        case .NewtypeVariant:
+            self = .NewtypeVariant(try container.decode(String.self, forKey: .NewtypeVariant).intoRustString())
+        case .TupleVariant:
+            var variantContainer = try container.nestedUnkeyedContainer(forKey: .TupleVariant)
+            self = .TupleVariant(try variantContainer.decode(UInt8.self), try variantContainer.decode(UInt16.self))
+        case .StructVariant:
+            let variantContainer = try container.nestedContainer(keyedBy: StructVariantCodingKeys.self, forKey: .StructVariant)
+            self = .StructVariant(some_field: try variantContainer.decode(Int32.self, forKey: .some_field))
+        }
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn serde_externally_tagged_enum_codable() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate a Codable conformance that uses serde's internally tagged enum
+/// representation.
+mod serde_internally_tagged_enum_codable {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                #[derive(Serialize, Deserialize)]
+                struct SomeStruct {
+                    field: u8,
+                }
+
+                #[derive(Serialize, Deserialize)]
+                #[serde(tag = "type")]
+                enum SomeEnum {
+                    UnitVariant,
+                    NewtypeVariant(SomeStruct),
+                    StructVariant { field: String },
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::SkipTest
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeEnum: Codable {
+    enum TagCodingKeys: String, CodingKey {
+        case tag = "type"
+    }
+
+    enum StructVariantCodingKeys: String, CodingKey {
+        case field = "field"
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        switch self {
+        case .UnitVariant:
+            var container = encoder.container(keyedBy: TagCodingKeys.self)
+            try container.encode("UnitVariant", forKey: .tag)
+        case let .NewtypeVariant(_0):
+            try _0.encode(to: encoder)
+            var container = encoder.container(keyedBy: TagCodingKeys.self)
+            try container.encode("NewtypeVariant", forKey: .tag)
+        case let .StructVariant(field: field):
+            var container = encoder.container(keyedBy: TagCodingKeys.self)
+            try container.encode("StructVariant", forKey: .tag)
+            var variantContainer = encoder.container(keyedBy: StructVariantCodingKeys.self)
+            try variantContainer.encode(field.toString(), forKey: .field)
+        }
+    }
+
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: TagCodingKeys.self)
+        let tag = try container.decode(String.self, forKey: .tag)
+        switch tag {
+        case "UnitVariant":
+            self = .UnitVariant
+        case "NewtypeVariant":
+            self = .NewtypeVariant(try SomeStruct(from: decoder))
+        case "StructVariant":
+            let variantContainer = try decoder.container(keyedBy: StructVariantCodingKeys.self)
+            self = .StructVariant(field: try variantContainer.decode(String.self, forKey: .field).intoRustString())
+        default:
+            throw DecodingError.dataCorruptedError(forKey: .tag, in: container, debugDescription: "Unknown variant \(tag)")
+        }
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn serde_internally_tagged_enum_codable() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate a Codable conformance that uses serde's adjacently tagged enum
+/// representation.
+mod serde_adjacently_tagged_enum_codable {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[derive(Serialize, Deserialize)]
+                #[serde(tag = "t", content = "c")]
+                enum SomeEnum {
+                    UnitVariant,
+                    NewtypeVariant(u8),
+                    TupleVariant(u8, String),
+                    StructVariant { field: Option<u8> },
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::SkipTest
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeEnum: Codable {
+    enum TagCodingKeys: String, CodingKey {
+        case tag = "t"
+        case content = "c"
+    }
+
+    enum StructVariantCodingKeys: String, CodingKey {
+        case field = "field"
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        switch self {
+        case .UnitVariant:
+            var container = encoder.container(keyedBy: TagCodingKeys.self)
+            try container.encode("UnitVariant", forKey: .tag)
+        case let .NewtypeVariant(_0):
+            var container = encoder.container(keyedBy: TagCodingKeys.self)
+            try container.encode("NewtypeVariant", forKey: .tag)
+            try container.encode(_0, forKey: .content)
+        case let .TupleVariant(_0, _1):
+            var container = encoder.container(keyedBy: TagCodingKeys.self)
+            try container.encode("TupleVariant", forKey: .tag)
+            var variantContainer = container.nestedUnkeyedContainer(forKey: .content)
+            try variantContainer.encode(_0)
+            try variantContainer.encode(_1.toString())
+        case let .StructVariant(field: field):
+            var container = encoder.container(keyedBy: TagCodingKeys.self)
+            try container.encode("StructVariant", forKey: .tag)
+            var variantContainer = container.nestedContainer(keyedBy: StructVariantCodingKeys.self, forKey: .content)
+            try variantContainer.encodeIfPresent(field, forKey: .field)
+        }
+    }
+
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: TagCodingKeys.self)
+        let tag = try container.decode(String.self, forKey: .tag)
+        switch tag {
+        case "UnitVariant":
+            self = .UnitVariant
+        case "NewtypeVariant":
+            self = .NewtypeVariant(try container.decode(UInt8.self, forKey: .content))
+        case "TupleVariant":
+            var variantContainer = try container.nestedUnkeyedContainer(forKey: .content)
+            self = .TupleVariant(try variantContainer.decode(UInt8.self), try variantContainer.decode(String.self).intoRustString())
+        case "StructVariant":
+            let variantContainer = try container.nestedContainer(keyedBy: StructVariantCodingKeys.self, forKey: .content)
+            self = .StructVariant(field: try variantContainer.decodeIfPresent(UInt8.self, forKey: .field))
+        default:
+            throw DecodingError.dataCorruptedError(forKey: .tag, in: container, debugDescription: "Unknown variant \(tag)")
+        }
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn serde_adjacently_tagged_enum_codable() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
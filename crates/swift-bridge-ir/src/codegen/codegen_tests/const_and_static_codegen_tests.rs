@@ -0,0 +1,134 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that primitive constants with literal values become Swift constants and C `#define`s.
+mod primitive_literal_consts {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                /// The maximum number of items.
+                pub const MAX_ITEMS: u32 = 1_000;
+                const MIN_OFFSET: i64 = -5;
+                const RATIO: f64 = 0.5;
+                static ENABLED: bool = true;
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[allow(dead_code)]
+            /// The maximum number of items.
+            pub const MAX_ITEMS: u32 = 1_000;
+            #[allow(dead_code)]
+            const MIN_OFFSET: i64 = -5;
+            #[allow(dead_code)]
+            const RATIO: f64 = 0.5;
+            #[allow(dead_code)]
+            static ENABLED: bool = true;
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+/// The maximum number of items.
+public let MAX_ITEMS: UInt32 = 1000
+public let MIN_OFFSET: Int64 = -5
+public let RATIO: Double = 0.5
+public let ENABLED: Bool = true
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+#define __swift_bridge__$MAX_ITEMS 1000
+#define __swift_bridge__$MIN_OFFSET -5
+#define __swift_bridge__$RATIO 0.5
+#define __swift_bridge__$ENABLED true
+"#,
+        )
+    }
+
+    #[test]
+    fn primitive_literal_consts() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we read constants that aren't primitive literals from Rust.
+mod non_literal_consts {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                const BUFFER_SIZE: usize = 4 * 1024;
+                static VERSION: &str = "1.2.3";
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                const BUFFER_SIZE: usize = 4 * 1024;
+                static VERSION: &str = "1.2.3";
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$BUFFER_SIZE"]
+                pub extern "C" fn __swift_bridge__BUFFER_SIZE() -> usize {
+                    BUFFER_SIZE
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$VERSION"]
+                pub extern "C" fn __swift_bridge__VERSION() -> swift_bridge::string::RustStr {
+                    swift_bridge::string::RustStr::from_str(Clone::clone(&VERSION))
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public let BUFFER_SIZE: UInt = __swift_bridge__$BUFFER_SIZE()
+public let VERSION: RustStr = __swift_bridge__$VERSION()
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+uintptr_t __swift_bridge__$BUFFER_SIZE(void);
+struct RustStr __swift_bridge__$VERSION(void);
+"#,
+        )
+    }
+
+    #[test]
+    fn non_literal_consts() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
@@ -66,3 +66,193 @@ void* __swift_bridge__$SomeEnum$Debug(__swift_bridge__$SomeEnum this);
         .test();
     }
 }
+
+/// Verify that we generate `Equatable`, `Hashable` and `Comparable` conformances when an enum with
+/// data derives `PartialEq`, `Eq`, `Hash`, `PartialOrd` and `Ord`.
+mod derive_comparable_enum_with_data {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+                enum SomeEnum {
+                    Unit,
+                    Unnamed(u8, String),
+                    Named { maybe_number: Option<u8> },
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+            pub enum SomeEnum {
+                Unit,
+                Unnamed(u8, String),
+                Named { maybe_number: Option<u8> }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeEnum: Equatable {
+    public static func == (lhs: SomeEnum, rhs: SomeEnum) -> Bool {
+        switch (lhs, rhs) {
+        case (.Unit, .Unit):
+            return true
+        case let (.Unnamed(lhs0, lhs1), .Unnamed(rhs0, rhs1)):
+            return lhs0 == rhs0 && lhs1.toString().utf8.elementsEqual(rhs1.toString().utf8)
+        case let (.Named(maybe_number: lhs0), .Named(maybe_number: rhs0)):
+            return lhs0 == rhs0
+        default:
+            return false
+        }
+    }
+}
+extension SomeEnum: Hashable {
+    public func hash(into hasher: inout Hasher) {
+        switch self {
+        case .Unit:
+            hasher.combine(0)
+        case let .Unnamed(value0, value1):
+            hasher.combine(1)
+            hasher.combine(value0)
+            hasher.combine(value1.toString())
+        case let .Named(maybe_number: value0):
+            hasher.combine(2)
+            hasher.combine(value0)
+        }
+    }
+}
+extension SomeEnum: Comparable {
+    public static func < (lhs: SomeEnum, rhs: SomeEnum) -> Bool {
+        func variantIndex(_ value: SomeEnum) -> Int {
+            switch value {
+            case .Unit:
+                return 0
+            case .Unnamed:
+                return 1
+            case .Named:
+                return 2
+            }
+        }
+
+        switch (lhs, rhs) {
+        case let (.Unnamed(lhs0, lhs1), .Unnamed(rhs0, rhs1)):
+            if lhs0 != rhs0 {
+                return lhs0 < rhs0
+            }
+            if !lhs1.toString().utf8.elementsEqual(rhs1.toString().utf8) {
+                return lhs1.toString().utf8.lexicographicallyPrecedes(rhs1.toString().utf8)
+            }
+            return false
+        case let (.Named(maybe_number: lhs0), .Named(maybe_number: rhs0)):
+            if lhs0 != rhs0 {
+                return { switch (lhs0, rhs0) { case (nil, .some): return true; case let (.some(lhsValue), .some(rhsValue)): return lhsValue < rhsValue; default: return false } }()
+            }
+            return false
+        default:
+            return variantIndex(lhs) < variantIndex(rhs)
+        }
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn generates_comparable_conformances() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that variants without data are compared by their declaration order.
+mod derive_comparable_enum_without_data {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[derive(PartialEq, PartialOrd)]
+                enum SomeEnum {
+                    Variant1,
+                    Variant2,
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[derive(Copy, Clone, PartialEq, PartialOrd)]
+            pub enum SomeEnum {
+                Variant1,
+                Variant2
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeEnum: Equatable {
+    public static func == (lhs: SomeEnum, rhs: SomeEnum) -> Bool {
+        switch (lhs, rhs) {
+        case (.Variant1, .Variant1):
+            return true
+        case (.Variant2, .Variant2):
+            return true
+        default:
+            return false
+        }
+    }
+}
+extension SomeEnum: Comparable {
+    public static func < (lhs: SomeEnum, rhs: SomeEnum) -> Bool {
+        func variantIndex(_ value: SomeEnum) -> Int {
+            switch value {
+            case .Variant1:
+                return 0
+            case .Variant2:
+                return 1
+            }
+        }
+
+        return variantIndex(lhs) < variantIndex(rhs)
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn generates_comparable_conformances() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
@@ -93,3 +93,140 @@ mod derive_clone_struct {
         .test();
     }
 }
+
+/// Verify that we generate `Equatable`, `Hashable` and `Comparable` conformances when a transparent struct
+/// derives `PartialEq`, `Eq`, `Hash`, `PartialOrd` and `Ord`.
+mod derive_comparable_struct {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                #[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+                struct SomeStruct {
+                    number: u8,
+                    flag: bool,
+                    text: String,
+                    maybe_number: Option<u16>,
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+            pub struct SomeStruct {
+                pub number: u8,
+                pub flag: bool,
+                pub text: String,
+                pub maybe_number: Option<u16>
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeStruct: Equatable {
+    public static func == (lhs: SomeStruct, rhs: SomeStruct) -> Bool {
+        return lhs.number == rhs.number && lhs.flag == rhs.flag && lhs.text.toString().utf8.elementsEqual(rhs.text.toString().utf8) && lhs.maybe_number == rhs.maybe_number
+    }
+}
+extension SomeStruct: Hashable {
+    public func hash(into hasher: inout Hasher) {
+        hasher.combine(self.number)
+        hasher.combine(self.flag)
+        hasher.combine(self.text.toString())
+        hasher.combine(self.maybe_number)
+    }
+}
+extension SomeStruct: Comparable {
+    public static func < (lhs: SomeStruct, rhs: SomeStruct) -> Bool {
+        if lhs.number != rhs.number {
+            return lhs.number < rhs.number
+        }
+        if lhs.flag != rhs.flag {
+            return !lhs.flag && rhs.flag
+        }
+        if !lhs.text.toString().utf8.elementsEqual(rhs.text.toString().utf8) {
+            return lhs.text.toString().utf8.lexicographicallyPrecedes(rhs.text.toString().utf8)
+        }
+        if lhs.maybe_number != rhs.maybe_number {
+            return { switch (lhs.maybe_number, rhs.maybe_number) { case (nil, .some): return true; case let (.some(lhsValue), .some(rhsValue)): return lhsValue < rhsValue; default: return false } }()
+        }
+        return false
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn generates_comparable_conformances() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we only generate an `Equatable` conformance when a transparent tuple struct only
+/// derives `PartialEq`.
+mod derive_partial_eq_tuple_struct {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                #[derive(PartialEq)]
+                struct SomeStruct(u8, Option<u16>);
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[derive(PartialEq)]
+            pub struct SomeStruct(pub u8, pub Option<u16>);
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeStruct: Equatable {
+    public static func == (lhs: SomeStruct, rhs: SomeStruct) -> Bool {
+        return lhs._0 == rhs._0 && lhs._1 == rhs._1
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn generates_equatable_conformance() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
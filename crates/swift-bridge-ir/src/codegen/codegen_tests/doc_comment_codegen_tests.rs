@@ -0,0 +1,221 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we copy the doc comments of an opaque Rust type and its methods to the generated
+/// Swift class.
+mod opaque_rust_type_doc_comments {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    /// A chat room.
+                    ///
+                    /// Rooms are **cheap** to create.
+                    type ChatRoom;
+
+                    /// Send a message to everyone in the room.
+                    ///
+                    /// # Arguments
+                    ///
+                    /// * `message` - The message to send.
+                    /// * `urgent` - Whether to notify people that
+                    ///   have muted the room.
+                    ///
+                    /// # Errors
+                    ///
+                    /// Returns an error if the room
+                    /// was closed.
+                    fn send(&self, message: String, urgent: bool) -> Result<(), String>;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+/// A chat room.
+///
+/// Rooms are **cheap** to create.
+public class ChatRoom: ChatRoomRefMut {
+"#,
+            r#"
+extension ChatRoomRef {
+    /// Send a message to everyone in the room.
+    ///
+    /// - Parameter message: The message to send.
+    /// - Parameter urgent: Whether to notify people that
+    ///   have muted the room.
+    /// - Throws: Returns an error if the room
+    ///   was closed.
+    public func send<GenericIntoRustString: IntoRustString>(_ message: GenericIntoRustString, _ urgent: Bool) throws -> () {
+"#,
+        ])
+    }
+
+    #[test]
+    fn opaque_rust_type_doc_comments() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we copy a freestanding function's doc comment, including its `# Returns` section,
+/// and drop the hidden lines of its doc test.
+mod function_doc_comment {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    /// Add two numbers.
+                    ///
+                    /// ```
+                    /// # use some_crate::add;
+                    /// assert_eq!(add(1, 2), 3);
+                    /// ```
+                    ///
+                    /// # Returns
+                    ///
+                    /// The sum.
+                    ///
+                    /// # Panics
+                    ///
+                    /// If the sum overflows.
+                    fn add(a: u8, b: u8) -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+/// Add two numbers.
+///
+/// ```
+/// assert_eq!(add(1, 2), 3);
+/// ```
+///
+/// - Returns: The sum.
+///
+/// # Panics
+///
+/// If the sum overflows.
+public func add(_ a: UInt8, _ b: UInt8) -> UInt8 {
+"#,
+        )
+    }
+
+    #[test]
+    fn function_doc_comment() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we copy the doc comments of a shared struct and its fields.
+mod shared_struct_doc_comments {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                /// A point on the map.
+                #[swift_bridge(swift_repr = "struct")]
+                struct Point {
+                    /// Meters east of the origin.
+                    x: i32,
+                    y: i32,
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+/// A point on the map.
+public struct Point {
+    /// Meters east of the origin.
+    public var x: Int32
+    public var y: Int32
+"#,
+        )
+    }
+
+    #[test]
+    fn shared_struct_doc_comments() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Verify that we copy the doc comments of a shared enum and its variants.
+mod shared_enum_doc_comments {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                /// The state of a download.
+                enum DownloadState {
+                    /// Waiting for a connection.
+                    Pending,
+                    Downloading(u8),
+                    /// The download is done.
+                    Done { bytes: u32 },
+                }
+            }
+        }
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+/// The state of a download.
+public enum DownloadState {
+    /// Waiting for a connection.
+    case Pending
+    case Downloading(UInt8)
+    /// The download is done.
+    case Done(bytes: UInt32)
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn shared_enum_doc_comments() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: ExpectedRustTokens::SkipTest,
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
@@ -399,6 +399,295 @@ mod get_with {
     }
 }
 
+/// Verify that we can use the get_mut attribute
+mod get_mut {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(get_mut(field))]
+                    fn some_function(&mut self) -> &mut SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub extern "C" fn __swift_bridge__SomeType_some_function(
+                this: *mut super::SomeType
+            ) -> *mut super::SomeType {
+                &mut (unsafe { &mut *this }).field as *mut super::SomeType
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::SkipTest
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn get_mut() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we can use the set attribute
+mod set {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(set(field))]
+                    fn set_field(&mut self, value: u16);
+
+                    #[swift_bridge(set(name))]
+                    fn set_name(&mut self, value: String);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub extern "C" fn __swift_bridge__SomeType_set_field(
+                    this: *mut super::SomeType,
+                    value: u16
+                ) {
+                    (unsafe { &mut *this }).field = value
+                }
+            },
+            quote! {
+                pub extern "C" fn __swift_bridge__SomeType_set_name(
+                    this: *mut super::SomeType,
+                    value: *mut swift_bridge::string::RustString
+                ) {
+                    (unsafe { &mut *this }).name = unsafe { Box::from_raw(value).0 }
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::SkipTest
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn set() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we can use the set_with attribute
+mod set_with {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(set_with(field = a::b::c))]
+                    fn set_field(&mut self, value: u16);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub extern "C" fn __swift_bridge__SomeType_set_field(
+                this: *mut super::SomeType,
+                value: u16
+            ) {
+                (unsafe { &mut *this }).field = super::a::b::c(value)
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::SkipTest
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::SkipTest
+    }
+
+    #[test]
+    fn set_with() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that getters and setters that use the swift_property attribute become Swift computed
+/// properties, and that a `Ref` can read a property that a `RefMut` can also set.
+mod swift_property {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+
+                    #[swift_bridge(get(count), swift_property)]
+                    fn count(&self) -> u32;
+
+                    #[swift_bridge(set(count), swift_property)]
+                    fn set_count(&mut self, count: u32);
+
+                    #[swift_bridge(swift_property)]
+                    fn name(&self) -> String;
+
+                    #[swift_bridge(swift_property)]
+                    fn set_name(&mut self, name: String);
+
+                    #[swift_bridge(get(id), swift_property)]
+                    fn id(&self) -> u64;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub extern "C" fn __swift_bridge__SomeType_count(
+                    this: *mut super::SomeType
+                ) -> u32 {
+                    (unsafe { &*this }).count
+                }
+            },
+            quote! {
+                pub extern "C" fn __swift_bridge__SomeType_set_count(
+                    this: *mut super::SomeType,
+                    count: u32
+                ) {
+                    (unsafe { &mut *this }).count = count
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public class SomeTypeRefMut: SomeTypeRef {
+    public override init(ptr: UnsafeMutableRawPointer) {
+        super.init(ptr: ptr)
+    }
+
+    public override var count: UInt32 {
+        get {
+            __swift_bridge__$SomeType$count(ptr)
+        }
+        set(count) {
+            __swift_bridge__$SomeType$set_count(ptr, count)
+        }
+    }
+
+    public override var name: RustString {
+        get {
+            RustString(ptr: __swift_bridge__$SomeType$name(ptr))
+        }
+        set(name) {
+            __swift_bridge__$SomeType$set_name(ptr, { let rustString = name.intoRustString(); rustString.isOwned = false; return rustString.ptr }())
+        }
+    }
+}
+"#,
+            r#"
+public class SomeTypeRef {
+    var ptr: UnsafeMutableRawPointer
+
+    public init(ptr: UnsafeMutableRawPointer) {
+        self.ptr = ptr
+    }
+
+    public var count: UInt32 {
+        get {
+            __swift_bridge__$SomeType$count(ptr)
+        }
+    }
+
+    public var name: RustString {
+        get {
+            RustString(ptr: __swift_bridge__$SomeType$name(ptr))
+        }
+    }
+}
+"#,
+            r#"
+extension SomeTypeRef {
+    public var id: UInt64 {
+        get {
+            __swift_bridge__$SomeType$id(ptr)
+        }
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            r#"uint32_t __swift_bridge__$SomeType$count(void* self);"#,
+            r#"void __swift_bridge__$SomeType$set_count(void* self, uint32_t count);"#,
+        ])
+    }
+
+    #[test]
+    fn swift_property() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
 /// Tests that the swift_name function attribute generates the correct code
 /// when using extern "Rust" (calling Rust code from Swift) and when using
 /// extern "Swift" (calling Swift code from Rust).
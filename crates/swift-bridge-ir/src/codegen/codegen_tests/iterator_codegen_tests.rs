@@ -0,0 +1,242 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate the correct code for an extern "Rust" function that returns a boxed
+/// iterator of primitives.
+mod extern_rust_fn_returns_boxed_iterator_of_u8 {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(arg: u32) -> Box<dyn Iterator<Item = u8>>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$some_function"]
+                pub extern "C" fn __swift_bridge__some_function(
+                    arg: u32
+                ) -> *mut swift_bridge::iterator_support::RustIterator<u8> {
+                    swift_bridge::iterator_support::RustIterator::new(super::some_function(arg)).box_into_raw()
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$some_function$next"]
+                pub extern "C" fn __swift_bridge__some_function__next(
+                    this: *mut swift_bridge::iterator_support::RustIterator<u8>
+                ) -> swift_bridge::option::OptionU8 {
+                    if let Some(val) = unsafe { (&mut *this).next() } {
+                        swift_bridge::option::OptionU8 { val, is_some: true }
+                    } else {
+                        swift_bridge::option::OptionU8 { val: 123, is_some: false }
+                    }
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$some_function$_free"]
+                pub extern "C" fn __swift_bridge__some_function__free(
+                    this: *mut swift_bridge::iterator_support::RustIterator<u8>
+                ) {
+                    let _ = unsafe { Box::from_raw(this) };
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func some_function(_ arg: UInt32) -> RustIterator$some_function {
+    RustIterator$some_function(ptr: __swift_bridge__$some_function(arg))
+}
+"#,
+            r#"
+public class RustIterator$some_function: Sequence, IteratorProtocol {
+    var ptr: UnsafeMutableRawPointer
+
+    init(ptr: UnsafeMutableRawPointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        __swift_bridge__$some_function$_free(ptr)
+    }
+
+    public func next() -> Optional<UInt8> {
+        __swift_bridge__$some_function$next(ptr).intoSwiftRepr()
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$some_function(uint32_t arg);
+struct __private__OptionU8 __swift_bridge__$some_function$next(void* this);
+void __swift_bridge__$some_function$_free(void* this);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_fn_returns_boxed_iterator_of_u8() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we convert each String that an `impl Iterator` yields.
+mod extern_rust_fn_returns_impl_iterator_of_string {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    fn some_function() -> impl Iterator<Item = String>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub extern "C" fn __swift_bridge__some_function() -> *mut swift_bridge::iterator_support::RustIterator<String> {
+                    swift_bridge::iterator_support::RustIterator::new(super::some_function()).box_into_raw()
+                }
+            },
+            quote! {
+                if let Some(val) = unsafe { (&mut *this).next() } {
+                    swift_bridge::string::RustString(val).box_into_raw()
+                } else {
+                    std::ptr::null::<swift_bridge::string::RustString>() as *mut swift_bridge::string::RustString
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func some_function() -> RustIterator$some_function {
+    RustIterator$some_function(ptr: __swift_bridge__$some_function())
+}
+"#,
+            r#"
+    public func next() -> Optional<RustString> {
+        { let val = __swift_bridge__$some_function$next(ptr); if val != nil { return RustString(ptr: val!) } else { return nil } }()
+    }
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$some_function$next(void* this);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_fn_returns_impl_iterator_of_string() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate the correct code for an extern "Rust" method that returns an iterator.
+mod extern_rust_method_returns_iterator {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+                    fn some_method(&self) -> Box<dyn Iterator<Item = u8>>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$some_method"]
+                pub extern "C" fn __swift_bridge__SomeType_some_method(
+                    this: *mut super::SomeType
+                ) -> *mut swift_bridge::iterator_support::RustIterator<u8> {
+                    swift_bridge::iterator_support::RustIterator::new((unsafe { &*this }).some_method()).box_into_raw()
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$some_method$next"]
+                pub extern "C" fn __swift_bridge__SomeType_some_method__next
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$some_method$_free"]
+                pub extern "C" fn __swift_bridge__SomeType_some_method__free
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+extension SomeTypeRef {
+    public func some_method() -> RustIterator$SomeType$some_method {
+        RustIterator$SomeType$some_method(ptr: __swift_bridge__$SomeType$some_method(ptr))
+    }
+    public class RustIterator$SomeType$some_method: Sequence, IteratorProtocol {
+"#,
+            r#"
+        public func next() -> Optional<UInt8> {
+            __swift_bridge__$SomeType$some_method$next(ptr).intoSwiftRepr()
+        }
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            "void* __swift_bridge__$SomeType$some_method(void* self);",
+            "struct __private__OptionU8 __swift_bridge__$SomeType$some_method$next(void* this);",
+            "void __swift_bridge__$SomeType$some_method$_free(void* this);",
+        ])
+    }
+
+    #[test]
+    fn extern_rust_method_returns_iterator() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
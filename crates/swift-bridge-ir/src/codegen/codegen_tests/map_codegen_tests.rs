@@ -0,0 +1,628 @@
+//! See also: crates/swift-integration-tests/src/map.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we can use a `HashMap<K, V>` as an argument and return type for an extern "Rust"
+/// function.
+mod extern_rust_fn_hash_map_arg_and_return {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(arg: HashMap<u32, String>) -> HashMap<u32, String>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: *mut std::collections::HashMap<u32, String>
+            ) -> *mut std::collections::HashMap<u32, String> {
+                Box::into_raw(Box::new(super::some_function(unsafe { *Box::from_raw(arg) })))
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: RustMap<UInt32, RustString>) -> RustMap<UInt32, RustString> {
+    RustMap<UInt32, RustString>(ptr: __swift_bridge__$some_function({ let val = arg; val.isOwned = false; return val.ptr }()), vtable: .hashMap)
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$some_function(void* arg);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_fn_hash_map_arg_and_return() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we emit the Rust functions, C declarations and Swift vtable that a `RustMap` uses
+/// to call into a `HashMap<K, V>` of primitives.
+mod hash_map_of_primitives_support {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function() -> HashMap<u8, u16>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$HashMap_U8_U16$new"]
+                pub extern "C" fn _new() -> *mut std::collections::HashMap<u8, u16> {
+                    Box::into_raw(Box::new(<std::collections::HashMap<u8, u16> >::new()))
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$HashMap_U8_U16$get"]
+                pub extern "C" fn _get(
+                    map: *const std::collections::HashMap<u8, u16>,
+                    key: u8
+                ) -> swift_bridge::option::OptionU16 {
+                    let val = unsafe { &*map }.get(&key);
+                    if let Some(val) = val.copied() {
+                        swift_bridge::option::OptionU16 { val, is_some: true }
+                    } else {
+                        swift_bridge::option::OptionU16 { val: 123, is_some: false }
+                    }
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$HashMap_U8_U16$insert"]
+                pub extern "C" fn _insert(
+                    map: *mut std::collections::HashMap<u8, u16>,
+                    key: u8,
+                    value: u16
+                ) {
+                    unsafe { &mut *map }.insert(key, value);
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$HashMap_U8_U16$remove"]
+                pub extern "C" fn _remove(
+                    map: *mut std::collections::HashMap<u8, u16>,
+                    key: u8
+                ) -> swift_bridge::option::OptionU16 {
+                    let val = unsafe { &mut *map }.remove(&key);
+                    if let Some(val) = val {
+                        swift_bridge::option::OptionU16 { val, is_some: true }
+                    } else {
+                        swift_bridge::option::OptionU16 { val: 123, is_some: false }
+                    }
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$HashMap_U8_U16$entries_key"]
+                pub extern "C" fn _entries_key(
+                    entries: *const Vec<(*const u8, *const u16)>,
+                    index: usize
+                ) -> u8 {
+                    let key = unsafe { &*(&*entries)[index].0 };
+                    *key
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension RustMapVtable where K == UInt8, V == UInt16 {
+    public static var hashMap: RustMapVtable<UInt8, UInt16> {
+        RustMapVtable(
+            new: { __swift_bridge__$some_function$HashMap_U8_U16$new() },
+            free: { map in __swift_bridge__$some_function$HashMap_U8_U16$drop(map) },
+            len: { map in __swift_bridge__$some_function$HashMap_U8_U16$len(map) },
+            containsKey: { map, key in __swift_bridge__$some_function$HashMap_U8_U16$contains_key(map, key) },
+            get: { map, key in __swift_bridge__$some_function$HashMap_U8_U16$get(map, key).intoSwiftRepr() },
+            insert: { map, key, value in __swift_bridge__$some_function$HashMap_U8_U16$insert(map, key, value) },
+            remove: { map, key in __swift_bridge__$some_function$HashMap_U8_U16$remove(map, key).intoSwiftRepr() },
+            entries: { map in __swift_bridge__$some_function$HashMap_U8_U16$entries(map) },
+            entriesKey: { entries, index in __swift_bridge__$some_function$HashMap_U8_U16$entries_key(entries, index) },
+            entriesValue: { entries, index in __swift_bridge__$some_function$HashMap_U8_U16$entries_value(entries, index) },
+            entriesFree: { entries in __swift_bridge__$some_function$HashMap_U8_U16$entries_drop(entries) }
+        )
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$some_function$HashMap_U8_U16$new(void);
+void __swift_bridge__$some_function$HashMap_U8_U16$drop(void* map);
+uintptr_t __swift_bridge__$some_function$HashMap_U8_U16$len(void* map);
+bool __swift_bridge__$some_function$HashMap_U8_U16$contains_key(void* map, uint8_t key);
+struct __private__OptionU16 __swift_bridge__$some_function$HashMap_U8_U16$get(void* map, uint8_t key);
+void __swift_bridge__$some_function$HashMap_U8_U16$insert(void* map, uint8_t key, uint16_t value);
+struct __private__OptionU16 __swift_bridge__$some_function$HashMap_U8_U16$remove(void* map, uint8_t key);
+void* __swift_bridge__$some_function$HashMap_U8_U16$entries(void* map);
+uint8_t __swift_bridge__$some_function$HashMap_U8_U16$entries_key(void* entries, uintptr_t index);
+uint16_t __swift_bridge__$some_function$HashMap_U8_U16$entries_value(void* entries, uintptr_t index);
+void __swift_bridge__$some_function$HashMap_U8_U16$entries_drop(void* entries);
+"#,
+        )
+    }
+
+    #[test]
+    fn hash_map_of_primitives_support() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we emit a `BTreeMap<K, V>` vtable that converts `String` keys and opaque Rust type
+/// values to and from their Swift representations.
+mod btree_map_of_string_and_opaque_rust_type_support {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    type MyRustType;
+                    fn some_function(arg: BTreeMap<String, MyRustType>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$BTreeMap_String_MyRustType$get"]
+                pub extern "C" fn _get(
+                    map: *const std::collections::BTreeMap<String, super::MyRustType>,
+                    key: *const swift_bridge::string::RustString
+                ) -> *const super::MyRustType {
+                    let val = unsafe { &*map }.get(unsafe { &(*key).0 });
+                    if let Some(val) = val {
+                        val as *const super::MyRustType
+                    } else {
+                        std::ptr::null()
+                    }
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$BTreeMap_String_MyRustType$insert"]
+                pub extern "C" fn _insert(
+                    map: *mut std::collections::BTreeMap<String, super::MyRustType>,
+                    key: *mut swift_bridge::string::RustString,
+                    value: *mut super::MyRustType
+                ) {
+                    unsafe { &mut *map }.insert(
+                        unsafe { Box::from_raw(key).0 },
+                        unsafe { *Box::from_raw(value) }
+                    );
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func some_function(_ arg: RustMap<RustString, MyRustType>) {
+"#,
+            r#"
+extension RustMapVtable where K == RustString, V == MyRustType {
+    public static var btreeMap: RustMapVtable<RustString, MyRustType> {
+"#,
+            r#"
+            get: { map, key in { let val = __swift_bridge__$some_function$BTreeMap_String_MyRustType$get(map, key.ptr); if val != nil { return MyRustTypeRef(ptr: val!) } else { return nil } }() },
+            insert: { map, key, value in __swift_bridge__$some_function$BTreeMap_String_MyRustType$insert(map, { let rustString = key.intoRustString(); rustString.isOwned = false; return rustString.ptr }(), {value.isOwned = false; return value.ptr;}()) },
+            remove: { map, key in { let val = __swift_bridge__$some_function$BTreeMap_String_MyRustType$remove(map, key.ptr); if val != nil { return MyRustType(ptr: val!) } else { return nil } }() },
+"#,
+            r#"
+            entriesKey: { entries, index in RustStringRef(ptr: __swift_bridge__$some_function$BTreeMap_String_MyRustType$entries_key(entries, index)) },
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            "void* __swift_bridge__$some_function$BTreeMap_String_MyRustType$get(void* map, void* key);",
+            "void __swift_bridge__$some_function$BTreeMap_String_MyRustType$insert(void* map, void* key, void* value);",
+        ])
+    }
+
+    #[test]
+    fn btree_map_of_string_and_opaque_rust_type_support() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we can use a `HashMap<K, V>` as an argument and return type for an extern "Swift"
+/// function.
+mod extern_swift_fn_hash_map_arg_and_return {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    fn some_function(arg: HashMap<u8, u16>) -> HashMap<u8, u16>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub fn some_function(
+                arg: std::collections::HashMap<u8, u16>
+            ) -> std::collections::HashMap<u8, u16> {
+                unsafe {
+                    *Box::from_raw(unsafe {
+                        __swift_bridge__some_function(Box::into_raw(Box::new(arg)))
+                    })
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function (_ arg: UnsafeMutableRawPointer) -> UnsafeMutableRawPointer {
+    { let val = some_function(arg: RustMap<UInt8, UInt16>(ptr: arg, vtable: .hashMap)); val.isOwned = false; return val.ptr }()
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$some_function$HashMap_U8_U16$new(void);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_swift_fn_hash_map_arg_and_return() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that two bridge modules that use the same map export the map's functions under
+/// different symbols, but declare the same Swift vtable so that it only gets written once.
+mod two_modules_use_same_map {
+    use super::*;
+    use crate::codegen::CodegenConfig;
+    use crate::test_utils::parse_ok;
+
+    fn first_bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn first_function() -> HashMap<u8, u16>;
+                }
+            }
+        }
+    }
+
+    fn second_bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn second_function(arg: HashMap<u8, u16>);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn two_modules_use_same_map() {
+        CodegenTest {
+            bridge_module: first_bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::Contains(quote! {
+                #[export_name = "__swift_bridge__$first_function$HashMap_U8_U16$new"]
+            }),
+            expected_swift_code: ExpectedSwiftCode::ContainsAfterTrim(
+                "new: { __swift_bridge__$first_function$HashMap_U8_U16$new() },",
+            ),
+            expected_c_header: ExpectedCHeader::ContainsAfterTrim(
+                "void* __swift_bridge__$first_function$HashMap_U8_U16$new(void);",
+            ),
+        }
+        .test();
+
+        CodegenTest {
+            bridge_module: second_bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::Contains(quote! {
+                #[export_name = "__swift_bridge__$second_function$HashMap_U8_U16$new"]
+            }),
+            expected_swift_code: ExpectedSwiftCode::ContainsAfterTrim(
+                "new: { __swift_bridge__$second_function$HashMap_U8_U16$new() },",
+            ),
+            expected_c_header: ExpectedCHeader::ContainsAfterTrim(
+                "void* __swift_bridge__$second_function$HashMap_U8_U16$new(void);",
+            ),
+        }
+        .test();
+
+        let config = CodegenConfig::no_features_enabled();
+        let first =
+            parse_ok(first_bridge_module_tokens()).generate_swift_collection_vtables(&config);
+        let second =
+            parse_ok(second_bridge_module_tokens()).generate_swift_collection_vtables(&config);
+
+        assert_eq!(
+            first.keys().collect::<Vec<_>>(),
+            vec!["RustMapVtable<UInt8, UInt16>.hashMap"]
+        );
+        assert_eq!(
+            first.keys().collect::<Vec<_>>(),
+            second.keys().collect::<Vec<_>>()
+        );
+    }
+}
+
+/// Verify that a transparent struct that derives `Clone` can be used as a map value, and that
+/// looking it up clones it.
+mod hash_map_of_transparent_struct_support {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                #[derive(Clone)]
+                struct SomeStruct {
+                    field: u8,
+                }
+
+                extern "Rust" {
+                    fn some_function() -> HashMap<u8, SomeStruct>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$HashMap_U8_SomeStruct$get"]
+                pub extern "C" fn _get(
+                    map: *const std::collections::HashMap<u8, SomeStruct>,
+                    key: u8
+                ) -> __swift_bridge__Option_SomeStruct {
+                    let val = unsafe { &*map }.get(&key);
+                    __swift_bridge__Option_SomeStruct::from_rust_repr(val.cloned())
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$HashMap_U8_SomeStruct$entries_value"]
+                pub extern "C" fn _entries_value(
+                    entries: *const Vec<(*const u8, *const SomeStruct)>,
+                    index: usize
+                ) -> __swift_bridge__SomeStruct {
+                    let val = unsafe { &*(&*entries)[index].1 };
+                    Clone::clone(val).into_ffi_repr()
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$Vec_SomeStruct$get"]
+                pub extern "C" fn _get(vec: *const Vec<SomeStruct>, index: usize) -> __swift_bridge__Option_SomeStruct {
+                    let vec = unsafe { &*vec };
+                    let val = vec.get(index).cloned();
+                    __swift_bridge__Option_SomeStruct::from_rust_repr(val)
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+extension SomeStruct: Vectorizable {
+"#,
+            r#"
+            get: { map, key in __swift_bridge__$some_function$HashMap_U8_SomeStruct$get(map, key).intoSwiftRepr() },
+"#,
+            r#"
+            entriesValue: { entries, index in __swift_bridge__$some_function$HashMap_U8_SomeStruct$entries_value(entries, index).intoSwiftRepr() },
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            "struct __swift_bridge__$Option$SomeStruct __swift_bridge__$some_function$HashMap_U8_SomeStruct$get(void* map, uint8_t key);",
+            "__swift_bridge__$Option$SomeStruct __swift_bridge__$Vec_SomeStruct$get(void* vec_ptr, uintptr_t index);",
+        ])
+    }
+
+    #[test]
+    fn hash_map_of_transparent_struct_support() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we emit the functions and vtables for maps that are nested in other types, such as
+/// an `Option<HashMap<K, V>>` or a `Result<BTreeMap<K, V>, E>`.
+mod nested_map_support {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function() -> Option<HashMap<u8, u16>>;
+                    fn another_function() -> Result<BTreeMap<u8, u16>, String>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$some_function$HashMap_U8_U16$new"]
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$some_function$BTreeMap_U8_U16$new"]
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+extension RustMapVtable where K == UInt8, V == UInt16 {
+    public static var hashMap: RustMapVtable<UInt8, UInt16> {
+        RustMapVtable(
+            new: { __swift_bridge__$some_function$HashMap_U8_U16$new() },
+"#,
+            r#"
+extension RustMapVtable where K == UInt8, V == UInt16 {
+    public static var btreeMap: RustMapVtable<UInt8, UInt16> {
+        RustMapVtable(
+            new: { __swift_bridge__$some_function$BTreeMap_U8_U16$new() },
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            "void* __swift_bridge__$some_function$HashMap_U8_U16$new(void);",
+            "void* __swift_bridge__$some_function$BTreeMap_U8_U16$new(void);",
+        ])
+    }
+
+    #[test]
+    fn nested_map_support() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we emit the functions and vtables for maps that are used in a shared struct's
+/// fields, and that a module without any functions scopes the map's symbols to its first shared
+/// type.
+mod shared_struct_with_map_field {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: HashMap<u8, u16>,
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub struct SomeStruct {
+                    pub field: std::collections::HashMap<u8, u16>
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeStruct$HashMap_U8_U16$new"]
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public struct SomeStruct {
+    public var field: RustMap<UInt8, UInt16>
+"#,
+            "new: { __swift_bridge__$SomeStruct$HashMap_U8_U16$new() },",
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            "void* __swift_bridge__$SomeStruct$HashMap_U8_U16$new(void);",
+        )
+    }
+
+    #[test]
+    fn shared_struct_with_map_field() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
@@ -208,6 +208,227 @@ bool __swift_bridge__$EquatableType$_partial_eq(void* lhs, void* rhs);
     }
 }
 
+/// Test code generation for an extern "Rust" type that implements Comparable.
+/// Comparable types are also Equatable, since Swift's Comparable requires Equatable.
+mod extern_rust_comparable_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Comparable)]
+                    type ComparableType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$ComparableType$_partial_eq"]
+                pub extern "C" fn __swift_bridge__ComparableType__partial_eq (
+                    lhs: *const super::ComparableType,
+                    rhs: *const super::ComparableType
+                ) -> bool {
+                    unsafe { &*lhs == &*rhs }
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$ComparableType$_partial_cmp"]
+                pub extern "C" fn __swift_bridge__ComparableType__partial_cmp (
+                    lhs: *const super::ComparableType,
+                    rhs: *const super::ComparableType
+                ) -> i8 {
+                    match unsafe { (&*lhs).partial_cmp(&*rhs) } {
+                        Some(std::cmp::Ordering::Less) => -1,
+                        Some(std::cmp::Ordering::Equal) => 0,
+                        Some(std::cmp::Ordering::Greater) => 1,
+                        None => 2,
+                    }
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+extension ComparableTypeRef: Equatable {
+    public static func == (lhs: ComparableTypeRef, rhs: ComparableTypeRef) -> Bool {
+        __swift_bridge__$ComparableType$_partial_eq(rhs.ptr, lhs.ptr)
+    }
+}
+"#,
+            r#"
+extension ComparableTypeRef: Comparable {
+    public static func < (lhs: ComparableTypeRef, rhs: ComparableTypeRef) -> Bool {
+        __swift_bridge__$ComparableType$_partial_cmp(lhs.ptr, rhs.ptr) == -1
+    }
+
+    public static func <= (lhs: ComparableTypeRef, rhs: ComparableTypeRef) -> Bool {
+        let ordering = __swift_bridge__$ComparableType$_partial_cmp(lhs.ptr, rhs.ptr)
+        return ordering == -1 || ordering == 0
+    }
+
+    public static func > (lhs: ComparableTypeRef, rhs: ComparableTypeRef) -> Bool {
+        __swift_bridge__$ComparableType$_partial_cmp(lhs.ptr, rhs.ptr) == 1
+    }
+
+    public static func >= (lhs: ComparableTypeRef, rhs: ComparableTypeRef) -> Bool {
+        let ordering = __swift_bridge__$ComparableType$_partial_cmp(lhs.ptr, rhs.ptr)
+        return ordering == 0 || ordering == 1
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            r#"
+bool __swift_bridge__$ComparableType$_partial_eq(void* lhs, void* rhs);
+int8_t __swift_bridge__$ComparableType$_partial_cmp(void* lhs, void* rhs);
+    "#,
+            r#"
+#include <stdint.h>
+"#,
+        ])
+    }
+
+    #[test]
+    fn extern_rust_comparable_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Test code generation for an extern "Rust" type that implements Display.
+mod extern_rust_display_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Display)]
+                    type DisplayType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+        #[export_name = "__swift_bridge__$DisplayType$_display"]
+        pub extern "C" fn __swift_bridge__DisplayType__display (
+            this: *const super::DisplayType,
+        ) -> *mut swift_bridge::string::RustString {
+            swift_bridge::string::RustString(
+                format!("{}", unsafe { &*this })
+            ).box_into_raw()
+        }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension DisplayTypeRef: CustomStringConvertible {
+    public var description: String {
+        RustString(ptr: __swift_bridge__$DisplayType$_display(self.ptr)).toString()
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$DisplayType$_display(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_display_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Test code generation for an extern "Rust" type that implements Debug.
+mod extern_rust_debug_type {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Debug)]
+                    type DebugType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+        #[export_name = "__swift_bridge__$DebugType$_debug"]
+        pub extern "C" fn __swift_bridge__DebugType__debug (
+            this: *const super::DebugType,
+        ) -> *mut swift_bridge::string::RustString {
+            swift_bridge::string::RustString(
+                format!("{:?}", unsafe { &*this })
+            ).box_into_raw()
+        }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension DebugTypeRef: CustomDebugStringConvertible {
+    public var debugDescription: String {
+        RustString(ptr: __swift_bridge__$DebugType$_debug(self.ptr)).toString()
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$DebugType$_debug(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_debug_type() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
 /// Test code generation for an extern "Rust" type that implements Copy.
 mod extern_rust_copy_type {
     use super::*;
@@ -0,0 +1,287 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate Swift operators for an opaque Rust type's operator trait impls.
+mod opaque_rust_type_operators {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Add, Neg)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_add"]
+                pub extern "C" fn __swift_bridge__SomeType__add(
+                    lhs: *const super::SomeType,
+                    rhs: *const super::SomeType
+                ) -> *mut super::SomeType {
+                    Box::into_raw(Box::new({
+                        let val: super::SomeType = std::ops::Add::add(
+                            Clone::clone(unsafe { &*lhs }),
+                            Clone::clone(unsafe { &*rhs })
+                        );
+                        val
+                    })) as *mut super::SomeType
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$_neg"]
+                pub extern "C" fn __swift_bridge__SomeType__neg(
+                    this: *const super::SomeType
+                ) -> *mut super::SomeType {
+                    Box::into_raw(Box::new({
+                        let val: super::SomeType = std::ops::Neg::neg(Clone::clone(unsafe { &*this }));
+                        val
+                    })) as *mut super::SomeType
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeTypeRef {
+    public static func + (lhs: SomeTypeRef, rhs: SomeTypeRef) -> SomeType {
+        SomeType(ptr: __swift_bridge__$SomeType$_add(lhs.ptr, rhs.ptr))
+    }
+
+    public static prefix func - (operand: SomeTypeRef) -> SomeType {
+        SomeType(ptr: __swift_bridge__$SomeType$_neg(operand.ptr))
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$SomeType$_add(void* lhs, void* rhs);
+void* __swift_bridge__$SomeType$_neg(void* self);
+"#,
+        )
+    }
+
+    #[test]
+    fn opaque_rust_type_operators() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate a Swift subscript for an opaque Rust type's `Index` impl.
+mod opaque_rust_type_index {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Index(usize, Output = f64))]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![quote! {
+            #[export_name = "__swift_bridge__$SomeType$_index"]
+            pub extern "C" fn __swift_bridge__SomeType__index(
+                this: *const super::SomeType,
+                index: usize
+            ) -> f64 {
+                Clone::clone(std::ops::Index::index(unsafe { &*this }, index))
+            }
+        }])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeTypeRef {
+    public subscript(index: UInt) -> Double {
+        __swift_bridge__$SomeType$_index(self.ptr, index)
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+double __swift_bridge__$SomeType$_index(void* self, uintptr_t index);
+"#,
+        )
+    }
+
+    #[test]
+    fn opaque_rust_type_index() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that the operands of a `Copy` opaque Rust type's operators are passed by value.
+mod opaque_copy_rust_type_operators {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[swift_bridge(Copy(8), Mul)]
+                    type SomeType;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![quote! {
+            #[export_name = "__swift_bridge__$SomeType$_mul"]
+            pub extern "C" fn __swift_bridge__SomeType__mul(
+                lhs: __swift_bridge__SomeType,
+                rhs: __swift_bridge__SomeType
+            ) -> __swift_bridge__SomeType {
+                __swift_bridge__SomeType::from_rust_repr(std::ops::Mul::mul(
+                    lhs.into_rust_repr(),
+                    rhs.into_rust_repr()
+                ))
+            }
+        }])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension SomeType {
+    public static func * (lhs: SomeType, rhs: SomeType) -> SomeType {
+        SomeType(bytes: __swift_bridge__$SomeType$_mul(lhs.intoFfiRepr(), rhs.intoFfiRepr()))
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+struct __swift_bridge__$SomeType __swift_bridge__$SomeType$_mul(struct __swift_bridge__$SomeType lhs, struct __swift_bridge__$SomeType rhs);
+"#,
+        )
+    }
+
+    #[test]
+    fn opaque_copy_rust_type_operators() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate Swift operators for a shared struct's operator trait impls.
+mod shared_struct_operators {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct", Add, Sub)]
+                struct Point {
+                    x: f64,
+                    y: f64,
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$Point$_add"]
+                pub extern "C" fn __swift_bridge__Point__add(
+                    lhs: __swift_bridge__Point,
+                    rhs: __swift_bridge__Point
+                ) -> __swift_bridge__Point {
+                    std::ops::Add::add(lhs.into_rust_repr(), rhs.into_rust_repr()).into_ffi_repr()
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$Point$_sub"]
+                pub extern "C" fn __swift_bridge__Point__sub(
+                    lhs: __swift_bridge__Point,
+                    rhs: __swift_bridge__Point
+                ) -> __swift_bridge__Point {
+                    std::ops::Sub::sub(lhs.into_rust_repr(), rhs.into_rust_repr()).into_ffi_repr()
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension Point {
+    public static func + (lhs: Point, rhs: Point) -> Point {
+        __swift_bridge__$Point$_add(lhs.intoFfiRepr(), rhs.intoFfiRepr()).intoSwiftRepr()
+    }
+
+    public static func - (lhs: Point, rhs: Point) -> Point {
+        __swift_bridge__$Point$_sub(lhs.intoFfiRepr(), rhs.intoFfiRepr()).intoSwiftRepr()
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+struct __swift_bridge__$Point __swift_bridge__$Point$_add(struct __swift_bridge__$Point lhs, struct __swift_bridge__$Point rhs);
+struct __swift_bridge__$Point __swift_bridge__$Point$_sub(struct __swift_bridge__$Point lhs, struct __swift_bridge__$Point rhs);
+"#,
+        )
+    }
+
+    #[test]
+    fn shared_struct_operators() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
@@ -687,3 +687,191 @@ typedef struct __swift_bridge__$ResultTupleI32U32AndSomeErrEnum{__swift_bridge__
         .test();
     }
 }
+
+/// Test code generation for a Swift function that throws and returns a Result<T, SwiftError>
+/// where T is a primitive, so the Result uses a custom FFI type.
+mod extern_swift_fn_return_result_primitive_and_swift_error {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    fn some_function() -> Result<u32, SwiftError>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[repr(C)]
+                pub enum ResultU32AndSwiftError {
+                    Ok(u32),
+                    Err(*mut swift_bridge::string::RustString),
+                }
+            },
+            quote! {
+                pub fn some_function() -> Result<u32, swift_bridge::result::SwiftError> {
+                    let val = unsafe { __swift_bridge__some_function() };
+                    match val {
+                        ResultU32AndSwiftError::Ok(ok) => std::result::Result::Ok(ok),
+                        ResultU32AndSwiftError::Err(err) => std::result::Result::Err(
+                            swift_bridge::result::SwiftError::new(unsafe { Box::from_raw(err).0 })
+                        ),
+                    }
+                }
+            },
+            quote! {
+                #[link_name = "__swift_bridge__$some_function"]
+                fn __swift_bridge__some_function() -> ResultU32AndSwiftError;
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function () -> __swift_bridge__$ResultU32AndSwiftError {
+    { do { let ok = try some_function(); return __swift_bridge__$ResultU32AndSwiftError(tag: __swift_bridge__$ResultU32AndSwiftError$ResultOk, payload: __swift_bridge__$ResultU32AndSwiftError$Fields(ok: ok)) } catch let err { return __swift_bridge__$ResultU32AndSwiftError(tag: __swift_bridge__$ResultU32AndSwiftError$ResultErr, payload: __swift_bridge__$ResultU32AndSwiftError$Fields(err: { let rustString = err.localizedDescription.intoRustString(); rustString.isOwned = false; return rustString.ptr }())) } }()
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+typedef enum __swift_bridge__$ResultU32AndSwiftError$Tag {__swift_bridge__$ResultU32AndSwiftError$ResultOk, __swift_bridge__$ResultU32AndSwiftError$ResultErr} __swift_bridge__$ResultU32AndSwiftError$Tag;
+union __swift_bridge__$ResultU32AndSwiftError$Fields {uint32_t ok; void* err;};
+typedef struct __swift_bridge__$ResultU32AndSwiftError{__swift_bridge__$ResultU32AndSwiftError$Tag tag; union __swift_bridge__$ResultU32AndSwiftError$Fields payload;} __swift_bridge__$ResultU32AndSwiftError;
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_swift_fn_return_result_primitive_and_swift_error() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Test code generation for a Swift function that throws and returns a Result<T, SwiftError>
+/// where T is an opaque Swift type.
+mod extern_swift_fn_return_result_opaque_swift_and_swift_error {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    type SomeType;
+
+                    fn some_function() -> Result<SomeType, SwiftError>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub fn some_function() -> Result<SomeType, swift_bridge::result::SwiftError> {
+                let val = unsafe { __swift_bridge__some_function() };
+                if val.is_ok {
+                    std::result::Result::Ok(unsafe { SomeType(val.ok_or_err) })
+                } else {
+                    std::result::Result::Err(swift_bridge::result::SwiftError::new(unsafe {
+                        Box::from_raw(val.ok_or_err as *mut swift_bridge::string::RustString).0
+                    }))
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function () -> __private__ResultPtrAndPtr {
+    { do { let ok = try some_function(); return __private__ResultPtrAndPtr(is_ok: true, ok_or_err: Unmanaged.passRetained(ok).toOpaque()) } catch let err { return __private__ResultPtrAndPtr(is_ok: false, ok_or_err: { let rustString = err.localizedDescription.intoRustString(); rustString.isOwned = false; return rustString.ptr }()) } }()
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_swift_fn_return_result_opaque_swift_and_swift_error() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
+
+/// Test code generation for a Swift method that throws and returns a Result<(), SwiftError>.
+mod extern_swift_method_return_result_null_and_swift_error {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    type SomeType;
+
+                    fn some_method(&self) -> Result<(), SwiftError>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub fn some_method(&self) -> Result<(), swift_bridge::result::SwiftError> {
+                    let val = unsafe { __swift_bridge__SomeType_some_method(swift_bridge::PointerToSwiftType(self.0)) };
+                    if val.is_null() {
+                        std::result::Result::Ok(())
+                    } else {
+                        std::result::Result::Err(swift_bridge::result::SwiftError::new(unsafe { Box::from_raw(val).0 }))
+                    }
+                }
+            },
+            quote! {
+                #[link_name = "__swift_bridge__$SomeType$some_method"]
+                fn __swift_bridge__SomeType_some_method(this: swift_bridge::PointerToSwiftType) -> *mut swift_bridge::string::RustString;
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$SomeType$some_method")
+func __swift_bridge__SomeType_some_method (_ this: UnsafeMutableRawPointer) -> UnsafeMutableRawPointer? {
+    { do { let _ = try Unmanaged<SomeType>.fromOpaque(this).takeUnretainedValue().some_method(); return nil } catch let err { return { let rustString = err.localizedDescription.intoRustString(); rustString.isOwned = false; return rustString.ptr }() } }()
+}
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_swift_method_return_result_null_and_swift_error() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: ExpectedCHeader::SkipTest,
+        }
+        .test();
+    }
+}
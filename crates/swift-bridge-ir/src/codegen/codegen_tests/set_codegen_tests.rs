@@ -0,0 +1,490 @@
+//! See also: crates/swift-integration-tests/src/set.rs
+
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we can use a `HashSet<T>` as an argument and return type for an extern "Rust"
+/// function.
+mod extern_rust_fn_hash_set_arg_and_return {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(arg: HashSet<u32>) -> HashSet<u32>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            #[export_name = "__swift_bridge__$some_function"]
+            pub extern "C" fn __swift_bridge__some_function(
+                arg: *mut std::collections::HashSet<u32>
+            ) -> *mut std::collections::HashSet<u32> {
+                Box::into_raw(Box::new(super::some_function(unsafe { *Box::from_raw(arg) })))
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+public func some_function(_ arg: RustSet<UInt32>) -> RustSet<UInt32> {
+    RustSet<UInt32>(ptr: __swift_bridge__$some_function({ let val = arg; val.isOwned = false; return val.ptr }()), vtable: .hashSet)
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$some_function(void* arg);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_fn_hash_set_arg_and_return() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we emit the Rust functions, C declarations and Swift vtable that a `RustSet` uses
+/// to call into a `HashSet<T>` of primitives.
+mod hash_set_of_primitives_support {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function() -> HashSet<u8>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$HashSet_U8$new"]
+                pub extern "C" fn _new() -> *mut std::collections::HashSet<u8> {
+                    Box::into_raw(Box::new(<std::collections::HashSet<u8> >::new()))
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$HashSet_U8$contains"]
+                pub extern "C" fn _contains(set: *const std::collections::HashSet<u8>, value: u8) -> bool {
+                    unsafe { &*set }.contains(&value)
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$HashSet_U8$insert"]
+                pub extern "C" fn _insert(set: *mut std::collections::HashSet<u8>, value: u8) -> bool {
+                    unsafe { &mut *set }.insert(value)
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$HashSet_U8$items_get"]
+                pub extern "C" fn _items_get(items: *const Vec<*const u8>, index: usize) -> u8 {
+                    let item = unsafe { &*(&*items)[index] };
+                    *item
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+extension RustSetVtable where T == UInt8 {
+    public static var hashSet: RustSetVtable<UInt8> {
+        RustSetVtable(
+            new: { __swift_bridge__$some_function$HashSet_U8$new() },
+            free: { set in __swift_bridge__$some_function$HashSet_U8$drop(set) },
+            len: { set in __swift_bridge__$some_function$HashSet_U8$len(set) },
+            contains: { set, value in __swift_bridge__$some_function$HashSet_U8$contains(set, value) },
+            insert: { set, value in __swift_bridge__$some_function$HashSet_U8$insert(set, value) },
+            remove: { set, value in __swift_bridge__$some_function$HashSet_U8$remove(set, value) },
+            items: { set in __swift_bridge__$some_function$HashSet_U8$items(set) },
+            itemsGet: { items, index in __swift_bridge__$some_function$HashSet_U8$items_get(items, index) },
+            itemsFree: { items in __swift_bridge__$some_function$HashSet_U8$items_drop(items) }
+        )
+    }
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$some_function$HashSet_U8$new(void);
+void __swift_bridge__$some_function$HashSet_U8$drop(void* set);
+uintptr_t __swift_bridge__$some_function$HashSet_U8$len(void* set);
+bool __swift_bridge__$some_function$HashSet_U8$contains(void* set, uint8_t value);
+bool __swift_bridge__$some_function$HashSet_U8$insert(void* set, uint8_t value);
+bool __swift_bridge__$some_function$HashSet_U8$remove(void* set, uint8_t value);
+void* __swift_bridge__$some_function$HashSet_U8$items(void* set);
+uint8_t __swift_bridge__$some_function$HashSet_U8$items_get(void* items, uintptr_t index);
+void __swift_bridge__$some_function$HashSet_U8$items_drop(void* items);
+"#,
+        )
+    }
+
+    #[test]
+    fn hash_set_of_primitives_support() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we emit a `BTreeSet<String>` vtable that converts `String`s to and from their
+/// Swift representations.
+mod btree_set_of_strings_support {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(arg: BTreeSet<String>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$BTreeSet_String$contains"]
+                pub extern "C" fn _contains(
+                    set: *const std::collections::BTreeSet<String>,
+                    value: *const swift_bridge::string::RustString
+                ) -> bool {
+                    unsafe { &*set }.contains(unsafe { &(*value).0 })
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                #[export_name = "__swift_bridge__$some_function$BTreeSet_String$insert"]
+                pub extern "C" fn _insert(
+                    set: *mut std::collections::BTreeSet<String>,
+                    value: *mut swift_bridge::string::RustString
+                ) -> bool {
+                    unsafe { &mut *set }.insert(unsafe { Box::from_raw(value).0 })
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func some_function(_ arg: RustSet<RustString>) {
+"#,
+            r#"
+extension RustSetVtable where T == RustString {
+    public static var btreeSet: RustSetVtable<RustString> {
+"#,
+            r#"
+            contains: { set, value in __swift_bridge__$some_function$BTreeSet_String$contains(set, value.ptr) },
+            insert: { set, value in __swift_bridge__$some_function$BTreeSet_String$insert(set, { let rustString = value.intoRustString(); rustString.isOwned = false; return rustString.ptr }()) },
+"#,
+            r#"
+            itemsGet: { items, index in RustStringRef(ptr: __swift_bridge__$some_function$BTreeSet_String$items_get(items, index)) },
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            "bool __swift_bridge__$some_function$BTreeSet_String$contains(void* set, void* value);",
+            "void* __swift_bridge__$some_function$BTreeSet_String$items_get(void* items, uintptr_t index);",
+        ])
+    }
+
+    #[test]
+    fn btree_set_of_strings_support() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we can use a `HashSet<T>` as an argument for an extern "Swift" function.
+mod extern_swift_fn_hash_set_arg {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Swift" {
+                    fn some_function(arg: HashSet<u8>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            pub fn some_function(arg: std::collections::HashSet<u8>) {
+                unsafe { __swift_bridge__some_function(Box::into_raw(Box::new(arg))) }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsAfterTrim(
+            r#"
+@_cdecl("__swift_bridge__$some_function")
+func __swift_bridge__some_function (_ arg: UnsafeMutableRawPointer) {
+    some_function(arg: RustSet<UInt8>(ptr: arg, vtable: .hashSet))
+}
+"#,
+        )
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$some_function$HashSet_U8$new(void);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_swift_fn_hash_set_arg() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that two bridge modules that use the same set export the set's functions under
+/// different symbols, but declare the same Swift vtable so that it only gets written once.
+mod two_modules_use_same_set {
+    use super::*;
+    use crate::codegen::CodegenConfig;
+    use crate::test_utils::parse_ok;
+
+    fn first_bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn first_function() -> HashSet<u8>;
+                }
+            }
+        }
+    }
+
+    fn second_bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn second_function(arg: HashSet<u8>);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn two_modules_use_same_set() {
+        CodegenTest {
+            bridge_module: first_bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::Contains(quote! {
+                #[export_name = "__swift_bridge__$first_function$HashSet_U8$new"]
+            }),
+            expected_swift_code: ExpectedSwiftCode::ContainsAfterTrim(
+                "new: { __swift_bridge__$first_function$HashSet_U8$new() },",
+            ),
+            expected_c_header: ExpectedCHeader::ContainsAfterTrim(
+                "void* __swift_bridge__$first_function$HashSet_U8$new(void);",
+            ),
+        }
+        .test();
+
+        CodegenTest {
+            bridge_module: second_bridge_module_tokens().into(),
+            expected_rust_tokens: ExpectedRustTokens::Contains(quote! {
+                #[export_name = "__swift_bridge__$second_function$HashSet_U8$new"]
+            }),
+            expected_swift_code: ExpectedSwiftCode::ContainsAfterTrim(
+                "new: { __swift_bridge__$second_function$HashSet_U8$new() },",
+            ),
+            expected_c_header: ExpectedCHeader::ContainsAfterTrim(
+                "void* __swift_bridge__$second_function$HashSet_U8$new(void);",
+            ),
+        }
+        .test();
+
+        let config = CodegenConfig::no_features_enabled();
+        let first =
+            parse_ok(first_bridge_module_tokens()).generate_swift_collection_vtables(&config);
+        let second =
+            parse_ok(second_bridge_module_tokens()).generate_swift_collection_vtables(&config);
+
+        assert_eq!(
+            first.keys().collect::<Vec<_>>(),
+            vec!["RustSetVtable<UInt8>.hashSet"]
+        );
+        assert_eq!(
+            first.keys().collect::<Vec<_>>(),
+            second.keys().collect::<Vec<_>>()
+        );
+    }
+}
+
+/// Verify that we emit the functions and vtables for sets that are nested in other types, such as
+/// a `Result<HashSet<T>, E>` or an `Option<BTreeSet<T>>`.
+mod nested_set_support {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                extern "Rust" {
+                    fn some_function() -> Result<HashSet<u32>, String>;
+                    fn another_function(arg: Option<BTreeSet<u32>>);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$some_function$HashSet_U32$new"]
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$some_function$BTreeSet_U32$new"]
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+extension RustSetVtable where T == UInt32 {
+    public static var hashSet: RustSetVtable<UInt32> {
+        RustSetVtable(
+            new: { __swift_bridge__$some_function$HashSet_U32$new() },
+"#,
+            r#"
+extension RustSetVtable where T == UInt32 {
+    public static var btreeSet: RustSetVtable<UInt32> {
+        RustSetVtable(
+            new: { __swift_bridge__$some_function$BTreeSet_U32$new() },
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            "void* __swift_bridge__$some_function$HashSet_U32$new(void);",
+            "void* __swift_bridge__$some_function$BTreeSet_U32$new(void);",
+        ])
+    }
+
+    #[test]
+    fn nested_set_support() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we emit the functions and vtables for sets that are used in a shared struct's
+/// fields and in a shared enum's variants.
+mod shared_types_with_set_fields {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: Option<HashSet<u32>>,
+                }
+
+                enum SomeEnum {
+                    Variant(BTreeSet<u32>),
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub struct SomeStruct {
+                    pub field: Option<std::collections::HashSet<u32> >
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeStruct$HashSet_U32$new"]
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeStruct$BTreeSet_U32$new"]
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            "new: { __swift_bridge__$SomeStruct$HashSet_U32$new() },",
+            "new: { __swift_bridge__$SomeStruct$BTreeSet_U32$new() },",
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            "void* __swift_bridge__$SomeStruct$HashSet_U32$new(void);",
+            "void* __swift_bridge__$SomeStruct$BTreeSet_U32$new(void);",
+        ])
+    }
+
+    #[test]
+    fn shared_types_with_set_fields() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
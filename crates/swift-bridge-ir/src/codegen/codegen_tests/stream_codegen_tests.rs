@@ -0,0 +1,290 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate the correct code for an extern "Rust" function that returns a stream of
+/// primitives.
+mod extern_rust_fn_returns_stream_of_u8 {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    fn some_function(arg: u32) -> impl Stream<Item = u8>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$some_function"]
+                pub extern "C" fn __swift_bridge__some_function(
+                    arg: u32
+                ) -> *mut swift_bridge::async_support::RustStream<u8> {
+                    swift_bridge::async_support::RustStream::new(super::some_function(arg)).box_into_raw()
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$some_function$next"]
+                pub extern "C" fn __swift_bridge__some_function__next(
+                    this: *mut swift_bridge::async_support::RustStream<u8>,
+                    task_handle: *const std::ffi::c_void,
+                    callback_wrapper: *mut std::ffi::c_void,
+                    callback: extern "C" fn(*mut std::ffi::c_void, swift_bridge::option::OptionU8) -> (),
+                    on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
+                ) {
+                    let fut = unsafe { swift_bridge::async_support::RustStream::next(this) };
+                    let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+                    let task = async move {
+                        let val = if let Some(val) = fut.await {
+                            swift_bridge::option::OptionU8 { val, is_some: true }
+                        } else {
+                            swift_bridge::option::OptionU8 { val: 123, is_some: false }
+                        };
+
+                        let callback_wrapper = task_callback_wrapper;
+                        let callback_wrapper = callback_wrapper.0;
+
+                        (callback)(callback_wrapper, val)
+                    };
+                    let task = unsafe {
+                        swift_bridge::async_support::CancellableTask::new(
+                            task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                            Box::pin(task),
+                            callback_wrapper,
+                            on_cancel
+                        )
+                    };
+                    swift_bridge::async_support::spawn_task(Box::pin(task))
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$some_function$_free"]
+                pub extern "C" fn __swift_bridge__some_function__free(
+                    this: *mut swift_bridge::async_support::RustStream<u8>
+                ) {
+                    let _ = unsafe { Box::from_raw(this) };
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func some_function(_ arg: UInt32) -> AsyncThrowingStream<UInt8, Error> {
+    AsyncThrowingStream(unfolding: RustStream$some_function(ptr: __swift_bridge__$some_function(arg)).next)
+}
+"#,
+            r#"
+class RustStream$some_function {
+    var ptr: UnsafeMutableRawPointer
+
+    init(ptr: UnsafeMutableRawPointer) {
+        self.ptr = ptr
+    }
+
+    deinit {
+        __swift_bridge__$some_function$_free(ptr)
+    }
+
+    func next() async throws -> Optional<UInt8> {
+        func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?, rustFnRetVal: __private__OptionU8) {
+            let wrapper = Unmanaged<CbWrapper$some_function$next>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+            wrapper.cb(.success(rustFnRetVal.intoSwiftRepr()))
+        }
+        func onCancel(cbWrapperPtr: UnsafeMutableRawPointer?) {
+            let wrapper = Unmanaged<CbWrapper$some_function$next>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+            wrapper.cb(.failure(CancellationError()))
+        }
+
+        let taskHandle = __swift_bridge__$AsyncTaskHandle$new()
+        defer { __swift_bridge__$AsyncTaskHandle$_free(taskHandle) }
+
+        return try await withTaskCancellationHandler(operation: {
+            try await withCheckedThrowingContinuation({ (continuation: CheckedContinuation<Optional<UInt8>, Error>) in
+                let callback = { rustFnRetVal in
+                    continuation.resume(with: rustFnRetVal)
+                }
+
+                let wrapper = CbWrapper$some_function$next(cb: callback)
+                let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+
+                __swift_bridge__$some_function$next(ptr, taskHandle, wrapperPtr, onComplete, onCancel)
+            })
+        }, onCancel: {
+            __swift_bridge__$AsyncTaskHandle$cancel(taskHandle)
+        })
+    }
+    class CbWrapper$some_function$next {
+        var cb: (Result<Optional<UInt8>, Error>) -> ()
+    
+        public init(cb: @escaping (Result<Optional<UInt8>, Error>) -> ()) {
+            self.cb = cb
+        }
+    }
+}
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void* __swift_bridge__$some_function(uint32_t arg);
+void __swift_bridge__$some_function$next(void* this, void* task_handle, void* callback_wrapper, void __swift_bridge__$some_function$next$async(void* callback_wrapper, struct __private__OptionU8 ret), void __swift_bridge__$some_function$next$cancel(void* callback_wrapper));
+void __swift_bridge__$some_function$_free(void* this);
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_fn_returns_stream_of_u8() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we convert each String that a stream yields.
+mod extern_rust_fn_returns_stream_of_string {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    fn some_function() -> impl Stream<Item = String>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub extern "C" fn __swift_bridge__some_function() -> *mut swift_bridge::async_support::RustStream<String> {
+                    swift_bridge::async_support::RustStream::new(super::some_function()).box_into_raw()
+                }
+            },
+            quote! {
+                let val = if let Some(val) = fut.await {
+                    swift_bridge::string::RustString(val).box_into_raw()
+                } else {
+                    std::ptr::null::<swift_bridge::string::RustString>() as *mut swift_bridge::string::RustString
+                };
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func some_function() -> AsyncThrowingStream<RustString, Error> {
+    AsyncThrowingStream(unfolding: RustStream$some_function(ptr: __swift_bridge__$some_function()).next)
+}
+"#,
+            r#"
+    func next() async throws -> Optional<RustString> {
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsAfterTrim(
+            r#"
+void __swift_bridge__$some_function$next(void* this, void* task_handle, void* callback_wrapper, void __swift_bridge__$some_function$next$async(void* callback_wrapper, void* ret), void __swift_bridge__$some_function$next$cancel(void* callback_wrapper));
+"#,
+        )
+    }
+
+    #[test]
+    fn extern_rust_fn_returns_stream_of_string() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
+
+/// Verify that we generate the correct code for an extern "Rust" method that returns a stream.
+mod extern_rust_method_returns_stream {
+    use super::*;
+
+    fn bridge_module() -> TokenStream {
+        quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type SomeType;
+                    fn some_method(&self) -> impl Stream<Item = u8>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$some_method"]
+                pub extern "C" fn __swift_bridge__SomeType_some_method(
+                    this: *mut super::SomeType
+                ) -> *mut swift_bridge::async_support::RustStream<u8> {
+                    swift_bridge::async_support::RustStream::new((unsafe { &*this }).some_method()).box_into_raw()
+                }
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$some_method$next"]
+                pub extern "C" fn __swift_bridge__SomeType_some_method__next
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeType$some_method$_free"]
+                pub extern "C" fn __swift_bridge__SomeType_some_method__free
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+    public func some_method() -> AsyncThrowingStream<UInt8, Error> {
+        AsyncThrowingStream(unfolding: RustStream$SomeType$some_method(ptr: __swift_bridge__$SomeType$some_method(ptr)).next)
+    }
+    class RustStream$SomeType$some_method {
+"#,
+            r#"
+                __swift_bridge__$SomeType$some_method$next(ptr, taskHandle, wrapperPtr, onComplete, onCancel)
+"#,
+        ])
+    }
+
+    fn expected_c_header() -> ExpectedCHeader {
+        ExpectedCHeader::ContainsManyAfterTrim(vec![
+            "void* __swift_bridge__$SomeType$some_method(void* self);",
+            "void __swift_bridge__$SomeType$some_method$_free(void* this);",
+        ])
+    }
+
+    #[test]
+    fn extern_rust_method_returns_stream() {
+        CodegenTest {
+            bridge_module: bridge_module().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: expected_c_header(),
+        }
+        .test();
+    }
+}
@@ -0,0 +1,126 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate a Swift protocol for an `extern "Swift"` type with a
+/// `#[swift_bridge(protocol)]` attribute, along with an implementation of the Rust trait of the
+/// same name.
+mod extern_swift_protocol {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Swift" {
+                    #[swift_bridge(protocol)]
+                    type Storage;
+
+                    fn read(&self, key: &str) -> String;
+                    fn write(&mut self, key: &str, value: u8);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            impl super::Storage for Storage {
+                fn read(&self, key: &str) -> String {
+                    Storage::read(self, key)
+                }
+
+                fn write(&mut self, key: &str, value: u8) {
+                    Storage::write(self, key, value)
+                }
+            }
+        })
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public protocol Storage: AnyObject {
+    func read(key: RustStr) -> RustString
+    func write(key: RustStr, value: UInt8)
+}
+"#,
+            r#"
+@_cdecl("__swift_bridge__$Storage$write")
+func __swift_bridge__Storage_write (_ this: UnsafeMutableRawPointer, _ key: RustStr, _ value: UInt8) {
+    (Unmanaged<AnyObject>.fromOpaque(this).takeUnretainedValue() as! Storage).write(key: key, value: value)
+}
+"#,
+            r#"
+@_cdecl("__swift_bridge__$Storage$_free")
+func __swift_bridge__Storage__free (ptr: UnsafeMutableRawPointer) {
+    let _ = Unmanaged<AnyObject>.fromOpaque(ptr).takeRetainedValue()
+}
+"#,
+        ])
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ExactAfterTrim(r#""#);
+
+    #[test]
+    fn extern_swift_protocol() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we pass Swift protocol instances to Rust as `AnyObject`s, since `Unmanaged`
+/// can only be used with classes.
+mod extern_rust_fn_takes_and_returns_swift_protocol {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    fn some_function(arg: Storage) -> Storage;
+                }
+
+                extern "Swift" {
+                    #[swift_bridge(protocol)]
+                    type Storage;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::Contains(quote! {
+            impl super::Storage for Storage {}
+        })
+    }
+
+    const EXPECTED_SWIFT_CODE: ExpectedSwiftCode = ExpectedSwiftCode::ContainsAfterTrim(
+        r#"
+public func some_function(_ arg: Storage) -> Storage {
+    (Unmanaged<AnyObject>.fromOpaque(__swift_bridge__$some_function(Unmanaged.passRetained(arg as AnyObject).toOpaque())).takeRetainedValue() as! Storage)
+}
+"#,
+    );
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+void* __swift_bridge__$some_function(void* arg);
+"#,
+    );
+
+    #[test]
+    fn extern_rust_fn_takes_and_returns_swift_protocol() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: EXPECTED_SWIFT_CODE,
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
@@ -0,0 +1,220 @@
+use super::{CodegenTest, ExpectedCHeader, ExpectedRustTokens, ExpectedSwiftCode};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Verify that we generate a `TraitObject` alias for a `#[swift_bridge(trait_object)]` type, and
+/// that its methods are called through that alias.
+mod trait_object_methods {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(trait_object)]
+                    type SomeTrait;
+
+                    fn some_method(&self, arg: u8) -> u8;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                #[export_name = "__swift_bridge__$SomeTrait$_free"]
+                pub extern "C" fn __swift_bridge__SomeTrait__free (this: *mut SomeTrait) {
+                    let this = unsafe { Box::from_raw(this) };
+                    drop(this);
+                }
+            },
+            quote! {
+                #[doc(hidden)]
+                pub type SomeTrait = swift_bridge::trait_object_support::TraitObject<dyn super::SomeTrait>;
+            },
+            quote! {
+                #[export_name = "__swift_bridge__$SomeTrait$some_method"]
+                pub extern "C" fn __swift_bridge__SomeTrait_some_method(this: *mut SomeTrait, arg: u8) -> u8 {
+                    (unsafe { &*this }).some_method(arg)
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public class SomeTrait: SomeTraitRefMut {
+"#,
+            r#"
+extension SomeTraitRef {
+    public func some_method(_ arg: UInt8) -> UInt8 {
+        __swift_bridge__$SomeTrait$some_method(ptr, arg)
+    }
+}
+"#,
+        ])
+    }
+
+    /// We don't support `Vec<Box<dyn Trait>>` yet, so we don't generate the `Vec` functions.
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::DoesNotContainAfterTrim(
+        r#"
+__swift_bridge__$Vec_SomeTrait$new
+"#,
+    );
+
+    #[test]
+    fn trait_object_methods() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we can return `Box<dyn Trait>` and `Arc<dyn Trait>` from Rust.
+mod return_boxed_and_arc_trait_object {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(trait_object)]
+                    type SomeTrait;
+
+                    fn make_boxed() -> Box<dyn SomeTrait>;
+                    fn make_arc() -> Arc<dyn SomeTrait>;
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub extern "C" fn __swift_bridge__make_boxed() -> *mut SomeTrait {
+                    Box::into_raw(Box::new({
+                        let val: SomeTrait = SomeTrait::from(super::make_boxed());
+                        val
+                    })) as *mut SomeTrait
+                }
+            },
+            quote! {
+                pub extern "C" fn __swift_bridge__make_arc() -> *mut SomeTrait {
+                    Box::into_raw(Box::new({
+                        let val: SomeTrait = SomeTrait::from(super::make_arc());
+                        val
+                    })) as *mut SomeTrait
+                }
+            },
+        ])
+    }
+
+    const EXPECTED_SWIFT_CODE: ExpectedSwiftCode = ExpectedSwiftCode::ContainsAfterTrim(
+        r#"
+public func make_boxed() -> SomeTrait {
+    SomeTrait(ptr: __swift_bridge__$make_boxed())
+}
+"#,
+    );
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+void* __swift_bridge__$make_arc(void);
+"#,
+    );
+
+    #[test]
+    fn return_boxed_and_arc_trait_object() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: EXPECTED_SWIFT_CODE,
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
+
+/// Verify that we can pass owned and referenced trait objects to Rust.
+mod trait_object_args {
+    use super::*;
+
+    fn bridge_module_tokens() -> TokenStream {
+        quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(trait_object)]
+                    type SomeTrait;
+
+                    fn take_boxed(arg: Box<dyn SomeTrait>);
+                    fn take_arc(arg: std::sync::Arc<dyn SomeTrait>);
+                    fn take_ref(arg: &dyn SomeTrait);
+                    fn take_ref_mut(arg: &mut dyn SomeTrait);
+                }
+            }
+        }
+    }
+
+    fn expected_rust_tokens() -> ExpectedRustTokens {
+        ExpectedRustTokens::ContainsMany(vec![
+            quote! {
+                pub extern "C" fn __swift_bridge__take_boxed(arg: *mut SomeTrait) {
+                    super::take_boxed((unsafe { *Box::from_raw(arg) }).into_box())
+                }
+            },
+            quote! {
+                pub extern "C" fn __swift_bridge__take_arc(arg: *mut SomeTrait) {
+                    super::take_arc((unsafe { *Box::from_raw(arg) }).into_arc())
+                }
+            },
+            quote! {
+                pub extern "C" fn __swift_bridge__take_ref(arg: *const SomeTrait) {
+                    super::take_ref(unsafe { &**arg })
+                }
+            },
+            quote! {
+                pub extern "C" fn __swift_bridge__take_ref_mut(arg: *mut SomeTrait) {
+                    super::take_ref_mut(unsafe { &mut **arg })
+                }
+            },
+        ])
+    }
+
+    fn expected_swift_code() -> ExpectedSwiftCode {
+        ExpectedSwiftCode::ContainsManyAfterTrim(vec![
+            r#"
+public func take_boxed(_ arg: SomeTrait) {
+    __swift_bridge__$take_boxed({arg.isOwned = false; return arg.ptr;}())
+}
+"#,
+            r#"
+public func take_ref(_ arg: SomeTraitRef) {
+    __swift_bridge__$take_ref(arg.ptr)
+}
+"#,
+        ])
+    }
+
+    const EXPECTED_C_HEADER: ExpectedCHeader = ExpectedCHeader::ContainsAfterTrim(
+        r#"
+void __swift_bridge__$take_ref_mut(void* arg);
+"#,
+    );
+
+    #[test]
+    fn trait_object_args() {
+        CodegenTest {
+            bridge_module: bridge_module_tokens().into(),
+            expected_rust_tokens: expected_rust_tokens(),
+            expected_swift_code: expected_swift_code(),
+            expected_c_header: EXPECTED_C_HEADER,
+        }
+        .test();
+    }
+}
@@ -3,11 +3,14 @@
 use crate::bridged_type::shared_struct::StructField;
 use crate::bridged_type::{BridgeableType, BridgedType, CFfiStruct, StdLibType, StructFields};
 use crate::codegen::CodegenConfig;
-use crate::parse::{SharedTypeDeclaration, TypeDeclaration, TypeDeclarations};
+use crate::parse::{
+    BridgeConst, OperatorTrait, SharedTypeDeclaration, TypeDeclaration, TypeDeclarations,
+};
 use crate::parsed_extern_fn::ParsedExternFn;
 use crate::{SwiftBridgeModule, SWIFT_BRIDGE_PREFIX};
+use proc_macro2::Ident;
 use std::collections::{BTreeSet, HashSet};
-use syn::{FnArg, ReturnType};
+use syn::{parse_quote, FnArg, ReturnType, Type};
 
 const NOTICE: &'static str = "// File automatically generated by swift-bridge.";
 
@@ -140,6 +143,19 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
 
                         header += &ty_decl;
                         header += "\n";
+
+                        if ty_struct.is_vectorizable() {
+                            bookkeeping.includes.insert("stdint.h");
+                            header += &vec_transparent_struct_c_support(&name);
+                        }
+
+                        header += &declare_operator_funcs(
+                            &ty_struct.name,
+                            &ty_struct.operators,
+                            false,
+                            &self.types,
+                            &mut bookkeeping,
+                        );
                     }
                     SharedTypeDeclaration::Enum(ty_enum) => {
                         if ty_enum.already_declared {
@@ -276,6 +292,16 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
                             format!("uint64_t __swift_bridge__${}$_hash(void* self);", ty_name);
                         header += &hash_ty;
                     }
+                    if ty.attributes.display {
+                        let ty_name = ty.ty_name_ident();
+                        header +=
+                            &format!("void* __swift_bridge__${}$_display(void* self);\n", ty_name);
+                    }
+                    if ty.attributes.debug {
+                        let ty_name = ty.ty_name_ident();
+                        header +=
+                            &format!("void* __swift_bridge__${}$_debug(void* self);\n", ty_name);
+                    }
                     if ty.attributes.equatable {
                         let ty_name = ty.ty_name_ident();
                         let equal_ty = format!(
@@ -287,6 +313,16 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
                         header += &equal_ty;
                         header += "\n";
                     }
+                    if ty.attributes.comparable {
+                        let ty_name = ty.ty_name_ident();
+                        let cmp_ty = format!(
+                            "int8_t __swift_bridge__${}$_partial_cmp(void* lhs, void* rhs);",
+                            ty_name
+                        );
+                        bookkeeping.includes.insert("stdint.h");
+                        header += &cmp_ty;
+                        header += "\n";
+                    }
                     let ty_name = ty.to_string();
 
                     if let Some(copy) = ty.attributes.copy {
@@ -325,12 +361,33 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
 
                         header += &drop_ty;
                         header += "\n";
+
+                        if !ty.attributes.already_declared && self.opaque_type_is_used_in_arc(ty) {
+                            let drop_arc = format!(
+                                "void {}(void* self);",
+                                ty.free_arc_rust_opaque_type_ffi_name()
+                            );
+                            header += &drop_arc;
+                            header += "\n";
+                        }
                     }
 
+                    header += &declare_operator_funcs(
+                        ty.ty_name_ident(),
+                        &ty.attributes.operators,
+                        ty.attributes.copy.is_none(),
+                        &self.types,
+                        &mut bookkeeping,
+                    );
+
                     // TODO: Support Vec<OpaqueCopyType>. Add codegen tests and then
                     //  make them pass.
                     // TODO: Support Vec<GenericOpaqueRustType
-                    if ty.attributes.copy.is_none() && ty.generics.len() == 0 {
+                    // TODO: Support Vec<Box<dyn Trait>>
+                    if ty.attributes.copy.is_none()
+                        && ty.generics.len() == 0
+                        && !ty.attributes.trait_object
+                    {
                         let vec_functions = vec_opaque_rust_type_c_support(&ty_name);
 
                         header += &vec_functions;
@@ -348,7 +405,7 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
             declare_custom_c_ffi_types(func, &self.types, &mut c_ffi_struct_bookkeeping);
             if func.host_lang.is_swift() {
                 for (idx, boxed_fn) in func.args_filtered_to_boxed_fns(&self.types) {
-                    if boxed_fn.params.is_empty() && boxed_fn.ret.is_null() {
+                    if boxed_fn.uses_no_args_no_return_support() {
                         continue;
                     }
 
@@ -362,6 +419,18 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
             header += &declare_func(&func, &mut bookkeeping, &self.types);
         }
 
+        // Maps and sets that are nested in other types, such as `Option<HashMap<K, V>>` or a
+        // shared struct's field, aren't declared alongside the functions that use them.
+        self.visit_maps_and_sets(&mut |ty| {
+            if let Some(declaration) = ty.generate_custom_c_ffi_types(&self.types) {
+                push_custom_type_declarations(&declaration, &mut c_ffi_struct_bookkeeping);
+            }
+        });
+
+        for bridge_const in self.consts.iter() {
+            header += &declare_const(bridge_const, &mut bookkeeping, &self.types);
+        }
+
         for slice_ty in bookkeeping.slice_types.iter() {
             header = format!(
                 r#"typedef struct FfiSlice_{slice_ty} {{ {slice_ty}* start; uintptr_t len; }} FfiSlice_{slice_ty};
@@ -388,6 +457,72 @@ typedef struct {option_ffi_name} {{ bool is_some; {ffi_name} val; }} {option_ffi
     }
 }
 
+/// Declare the functions that call a type's Rust operator trait implementations.
+fn declare_operator_funcs(
+    ty_name: &Ident,
+    operators: &[OperatorTrait],
+    operands_by_ref: bool,
+    types: &TypeDeclarations,
+    bookkeeping: &mut Bookkeeping,
+) -> String {
+    let mut declarations = "".to_string();
+    if operators.is_empty() {
+        return declarations;
+    }
+
+    let operand_ty: Type = if operands_by_ref {
+        parse_quote! { &#ty_name }
+    } else {
+        parse_quote! { #ty_name }
+    };
+    let operand = BridgedType::new_with_type(&operand_ty, types).unwrap();
+    let owned = BridgedType::new_with_type(&parse_quote! { #ty_name }, types).unwrap();
+
+    let mut add_includes = |ty: &BridgedType| {
+        if let Some(includes) = ty.to_c_include(types) {
+            for include in includes {
+                bookkeeping.includes.insert(include);
+            }
+        }
+    };
+    add_includes(&operand);
+    add_includes(&owned);
+
+    let operand_c = operand.to_c(types);
+    let owned_c = owned.to_c(types);
+
+    for operator in operators {
+        let link_name = operator.link_name(ty_name);
+
+        let declaration = match operator {
+            OperatorTrait::Add(_)
+            | OperatorTrait::Sub(_)
+            | OperatorTrait::Mul(_)
+            | OperatorTrait::Div(_) => {
+                format!("{owned_c} {link_name}({operand_c} lhs, {operand_c} rhs);")
+            }
+            OperatorTrait::Neg(_) => format!("{owned_c} {link_name}({operand_c} self);"),
+            OperatorTrait::Index { index, output, .. } => {
+                let index = BridgedType::new_with_type(index, types).unwrap();
+                let output = BridgedType::new_with_type(output, types).unwrap();
+                add_includes(&index);
+                add_includes(&output);
+
+                format!(
+                    "{} {link_name}({operand_c} self, {} index);",
+                    output.to_c(types),
+                    index.to_c(types)
+                )
+            }
+        };
+
+        declarations += &declaration;
+        declarations += "\n";
+    }
+
+    declarations
+}
+
 fn vec_opaque_rust_type_c_support(ty_name: &str) -> String {
     format!(
         r#"
@@ -420,6 +555,20 @@ void* __swift_bridge__$Vec_{enum_name}$as_ptr(void* vec_ptr);
     )
 }
 
+fn vec_transparent_struct_c_support(struct_name: &str) -> String {
+    format!(
+        r#"void* __swift_bridge__$Vec_{struct_name}$new(void);
+void __swift_bridge__$Vec_{struct_name}$drop(void* vec_ptr);
+void __swift_bridge__$Vec_{struct_name}$push(void* vec_ptr, __swift_bridge__${struct_name} item);
+__swift_bridge__$Option${struct_name} __swift_bridge__$Vec_{struct_name}$pop(void* vec_ptr);
+__swift_bridge__$Option${struct_name} __swift_bridge__$Vec_{struct_name}$get(void* vec_ptr, uintptr_t index);
+__swift_bridge__$Option${struct_name} __swift_bridge__$Vec_{struct_name}$get_mut(void* vec_ptr, uintptr_t index);
+uintptr_t __swift_bridge__$Vec_{struct_name}$len(void* vec_ptr);
+void* __swift_bridge__$Vec_{struct_name}$as_ptr(void* vec_ptr);
+"#
+    )
+}
+
 fn push_custom_type_declarations(
     custom_type_declaration: &CFfiStruct,
     c_ffi_struct_bookkeeping: &mut CFfiStructDeclarationBookkeeping,
@@ -453,6 +602,16 @@ fn declare_custom_c_ffi_types(
             }
         }
     }
+    if let Some(next_ty) = func.stream_next_ty(types) {
+        if let Some(declaration) = next_ty.generate_custom_c_ffi_types(types) {
+            push_custom_type_declarations(&declaration, c_ffi_struct_bookkeeping);
+        }
+    }
+    if let Some(next_ty) = func.iterator_next_ty(types) {
+        if let Some(declaration) = next_ty.generate_custom_c_ffi_types(types) {
+            push_custom_type_declarations(&declaration, c_ffi_struct_bookkeeping);
+        }
+    }
     for arg in &func.sig.inputs {
         match arg {
             FnArg::Receiver(_receiver) => {}
@@ -466,6 +625,30 @@ fn declare_custom_c_ffi_types(
     }
 }
 
+/// Declare a `const` or `static` item.
+///
+/// Primitive literals become `#define`s. Their names are prefixed so that they don't clash with
+/// the Swift constants that we generate for them.
+fn declare_const(
+    bridge_const: &BridgeConst,
+    bookkeeping: &mut Bookkeeping,
+    types: &TypeDeclarations,
+) -> String {
+    let link_name = bridge_const.link_name();
+    let ty = BridgedType::new_with_type(&bridge_const.ty, types).unwrap();
+
+    if let Some(includes) = ty.to_c_include(types) {
+        for include in includes {
+            bookkeeping.includes.insert(include);
+        }
+    }
+
+    match bridge_const.primitive_literal(types) {
+        Some(literal) => format!("#define {} {}\n", link_name, literal),
+        None => format!("{} {}(void);\n", ty.to_c(types), link_name),
+    }
+}
+
 fn declare_func(
     func: &ParsedExternFn,
     bookkeeping: &mut Bookkeeping,
@@ -489,7 +672,29 @@ fn declare_func(
         }
     }
 
-    let declaration = if func.sig.asyncness.is_some() {
+    let declaration = if let Some(next_ty) = func.stream_next_ty(types) {
+        let next_link_name = func.stream_next_link_name();
+        let free_link_name = func.stream_free_link_name();
+
+        format!(
+            r#"void* {name}({params});
+void {next_link_name}(void* this, void* task_handle, void* callback_wrapper, void {next_link_name}$async(void* callback_wrapper, {next_ty} ret), void {next_link_name}$cancel(void* callback_wrapper));
+void {free_link_name}(void* this);
+"#,
+            next_ty = next_ty.to_c(types)
+        )
+    } else if let Some(next_ty) = func.iterator_next_ty(types) {
+        let next_link_name = func.iterator_next_link_name();
+        let free_link_name = func.iterator_free_link_name();
+
+        format!(
+            r#"void* {name}({params});
+{next_ty} {next_link_name}(void* this);
+void {free_link_name}(void* this);
+"#,
+            next_ty = next_ty.to_c(types)
+        )
+    } else if func.sig.asyncness.is_some() {
         let maybe_ret = BridgedType::new_with_return_type(&func.sig.output, types).unwrap();
         let maybe_ret = if maybe_ret.is_null() {
             "".to_string()
@@ -504,7 +709,7 @@ fn declare_func(
         };
 
         format!(
-            "void {name}(void* callback_wrapper, void {name}$async(void* callback_wrapper{maybe_ret}){maybe_params});\n",
+            "void {name}(void* task_handle, void* callback_wrapper, void {name}$async(void* callback_wrapper{maybe_ret}), void {name}$cancel(void* callback_wrapper){maybe_params});\n",
             name = name,
             maybe_ret = maybe_ret
         )
@@ -8,9 +8,12 @@ use quote::{quote, quote_spanned};
 
 use self::vec::vec_of_opaque_rust_type::generate_vec_of_opaque_rust_type_functions;
 use crate::bridge_module_attributes::CfgAttr;
+use crate::bridged_type::BridgeableType;
 use crate::parse::{HostLang, SharedTypeDeclaration, TypeDeclaration};
 use crate::SwiftBridgeModule;
 
+mod bridge_const;
+mod operators;
 mod shared_enum;
 mod shared_struct;
 mod vec;
@@ -28,6 +31,7 @@ impl ToTokens for SwiftBridgeModule {
         let mut shared_enum_definitions = vec![];
         let mut custom_type_definitions: HashMap<String, TokenStream> = HashMap::new();
         let mut impl_fn_tokens: HashMap<String, Vec<TokenStream>> = HashMap::new();
+        let mut protocol_impl_fn_tokens: HashMap<String, Vec<TokenStream>> = HashMap::new();
         let mut callbacks_support = vec![];
         let mut freestanding_rust_call_swift_fn_tokens = vec![];
         let mut extern_swift_fn_tokens = vec![];
@@ -55,6 +59,16 @@ impl ToTokens for SwiftBridgeModule {
                                 todo!()
                             }
                             TypeDeclaration::Opaque(ty) => {
+                                if ty.attributes.protocol && func.is_method() {
+                                    protocol_impl_fn_tokens
+                                        .entry(ty.to_string())
+                                        .or_default()
+                                        .push(func.to_rust_trait_method_that_calls_a_swift_extern(
+                                            &ty.ty,
+                                            &self.types,
+                                        ));
+                                }
+
                                 impl_fn_tokens
                                     .entry(ty.to_string())
                                     .or_default()
@@ -74,11 +88,33 @@ impl ToTokens for SwiftBridgeModule {
             };
         }
 
+        // Maps and sets that are nested in other types, such as `Option<HashMap<K, V>>` or a
+        // shared struct's field, don't get their functions from the functions that use them.
+        self.visit_maps_and_sets(&mut |ty| {
+            if let Some(tokens) = ty.generate_custom_rust_ffi_types(swift_bridge_path, &self.types)
+            {
+                for token in tokens.into_iter() {
+                    custom_type_definitions.insert(token.to_string(), token);
+                }
+            }
+        });
+
+        let mut const_definitions = vec![];
+        for bridge_const in &self.consts {
+            const_definitions.push(self.generate_bridge_const_item_tokens(bridge_const));
+            extern_rust_fn_tokens.extend(self.generate_bridge_const_fn_tokens(bridge_const));
+        }
+
         for ty in &self.types.types() {
             match ty {
                 TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct)) => {
                     if let Some(definition) = self.generate_shared_struct_tokens(shared_struct) {
                         shared_struct_definitions.push(definition);
+                        extern_rust_fn_tokens.extend(self.generate_operator_tokens(
+                            &shared_struct.name,
+                            &shared_struct.operators,
+                            false,
+                        ));
                     }
                 }
                 TypeDeclaration::Shared(SharedTypeDeclaration::Enum(shared_enum)) => {
@@ -95,7 +131,6 @@ impl ToTokens for SwiftBridgeModule {
 
                     let link_name = ty.free_rust_opaque_type_ffi_name();
                     let free_mem_func_name = ty.free_rust_opaque_type_ident();
-                    let this = &ty.ty;
                     let ty_name = &ty.ty;
 
                     match ty.host_lang {
@@ -120,6 +155,50 @@ impl ToTokens for SwiftBridgeModule {
                                 };
                                 extern_rust_fn_tokens.push(tokens);
                             }
+                            if ty.attributes.display {
+                                let export_name = format!("__swift_bridge__${}$_display", ty_name);
+                                let function_name = syn::Ident::new(
+                                    &format!("__swift_bridge__{}__display", ty_name),
+                                    ty.ty.span(),
+                                );
+                                let swift_bridge_path = &self.swift_bridge_path;
+                                let body = self.maybe_catch_panics(quote! {
+                                    #swift_bridge_path::string::RustString(
+                                        format!("{}", unsafe { &*this })
+                                    ).box_into_raw()
+                                });
+                                let tokens = quote! {
+                                    #[export_name = #export_name]
+                                    pub extern "C" fn #function_name (
+                                        this: *const super::#ty_name,
+                                    ) -> *mut #swift_bridge_path::string::RustString {
+                                        #body
+                                    }
+                                };
+                                extern_rust_fn_tokens.push(tokens);
+                            }
+                            if ty.attributes.debug {
+                                let export_name = format!("__swift_bridge__${}$_debug", ty_name);
+                                let function_name = syn::Ident::new(
+                                    &format!("__swift_bridge__{}__debug", ty_name),
+                                    ty.ty.span(),
+                                );
+                                let swift_bridge_path = &self.swift_bridge_path;
+                                let body = self.maybe_catch_panics(quote! {
+                                    #swift_bridge_path::string::RustString(
+                                        format!("{:?}", unsafe { &*this })
+                                    ).box_into_raw()
+                                });
+                                let tokens = quote! {
+                                    #[export_name = #export_name]
+                                    pub extern "C" fn #function_name (
+                                        this: *const super::#ty_name,
+                                    ) -> *mut #swift_bridge_path::string::RustString {
+                                        #body
+                                    }
+                                };
+                                extern_rust_fn_tokens.push(tokens);
+                            }
                             if ty.attributes.equatable {
                                 let export_name =
                                     format!("__swift_bridge__${}$_partial_eq", ty_name);
@@ -127,17 +206,51 @@ impl ToTokens for SwiftBridgeModule {
                                     &format!("__swift_bridge__{}__partial_eq", ty_name),
                                     ty.ty.span(),
                                 );
+                                let body = self.maybe_catch_panics(quote! {
+                                    unsafe { &*lhs == &*rhs }
+                                });
                                 let tokens = quote! {
                                     #[export_name = #export_name]
                                     pub extern "C" fn #function_name (
                                         lhs: *const super::#ty_name,
                                         rhs: *const super::#ty_name
                                     ) -> bool {
-                                        unsafe { &*lhs == &*rhs }
+                                        #body
                                     }
                                 };
                                 extern_rust_fn_tokens.push(tokens);
                             }
+                            if ty.attributes.comparable {
+                                let export_name =
+                                    format!("__swift_bridge__${}$_partial_cmp", ty_name);
+                                let function_name = syn::Ident::new(
+                                    &format!("__swift_bridge__{}__partial_cmp", ty_name),
+                                    ty.ty.span(),
+                                );
+                                let body = self.maybe_catch_panics(quote! {
+                                    match unsafe { (&*lhs).partial_cmp(&*rhs) } {
+                                        Some(std::cmp::Ordering::Less) => -1,
+                                        Some(std::cmp::Ordering::Equal) => 0,
+                                        Some(std::cmp::Ordering::Greater) => 1,
+                                        None => 2,
+                                    }
+                                });
+                                let tokens = quote! {
+                                    #[export_name = #export_name]
+                                    pub extern "C" fn #function_name (
+                                        lhs: *const super::#ty_name,
+                                        rhs: *const super::#ty_name
+                                    ) -> i8 {
+                                        #body
+                                    }
+                                };
+                                extern_rust_fn_tokens.push(tokens);
+                            }
+                            extern_rust_fn_tokens.extend(self.generate_operator_tokens(
+                                ty_name,
+                                &ty.attributes.operators,
+                                ty.attributes.copy.is_none(),
+                            ));
                             if let Some(copy) = ty.attributes.copy {
                                 let size = copy.size_bytes;
 
@@ -196,9 +309,13 @@ impl ToTokens for SwiftBridgeModule {
                                         .generics
                                         .angle_bracketed_concrete_generics_tokens(&self.types);
 
+                                    let this = ty
+                                        .to_opaque_type(false, false)
+                                        .rust_pointee_type(&generics);
+
                                     let free = quote! {
                                         #[export_name = #link_name]
-                                        pub extern "C" fn #free_mem_func_name (this: *mut super::#this #generics) {
+                                        pub extern "C" fn #free_mem_func_name (this: *mut #this) {
                                             let this = unsafe { Box::from_raw(this) };
                                             drop(this);
                                         }
@@ -206,10 +323,37 @@ impl ToTokens for SwiftBridgeModule {
 
                                     extern_rust_fn_tokens.push(free);
 
+                                    if self.opaque_type_is_used_in_arc(ty) {
+                                        let link_name = ty.free_arc_rust_opaque_type_ffi_name();
+                                        let free_arc_func_name =
+                                            ty.free_arc_rust_opaque_type_ident();
+
+                                        let free_arc = quote! {
+                                            #[export_name = #link_name]
+                                            pub extern "C" fn #free_arc_func_name (this: *const super::#ty_name) {
+                                                let this = unsafe { std::sync::Arc::from_raw(this) };
+                                                drop(this);
+                                            }
+                                        };
+                                        extern_rust_fn_tokens.push(free_arc);
+                                    }
+
+                                    // Trait objects are fat pointers, so Swift holds a pointer
+                                    // to a `TraitObject` instead.
+                                    if ty.attributes.trait_object {
+                                        let swift_bridge_path = &self.swift_bridge_path;
+                                        let trait_object = quote! {
+                                            #[doc(hidden)]
+                                            pub type #ty_name = #swift_bridge_path::trait_object_support::TraitObject<dyn super::#ty_name>;
+                                        };
+                                        extern_rust_fn_tokens.push(trait_object);
+                                    }
+
                                     // TODO: Support Vec<OpaqueCopyType>. Add codegen tests and then
                                     //  make them pass.
                                     // TODO: Support Vec<GenericOpaqueRustType
-                                    if ty.generics.len() == 0 {
+                                    // TODO: Support Vec<Box<dyn Trait>>
+                                    if ty.generics.len() == 0 && !ty.attributes.trait_object {
                                         let vec_functions =
                                             generate_vec_of_opaque_rust_type_functions(ty_name);
                                         extern_rust_fn_tokens.push(vec_functions);
@@ -233,12 +377,30 @@ impl ToTokens for SwiftBridgeModule {
                                 }
                             };
 
+                            // Implement the Rust trait with the same name as the protocol, so
+                            // that Swift instances can be used as a `Box<dyn Trait>`.
+                            let protocol_impl = if ty.attributes.protocol {
+                                let methods = protocol_impl_fn_tokens
+                                    .get(&ty_name.to_string())
+                                    .map(|m| m.as_slice())
+                                    .unwrap_or(&[]);
+                                quote! {
+                                    impl super::#ty_name for #ty_name {
+                                        #(#methods)*
+                                    }
+                                }
+                            } else {
+                                quote! {}
+                            };
+
                             let struct_tokens = quote! {
                                 #[repr(C)]
                                 pub struct #ty_name(*mut std::ffi::c_void);
 
                                 #impls
 
+                                #protocol_impl
+
                                 impl Drop for #ty_name {
                                     fn drop (&mut self) {
                                         unsafe { #free_mem_func_name(self.0) }
@@ -288,6 +450,8 @@ impl ToTokens for SwiftBridgeModule {
 
             #(#custom_type_definitions)*
 
+            #(#const_definitions)*
+
             #(#extern_rust_fn_tokens)*
 
             #(#freestanding_rust_call_swift_fn_tokens)*
@@ -310,6 +474,21 @@ impl ToTokens for SwiftBridgeModule {
     }
 }
 
+impl SwiftBridgeModule {
+    /// When catching panics, wraps the body of a function that Swift calls to use one of an
+    /// opaque type's trait implementations so that panics don't unwind into Swift.
+    fn maybe_catch_panics(&self, body: TokenStream) -> TokenStream {
+        if !self.catch_panics {
+            return body;
+        }
+
+        let swift_bridge_path = &self.swift_bridge_path;
+        quote! {
+            #swift_bridge_path::panic_support::call_or_abort(|| { #body })
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     //! More tests can be found in src/codegen/codegen_tests.rs and its submodules.
@@ -0,0 +1,63 @@
+use crate::bridged_type::BridgedType;
+use crate::parse::BridgeConst;
+use crate::SwiftBridgeModule;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+impl SwiftBridgeModule {
+    /// Generate the `const` or `static` item itself.
+    pub(super) fn generate_bridge_const_item_tokens(
+        &self,
+        bridge_const: &BridgeConst,
+    ) -> TokenStream {
+        let item = &bridge_const.item;
+
+        // Primitive literals are only read by Swift and C, so Rust might never use them.
+        if bridge_const.primitive_literal(&self.types).is_some() {
+            quote! {
+                #[allow(dead_code)]
+                #item
+            }
+        } else {
+            quote! { #item }
+        }
+    }
+
+    /// Generate the function that Swift calls to read a `const` or `static` item.
+    ///
+    /// Primitive literals don't need one, since Swift and C use their values directly.
+    pub(super) fn generate_bridge_const_fn_tokens(
+        &self,
+        bridge_const: &BridgeConst,
+    ) -> Option<TokenStream> {
+        if bridge_const.primitive_literal(&self.types).is_some() {
+            return None;
+        }
+
+        let swift_bridge_path = &self.swift_bridge_path;
+        let name = &bridge_const.name;
+        let span = name.span();
+
+        let ty = BridgedType::new_with_type(&bridge_const.ty, &self.types).unwrap();
+        let ffi_ty = ty.to_ffi_compatible_rust_type(swift_bridge_path, &self.types);
+
+        // Values can't be moved out of a static, so we clone them.
+        let value = if bridge_const.is_static {
+            quote! { Clone::clone(&#name) }
+        } else {
+            quote! { #name }
+        };
+        let value =
+            ty.convert_rust_expression_to_ffi_type(&value, swift_bridge_path, &self.types, span);
+
+        let link_name = bridge_const.link_name();
+        let fn_name = bridge_const.prefixed_fn_name();
+
+        Some(quote! {
+            #[export_name = #link_name]
+            pub extern "C" fn #fn_name() -> #ffi_ty {
+                #value
+            }
+        })
+    }
+}
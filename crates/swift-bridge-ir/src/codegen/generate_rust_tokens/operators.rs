@@ -0,0 +1,167 @@
+//! More tests can be found in
+//! crates/swift-bridge-ir/src/codegen/codegen_tests/operator_codegen_tests.rs
+
+use crate::bridged_type::BridgedType;
+use crate::parse::OperatorTrait;
+use crate::SwiftBridgeModule;
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, Type};
+
+impl SwiftBridgeModule {
+    /// Generate the functions that Swift calls to use a type's Rust operator trait
+    /// implementations.
+    ///
+    /// Opaque types get passed by reference and cloned before being passed to the trait's method,
+    /// since Swift still owns them. Shared structs and `Copy` opaque types get passed by value.
+    ///
+    /// The `Clone::clone` calls get the operator attribute's span, so a type that doesn't
+    /// implement `Clone` gets an error that points at the attribute.
+    ///
+    /// When catching panics, a panicking operator aborts after calling Swift's panic handler.
+    pub(super) fn generate_operator_tokens(
+        &self,
+        ty_name: &Ident,
+        operators: &[OperatorTrait],
+        operands_by_ref: bool,
+    ) -> Vec<TokenStream> {
+        if operators.is_empty() {
+            return vec![];
+        }
+
+        let swift_bridge_path = &self.swift_bridge_path;
+        let types = &self.types;
+        let span = ty_name.span();
+
+        let operand_ty: Type = if operands_by_ref {
+            parse_quote! { &#ty_name }
+        } else {
+            parse_quote! { #ty_name }
+        };
+        let operand = BridgedType::new_with_type(&operand_ty, types).unwrap();
+        let operand_ffi_ty = operand.to_ffi_compatible_rust_type(swift_bridge_path, types);
+
+        let owned = BridgedType::new_with_type(&parse_quote! { #ty_name }, types).unwrap();
+        let owned_ffi_ty = owned.to_ffi_compatible_rust_type(swift_bridge_path, types);
+
+        let owned_operand = |arg: TokenStream, operator: &OperatorTrait| {
+            let value =
+                operand.convert_ffi_expression_to_rust_type(&arg, span, swift_bridge_path, types);
+            if operands_by_ref {
+                quote_spanned! {operator.span()=> Clone::clone(#value) }
+            } else {
+                value
+            }
+        };
+
+        let mut tokens = vec![];
+
+        for operator in operators {
+            let export_name = operator.link_name(ty_name);
+            let fn_name = operator.prefixed_fn_name(ty_name);
+            let method = Ident::new(operator.method_name(), span);
+
+            let function = match operator {
+                OperatorTrait::Add(_)
+                | OperatorTrait::Sub(_)
+                | OperatorTrait::Mul(_)
+                | OperatorTrait::Div(_) => {
+                    let trait_name = Ident::new(operator.trait_name(), span);
+                    let lhs = owned_operand(quote! { lhs }, operator);
+                    let rhs = owned_operand(quote! { rhs }, operator);
+                    let ret = owned.convert_rust_expression_to_ffi_type(
+                        &quote! { std::ops::#trait_name::#method(#lhs, #rhs) },
+                        swift_bridge_path,
+                        types,
+                        span,
+                    );
+                    let body = self.maybe_catch_panics(ret);
+
+                    quote! {
+                        #[export_name = #export_name]
+                        pub extern "C" fn #fn_name(
+                            lhs: #operand_ffi_ty,
+                            rhs: #operand_ffi_ty
+                        ) -> #owned_ffi_ty {
+                            #body
+                        }
+                    }
+                }
+                OperatorTrait::Neg(_) => {
+                    let this = owned_operand(quote! { this }, operator);
+                    let ret = owned.convert_rust_expression_to_ffi_type(
+                        &quote! { std::ops::Neg::neg(#this) },
+                        swift_bridge_path,
+                        types,
+                        span,
+                    );
+                    let body = self.maybe_catch_panics(ret);
+
+                    quote! {
+                        #[export_name = #export_name]
+                        pub extern "C" fn #fn_name(this: #operand_ffi_ty) -> #owned_ffi_ty {
+                            #body
+                        }
+                    }
+                }
+                OperatorTrait::Index { index, output, .. } => {
+                    let index_ty = BridgedType::new_with_type(index, types).unwrap();
+                    let index_ffi_ty =
+                        index_ty.to_ffi_compatible_rust_type(swift_bridge_path, types);
+                    let index_value = index_ty.convert_ffi_expression_to_rust_type(
+                        &quote! { index },
+                        span,
+                        swift_bridge_path,
+                        types,
+                    );
+
+                    let output_ty = BridgedType::new_with_type(output, types).unwrap();
+                    let output_ffi_ty =
+                        output_ty.to_ffi_compatible_rust_type(swift_bridge_path, types);
+
+                    let this = operand.convert_ffi_expression_to_rust_type(
+                        &quote! { this },
+                        span,
+                        swift_bridge_path,
+                        types,
+                    );
+                    let this = if operands_by_ref {
+                        this
+                    } else {
+                        quote! { &#this }
+                    };
+
+                    let indexed = quote! { std::ops::Index::index(#this, #index_value) };
+                    // `Index::index` returns a reference, so we clone the value unless the output
+                    // is meant to be a reference.
+                    let indexed = if matches!(output.as_ref(), Type::Reference(_)) {
+                        indexed
+                    } else {
+                        quote_spanned! {operator.span()=> Clone::clone(#indexed) }
+                    };
+                    let ret = output_ty.convert_rust_expression_to_ffi_type(
+                        &indexed,
+                        swift_bridge_path,
+                        types,
+                        span,
+                    );
+                    let body = self.maybe_catch_panics(ret);
+
+                    quote! {
+                        #[export_name = #export_name]
+                        pub extern "C" fn #fn_name(
+                            this: #operand_ffi_ty,
+                            index: #index_ffi_ty
+                        ) -> #output_ffi_ty {
+                            #body
+                        }
+                    }
+                }
+            };
+
+            tokens.push(function);
+        }
+
+        tokens
+    }
+}
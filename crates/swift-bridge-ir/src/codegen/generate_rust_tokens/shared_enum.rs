@@ -41,7 +41,8 @@ impl SwiftBridgeModule {
                         let ty = BridgedType::new_with_type(&named_field.ty, &self.types)
                             .unwrap()
                             .to_rust_type_path(types);
-                        let field = quote! {#field_name : #ty};
+                        let serde_attributes = &named_field.serde.attributes;
+                        let field = quote! {#(#serde_attributes)* #field_name : #ty};
                         names.push(field);
                     }
                     quote! {
@@ -53,7 +54,9 @@ impl SwiftBridgeModule {
                     for unnamed_field in unamed_fields {
                         let ty =
                             BridgedType::new_with_type(&unnamed_field.ty, &self.types).unwrap();
-                        names.push(ty.to_rust_type_path(types));
+                        let ty = ty.to_rust_type_path(types);
+                        let serde_attributes = &unnamed_field.serde.attributes;
+                        names.push(quote! {#(#serde_attributes)* #ty});
                     }
                     quote! {
                         #variant_name (#(#names),*)
@@ -65,7 +68,11 @@ impl SwiftBridgeModule {
                     }
                 }
             };
-            enum_variants.push(enum_variant);
+            let serde_attributes = &variant.serde.attributes;
+            enum_variants.push(quote! {
+                #(#serde_attributes)*
+                #enum_variant
+            });
         }
 
         for variant in shared_enum.variants.iter() {
@@ -140,6 +147,29 @@ impl SwiftBridgeModule {
         // User derives
         let mut derive_impl_ffi_bridges = vec![];
 
+        if shared_enum.derive.partial_eq {
+            derives.push(quote! {PartialEq});
+        }
+        if shared_enum.derive.eq {
+            derives.push(quote! {Eq});
+        }
+        if shared_enum.derive.hash {
+            derives.push(quote! {Hash});
+        }
+        if shared_enum.derive.partial_ord {
+            derives.push(quote! {PartialOrd});
+        }
+        if shared_enum.derive.ord {
+            derives.push(quote! {Ord});
+        }
+        if shared_enum.derive.serialize {
+            derives.push(quote! {::serde::Serialize});
+        }
+        if shared_enum.derive.deserialize {
+            derives.push(quote! {::serde::Deserialize});
+        }
+        let serde_attributes = &shared_enum.serde.attributes;
+
         // We currently only allow derive(Debug) on non data carrying enums in order
         // to prevent a potential memory safety issue.
         // https://github.com/chinedufn/swift-bridge/pull/194#discussion_r1134386788
@@ -167,6 +197,7 @@ impl SwiftBridgeModule {
 
         let definition = quote! {
             #[derive(#(#derives),*)]
+            #(#serde_attributes)*
             pub enum #enum_name {
                 #(#enum_variants),*
             }
@@ -2,6 +2,7 @@
 //! crates/swift-bridge-ir/src/codegen/codegen_tests/shared_struct_codegen_tests.rs
 
 use crate::bridged_type::{BridgedType, SharedStruct};
+use crate::codegen::generate_rust_tokens::vec::vec_of_transparent_struct::generate_vec_of_transparent_struct_functions;
 use crate::{SwiftBridgeModule, SWIFT_BRIDGE_PREFIX};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
@@ -31,9 +32,13 @@ impl SwiftBridgeModule {
             .iter()
             .map(|norm_field| {
                 let maybe_name_and_colon = norm_field.maybe_name_and_colon();
-                let ty = &norm_field.ty;
+                let ty = BridgedType::new_with_type(&norm_field.ty, &self.types)
+                    .unwrap()
+                    .to_rust_type_path(&self.types);
+                let serde_attributes = &norm_field.serde.attributes;
 
                 quote! {
+                    #(#serde_attributes)*
                     pub #maybe_name_and_colon #ty
                 }
             })
@@ -98,9 +103,38 @@ impl SwiftBridgeModule {
         if shared_struct.derives.clone {
             derives.push(quote! {Clone});
         }
+        if shared_struct.derives.partial_eq {
+            derives.push(quote! {PartialEq});
+        }
+        if shared_struct.derives.eq {
+            derives.push(quote! {Eq});
+        }
+        if shared_struct.derives.hash {
+            derives.push(quote! {Hash});
+        }
+        if shared_struct.derives.partial_ord {
+            derives.push(quote! {PartialOrd});
+        }
+        if shared_struct.derives.ord {
+            derives.push(quote! {Ord});
+        }
+        if shared_struct.derives.serialize {
+            derives.push(quote! {::serde::Serialize});
+        }
+        if shared_struct.derives.deserialize {
+            derives.push(quote! {::serde::Deserialize});
+        }
+        let serde_attributes = &shared_struct.serde.attributes;
+
+        let vec_support = if shared_struct.is_vectorizable() {
+            generate_vec_of_transparent_struct_functions(shared_struct)
+        } else {
+            quote! {}
+        };
 
         let definition = quote! {
             #[derive(#(#derives),*)]
+            #(#serde_attributes)*
             pub struct #struct_name #struct_fields
 
             #struct_ffi_repr
@@ -153,6 +187,8 @@ impl SwiftBridgeModule {
                     }
                 }
             }
+
+            #vec_support
         };
 
         Some(definition)
@@ -96,6 +96,7 @@ pub(in super::super) fn generate_vec_of_transparent_enum_functions(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse::SerdeAttributes;
     use crate::{bridged_type::DeriveAttrs, test_utils::assert_tokens_eq};
     use proc_macro2::{Ident, Span};
 
@@ -169,6 +170,10 @@ mod tests {
             already_declared: false,
             swift_name: None,
             derive: DeriveAttrs::default(),
+            serde: SerdeAttributes::default(),
+            doc_comment: None,
+            available: vec![],
+            deprecated: None,
         };
         assert_tokens_eq(
             &generate_vec_of_transparent_enum_functions(&shared_enum),
@@ -0,0 +1,97 @@
+use crate::bridged_type::SharedStruct;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Generate the functions that Swift calls uses inside of the corresponding class for a
+/// transparent struct's Vectorizable implementation.
+///
+/// So inside of `extension SomeTransparentStruct: Vectorizable {}` on the Swift side.
+///
+/// Swift gets a clone of an item whenever it reads one, so these are only generated for structs
+/// that derive `Clone`.
+pub(in super::super) fn generate_vec_of_transparent_struct_functions(
+    shared_struct: &SharedStruct,
+) -> TokenStream {
+    let struct_name = &shared_struct.name;
+
+    // examples:
+    // "__swift_bridge__$Vec_SomeTransparentStruct$new"
+    // "__swift_bridge__$Vec_SomeTransparentStruct$drop"
+    let make_export_name = |fn_name| {
+        format!(
+            "__swift_bridge__$Vec_{}${}",
+            shared_struct.swift_name_string(),
+            fn_name
+        )
+    };
+    let export_name_new = make_export_name("new");
+    let export_name_drop = make_export_name("drop");
+    let export_name_len = make_export_name("len");
+    let export_name_get = make_export_name("get");
+    let export_name_get_mut = make_export_name("get_mut");
+    let export_name_push = make_export_name("push");
+    let export_name_pop = make_export_name("pop");
+    let export_name_as_ptr = make_export_name("as_ptr");
+
+    let ffi_struct_repr = &shared_struct.ffi_name_tokens();
+    let ffi_option_struct_repr = shared_struct.ffi_option_name_tokens();
+
+    quote! {
+        const _: () = {
+            #[doc(hidden)]
+            #[export_name = #export_name_new]
+            pub extern "C" fn _new() -> *mut Vec<#struct_name> {
+                Box::into_raw(Box::new(Vec::new()))
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_drop]
+            pub extern "C" fn _drop(vec: *mut Vec<#struct_name>) {
+                let vec = unsafe { Box::from_raw(vec) };
+                drop(vec)
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_len]
+            pub extern "C" fn _len(vec: *const Vec<#struct_name>) -> usize {
+                unsafe { &*vec }.len()
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_get]
+            pub extern "C" fn _get(vec: *const Vec<#struct_name>, index: usize) -> #ffi_option_struct_repr {
+                let vec = unsafe { &*vec };
+                let val = vec.get(index).cloned();
+                #ffi_option_struct_repr::from_rust_repr(val)
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_get_mut]
+            pub extern "C" fn _get_mut(vec: *mut Vec<#struct_name>, index: usize) -> #ffi_option_struct_repr {
+                let vec = unsafe { &mut *vec };
+                let val = vec.get_mut(index).map(|v| v.clone());
+                #ffi_option_struct_repr::from_rust_repr(val)
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_push]
+            pub extern "C" fn _push(vec: *mut Vec<#struct_name>, val: #ffi_struct_repr) {
+                unsafe { &mut *vec }.push( val.into_rust_repr() )
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_pop]
+            pub extern "C" fn _pop(vec: *mut Vec<#struct_name>) -> #ffi_option_struct_repr {
+                let vec = unsafe { &mut *vec };
+                let val = vec.pop();
+                #ffi_option_struct_repr::from_rust_repr(val)
+            }
+
+            #[doc(hidden)]
+            #[export_name = #export_name_as_ptr]
+            pub extern "C" fn _as_ptr(vec: *const Vec<#struct_name>) -> *const #struct_name {
+                unsafe { & *vec }.as_ptr()
+            }
+        };
+    }
+}
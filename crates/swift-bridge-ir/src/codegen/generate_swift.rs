@@ -1,11 +1,20 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use syn::Path;
 
+use crate::bridged_type::boxed_fn::BoxedFnKind;
 use crate::bridged_type::{BridgeableType, BridgedType, TypePosition};
-use crate::codegen::generate_swift::generate_function_swift_calls_rust::gen_func_swift_calls_rust;
+use crate::codegen::generate_swift::availability::{
+    function_available_platforms, opaque_type_availability, swift_availability_attributes,
+};
+use crate::codegen::generate_swift::bridge_const::generate_swift_const;
+use crate::codegen::generate_swift::collections::generate_collection_vtables;
+use crate::codegen::generate_swift::generate_function_swift_calls_rust::{
+    gen_func_swift_calls_rust, gen_swift_callbacks_passed_to_rust, gen_swift_property_calls_rust,
+};
 use crate::codegen::generate_swift::opaque_copy_type::generate_opaque_copy_struct;
-use crate::codegen::generate_swift::swift_class::generate_swift_class;
+use crate::codegen::generate_swift::swift_class::{generate_swift_arc_class, generate_swift_class};
+use crate::codegen::generate_swift::swift_protocol::generate_swift_protocol;
 use crate::codegen::generate_swift::vec::generate_vectorizable_extension;
 use crate::codegen::CodegenConfig;
 use crate::parse::{
@@ -17,11 +26,19 @@ use crate::SwiftBridgeModule;
 
 mod vec;
 
+mod availability;
+mod bridge_const;
+mod codable;
+mod collections;
+mod comparable;
+mod doc_comment;
 mod generate_function_swift_calls_rust;
 mod opaque_copy_type;
+mod operators;
 mod shared_enum;
 mod shared_struct;
 mod swift_class;
+mod swift_protocol;
 
 impl SwiftBridgeModule {
     /// Generate the corresponding Swift code for the bridging module.
@@ -87,6 +104,16 @@ impl SwiftBridgeModule {
             swift += "\n";
         }
 
+        for bridge_const in &self.consts {
+            swift += &generate_swift_const(bridge_const, &self.types);
+        }
+
+        for function in &self.functions {
+            if function.host_lang.is_rust() {
+                swift += &gen_swift_callbacks_passed_to_rust(function, &self.types);
+            }
+        }
+
         for ty in self.types.types() {
             match ty {
                 TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct)) => {
@@ -127,16 +154,33 @@ impl SwiftBridgeModule {
                         swift += "\n";
 
                         if !ty.attributes.already_declared {
+                            if self.opaque_type_is_used_in_arc(ty) {
+                                swift += &generate_swift_arc_class(ty);
+                                swift += "\n";
+                            }
+
                             // TODO: Support Vec<OpaqueCopyType>. Add codegen tests and then
                             //  make them pass.
                             // TODO: Support Vec<GenericOpaqueRustType
-                            if ty.attributes.copy.is_none() && ty.generics.len() == 0 {
-                                swift += &generate_vectorizable_extension(&ty);
+                            // TODO: Support Vec<Box<dyn Trait>>
+                            if ty.attributes.copy.is_none()
+                                && ty.generics.len() == 0
+                                && !ty.attributes.trait_object
+                            {
+                                swift += &generate_vectorizable_extension(
+                                    &ty,
+                                    &opaque_type_availability(ty),
+                                );
                                 swift += "\n";
                             }
                         }
                     }
                     HostLang::Swift => {
+                        if ty.attributes.protocol {
+                            swift += &generate_swift_protocol(ty, &self.functions, &self.types);
+                            swift += "\n";
+                        }
+
                         swift += &generate_drop_swift_instance_reference_count(ty);
                         swift += "\n";
                     }
@@ -148,6 +192,24 @@ impl SwiftBridgeModule {
     }
 }
 
+impl SwiftBridgeModule {
+    /// Generate the `RustMapVtable`s and `RustSetVtable`s for the collections that the module
+    /// uses, keyed by the Swift property that each one declares.
+    ///
+    /// These are kept out of [`SwiftBridgeModule::generate_swift`] since many bridge modules can
+    /// use the same collection, and Swift only allows the property to be declared once.
+    pub(crate) fn generate_swift_collection_vtables(
+        &self,
+        config: &CodegenConfig,
+    ) -> BTreeMap<String, String> {
+        if !self.module_will_be_compiled(config) {
+            return BTreeMap::new();
+        }
+
+        generate_collection_vtables(self)
+    }
+}
+
 #[derive(Default)]
 struct ClassProtocols {
     // The name of the function to use for the Identifiable protocol implementation.
@@ -181,7 +243,12 @@ func {fn_name} (ptr: UnsafeMutableRawPointer) {{
 "##,
         link_name = link_name,
         fn_name = fn_name,
-        ty_name = ty.ty_name_ident()
+        // Protocols can't be used with `Unmanaged`, so we release them as an `AnyObject`.
+        ty_name = if ty.attributes.protocol {
+            "AnyObject".to_string()
+        } else {
+            ty.ty_name_ident().to_string()
+        }
     )
 }
 
@@ -190,6 +257,10 @@ fn gen_function_exposes_swift_to_rust(
     types: &TypeDeclarations,
     swift_bridge_path: &Path,
 ) -> String {
+    if func.sig.asyncness.is_some() {
+        return gen_async_function_exposes_swift_to_rust(func, types);
+    }
+
     let link_name = func.link_name();
     let prefixed_fn_name = func.prefixed_fn_name();
     let fn_name = if let Some(swift_name) = func.swift_name_override.as_ref() {
@@ -205,42 +276,34 @@ fn gen_function_exposes_swift_to_rust(
     let mut call_fn = format!("{}({})", fn_name, args);
     if let Some(built_in) = BridgedType::new_with_return_type(&func.sig.output, types) {
         if let Some(associated_type) = func.associated_type.as_ref() {
-            let ty_name = match associated_type {
+            let associated_type = match associated_type {
                 TypeDeclaration::Shared(_) => {
                     //
                     todo!()
                 }
-                TypeDeclaration::Opaque(associated_type) => associated_type.to_string(),
+                TypeDeclaration::Opaque(associated_type) => associated_type,
             };
+            let ty_name = associated_type.to_string();
 
             if func.is_method() {
-                call_fn = format!(
-                    "Unmanaged<{ty_name}>.fromOpaque(this).takeUnretainedValue().{call_fn}",
-                    ty_name = ty_name,
-                    call_fn = call_fn
-                );
-                call_fn = built_in.convert_swift_expression_to_ffi_type(
-                    &call_fn,
-                    types,
-                    TypePosition::FnReturn(func.host_lang),
-                );
+                let this = associated_type
+                    .to_opaque_type(false, false)
+                    .swift_take_value(&ty_name, "this", "takeUnretainedValue");
+                call_fn = format!("{this}.{call_fn}");
+                call_fn = convert_swift_return_value_to_ffi_type(&built_in, &call_fn, func, types);
             } else if func.is_swift_initializer {
                 call_fn = format!("Unmanaged.passRetained({}({})).toOpaque()", ty_name, args);
             } else {
                 call_fn = format!("{}::{}", ty_name, call_fn);
             }
         } else {
-            call_fn = built_in.convert_swift_expression_to_ffi_type(
-                &call_fn,
-                types,
-                TypePosition::FnReturn(func.host_lang),
-            );
+            call_fn = convert_swift_return_value_to_ffi_type(&built_in, &call_fn, func, types);
         }
     } else {
         todo!("Push to ParsedErrors")
     };
 
-    let mut rust_fn_once_callback_classes = "".to_string();
+    let mut rust_callback_classes = "".to_string();
 
     let maybe_associated_ty = if let Some(ty) = func.associated_type.as_ref() {
         format!("${}", ty.as_opaque().unwrap().ty.to_string())
@@ -249,7 +312,7 @@ fn gen_function_exposes_swift_to_rust(
     };
 
     for (idx, boxed_fn) in func.args_filtered_to_boxed_fns(types) {
-        if boxed_fn.does_not_have_params_or_return() {
+        if boxed_fn.uses_no_args_no_return_support() {
             continue;
         }
 
@@ -276,9 +339,16 @@ fn gen_function_exposes_swift_to_rust(
 
         let maybe_generics = boxed_fn.maybe_swift_generics(types);
 
-        rust_fn_once_callback_classes += &format!(
-            r#"
-class __private__RustFnOnceCallback{maybe_associated_ty}${fn_name}$param{idx} {{
+        let class_name = format!(
+            "{}{maybe_associated_ty}${fn_name}$param{idx}",
+            boxed_fn.swift_callback_class_prefix()
+        );
+        let free_fn = format!("__swift_bridge__{maybe_associated_ty}${fn_name}$_free$param{idx}");
+
+        rust_callback_classes += &match boxed_fn.kind {
+            BoxedFnKind::FnOnce => format!(
+                r#"
+class {class_name} {{
     var ptr: UnsafeMutableRawPointer
     var called = false
 
@@ -288,7 +358,7 @@ class __private__RustFnOnceCallback{maybe_associated_ty}${fn_name}$param{idx} {{
 
     deinit {{
         if !called {{
-            __swift_bridge__{maybe_associated_ty}${fn_name}$_free$param{idx}(ptr)
+            {free_fn}(ptr)
         }}
     }}
 
@@ -300,11 +370,30 @@ class __private__RustFnOnceCallback{maybe_associated_ty}${fn_name}$param{idx} {{
         return {ret_value}
     }}
 }}"#
-        );
+            ),
+            BoxedFnKind::Fn | BoxedFnKind::FnMut => format!(
+                r#"
+class {class_name} {{
+    var ptr: UnsafeMutableRawPointer
+
+    init(ptr: UnsafeMutableRawPointer) {{
+        self.ptr = ptr
+    }}
+
+    deinit {{
+        {free_fn}(ptr)
+    }}
+
+    func call{maybe_generics}({params_as_swift}){maybe_ret} {{
+        return {ret_value}
+    }}
+}}"#
+            ),
+        };
     }
 
     let callback_initializers =
-        func.fnonce_callback_initializers(&fn_name, &maybe_associated_ty, types);
+        func.boxed_fn_callback_initializers(&fn_name, &maybe_associated_ty, types);
     if !callback_initializers.is_empty() {
         let maybe_ret = if ret.is_empty() {
             "let _ = "
@@ -315,11 +404,14 @@ class __private__RustFnOnceCallback{maybe_associated_ty}${fn_name}$param{idx} {{
         call_fn = format!("{{ {callback_initializers} {maybe_ret}{call_fn} }}()")
     }
 
+    let availability =
+        swift_availability_attributes(&function_available_platforms(func, types), None, "");
+
     let generated_func = format!(
-        r#"@_cdecl("{link_name}")
+        r#"{availability}@_cdecl("{link_name}")
 func {prefixed_fn_name} ({params}){ret} {{
     {call_fn}
-}}{rust_fn_once_callback_classes}
+}}{rust_callback_classes}
 "#,
         link_name = link_name,
         prefixed_fn_name = prefixed_fn_name,
@@ -331,16 +423,151 @@ func {prefixed_fn_name} ({params}){ret} {{
     generated_func
 }
 
+// A Swift function that returns a `Result` to Rust `throws` its error, so we catch it and pass
+// the `Result` to Rust.
+fn convert_swift_return_value_to_ffi_type(
+    ret: &BridgedType,
+    call_fn: &str,
+    func: &ParsedExternFn,
+    types: &TypeDeclarations,
+) -> String {
+    let type_pos = TypePosition::FnReturn(func.host_lang);
+
+    if let Some(result) = ret.as_result() {
+        result.convert_swift_throwing_expression_to_ffi_type(call_fn, types, type_pos)
+    } else {
+        ret.convert_swift_expression_to_ffi_type(call_fn, types, type_pos)
+    }
+}
+
+// Rust passes a callback along with a pointer to the future that it is awaiting.
+// We run the async Swift function in a `Task` and then pass its output to the callback.
+//
+// @_cdecl("__swift_bridge__$some_function")
+// func __swift_bridge__some_function (_ callbackWrapper: UnsafeMutableRawPointer, _ callback: @escaping @convention(c) (UnsafeMutableRawPointer, UInt16) -> (), _ arg: UInt8) {
+//     Task {
+//         let val = await some_function(arg: arg)
+//         callback(callbackWrapper, val)
+//     }
+// }
+fn gen_async_function_exposes_swift_to_rust(
+    func: &ParsedExternFn,
+    types: &TypeDeclarations,
+) -> String {
+    let link_name = func.link_name();
+    let prefixed_fn_name = func.prefixed_fn_name();
+    let fn_name = if let Some(swift_name) = func.swift_name_override.as_ref() {
+        swift_name.value()
+    } else {
+        func.sig.ident.to_string()
+    };
+
+    let return_ty = func.return_ty_built_in(types).unwrap();
+    let (maybe_callback_val_ty, maybe_callback_val) = if return_ty.is_null() {
+        ("".to_string(), "".to_string())
+    } else {
+        let ffi_ty = if return_ty.as_result().is_some() {
+            return_ty.to_swift_type(TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy, types)
+        } else {
+            return_ty.to_swift_type(TypePosition::FnReturn(HostLang::Swift), types)
+        };
+        let val = return_ty.convert_swift_expression_to_ffi_type(
+            "val",
+            types,
+            TypePosition::FnReturn(HostLang::Swift),
+        );
+
+        (format!(", {}", ffi_ty), format!(", {}", val))
+    };
+
+    let mut params = vec![
+        "_ callbackWrapper: UnsafeMutableRawPointer".to_string(),
+        format!(
+            "_ callback: @escaping @convention(c) (UnsafeMutableRawPointer{}) -> ()",
+            maybe_callback_val_ty
+        ),
+    ];
+    let fn_params = func.to_swift_param_names_and_types(true, types);
+    if !fn_params.is_empty() {
+        params.push(fn_params);
+    }
+    let params = params.join(", ");
+
+    let (mut bindings, args) = func.to_swift_async_call_arg_bindings(types);
+    let call_fn = match func.associated_type.as_ref() {
+        Some(TypeDeclaration::Opaque(associated_type)) => {
+            let ty_name = associated_type.to_string();
+
+            if func.is_method() {
+                let this = associated_type
+                    .to_opaque_type(false, false)
+                    .swift_take_value(&ty_name, "this", "takeUnretainedValue");
+                bindings.insert(0, format!("let this = {}", this));
+
+                format!("this.{}({})", fn_name, args)
+            } else {
+                format!("{}.{}({})", ty_name, fn_name, args)
+            }
+        }
+        Some(TypeDeclaration::Shared(_)) => todo!(),
+        None => format!("{}({})", fn_name, args),
+    };
+
+    let task_body = match return_ty.as_result() {
+        // Swift implements functions that return a `Result<T, SwiftError>` as `async throws`.
+        Some(result) if func.returns_result_with_swift_error(types) => result
+            .call_swift_throwing_expression_with_ffi_value(
+                &format!("await {}", call_fn),
+                |val| format!("callback(callbackWrapper, {})", val),
+                types,
+                TypePosition::FnReturn(HostLang::Swift),
+            ),
+        _ => {
+            let call_fn = if return_ty.is_null() {
+                format!("await {}", call_fn)
+            } else {
+                format!("let val = await {}", call_fn)
+            };
+            format!("{call_fn}\n        callback(callbackWrapper{maybe_callback_val})")
+        }
+    };
+
+    let bindings: String = bindings
+        .into_iter()
+        .map(|binding| format!("    {}\n", binding))
+        .collect();
+
+    let availability =
+        swift_availability_attributes(&function_available_platforms(func, types), None, "");
+
+    format!(
+        r#"{availability}@_cdecl("{link_name}")
+func {prefixed_fn_name} ({params}) {{
+{bindings}    Task {{
+        {task_body}
+    }}
+}}
+"#
+    )
+}
+
 struct ClassMethods {
     initializers: Vec<String>,
     owned_self_methods: Vec<String>,
     ref_self_methods: Vec<String>,
     ref_mut_self_methods: Vec<String>,
+    /// Properties that get declared in the `Ref` and `RefMut` class bodies instead of in
+    /// extensions, since Swift can't override a property that was declared in an extension.
+    ref_self_properties: Vec<String>,
+    ref_mut_self_properties: Vec<String>,
 }
 
+/// `declares_class` is whether or not this module declares the type's classes, in which case
+/// the `Ref` class gets a get-only property that the `RefMut` class overrides with a setter.
 fn generate_swift_class_methods(
     type_name: &str,
     associated_funcs_and_methods: &HashMap<String, Vec<&ParsedExternFn>>,
+    declares_class: bool,
     types: &TypeDeclarations,
     swift_bridge_path: &Path,
 ) -> ClassMethods {
@@ -348,9 +575,15 @@ fn generate_swift_class_methods(
     let mut owned_self_methods = vec![];
     let mut ref_self_methods = vec![];
     let mut ref_mut_self_methods = vec![];
+    let mut ref_self_properties = vec![];
+    let mut ref_mut_self_properties = vec![];
 
     if let Some(methods) = associated_funcs_and_methods.get(type_name) {
         for type_method in methods {
+            if type_method.is_swift_property {
+                continue;
+            }
+
             let func_definition = gen_func_swift_calls_rust(type_method, types, swift_bridge_path);
 
             let is_class_func = type_method.func.sig.inputs.is_empty();
@@ -371,6 +604,57 @@ fn generate_swift_class_methods(
                 }
             }
         }
+
+        // A property's setter can only be used through a mutable reference. When we declare the
+        // classes, the `Ref` gets a get-only property that the `RefMut` overrides with a setter.
+        // Otherwise the property can only be declared once, so it only goes on the `RefMut`.
+        for getter in methods
+            .iter()
+            .filter(|m| m.is_swift_property && !m.is_swift_property_setter())
+        {
+            let property_name = getter.swift_property_name();
+            let setter = methods
+                .iter()
+                .find(|m| m.is_swift_property_setter() && m.swift_property_name() == property_name)
+                .copied();
+
+            match setter {
+                Some(setter) if declares_class => {
+                    ref_self_properties.push(gen_swift_property_calls_rust(
+                        getter,
+                        None,
+                        false,
+                        types,
+                        swift_bridge_path,
+                    ));
+                    ref_mut_self_properties.push(gen_swift_property_calls_rust(
+                        getter,
+                        Some(setter),
+                        true,
+                        types,
+                        swift_bridge_path,
+                    ));
+                }
+                Some(setter) => {
+                    ref_mut_self_methods.push(gen_swift_property_calls_rust(
+                        getter,
+                        Some(setter),
+                        false,
+                        types,
+                        swift_bridge_path,
+                    ));
+                }
+                None => {
+                    ref_self_methods.push(gen_swift_property_calls_rust(
+                        getter,
+                        None,
+                        false,
+                        types,
+                        swift_bridge_path,
+                    ));
+                }
+            }
+        }
     }
 
     ClassMethods {
@@ -378,6 +662,8 @@ fn generate_swift_class_methods(
         owned_self_methods,
         ref_self_methods,
         ref_mut_self_methods,
+        ref_self_properties,
+        ref_mut_self_properties,
     }
 }
 
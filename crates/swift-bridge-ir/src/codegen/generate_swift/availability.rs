@@ -0,0 +1,186 @@
+//! Generates Swift `@available` attributes from `#[swift_bridge(available(...))]` and
+//! `#[deprecated]`.
+//!
+//! More tests can be found in
+//! crates/swift-bridge-ir/src/codegen/codegen_tests/availability_codegen_tests.rs
+
+use crate::bridged_type::{SharedEnum, SharedStruct};
+use crate::parse::{
+    Deprecation, OpaqueForeignTypeDeclaration, PlatformVersion, SharedTypeDeclaration,
+    TypeDeclaration,
+};
+use crate::{ParsedExternFn, TypeDeclarations};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{FnArg, ReturnType};
+
+/// Generate the `@available` lines that go right above a Swift declaration.
+///
+/// Returns an empty string if the declaration is available everywhere and isn't deprecated.
+///
+/// ```no_rust,ignore
+/// @available(iOS 15, macOS 12, *)
+/// @available(*, deprecated, message: "Use another_function instead.")
+/// ```
+pub(super) fn swift_availability_attributes(
+    available: &[PlatformVersion],
+    deprecated: Option<&Deprecation>,
+    indentation: &str,
+) -> String {
+    let mut attributes = "".to_string();
+
+    if !available.is_empty() {
+        let platforms: Vec<String> = available
+            .iter()
+            .map(|p| format!("{} {}", p.platform, p.version.value()))
+            .collect();
+        attributes += &format!("{}@available({}, *)\n", indentation, platforms.join(", "));
+    }
+
+    if let Some(deprecated) = deprecated {
+        let since = deprecated.since.as_ref().map(|since| since.value());
+        let note = deprecated.note.as_ref().map(|note| note.value());
+
+        let message = match (since, note) {
+            (Some(since), Some(note)) => Some(format!("Deprecated since {}. {}", since, note)),
+            (Some(since), None) => Some(format!("Deprecated since {}.", since)),
+            (None, Some(note)) => Some(note),
+            (None, None) => None,
+        };
+
+        if let Some(message) = message {
+            attributes += &format!(
+                "{}@available(*, deprecated, message: \"{}\")\n",
+                indentation,
+                escape_swift_string(&message)
+            );
+        } else {
+            attributes += &format!("{}@available(*, deprecated)\n", indentation);
+        }
+    }
+
+    attributes
+}
+
+/// The `@available` lines for an opaque type.
+///
+/// These go above the type's classes and every extension of them, since Swift does not allow
+/// extending a type on platform versions where the type is unavailable.
+pub(super) fn opaque_type_availability(ty: &OpaqueForeignTypeDeclaration) -> String {
+    swift_availability_attributes(
+        &ty.attributes.available,
+        ty.attributes.deprecated.as_ref(),
+        "",
+    )
+}
+
+/// The `@available` lines for a shared struct and its extensions.
+pub(super) fn shared_struct_availability(shared_struct: &SharedStruct) -> String {
+    swift_availability_attributes(
+        &shared_struct.available,
+        shared_struct.deprecated.as_ref(),
+        "",
+    )
+}
+
+/// The `@available` lines for a shared enum and its extensions.
+pub(super) fn shared_enum_availability(shared_enum: &SharedEnum) -> String {
+    swift_availability_attributes(&shared_enum.available, shared_enum.deprecated.as_ref(), "")
+}
+
+/// The platform versions that a function is available on.
+///
+/// A Swift function can only mention types that are available wherever the function is, so this
+/// includes the platforms of every type in its signature. The function's associated type is
+/// skipped since the extension that holds the function already has the type's attributes.
+pub(super) fn function_available_platforms(
+    function: &ParsedExternFn,
+    types: &TypeDeclarations,
+) -> Vec<PlatformVersion> {
+    let mut platforms = function.available.clone();
+
+    let associated_type = function
+        .associated_type
+        .as_ref()
+        .and_then(|ty| ty.as_opaque())
+        .map(|ty| ty.ty.to_string());
+
+    let arg_types = function.func.sig.inputs.iter().filter_map(|arg| match arg {
+        FnArg::Receiver(_) => None,
+        FnArg::Typed(pat_ty) => Some(pat_ty.ty.to_token_stream()),
+    });
+    let return_type = match &function.func.sig.output {
+        ReturnType::Default => None,
+        ReturnType::Type(_, ty) => Some(ty.to_token_stream()),
+    };
+    for tokens in arg_types.chain(return_type) {
+        for ident in idents(tokens) {
+            if associated_type.as_ref() == Some(&ident) {
+                continue;
+            }
+
+            if let Some(declaration) = types.get(&ident) {
+                merge_platforms(&mut platforms, declared_platforms(declaration));
+            }
+        }
+    }
+
+    platforms
+}
+
+fn declared_platforms(declaration: &TypeDeclaration) -> &[PlatformVersion] {
+    match declaration {
+        TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct)) => {
+            &shared_struct.available
+        }
+        TypeDeclaration::Shared(SharedTypeDeclaration::Enum(shared_enum)) => &shared_enum.available,
+        TypeDeclaration::Opaque(opaque) => &opaque.attributes.available,
+    }
+}
+
+/// Add the platforms to the list, keeping the newest version of each platform.
+fn merge_platforms(platforms: &mut Vec<PlatformVersion>, other: &[PlatformVersion]) {
+    for platform in other {
+        let existing = platforms
+            .iter_mut()
+            .find(|existing| existing.platform == platform.platform);
+
+        match existing {
+            Some(existing) => {
+                if version_numbers(platform) > version_numbers(existing) {
+                    *existing = platform.clone();
+                }
+            }
+            None => platforms.push(platform.clone()),
+        }
+    }
+}
+
+/// "15.4" -> [15, 4]
+fn version_numbers(platform: &PlatformVersion) -> Vec<u32> {
+    platform
+        .version
+        .value()
+        .split('.')
+        .map(|number| number.parse().unwrap_or(0))
+        .collect()
+}
+
+/// Every identifier in the tokens, such as `Vec` and `SomeType` in `Vec<SomeType>`.
+fn idents(tokens: TokenStream) -> Vec<String> {
+    tokens
+        .into_iter()
+        .flat_map(|token| match token {
+            TokenTree::Ident(ident) => vec![ident.to_string()],
+            TokenTree::Group(group) => idents(group.stream()),
+            _ => vec![],
+        })
+        .collect()
+}
+
+fn escape_swift_string(string: &str) -> String {
+    string
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,35 @@
+use crate::bridged_type::{BridgedType, TypePosition};
+use crate::codegen::generate_swift::doc_comment::swift_doc_comment;
+use crate::parse::{BridgeConst, HostLang};
+use crate::TypeDeclarations;
+
+/// Generate the Swift constant for a `const` or `static` item.
+///
+/// ```no_rust,ignore
+/// public let MAX_ITEMS: UInt32 = 100
+/// public let VERSION: RustStr = __swift_bridge__$VERSION()
+/// ```
+///
+/// Swift initializes global constants lazily, so values that aren't primitive literals only get
+/// read from Rust the first time that they're used.
+pub(super) fn generate_swift_const(bridge_const: &BridgeConst, types: &TypeDeclarations) -> String {
+    let return_pos = TypePosition::FnReturn(HostLang::Rust);
+
+    let ty = BridgedType::new_with_type(&bridge_const.ty, types).unwrap();
+    let swift_ty = ty.to_swift_type(return_pos, types);
+
+    let value = match bridge_const.primitive_literal(types) {
+        Some(literal) => literal,
+        None => ty.convert_ffi_value_to_swift_value(
+            &format!("{}()", bridge_const.link_name()),
+            return_pos,
+            types,
+        ),
+    };
+
+    format!(
+        "{doc_comment}public let {name}: {swift_ty} = {value}\n",
+        doc_comment = swift_doc_comment(bridge_const.doc_comment.as_ref(), ""),
+        name = bridge_const.name,
+    )
+}
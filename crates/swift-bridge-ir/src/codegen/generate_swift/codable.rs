@@ -0,0 +1,108 @@
+//! Helpers for generating Swift `Codable` conformances that mirror a shared type's serde derives.
+//!
+//! More tests can be found in
+//! crates/swift-bridge-ir/src/codegen/codegen_tests/codable_codegen_tests.rs
+
+/// The protocol that matches the serde derives.
+///
+/// `#[derive(Serialize, Deserialize)]` -> "Codable"
+/// `#[derive(Serialize)]` -> "Encodable"
+/// `#[derive(Deserialize)]` -> "Decodable"
+pub(super) fn codable_protocol(serialize: bool, deserialize: bool) -> Option<&'static str> {
+    match (serialize, deserialize) {
+        (true, true) => Some("Codable"),
+        (true, false) => Some("Encodable"),
+        (false, true) => Some("Decodable"),
+        (false, false) => None,
+    }
+}
+
+/// Generate an enum that maps Swift names to their serialized keys.
+///
+/// enum CodingKeys: String, CodingKey {
+///     case someField = "some_field"
+/// }
+pub(super) fn generate_coding_keys(name: &str, keys: &[(String, String)]) -> String {
+    if keys.is_empty() {
+        return format!("    enum {name}: CodingKey {{}}");
+    }
+
+    let cases: Vec<String> = keys
+        .iter()
+        .map(|(case, key)| format!("        case {case} = \"{key}\""))
+        .collect();
+
+    format!(
+        r#"    enum {name}: String, CodingKey {{
+{cases}
+    }}"#,
+        cases = cases.join("\n")
+    )
+}
+
+/// Encode a value into a container.
+///
+/// Swift `RustString`s are encoded as Swift `String`s, and optional values are left out of keyed
+/// containers when they are `nil`.
+///
+/// Example -> "try container.encode(self.field, forKey: .field)"
+pub(super) fn encode_value(
+    swift_ty: &str,
+    value: &str,
+    container: &str,
+    key: Option<&str>,
+) -> String {
+    let value = if swift_ty == "RustString" {
+        format!("{}.toString()", value)
+    } else {
+        value.to_string()
+    };
+
+    match key {
+        Some(key) if optional_inner_type(swift_ty).is_some() => {
+            format!("try {container}.encodeIfPresent({value}, forKey: {key})")
+        }
+        Some(key) => format!("try {container}.encode({value}, forKey: {key})"),
+        None => format!("try {container}.encode({value})"),
+    }
+}
+
+/// Decode a value from a container.
+///
+/// Example -> "try container.decode(UInt8.self, forKey: .field)"
+pub(super) fn decode_value(swift_ty: &str, container: &str, key: Option<&str>) -> String {
+    if swift_ty == "RustString" {
+        return match key {
+            Some(key) => {
+                format!("try {container}.decode(String.self, forKey: {key}).intoRustString()")
+            }
+            None => format!("try {container}.decode(String.self).intoRustString()"),
+        };
+    }
+
+    match (key, optional_inner_type(swift_ty)) {
+        (Some(key), Some(inner)) => {
+            format!("try {container}.decodeIfPresent({inner}.self, forKey: {key})")
+        }
+        (Some(key), None) => format!("try {container}.decode({swift_ty}.self, forKey: {key})"),
+        (None, _) => format!("try {container}.decode({swift_ty}.self)"),
+    }
+}
+
+/// Decode a value directly from a `Decoder`.
+///
+/// Example -> "try UInt8(from: decoder)"
+pub(super) fn decode_value_from_decoder(swift_ty: &str) -> String {
+    if swift_ty == "RustString" {
+        "try String(from: decoder).intoRustString()".to_string()
+    } else {
+        format!("try {swift_ty}(from: decoder)")
+    }
+}
+
+/// "Optional<UInt8>" -> Some("UInt8")
+pub(super) fn optional_inner_type(swift_ty: &str) -> Option<&str> {
+    swift_ty
+        .strip_prefix("Optional<")
+        .and_then(|ty| ty.strip_suffix('>'))
+}
@@ -0,0 +1,31 @@
+use crate::bridged_type::{BridgedType, StdLibType};
+use crate::SwiftBridgeModule;
+use std::collections::BTreeMap;
+
+/// Generate a `RustMapVtable` for every `HashMap<K, V>` and `BTreeMap<K, V>`, and a
+/// `RustSetVtable` for every `HashSet<T>` and `BTreeSet<T>`, that the module passes over FFI.
+///
+/// The vtables are keyed by the Swift property that they declare, so that a collection that is
+/// used in many bridge modules only gets one vtable.
+pub(super) fn generate_collection_vtables(module: &SwiftBridgeModule) -> BTreeMap<String, String> {
+    let types = &module.types;
+    let mut vtables = BTreeMap::new();
+
+    module.visit_maps_and_sets(&mut |ty| match ty {
+        BridgedType::StdLib(StdLibType::Map(map)) => {
+            vtables.insert(
+                map.swift_vtable_key(types),
+                map.generate_swift_vtable(types),
+            );
+        }
+        BridgedType::StdLib(StdLibType::Set(set)) => {
+            vtables.insert(
+                set.swift_vtable_key(types),
+                set.generate_swift_vtable(types),
+            );
+        }
+        _ => {}
+    });
+
+    vtables
+}
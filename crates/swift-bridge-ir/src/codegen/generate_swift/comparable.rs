@@ -0,0 +1,107 @@
+//! Helpers for generating Swift `Equatable`, `Hashable` and `Comparable` conformances that mirror
+//! a shared type's `PartialEq`, `Hash` and `PartialOrd` derives.
+//!
+//! Values are compared the same way that Rust compares them. For example, `RustString`s are
+//! compared byte by byte, `false` is less than `true` and `None` is less than `Some`.
+//!
+//! More tests can be found in
+//! crates/swift-bridge-ir/src/codegen/codegen_tests/derive_struct_attribute_codegen_tests.rs and
+//! crates/swift-bridge-ir/src/codegen/codegen_tests/derive_attribute_codegen_tests.rs
+
+use crate::codegen::generate_swift::codable::optional_inner_type;
+
+/// An expression that evaluates to true if the two values are equal.
+///
+/// Example -> "lhs.field == rhs.field"
+pub(super) fn equal_values(swift_ty: &str, lhs: &str, rhs: &str) -> String {
+    if swift_ty == "RustString" {
+        return format!("{lhs}.toString().utf8.elementsEqual({rhs}.toString().utf8)");
+    }
+
+    match optional_inner_type(swift_ty) {
+        Some(inner) if needs_custom_comparison(inner) => format!(
+            "{{ switch ({lhs}, {rhs}) {{ case (nil, nil): return true; case let (.some(lhsValue), .some(rhsValue)): return {equal}; default: return false }} }}()",
+            equal = equal_values(inner, "lhsValue", "rhsValue")
+        ),
+        _ => format!("{lhs} == {rhs}"),
+    }
+}
+
+/// An expression that evaluates to true if the two values are not equal.
+///
+/// Example -> "lhs.field != rhs.field"
+pub(super) fn not_equal_values(swift_ty: &str, lhs: &str, rhs: &str) -> String {
+    if needs_custom_comparison(swift_ty) {
+        format!("!{}", equal_values(swift_ty, lhs, rhs))
+    } else {
+        format!("{lhs} != {rhs}")
+    }
+}
+
+/// An expression that evaluates to true if the left value is less than the right value.
+///
+/// Example -> "lhs.field < rhs.field"
+pub(super) fn less_than(swift_ty: &str, lhs: &str, rhs: &str) -> String {
+    if swift_ty == "RustString" {
+        return format!("{lhs}.toString().utf8.lexicographicallyPrecedes({rhs}.toString().utf8)");
+    }
+    if swift_ty == "Bool" {
+        return format!("!{lhs} && {rhs}");
+    }
+
+    match optional_inner_type(swift_ty) {
+        Some(inner) => format!(
+            "{{ switch ({lhs}, {rhs}) {{ case (nil, .some): return true; case let (.some(lhsValue), .some(rhsValue)): return {less_than}; default: return false }} }}()",
+            less_than = less_than(inner, "lhsValue", "rhsValue")
+        ),
+        None => format!("{lhs} < {rhs}"),
+    }
+}
+
+/// Feed a value into a `Hasher`.
+///
+/// Example -> "hasher.combine(self.field)"
+pub(super) fn hash_value(swift_ty: &str, value: &str) -> String {
+    if swift_ty == "RustString" {
+        return format!("hasher.combine({value}.toString())");
+    }
+
+    match optional_inner_type(swift_ty) {
+        Some("RustString") => format!("hasher.combine({value}?.toString())"),
+        _ => format!("hasher.combine({value})"),
+    }
+}
+
+/// Compare the values one after the other, the same way that a derived `PartialOrd` does.
+///
+/// Each value is a (swift type, lhs, rhs) tuple.
+///
+/// if lhs.first != rhs.first {
+///     return lhs.first < rhs.first
+/// }
+/// return false
+pub(super) fn lexicographic_less_than(values: &[(String, String, String)], indent: &str) -> String {
+    let mut lines = vec![];
+
+    for (ty, lhs, rhs) in values {
+        lines.push(format!(
+            r#"{indent}if {not_equal} {{
+{indent}    return {less_than}
+{indent}}}"#,
+            not_equal = not_equal_values(ty, lhs, rhs),
+            less_than = less_than(ty, lhs, rhs)
+        ));
+    }
+    lines.push(format!("{indent}return false"));
+
+    lines.join("\n")
+}
+
+/// Swift's `==` does not compare `RustString`s, and it compares `String`s using unicode
+/// canonical equivalence instead of byte by byte, so we compare them ourselves.
+fn needs_custom_comparison(swift_ty: &str) -> bool {
+    match optional_inner_type(swift_ty) {
+        Some(inner) => needs_custom_comparison(inner),
+        None => swift_ty == "RustString",
+    }
+}
@@ -0,0 +1,150 @@
+//! Converts a Rust doc comment into a Swift doc comment.
+//!
+//! Swift's Quick Help renders Markdown, so most of a Rust doc comment gets copied over as is.
+//! The `# Arguments`, `# Errors` and `# Returns` sections are turned into Swift's
+//! `- Parameter`, `- Throws` and `- Returns` callouts.
+//!
+//! More tests can be found in
+//! crates/swift-bridge-ir/src/codegen/codegen_tests/doc_comment_codegen_tests.rs
+
+/// The section of a Rust doc comment that we're in.
+#[derive(Copy, Clone, PartialEq)]
+enum Section {
+    /// Text that gets copied over as is.
+    Text,
+    /// `# Arguments`
+    Arguments,
+    /// `# Errors`
+    Errors,
+    /// `# Returns`
+    Returns,
+}
+
+/// Convert a Rust doc comment into `///` lines that go right above a Swift declaration.
+///
+/// Returns an empty string if there is no doc comment.
+///
+/// ```no_rust,ignore
+/// /// Sends a message.
+/// ///
+/// /// - Parameter message: The message to send.
+/// /// - Throws: If the connection was closed.
+/// ```
+pub(super) fn swift_doc_comment(doc_comment: Option<&String>, indentation: &str) -> String {
+    let doc_comment = match doc_comment {
+        Some(doc_comment) => doc_comment,
+        None => return "".to_string(),
+    };
+
+    let mut lines: Vec<String> = vec![];
+    let mut section = Section::Text;
+    let mut callout_started = false;
+    let mut in_code_block = false;
+
+    for line in doc_comment.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            lines.push(line.to_string());
+            continue;
+        }
+        if in_code_block {
+            // Rust doc tests hide lines that start with `# `.
+            if trimmed != "#" && !trimmed.starts_with("# ") {
+                lines.push(line.to_string());
+            }
+            continue;
+        }
+
+        if let Some(heading) = trimmed.strip_prefix("# ") {
+            let previous_section = section;
+            section = match heading.trim() {
+                "Arguments" | "Parameters" => Section::Arguments,
+                "Errors" => Section::Errors,
+                "Returns" => Section::Returns,
+                _ => Section::Text,
+            };
+            callout_started = false;
+
+            if section == Section::Text {
+                if previous_section != Section::Text {
+                    lines.push("".to_string());
+                }
+                lines.push(line.to_string());
+            }
+            continue;
+        }
+
+        match section {
+            Section::Text => lines.push(line.to_string()),
+            Section::Arguments => {
+                if trimmed.is_empty() {
+                    continue;
+                }
+
+                if let Some((name, description)) = parse_argument(trimmed) {
+                    lines.push(format!(" - Parameter {}: {}", name, description));
+                } else {
+                    lines.push(format!("   {}", trimmed));
+                }
+            }
+            Section::Errors | Section::Returns => {
+                if trimmed.is_empty() {
+                    continue;
+                }
+
+                if callout_started {
+                    lines.push(format!("   {}", trimmed));
+                } else {
+                    let callout = if section == Section::Errors {
+                        "Throws"
+                    } else {
+                        "Returns"
+                    };
+                    lines.push(format!(" - {}: {}", callout, trimmed));
+                    callout_started = true;
+                }
+            }
+        }
+    }
+
+    while lines.last().map(|line| line.trim().is_empty()) == Some(true) {
+        lines.pop();
+    }
+
+    lines
+        .into_iter()
+        .map(|line| format!("{}///{}\n", indentation, line))
+        .collect()
+}
+
+/// Parse an item in a Rust `# Arguments` list.
+///
+/// `* `name` - Some description.` -> ("name", "Some description.")
+fn parse_argument(item: &str) -> Option<(&str, &str)> {
+    let item = item
+        .strip_prefix("* ")
+        .or_else(|| item.strip_prefix("- "))?
+        .trim_start();
+
+    let (name, rest) = if let Some(item) = item.strip_prefix('`') {
+        let end = item.find('`')?;
+        (&item[..end], &item[end + 1..])
+    } else {
+        let end = item
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(item.len());
+        (&item[..end], &item[end..])
+    };
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    let description = rest
+        .trim_start()
+        .trim_start_matches(['-', ':', '–'])
+        .trim_start();
+
+    Some((name, description))
+}
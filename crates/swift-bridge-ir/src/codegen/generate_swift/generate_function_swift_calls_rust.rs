@@ -1,4 +1,8 @@
 use crate::bridged_type::{fn_arg_name, BridgeableType, BridgedType, StdLibType, TypePosition};
+use crate::codegen::generate_swift::availability::{
+    function_available_platforms, swift_availability_attributes,
+};
+use crate::codegen::generate_swift::doc_comment::swift_doc_comment;
 use crate::parse::{HostLang, TypeDeclaration};
 use crate::{ParsedExternFn, TypeDeclarations, SWIFT_BRIDGE_PREFIX};
 use quote::ToTokens;
@@ -20,7 +24,10 @@ pub(super) fn gen_func_swift_calls_rust(
             format!(", {}", call_args)
         };
 
-        format!("{}(wrapperPtr, onComplete{})", fn_name, maybe_args)
+        format!(
+            "{}(taskHandle, wrapperPtr, onComplete, onCancel{})",
+            fn_name, maybe_args
+        )
     } else {
         format!("{}({})", fn_name, call_args)
     };
@@ -71,16 +78,218 @@ pub(super) fn gen_func_swift_calls_rust(
         ""
     };
 
+    let stream_class_ty = format!("RustStream{}${}", maybe_type_name_segment, fn_name);
+    let iterator_class_ty = format!("RustIterator{}${}", maybe_type_name_segment, fn_name);
+    let mut call_rust = gen_call_rust_statement(
+        function,
+        &call_fn,
+        &maybe_type_name_segment,
+        indentation,
+        types,
+    );
+
+    if function.is_swift_initializer {
+        if function.is_copy_method_on_opaque_type() {
+            call_rust = format!("self.bytes = {}", call_rust)
+        } else {
+            call_rust = format!("self.init(ptr: {})", call_rust)
+        }
+    }
+
+    let maybe_return = if function.is_swift_initializer {
+        "".to_string()
+    } else {
+        function.to_swift_return_type(types)
+    };
+
+    let maybe_generics = function.maybe_swift_generics(types);
+    let doc_comment = swift_doc_comment(function.doc_comment.as_ref(), indentation);
+    let availability = swift_availability_attributes(
+        &function_available_platforms(function, types),
+        function.deprecated.as_ref(),
+        indentation,
+    );
+
+    let func_definition = if function.sig.asyncness.is_some() {
+        let func_ret_ty = function.return_ty_built_in(types).unwrap();
+        let callback_wrapper_ty = format!("CbWrapper{}${}", maybe_type_name_segment, fn_name);
+        let (fn_body_indented, callback_wrapper) = gen_swift_async_call_body(
+            &func_ret_ty,
+            &callback_wrapper_ty,
+            &call_rust,
+            function.catch_panics,
+            indentation,
+            types,
+        );
+
+        format!(
+            r#"{doc_comment}{availability}{indentation}{maybe_static_class_func}{swift_class_func_name}{maybe_generics}({params}) async throws{maybe_ret} {{
+{fn_body_indented}
+{indentation}}}
+{callback_wrapper}"#,
+            indentation = indentation,
+            maybe_static_class_func = maybe_static_class_func,
+            swift_class_func_name = public_func_fn_name,
+            maybe_generics = maybe_generics,
+            params = params,
+            maybe_ret = maybe_return.replacen(" throws", "", 1),
+            fn_body_indented = fn_body_indented,
+            callback_wrapper = callback_wrapper
+        )
+    } else {
+        let func_definition = format!(
+            r#"{doc_comment}{availability}{indentation}{maybe_static_class_func}{swift_class_func_name}{maybe_generics}({params}){maybe_ret} {{
+{indentation}    {call_rust}
+{indentation}}}"#,
+            indentation = indentation,
+            maybe_static_class_func = maybe_static_class_func,
+            swift_class_func_name = public_func_fn_name,
+            maybe_generics = maybe_generics,
+            params = params,
+            maybe_ret = maybe_return,
+            call_rust = call_rust,
+        );
+
+        if let Some(next_ty) = function.stream_next_ty(types) {
+            let stream_class = gen_rust_stream_class(
+                function,
+                &next_ty,
+                &stream_class_ty,
+                &maybe_type_name_segment,
+                indentation,
+                types,
+            );
+            format!("{func_definition}\n{stream_class}")
+        } else if let Some(next_ty) = function.iterator_next_ty(types) {
+            let iterator_class =
+                gen_rust_iterator_class(function, &next_ty, &iterator_class_ty, indentation, types);
+            format!("{func_definition}\n{iterator_class}")
+        } else {
+            func_definition
+        }
+    };
+    func_definition
+}
+
+/// Generates a Swift computed property that calls a `#[swift_bridge(swift_property)]` getter and,
+/// if there is one, its setter.
+///
+/// `is_override` is used for a `RefMut` property that adds a setter to its `Ref`'s get-only
+/// property.
+///
+/// ```no_rust,ignore
+/// public var count: UInt32 {
+///     get {
+///         __swift_bridge__$SomeType$count(ptr)
+///     }
+///     set(count) {
+///         __swift_bridge__$SomeType$set_count(ptr, count)
+///     }
+/// }
+/// ```
+pub(super) fn gen_swift_property_calls_rust(
+    getter: &ParsedExternFn,
+    setter: Option<&ParsedExternFn>,
+    is_override: bool,
+    types: &TypeDeclarations,
+    swift_bridge_path: &Path,
+) -> String {
+    let property_name = getter.swift_property_name().unwrap();
+    let property_ty = BridgedType::new_with_return_type(&getter.func.sig.output, types)
+        .unwrap()
+        .to_swift_type(TypePosition::FnReturn(getter.host_lang), types);
+
+    let accessor_body = |function: &ParsedExternFn| {
+        let maybe_type_name_segment = match function.associated_type.as_ref() {
+            Some(TypeDeclaration::Opaque(ty)) => format!("${}", ty.ty),
+            _ => "".to_string(),
+        };
+        let call_fn = format!(
+            "{}({})",
+            function.sig.ident,
+            function.to_swift_call_args(true, false, types, swift_bridge_path)
+        );
+
+        gen_call_rust_statement(
+            function,
+            &call_fn,
+            &maybe_type_name_segment,
+            "        ",
+            types,
+        )
+    };
+
+    let doc_comment = swift_doc_comment(getter.doc_comment.as_ref(), "    ");
+    let availability =
+        swift_availability_attributes(&getter.available, getter.deprecated.as_ref(), "    ");
+
+    let get = accessor_body(getter);
+    let maybe_set = match setter {
+        Some(setter) => {
+            let new_value = fn_arg_name(setter.func.sig.inputs.iter().nth(1).unwrap()).unwrap();
+            let set = accessor_body(setter);
+
+            format!(
+                r#"
+        set({new_value}) {{
+            {set}
+        }}"#
+            )
+        }
+        None => "".to_string(),
+    };
+
+    let maybe_override = if is_override { "override " } else { "" };
+
+    format!(
+        r#"{doc_comment}{availability}    public {maybe_override}var {property_name}: {property_ty} {{
+        get {{
+            {get}
+        }}{maybe_set}
+    }}"#
+    )
+}
+
+/// Generates the Swift statement that calls the Rust function and converts its return value.
+///
+/// `&str` arguments get wrapped in a closure, so the statement can span multiple lines that are
+/// indented using the function body's `indentation`.
+fn gen_call_rust_statement(
+    function: &ParsedExternFn,
+    call_fn: &str,
+    maybe_type_name_segment: &str,
+    indentation: &str,
+    types: &TypeDeclarations,
+) -> String {
     let call_rust = format!(
         "{prefix}{type_name_segment}${call_fn}",
         prefix = SWIFT_BRIDGE_PREFIX,
         type_name_segment = maybe_type_name_segment,
         call_fn = call_fn
     );
+    // A caught panic gets thrown as a `RustPanic` before the returned value gets converted.
+    let call_rust = if function.catch_panics
+        && function.sig.asyncness.is_none()
+        && function.throws_in_swift(types)
+    {
+        format!("try RustPanic.check({})", call_rust)
+    } else {
+        call_rust
+    };
+    let fn_name = function.sig.ident.to_string();
+    let stream_class_ty = format!("RustStream{}${}", maybe_type_name_segment, fn_name);
+    let iterator_class_ty = format!("RustIterator{}${}", maybe_type_name_segment, fn_name);
     let mut call_rust = if function.sig.asyncness.is_some() {
         call_rust
     } else if function.is_swift_initializer {
         call_rust
+    } else if function.stream_item_ty().is_some() {
+        format!(
+            "AsyncThrowingStream(unfolding: {}(ptr: {}).next)",
+            stream_class_ty, call_rust
+        )
+    } else if function.iterator_item_ty().is_some() {
+        format!("{}(ptr: {})", iterator_class_ty, call_rust)
     } else if let Some(built_in) = function.return_ty_built_in(types) {
         built_in.convert_ffi_value_to_swift_value(
             &call_rust,
@@ -116,9 +325,10 @@ pub(super) fn gen_func_swift_calls_rust(
                                 format!("{}(ptr: {}, isOwned: {})", ty, call_rust, is_owned)
                             } else {
                                 let ty = ty.to_token_stream().to_string();
-                                format!(
-                                    "Unmanaged<{}>.fromOpaque({}).takeRetainedValue()",
-                                    ty, call_rust
+                                opaque.to_opaque_type(false, false).swift_take_value(
+                                    &ty,
+                                    &call_rust,
+                                    "takeRetainedValue",
                                 )
                             }
                         }
@@ -174,138 +384,346 @@ pub(super) fn gen_func_swift_calls_rust(
         }
     }
 
-    if function.is_swift_initializer {
-        if function.is_copy_method_on_opaque_type() {
-            call_rust = format!("self.bytes = {}", call_rust)
-        } else {
-            call_rust = format!("self.init(ptr: {})", call_rust)
-        }
-    }
+    call_rust
+}
 
-    let maybe_return = if function.is_swift_initializer {
+/// Generates the class that holds on to a stream returned by a Rust function.
+///
+/// Swift iterates over the stream using `AsyncThrowingStream(unfolding: stream.next)`, and
+/// the Rust stream gets dropped when the class is deinitialized.
+///
+/// ```no_rust,ignore
+/// class RustStream$some_function {
+///     var ptr: UnsafeMutableRawPointer
+///
+///     init(ptr: UnsafeMutableRawPointer) {
+///         self.ptr = ptr
+///     }
+///
+///     deinit {
+///         __swift_bridge__$some_function$_free(ptr)
+///     }
+///
+///     func next() async throws -> UInt8? {
+///         // ... Calls __swift_bridge__$some_function$next and awaits the item ...
+///     }
+///     class CbWrapper$some_function$next {
+///         // ...
+///     }
+/// }
+/// ```
+fn gen_rust_stream_class(
+    function: &ParsedExternFn,
+    next_ty: &BridgedType,
+    stream_class_ty: &str,
+    maybe_type_name_segment: &str,
+    indentation: &str,
+    types: &TypeDeclarations,
+) -> String {
+    let next_link_name = function.stream_next_link_name();
+    let free_link_name = function.stream_free_link_name();
+    let next_swift_ty = next_ty.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+
+    let callback_wrapper_ty = format!(
+        "CbWrapper{}${}$next",
+        maybe_type_name_segment, function.sig.ident
+    );
+    let call_rust = format!("{next_link_name}(ptr, taskHandle, wrapperPtr, onComplete, onCancel)");
+    let method_indentation = format!("{indentation}    ");
+    let (next_body, callback_wrapper) = gen_swift_async_call_body(
+        next_ty,
+        &callback_wrapper_ty,
+        &call_rust,
+        function.catch_panics,
+        &method_indentation,
+        types,
+    );
+
+    format!(
+        r#"{indentation}class {stream_class_ty} {{
+{indentation}    var ptr: UnsafeMutableRawPointer
+
+{indentation}    init(ptr: UnsafeMutableRawPointer) {{
+{indentation}        self.ptr = ptr
+{indentation}    }}
+
+{indentation}    deinit {{
+{indentation}        {free_link_name}(ptr)
+{indentation}    }}
+
+{indentation}    func next() async throws -> {next_swift_ty} {{
+{next_body}
+{indentation}    }}
+{callback_wrapper}
+{indentation}}}"#
+    )
+}
+
+/// Generates the class that holds on to an iterator returned by a Rust function.
+///
+/// The class is its own iterator, so Swift can use it anywhere that a `Sequence` is expected.
+/// The Rust iterator gets dropped when the class is deinitialized.
+///
+/// ```no_rust,ignore
+/// public class RustIterator$some_function: Sequence, IteratorProtocol {
+///     var ptr: UnsafeMutableRawPointer
+///
+///     init(ptr: UnsafeMutableRawPointer) {
+///         self.ptr = ptr
+///     }
+///
+///     deinit {
+///         __swift_bridge__$some_function$_free(ptr)
+///     }
+///
+///     public func next() -> UInt8? {
+///         // ... Calls __swift_bridge__$some_function$next ...
+///     }
+/// }
+/// ```
+fn gen_rust_iterator_class(
+    function: &ParsedExternFn,
+    next_ty: &BridgedType,
+    iterator_class_ty: &str,
+    indentation: &str,
+    types: &TypeDeclarations,
+) -> String {
+    let next_link_name = function.iterator_next_link_name();
+    let free_link_name = function.iterator_free_link_name();
+    let next_swift_ty = next_ty.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+    let next_item = next_ty.convert_ffi_value_to_swift_value(
+        &format!("{next_link_name}(ptr)"),
+        TypePosition::FnReturn(HostLang::Rust),
+        types,
+    );
+
+    format!(
+        r#"{indentation}public class {iterator_class_ty}: Sequence, IteratorProtocol {{
+{indentation}    var ptr: UnsafeMutableRawPointer
+
+{indentation}    init(ptr: UnsafeMutableRawPointer) {{
+{indentation}        self.ptr = ptr
+{indentation}    }}
+
+{indentation}    deinit {{
+{indentation}        {free_link_name}(ptr)
+{indentation}    }}
+
+{indentation}    public func next() -> {next_swift_ty} {{
+{indentation}        {next_item}
+{indentation}    }}
+{indentation}}}"#
+    )
+}
+
+/// Generates the body of a Swift function that calls an async Rust function and awaits its
+/// result, along with the class that holds the continuation's callback.
+///
+/// The body is indented one level past `indentation`.
+///
+/// When catching panics, Rust completes a task that panicked with a zeroed value, so we check
+/// for a caught panic before using the value.
+fn gen_swift_async_call_body(
+    func_ret_ty: &BridgedType,
+    callback_wrapper_ty: &str,
+    call_rust: &str,
+    catch_panics: bool,
+    indentation: &str,
+    types: &TypeDeclarations,
+) -> (String, String) {
+    let rust_fn_ret_ty = func_ret_ty.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
+    let maybe_on_complete_sig_ret_val = if func_ret_ty.is_null() {
         "".to_string()
     } else {
-        function.to_swift_return_type(types)
+        format!(
+            ", rustFnRetVal: {}",
+            func_ret_ty.to_swift_type(TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy, types)
+        )
     };
-
-    let maybe_generics = function.maybe_swift_generics(types);
-
-    let func_definition = if function.sig.asyncness.is_some() {
-        let func_ret_ty = function.return_ty_built_in(types).unwrap();
-        let rust_fn_ret_ty =
-            func_ret_ty.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types);
-        let maybe_on_complete_sig_ret_val = if func_ret_ty.is_null() {
-            "".to_string()
+    // Async functions always throw, since the Swift `Task` awaiting them can get cancelled.
+    let run_wrapper_cb = if let Some(result) = func_ret_ty.as_result() {
+        result.generate_swift_calls_async_rust_callback(
+            "rustFnRetVal",
+            TypePosition::FnReturn(HostLang::Rust),
+            types,
+        )
+    } else {
+        let on_complete_ret_val = if func_ret_ty.is_null() {
+            "()".to_string()
         } else {
-            format!(
-                ", rustFnRetVal: {}",
-                func_ret_ty
-                    .to_swift_type(TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy, types)
+            func_ret_ty.convert_ffi_value_to_swift_value(
+                "rustFnRetVal",
+                TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy,
+                types,
             )
         };
-        let callback_wrapper_ty = format!("CbWrapper{}${}", maybe_type_name_segment, fn_name);
-        let (run_wrapper_cb, error, maybe_try, with_checked_continuation_function_name) =
-            if let Some(result) = func_ret_ty.as_result() {
-                let run_wrapper_cb = result.generate_swift_calls_async_rust_callback(
-                    "rustFnRetVal",
-                    TypePosition::FnReturn(HostLang::Rust),
-                    types,
-                );
-                (
-                    run_wrapper_cb,
-                    "Error".to_string(),
-                    " try ".to_string(),
-                    "withCheckedThrowingContinuation".to_string(),
-                )
-            } else {
-                let on_complete_ret_val = if func_ret_ty.is_null() {
-                    "()".to_string()
-                } else {
-                    func_ret_ty.convert_ffi_value_to_swift_value(
-                        "rustFnRetVal",
-                        TypePosition::SwiftCallsRustAsyncOnCompleteReturnTy,
-                        types,
-                    )
-                };
-                (
-                    format!(r#"wrapper.cb(.success({on_complete_ret_val}))"#),
-                    "Never".to_string(),
-                    " ".to_string(),
-                    "withCheckedContinuation".to_string(),
-                )
-            };
-        let callback_wrapper = format!(
-            r#"{indentation}class {cb_wrapper_ty} {{
-{indentation}    var cb: (Result<{rust_fn_ret_ty}, {error}>) -> ()
+        format!(r#"wrapper.cb(.success({on_complete_ret_val}))"#)
+    };
+    let run_wrapper_cb = if catch_panics {
+        format!(
+            r#"if let rustPanic = RustPanic.take() {{
+        wrapper.cb(.failure(rustPanic))
+        return
+    }}
+    {run_wrapper_cb}"#
+        )
+    } else {
+        run_wrapper_cb
+    };
+    let callback_wrapper = format!(
+        r#"{indentation}class {cb_wrapper_ty} {{
+{indentation}    var cb: (Result<{rust_fn_ret_ty}, Error>) -> ()
 {indentation}
-{indentation}    public init(cb: @escaping (Result<{rust_fn_ret_ty}, {error}>) -> ()) {{
+{indentation}    public init(cb: @escaping (Result<{rust_fn_ret_ty}, Error>) -> ()) {{
 {indentation}        self.cb = cb
 {indentation}    }}
 {indentation}}}"#,
-            indentation = indentation,
-            cb_wrapper_ty = callback_wrapper_ty
-        );
+        indentation = indentation,
+        cb_wrapper_ty = callback_wrapper_ty
+    );
 
-        let fn_body = format!(
-            r#"func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?{maybe_on_complete_sig_ret_val}) {{
+    let fn_body = format!(
+        r#"func onComplete(cbWrapperPtr: UnsafeMutableRawPointer?{maybe_on_complete_sig_ret_val}) {{
     let wrapper = Unmanaged<{cb_wrapper_ty}>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
     {run_wrapper_cb}
 }}
+func onCancel(cbWrapperPtr: UnsafeMutableRawPointer?) {{
+    let wrapper = Unmanaged<{cb_wrapper_ty}>.fromOpaque(cbWrapperPtr!).takeRetainedValue()
+    wrapper.cb(.failure(CancellationError()))
+}}
 
-return{maybe_try}await {with_checked_continuation_function_name}({{ (continuation: CheckedContinuation<{rust_fn_ret_ty}, {error}>) in
-    let callback = {{ rustFnRetVal in
-        continuation.resume(with: rustFnRetVal)
-    }}
+let taskHandle = __swift_bridge__$AsyncTaskHandle$new()
+defer {{ __swift_bridge__$AsyncTaskHandle$_free(taskHandle) }}
 
-    let wrapper = {cb_wrapper_ty}(cb: callback)
-    let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+return try await withTaskCancellationHandler(operation: {{
+    try await withCheckedThrowingContinuation({{ (continuation: CheckedContinuation<{rust_fn_ret_ty}, Error>) in
+        let callback = {{ rustFnRetVal in
+            continuation.resume(with: rustFnRetVal)
+        }}
 
-    {call_rust}
+        let wrapper = {cb_wrapper_ty}(cb: callback)
+        let wrapperPtr = Unmanaged.passRetained(wrapper).toOpaque()
+
+        {call_rust}
+    }})
+}}, onCancel: {{
+    __swift_bridge__$AsyncTaskHandle$cancel(taskHandle)
 }})"#,
-            rust_fn_ret_ty = rust_fn_ret_ty,
-            error = error,
-            maybe_on_complete_sig_ret_val = maybe_on_complete_sig_ret_val,
-            cb_wrapper_ty = callback_wrapper_ty,
-            call_rust = call_rust,
-        );
+        rust_fn_ret_ty = rust_fn_ret_ty,
+        maybe_on_complete_sig_ret_val = maybe_on_complete_sig_ret_val,
+        cb_wrapper_ty = callback_wrapper_ty,
+        call_rust = call_rust,
+    );
 
-        let mut fn_body_indented = "".to_string();
-        for line in fn_body.lines() {
-            if line.len() > 0 {
-                fn_body_indented += &format!("{}    {}\n", indentation, line);
-            } else {
-                fn_body_indented += "\n"
-            }
+    let mut fn_body_indented = "".to_string();
+    for line in fn_body.lines() {
+        if line.len() > 0 {
+            fn_body_indented += &format!("{}    {}\n", indentation, line);
+        } else {
+            fn_body_indented += "\n"
         }
-        let fn_body_indented = fn_body_indented.trim_end();
+    }
 
-        format!(
-            r#"{indentation}{maybe_static_class_func}{swift_class_func_name}{maybe_generics}({params}) async{maybe_ret} {{
-{fn_body_indented}
-{indentation}}}
-{callback_wrapper}"#,
-            indentation = indentation,
-            maybe_static_class_func = maybe_static_class_func,
-            swift_class_func_name = public_func_fn_name,
-            maybe_generics = maybe_generics,
-            params = params,
-            maybe_ret = maybe_return,
-            fn_body_indented = fn_body_indented,
-            callback_wrapper = callback_wrapper
-        )
-    } else {
-        format!(
-            r#"{indentation}{maybe_static_class_func}{swift_class_func_name}{maybe_generics}({params}){maybe_ret} {{
-{indentation}    {call_rust}
-{indentation}}}"#,
-            indentation = indentation,
-            maybe_static_class_func = maybe_static_class_func,
-            swift_class_func_name = public_func_fn_name,
-            maybe_generics = maybe_generics,
-            params = params,
-            maybe_ret = maybe_return,
-            call_rust = call_rust,
-        )
-    };
-    func_definition
+    (fn_body_indented.trim_end().to_string(), callback_wrapper)
+}
+
+/// Generate support for the Swift closures that a function passes to Rust.
+///
+/// Each closure is retained in a class until Rust drops the boxed function, and Rust calls
+/// the closure through an exported function.
+///
+/// ```no_rust,ignore
+/// class __private__SwiftCallback$some_function$param0 {
+///     let callback: (UInt8) -> UInt16
+///     ...
+/// }
+///
+/// @_cdecl("__swift_bridge__$some_function$param0")
+/// func __swift_bridge__some_function_param0 (_ callback: UnsafeMutableRawPointer, _ arg0: UInt8) -> UInt16 {
+///     let ret = Unmanaged<__private__SwiftCallback$some_function$param0>.fromOpaque(callback).takeUnretainedValue().callback(arg0)
+///     return ret
+/// }
+///
+/// @_cdecl("__swift_bridge__$some_function$_free$param0")
+/// func __swift_bridge__some_function__free_param0 (_ callback: UnsafeMutableRawPointer) {
+///     Unmanaged<__private__SwiftCallback$some_function$param0>.fromOpaque(callback).release()
+/// }
+/// ```
+pub(super) fn gen_swift_callbacks_passed_to_rust(
+    function: &ParsedExternFn,
+    types: &TypeDeclarations,
+) -> String {
+    let mut swift_callbacks = "".to_string();
+
+    let prefixed_fn_name = function.prefixed_fn_name();
+
+    for (idx, boxed_fn) in function.args_filtered_to_boxed_fns(types) {
+        let class_name = function.swift_callback_class_name(idx);
+        let closure_ty = boxed_fn.to_swift_closure_type(types);
+
+        let call_link_name = function.call_boxed_fn_link_name(idx);
+        let free_link_name = function.free_boxed_fn_link_name(idx);
+
+        let mut params = "".to_string();
+        let mut args = vec![];
+        for (arg_idx, ty) in boxed_fn.params.iter().enumerate() {
+            let arg_name = format!("arg{arg_idx}");
+            let type_pos = TypePosition::FnArg(HostLang::Swift, arg_idx);
+
+            params += &format!(", _ {arg_name}: {}", ty.to_swift_type(type_pos, types));
+            args.push(ty.convert_ffi_value_to_swift_value(&arg_name, type_pos, types));
+        }
+        let args = args.join(", ");
+
+        let maybe_ret = if boxed_fn.ret.is_null() {
+            "".to_string()
+        } else {
+            format!(
+                " -> {}",
+                boxed_fn
+                    .ret
+                    .to_swift_type(TypePosition::FnReturn(HostLang::Swift), types)
+            )
+        };
+
+        let call_callback = format!(
+            "Unmanaged<{class_name}>.fromOpaque(callback).takeUnretainedValue().callback({args})"
+        );
+        // The return value conversion can reference its expression more than once, so we store
+        // the closure's return value instead of calling the closure multiple times.
+        let call_callback = if boxed_fn.ret.is_null() {
+            call_callback
+        } else {
+            let ret = boxed_fn.ret.convert_swift_expression_to_ffi_type(
+                "ret",
+                types,
+                TypePosition::FnReturn(HostLang::Swift),
+            );
+            format!("let ret = {call_callback}\n    return {ret}")
+        };
+
+        swift_callbacks += &format!(
+            r#"
+class {class_name} {{
+    let callback: {closure_ty}
+
+    init(_ callback: @escaping {closure_ty}) {{
+        self.callback = callback
+    }}
+}}
+
+@_cdecl("{call_link_name}")
+func {prefixed_fn_name}_param{idx} (_ callback: UnsafeMutableRawPointer{params}){maybe_ret} {{
+    {call_callback}
+}}
+
+@_cdecl("{free_link_name}")
+func {prefixed_fn_name}__free_param{idx} (_ callback: UnsafeMutableRawPointer) {{
+    Unmanaged<{class_name}>.fromOpaque(callback).release()
+}}
+"#
+        );
+    }
+
+    swift_callbacks
 }
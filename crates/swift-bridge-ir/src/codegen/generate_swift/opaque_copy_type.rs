@@ -1,4 +1,7 @@
+use crate::codegen::generate_swift::availability::opaque_type_availability;
+use crate::codegen::generate_swift::doc_comment::swift_doc_comment;
 use crate::codegen::generate_swift::generate_swift_class_methods;
+use crate::codegen::generate_swift::operators::generate_swift_operators;
 use crate::parse::OpaqueForeignTypeDeclaration;
 use crate::{ParsedExternFn, TypeDeclarations, SWIFT_BRIDGE_PREFIX};
 use std::collections::HashMap;
@@ -15,22 +18,44 @@ pub(super) fn generate_opaque_copy_struct(
     let class_methods = generate_swift_class_methods(
         &type_name,
         associated_funcs_and_methods,
+        false,
         types,
         swift_bridge_path,
     );
 
+    let availability = opaque_type_availability(ty);
     let mut extensions = "".to_string();
 
-    append_methods_extension(&mut extensions, type_name, &class_methods.initializers);
     append_methods_extension(
         &mut extensions,
         type_name,
+        &availability,
+        &class_methods.initializers,
+    );
+    append_methods_extension(
+        &mut extensions,
+        type_name,
+        &availability,
         &class_methods.owned_self_methods,
     );
-    append_methods_extension(&mut extensions, type_name, &class_methods.ref_self_methods);
+    append_methods_extension(
+        &mut extensions,
+        type_name,
+        &availability,
+        &class_methods.ref_self_methods,
+    );
 
     if class_methods.owned_self_methods.len() > 0 {};
 
+    extensions += &generate_swift_operators(
+        type_name,
+        ty.ty_name_ident(),
+        &ty.attributes.operators,
+        false,
+        &availability,
+        types,
+    );
+
     let struct_definition = if !ty.attributes.already_declared {
         generate_struct_definition(ty, types)
     } else {
@@ -50,10 +75,12 @@ fn generate_struct_definition(
 ) -> String {
     let type_name = ty.ty.to_string();
     let generics = ty.generics.angle_bracketed_generic_placeholders_string();
+    let doc_comment = swift_doc_comment(ty.attributes.doc_comment.as_ref(), "");
+    let availability = opaque_type_availability(ty);
 
     let declare_struct = if ty.generics.is_empty() {
         format!(
-            r#"public struct {type_name} {{
+            r#"{doc_comment}{availability}public struct {type_name} {{
     fileprivate var bytes: {prefix}${type_name}
 
     func intoFfiRepr() -> {prefix}${type_name} {{
@@ -65,7 +92,7 @@ fn generate_struct_definition(
         )
     } else {
         format!(
-            r#"public struct {type_name}{generics} {{
+            r#"{doc_comment}{availability}public struct {type_name}{generics} {{
     fileprivate var bytes: SwiftBridgeGenericCopyTypeFfiRepr
 }}"#,
             type_name = type_name,
@@ -75,7 +102,7 @@ fn generate_struct_definition(
 
     let ffi_repr_conversion = if ty.generics.is_empty() {
         format!(
-            r#"extension {prefix}${type_name} {{
+            r#"{availability}extension {prefix}${type_name} {{
     func intoSwiftRepr() -> {type_name} {{
         {type_name}(bytes: self)
     }}
@@ -88,13 +115,13 @@ fn generate_struct_definition(
         let bounds = ty.generics.rust_opaque_type_swift_generic_bounds(types);
 
         format!(
-            r#"extension {type_name}
+            r#"{availability}extension {type_name}
 where {bounds} {{
     func intoFfiRepr() -> {ffi_repr_name} {{
         self.bytes as! {ffi_repr_name}
     }}
 }}
-extension {ffi_repr_name} {{
+{availability}extension {ffi_repr_name} {{
     func intoSwiftRepr() -> {type_name}{generics} {{
         {type_name}(bytes: self)
     }}
@@ -117,14 +144,19 @@ extension {ffi_repr_name}: SwiftBridgeGenericCopyTypeFfiRepr {{}}"#,
     )
 }
 
-fn append_methods_extension(extensions: &mut String, type_name: &str, methods: &[String]) {
+fn append_methods_extension(
+    extensions: &mut String,
+    type_name: &str,
+    availability: &str,
+    methods: &[String],
+) {
     if methods.len() == 0 {
         return;
     }
 
     *extensions += &format!(
         r#"
-extension {type_name} {{
+{availability}extension {type_name} {{
 "#,
         type_name = type_name
     );
@@ -0,0 +1,119 @@
+use crate::bridged_type::{BridgedType, TypePosition};
+use crate::parse::{HostLang, OperatorTrait};
+use crate::TypeDeclarations;
+use proc_macro2::Ident;
+use syn::{parse_quote, Type};
+
+/// Generates the Swift operators and subscripts that call a type's Rust operator trait
+/// implementations.
+///
+/// ```no_rust,ignore
+/// extension SomeTypeRef {
+///     public static func + (lhs: SomeTypeRef, rhs: SomeTypeRef) -> SomeType {
+///         SomeType(ptr: __swift_bridge__$SomeType$_add(lhs.ptr, rhs.ptr))
+///     }
+/// }
+/// ```
+pub(super) fn generate_swift_operators(
+    extension_ty: &str,
+    ty_name: &Ident,
+    operators: &[OperatorTrait],
+    operands_by_ref: bool,
+    availability: &str,
+    types: &TypeDeclarations,
+) -> String {
+    if operators.is_empty() {
+        return "".to_string();
+    }
+
+    let arg_pos = TypePosition::FnArg(HostLang::Rust, 0);
+    let return_pos = TypePosition::FnReturn(HostLang::Rust);
+
+    let operand_ty: Type = if operands_by_ref {
+        parse_quote! { &#ty_name }
+    } else {
+        parse_quote! { #ty_name }
+    };
+    let operand = BridgedType::new_with_type(&operand_ty, types).unwrap();
+    let operand_swift_ty = operand.to_swift_type(arg_pos, types);
+
+    let owned = BridgedType::new_with_type(&parse_quote! { #ty_name }, types).unwrap();
+    let owned_swift_ty = owned.to_swift_type(return_pos, types);
+
+    let mut members = vec![];
+    for operator in operators {
+        let link_name = operator.link_name(ty_name);
+
+        let member = match operator {
+            OperatorTrait::Add(_)
+            | OperatorTrait::Sub(_)
+            | OperatorTrait::Mul(_)
+            | OperatorTrait::Div(_) => {
+                let swift_operator = operator.swift_binary_operator().unwrap();
+                let lhs = operand.convert_swift_expression_to_ffi_type("lhs", types, arg_pos);
+                let rhs = operand.convert_swift_expression_to_ffi_type("rhs", types, arg_pos);
+                let ret = owned.convert_ffi_value_to_swift_value(
+                    &format!("{link_name}({lhs}, {rhs})"),
+                    return_pos,
+                    types,
+                );
+
+                format!(
+                    r#"    public static func {swift_operator} (lhs: {operand_swift_ty}, rhs: {operand_swift_ty}) -> {owned_swift_ty} {{
+        {ret}
+    }}"#
+                )
+            }
+            OperatorTrait::Neg(_) => {
+                let operand_value =
+                    operand.convert_swift_expression_to_ffi_type("operand", types, arg_pos);
+                let ret = owned.convert_ffi_value_to_swift_value(
+                    &format!("{link_name}({operand_value})"),
+                    return_pos,
+                    types,
+                );
+
+                format!(
+                    r#"    public static prefix func - (operand: {operand_swift_ty}) -> {owned_swift_ty} {{
+        {ret}
+    }}"#
+                )
+            }
+            OperatorTrait::Index { index, output, .. } => {
+                let index_ty = BridgedType::new_with_type(index, types).unwrap();
+                let index_swift_ty = index_ty.to_swift_type(arg_pos, types);
+                let index_value = index_ty.convert_swift_expression_to_ffi_type(
+                    "index",
+                    types,
+                    TypePosition::FnArg(HostLang::Rust, 1),
+                );
+
+                let output_ty = BridgedType::new_with_type(output, types).unwrap();
+                let output_swift_ty = output_ty.to_swift_type(return_pos, types);
+
+                let this = operand.convert_swift_expression_to_ffi_type("self", types, arg_pos);
+                let ret = output_ty.convert_ffi_value_to_swift_value(
+                    &format!("{link_name}({this}, {index_value})"),
+                    return_pos,
+                    types,
+                );
+
+                format!(
+                    r#"    public subscript(index: {index_swift_ty}) -> {output_swift_ty} {{
+        {ret}
+    }}"#
+                )
+            }
+        };
+
+        members.push(member);
+    }
+
+    let members = members.join("\n\n");
+    format!(
+        r#"
+{availability}extension {extension_ty} {{
+{members}
+}}"#
+    )
+}
@@ -1,4 +1,15 @@
-use crate::bridged_type::{BridgedType, SharedEnum, StructFields, TypePosition};
+use crate::bridged_type::{BridgedType, EnumVariant, SharedEnum, StructFields, TypePosition};
+use crate::codegen::generate_swift::availability::{
+    shared_enum_availability, swift_availability_attributes,
+};
+use crate::codegen::generate_swift::codable::{
+    codable_protocol, decode_value, decode_value_from_decoder, encode_value, generate_coding_keys,
+};
+use crate::codegen::generate_swift::comparable::{
+    equal_values, hash_value, lexicographic_less_than,
+};
+use crate::codegen::generate_swift::doc_comment::swift_doc_comment;
+use crate::parse::TypeDeclarations;
 use crate::SwiftBridgeModule;
 
 impl SwiftBridgeModule {
@@ -9,6 +20,7 @@ impl SwiftBridgeModule {
         }
 
         let enum_name = shared_enum.swift_name_string();
+        let availability = shared_enum_availability(shared_enum);
         let enum_ffi_name = shared_enum.ffi_name_string();
         let option_ffi_name = shared_enum.ffi_option_name_string();
 
@@ -17,6 +29,9 @@ impl SwiftBridgeModule {
         let mut convert_ffi_repr_to_swift = "\n".to_string();
         let all_variants_empty = shared_enum.all_variants_empty();
         for variant in shared_enum.variants.iter() {
+            let doc_comment = swift_doc_comment(variant.doc_comment.as_ref(), "    ");
+            let availability =
+                swift_availability_attributes(&[], variant.deprecated.as_ref(), "    ");
             let v = match &variant.fields {
                 StructFields::Named(named_fields) => {
                     let mut params = vec![];
@@ -29,7 +44,9 @@ impl SwiftBridgeModule {
                     let params = params.join(", ");
                     format!(
                         r#"
-    case {name}({params})"#,
+{doc_comment}{availability}    case {name}({params})"#,
+                        doc_comment = doc_comment,
+                        availability = availability,
                         name = variant.name,
                         params = params,
                     )
@@ -45,7 +62,9 @@ impl SwiftBridgeModule {
                     let params = params.join(", ");
                     format!(
                         r#"
-    case {name}({params})"#,
+{doc_comment}{availability}    case {name}({params})"#,
+                        doc_comment = doc_comment,
+                        availability = availability,
                         name = variant.name,
                         params = params,
                     )
@@ -53,7 +72,9 @@ impl SwiftBridgeModule {
                 StructFields::Unit => {
                     format!(
                         r#"
-    case {name}"#,
+{doc_comment}{availability}    case {name}"#,
+                        doc_comment = doc_comment,
+                        availability = availability,
                         name = variant.name
                     )
                 }
@@ -95,7 +116,7 @@ impl SwiftBridgeModule {
         } else {
             format!(
                 r#"
-extension {enum_name}: Vectorizable {{
+{availability}extension {enum_name}: Vectorizable {{
     public static func vecOfSelfNew() -> UnsafeMutableRawPointer {{
         __swift_bridge__$Vec_{enum_name}$new()
     }}
@@ -137,7 +158,7 @@ extension {enum_name}: Vectorizable {{
         let derive_debug_impl = if shared_enum.derive.debug {
             format!(
                 r#"
-extension {enum_name}: CustomDebugStringConvertible {{
+{availability}extension {enum_name}: CustomDebugStringConvertible {{
     public var debugDescription: String {{
         RustString(ptr: __swift_bridge__${enum_name}$Debug(self.intoFfiRepr())).toString()
     }}
@@ -148,18 +169,18 @@ extension {enum_name}: CustomDebugStringConvertible {{
         };
 
         let swift_enum = format!(
-            r#"public enum {enum_name} {{{variants}}}
-extension {enum_name} {{
+            r#"{doc_comment}{availability}public enum {enum_name} {{{variants}}}
+{availability}extension {enum_name} {{
     func intoFfiRepr() -> {ffi_repr_name} {{
         switch self {{{convert_swift_to_ffi_repr}}}
     }}
 }}
-extension {enum_ffi_name} {{
+{availability}extension {enum_ffi_name} {{
     func intoSwiftRepr() -> {enum_name} {{
         switch self.tag {{{convert_ffi_repr_to_swift}}}
     }}
 }}
-extension {option_ffi_name} {{
+{availability}extension {option_ffi_name} {{
     @inline(__always)
     func intoSwiftRepr() -> Optional<{enum_name}> {{
         if self.is_some {{
@@ -176,16 +197,718 @@ extension {option_ffi_name} {{
             return {option_ffi_name}(is_some: false, val: {ffi_repr_name}())
         }}
     }}
-}}{vectorizable_impl}{derive_debug_impl}"#,
+}}{vectorizable_impl}{derive_debug_impl}{comparable_impl}{codable_impl}"#,
+            doc_comment = swift_doc_comment(shared_enum.doc_comment.as_ref(), ""),
             enum_name = enum_name,
             enum_ffi_name = enum_ffi_name,
             option_ffi_name = option_ffi_name,
             ffi_repr_name = shared_enum.ffi_name_string(),
             variants = variants,
             convert_swift_to_ffi_repr = convert_swift_to_ffi_repr,
-            convert_ffi_repr_to_swift = convert_ffi_repr_to_swift
+            convert_ffi_repr_to_swift = convert_ffi_repr_to_swift,
+            comparable_impl = self.generate_shared_enum_comparable_string(shared_enum),
+            codable_impl = self.generate_shared_enum_codable_string(shared_enum),
         );
 
         Some(swift_enum)
     }
+
+    /// Generate `Equatable`, `Hashable` and `Comparable` conformances that match the enum's
+    /// `PartialEq`, `Hash` and `PartialOrd` derives.
+    ///
+    /// Like Rust, variants are ordered by their declaration order and variants of the same kind
+    /// are ordered by their fields.
+    fn generate_shared_enum_comparable_string(&self, shared_enum: &SharedEnum) -> String {
+        // Swift's `Hashable` and `Comparable` both require `Equatable`.
+        if !shared_enum.derive.partial_eq {
+            return "".to_string();
+        }
+
+        let enum_name = shared_enum.swift_name_string();
+        let availability = shared_enum_availability(shared_enum);
+        let variants: Vec<ComparableVariant> = shared_enum
+            .variants
+            .iter()
+            .map(|variant| ComparableVariant::new(variant, &self.types))
+            .collect();
+        let has_multiple_variants = variants.len() > 1;
+
+        let mut equal_cases = vec![];
+        for variant in variants.iter() {
+            let equal = if variant.fields.is_empty() {
+                "true".to_string()
+            } else {
+                variant
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, (_, ty))| {
+                        equal_values(ty, &format!("lhs{}", idx), &format!("rhs{}", idx))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" && ")
+            };
+            equal_cases.push(format!(
+                r#"        case {pattern}:
+            return {equal}"#,
+                pattern = variant.pair_pattern()
+            ));
+        }
+        if has_multiple_variants {
+            equal_cases.push(
+                r#"        default:
+            return false"#
+                    .to_string(),
+            );
+        }
+        let mut conformances = format!(
+            r#"
+{availability}extension {enum_name}: Equatable {{
+    public static func == (lhs: {enum_name}, rhs: {enum_name}) -> Bool {{
+        switch (lhs, rhs) {{
+{equal_cases}
+        }}
+    }}
+}}"#,
+            equal_cases = equal_cases.join("\n")
+        );
+
+        if shared_enum.derive.hash {
+            let mut hash_cases = vec![];
+            for (variant_idx, variant) in variants.iter().enumerate() {
+                let mut lines = vec![format!("            hasher.combine({})", variant_idx)];
+                for (idx, (_, ty)) in variant.fields.iter().enumerate() {
+                    lines.push(format!(
+                        "            {}",
+                        hash_value(ty, &format!("value{}", idx))
+                    ));
+                }
+                hash_cases.push(format!(
+                    r#"        case {pattern}:
+{lines}"#,
+                    pattern = variant.pattern("value"),
+                    lines = lines.join("\n")
+                ));
+            }
+            conformances += &format!(
+                r#"
+{availability}extension {enum_name}: Hashable {{
+    public func hash(into hasher: inout Hasher) {{
+        switch self {{
+{hash_cases}
+        }}
+    }}
+}}"#,
+                hash_cases = hash_cases.join("\n")
+            );
+        }
+
+        if shared_enum.derive.partial_ord {
+            let variant_indices: Vec<String> = variants
+                .iter()
+                .enumerate()
+                .map(|(idx, variant)| {
+                    format!(
+                        r#"            case .{name}:
+                return {idx}"#,
+                        name = variant.name
+                    )
+                })
+                .collect();
+
+            let mut less_than_cases = vec![];
+            for variant in variants.iter().filter(|v| !v.fields.is_empty()) {
+                let values: Vec<(String, String, String)> = variant
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, (_, ty))| {
+                        (ty.to_string(), format!("lhs{}", idx), format!("rhs{}", idx))
+                    })
+                    .collect();
+                less_than_cases.push(format!(
+                    r#"        case {pattern}:
+{less_than}"#,
+                    pattern = variant.pair_pattern(),
+                    less_than = lexicographic_less_than(&values, "            ")
+                ));
+            }
+            let compare_variants = "variantIndex(lhs) < variantIndex(rhs)";
+            let less_than = if less_than_cases.is_empty() {
+                format!("        return {}", compare_variants)
+            } else {
+                if has_multiple_variants {
+                    less_than_cases.push(format!(
+                        r#"        default:
+            return {compare_variants}"#
+                    ));
+                }
+                format!(
+                    r#"        switch (lhs, rhs) {{
+{less_than_cases}
+        }}"#,
+                    less_than_cases = less_than_cases.join("\n")
+                )
+            };
+
+            conformances += &format!(
+                r#"
+{availability}extension {enum_name}: Comparable {{
+    public static func < (lhs: {enum_name}, rhs: {enum_name}) -> Bool {{
+        func variantIndex(_ value: {enum_name}) -> Int {{
+            switch value {{
+{variant_indices}
+            }}
+        }}
+
+{less_than}
+    }}
+}}"#,
+                variant_indices = variant_indices.join("\n")
+            );
+        }
+
+        conformances
+    }
+
+    /// Generate a `Codable` conformance that matches the enum's serde derives and serde's
+    /// externally, internally or adjacently tagged representation.
+    fn generate_shared_enum_codable_string(&self, shared_enum: &SharedEnum) -> String {
+        let protocol =
+            match codable_protocol(shared_enum.derive.serialize, shared_enum.derive.deserialize) {
+                Some(protocol) => protocol,
+                None => return "".to_string(),
+            };
+
+        let enum_name = shared_enum.swift_name_string();
+        let availability = shared_enum_availability(shared_enum);
+        let variants: Vec<CodableVariant> = shared_enum
+            .variants
+            .iter()
+            .map(|variant| CodableVariant::new(variant, shared_enum, &self.types))
+            .collect();
+
+        let tag = shared_enum.serde.tag.as_ref().map(|tag| tag.value());
+        let content = shared_enum
+            .serde
+            .content
+            .as_ref()
+            .map(|content| content.value());
+        let has_unit_variant = variants.iter().any(|v| v.is_unit());
+        let has_data_variant = variants.iter().any(|v| !v.is_unit());
+
+        let mut sections = vec![];
+
+        match (&tag, &content) {
+            (Some(tag), content) => {
+                let mut keys = vec![("tag".to_string(), tag.to_string())];
+                if let Some(content) = content {
+                    keys.push(("content".to_string(), content.to_string()));
+                }
+                sections.push(generate_coding_keys("TagCodingKeys", &keys));
+            }
+            (None, _) => {
+                if has_data_variant {
+                    let keys: Vec<(String, String)> = variants
+                        .iter()
+                        .map(|v| (v.name.clone(), v.key.clone()))
+                        .collect();
+                    sections.push(generate_coding_keys("CodingKeys", &keys));
+                }
+            }
+        };
+        for variant in variants.iter() {
+            if let CodableVariantFields::Named(fields) = &variant.fields {
+                let keys: Vec<(String, String)> = fields
+                    .iter()
+                    .map(|f| (f.name.clone(), f.key.clone()))
+                    .collect();
+                sections.push(generate_coding_keys(
+                    &variant.fields_coding_keys_name(),
+                    &keys,
+                ));
+            }
+        }
+
+        let tag_lines = |variant: &CodableVariant| {
+            vec![
+                "var container = encoder.container(keyedBy: TagCodingKeys.self)".to_string(),
+                format!(r#"try container.encode("{}", forKey: .tag)"#, variant.key),
+            ]
+        };
+
+        if shared_enum.derive.serialize {
+            let mut cases = vec![];
+
+            for variant in variants.iter() {
+                let body = match (&tag, &content) {
+                    (None, _) => {
+                        if variant.is_unit() {
+                            vec![
+                                "var container = encoder.singleValueContainer()".to_string(),
+                                format!(r#"try container.encode("{}")"#, variant.key),
+                            ]
+                        } else {
+                            let mut body = vec![
+                                "var container = encoder.container(keyedBy: CodingKeys.self)"
+                                    .to_string(),
+                            ];
+                            body.extend(
+                                variant.encode_payload("container", &format!(".{}", variant.name)),
+                            );
+                            body
+                        }
+                    }
+                    (Some(_), None) => match &variant.fields {
+                        CodableVariantFields::Unit => tag_lines(variant),
+                        CodableVariantFields::Newtype(ty) => {
+                            let value = if ty == "RustString" {
+                                "_0.toString()"
+                            } else {
+                                "_0"
+                            };
+                            let mut body = vec![format!("try {}.encode(to: encoder)", value)];
+                            body.extend(tag_lines(variant));
+                            body
+                        }
+                        CodableVariantFields::Tuple(_) => {
+                            vec![r#"throw EncodingError.invalidValue(self, EncodingError.Context(codingPath: encoder.codingPath, debugDescription: "Internally tagged tuple variants are not supported"))"#.to_string()]
+                        }
+                        CodableVariantFields::Named(fields) => {
+                            let mut body = tag_lines(variant);
+                            body.push(format!(
+                                "var variantContainer = encoder.container(keyedBy: {}.self)",
+                                variant.fields_coding_keys_name()
+                            ));
+                            for field in fields {
+                                body.push(encode_value(
+                                    &field.ty,
+                                    &field.name,
+                                    "variantContainer",
+                                    Some(&format!(".{}", field.name)),
+                                ));
+                            }
+                            body
+                        }
+                    },
+                    (Some(_), Some(_)) => {
+                        let mut body = tag_lines(variant);
+                        if !variant.is_unit() {
+                            body.extend(variant.encode_payload("container", ".content"));
+                        }
+                        body
+                    }
+                };
+
+                let pattern = match (&tag, &content, &variant.fields) {
+                    (Some(_), None, CodableVariantFields::Tuple(_)) => {
+                        format!(".{}", variant.name)
+                    }
+                    _ => variant.pattern(),
+                };
+                cases.push(format!(
+                    "        case {pattern}:\n            {body}",
+                    body = body.join("\n            ")
+                ));
+            }
+
+            sections.push(format!(
+                r#"    public func encode(to encoder: Encoder) throws {{
+        switch self {{
+{cases}
+        }}
+    }}"#,
+                cases = cases.join("\n")
+            ));
+        }
+
+        if shared_enum.derive.deserialize {
+            let mut body = vec![];
+
+            match (&tag, &content) {
+                (None, _) => {
+                    let unit_variants: Vec<&CodableVariant> =
+                        variants.iter().filter(|v| v.is_unit()).collect();
+
+                    if has_unit_variant && !has_data_variant {
+                        body.push("let container = try decoder.singleValueContainer()".to_string());
+                        body.push("let variant = try container.decode(String.self)".to_string());
+                        body.push("switch variant {".to_string());
+                        for v in unit_variants.iter() {
+                            body.push(format!("case \"{}\":", v.key));
+                            body.push(format!("    self = .{}", v.name));
+                        }
+                        body.push("default:".to_string());
+                        body.push(r#"    throw DecodingError.dataCorruptedError(in: container, debugDescription: "Unknown variant \(variant)")"#.to_string());
+                        body.push("}".to_string());
+                    } else if has_data_variant {
+                        if has_unit_variant {
+                            body.push("if let variant = try? decoder.singleValueContainer().decode(String.self) {".to_string());
+                            body.push("    switch variant {".to_string());
+                            for v in unit_variants.iter() {
+                                body.push(format!("    case \"{}\":", v.key));
+                                body.push(format!("        self = .{}", v.name));
+                                body.push("        return".to_string());
+                            }
+                            body.push("    default:".to_string());
+                            body.push("        break".to_string());
+                            body.push("    }".to_string());
+                            body.push("}".to_string());
+                        }
+
+                        body.push(
+                            "let container = try decoder.container(keyedBy: CodingKeys.self)"
+                                .to_string(),
+                        );
+                        body.push("guard let key = container.allKeys.first else {".to_string());
+                        body.push(r#"    throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Expected an enum variant"))"#.to_string());
+                        body.push("}".to_string());
+                        body.push("switch key {".to_string());
+                        for variant in variants.iter() {
+                            body.push(format!("case .{}:", variant.name));
+                            let (setup, value) =
+                                variant.decode_payload("container", &format!(".{}", variant.name));
+                            for line in setup {
+                                body.push(format!("    {}", line));
+                            }
+                            body.push(format!("    self = {}", value));
+                        }
+                        body.push("}".to_string());
+                    } else {
+                        body.push(r#"throw DecodingError.dataCorrupted(DecodingError.Context(codingPath: decoder.codingPath, debugDescription: "Enum has no variants"))"#.to_string());
+                    }
+                }
+                (Some(_), content) => {
+                    body.push(
+                        "let container = try decoder.container(keyedBy: TagCodingKeys.self)"
+                            .to_string(),
+                    );
+                    body.push(
+                        "let tag = try container.decode(String.self, forKey: .tag)".to_string(),
+                    );
+                    body.push("switch tag {".to_string());
+                    for variant in variants.iter() {
+                        body.push(format!("case \"{}\":", variant.key));
+
+                        let (setup, value) = match (content, &variant.fields) {
+                            (_, CodableVariantFields::Unit) => {
+                                (vec![], format!(".{}", variant.name))
+                            }
+                            (Some(_), _) => variant.decode_payload("container", ".content"),
+                            (None, CodableVariantFields::Newtype(ty)) => (
+                                vec![],
+                                format!(".{}({})", variant.name, decode_value_from_decoder(ty)),
+                            ),
+                            (None, CodableVariantFields::Tuple(_)) => {
+                                body.push(r#"    throw DecodingError.dataCorruptedError(forKey: .tag, in: container, debugDescription: "Internally tagged tuple variants are not supported")"#.to_string());
+                                continue;
+                            }
+                            (None, CodableVariantFields::Named(_)) => (
+                                vec![format!(
+                                    "let variantContainer = try decoder.container(keyedBy: {}.self)",
+                                    variant.fields_coding_keys_name()
+                                )],
+                                variant.construct("variantContainer"),
+                            ),
+                        };
+                        for line in setup {
+                            body.push(format!("    {}", line));
+                        }
+                        body.push(format!("    self = {}", value));
+                    }
+                    body.push("default:".to_string());
+                    body.push(r#"    throw DecodingError.dataCorruptedError(forKey: .tag, in: container, debugDescription: "Unknown variant \(tag)")"#.to_string());
+                    body.push("}".to_string());
+                }
+            };
+
+            sections.push(format!(
+                r#"    public init(from decoder: Decoder) throws {{
+        {body}
+    }}"#,
+                body = body.join("\n        ")
+            ));
+        }
+
+        format!(
+            r#"
+{availability}extension {enum_name}: {protocol} {{
+{sections}
+}}"#,
+            sections = sections.join("\n\n")
+        )
+    }
+}
+
+/// An enum variant's information that is needed to generate its `Equatable`, `Hashable` and
+/// `Comparable` conformances.
+struct ComparableVariant {
+    /// The name of the Swift enum case.
+    name: String,
+    /// The label (for named fields) and Swift type of each of the variant's fields.
+    fields: Vec<(Option<String>, String)>,
+}
+
+impl ComparableVariant {
+    fn new(variant: &EnumVariant, types: &TypeDeclarations) -> Self {
+        let swift_type = |ty| {
+            BridgedType::new_with_type(ty, types)
+                .unwrap()
+                .to_swift_type(TypePosition::SharedStructField, types)
+        };
+
+        let fields = match &variant.fields {
+            StructFields::Named(named) => named
+                .iter()
+                .map(|field| (Some(field.name.to_string()), swift_type(&field.ty)))
+                .collect(),
+            StructFields::Unnamed(unnamed) => unnamed
+                .iter()
+                .map(|field| (None, swift_type(&field.ty)))
+                .collect(),
+            StructFields::Unit => vec![],
+        };
+
+        ComparableVariant {
+            name: variant.name.to_string(),
+            fields,
+        }
+    }
+
+    /// A pattern that binds the variant's fields to `{prefix}0`, `{prefix}1`, ...
+    ///
+    /// `.Variant`, `let .Variant(value0, value1)` or `let .Variant(field: value0)`
+    fn pattern(&self, prefix: &str) -> String {
+        if self.fields.is_empty() {
+            return format!(".{}", self.name);
+        }
+
+        format!("let {}", self.bindings(prefix))
+    }
+
+    /// A pattern that matches two values that are both this variant.
+    ///
+    /// `(.Variant, .Variant)` or `let (.Variant(lhs0), .Variant(rhs0))`
+    fn pair_pattern(&self) -> String {
+        if self.fields.is_empty() {
+            return format!("(.{name}, .{name})", name = self.name);
+        }
+
+        format!("let ({}, {})", self.bindings("lhs"), self.bindings("rhs"))
+    }
+
+    /// `.Variant(value0, field: value1)`
+    fn bindings(&self, prefix: &str) -> String {
+        let bindings: Vec<String> = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(idx, (label, _))| match label {
+                Some(label) => format!("{}: {}{}", label, prefix, idx),
+                None => format!("{}{}", prefix, idx),
+            })
+            .collect();
+
+        format!(".{}({})", self.name, bindings.join(", "))
+    }
+}
+
+/// An enum variant's information that is needed to generate its `Codable` conformance.
+struct CodableVariant {
+    /// The name of the Swift enum case.
+    name: String,
+    /// The name that serde uses for the variant.
+    key: String,
+    fields: CodableVariantFields,
+}
+
+enum CodableVariantFields {
+    /// `Variant` or `Variant()` or `Variant {}`
+    Unit,
+    /// `Variant(T)`. Holds the Swift type.
+    Newtype(String),
+    /// `Variant(T, U)`. Holds the Swift types.
+    Tuple(Vec<String>),
+    /// `Variant { field: T }`
+    Named(Vec<CodableNamedField>),
+}
+
+struct CodableNamedField {
+    name: String,
+    /// The name that serde uses for the field.
+    key: String,
+    /// The Swift type.
+    ty: String,
+}
+
+impl CodableVariant {
+    fn new(variant: &EnumVariant, shared_enum: &SharedEnum, types: &TypeDeclarations) -> Self {
+        let swift_type = |ty| {
+            BridgedType::new_with_type(ty, types)
+                .unwrap()
+                .to_swift_type(TypePosition::SharedStructField, types)
+        };
+
+        let name = variant.name.to_string();
+        let key = variant
+            .serde
+            .serialized_name(&name, shared_enum.serde.rename_all, true);
+
+        let fields = match &variant.fields {
+            _ if variant.fields.is_empty() => CodableVariantFields::Unit,
+            StructFields::Named(named) => CodableVariantFields::Named(
+                named
+                    .iter()
+                    .map(|field| {
+                        let field_name = field.name.to_string();
+                        CodableNamedField {
+                            key: field.serde.serialized_name(
+                                &field_name,
+                                variant.serde.rename_all,
+                                false,
+                            ),
+                            name: field_name,
+                            ty: swift_type(&field.ty),
+                        }
+                    })
+                    .collect(),
+            ),
+            StructFields::Unnamed(unnamed) if unnamed.len() == 1 => {
+                CodableVariantFields::Newtype(swift_type(&unnamed[0].ty))
+            }
+            StructFields::Unnamed(unnamed) => {
+                CodableVariantFields::Tuple(unnamed.iter().map(|f| swift_type(&f.ty)).collect())
+            }
+            StructFields::Unit => CodableVariantFields::Unit,
+        };
+
+        CodableVariant { name, key, fields }
+    }
+
+    fn is_unit(&self) -> bool {
+        matches!(self.fields, CodableVariantFields::Unit)
+    }
+
+    /// VariantCodingKeys
+    fn fields_coding_keys_name(&self) -> String {
+        format!("{}CodingKeys", self.name)
+    }
+
+    /// A pattern that binds the variant's fields.
+    ///
+    /// `.Variant`, `let .Variant(_0, _1)` or `let .Variant(field: field)`
+    fn pattern(&self) -> String {
+        match &self.fields {
+            CodableVariantFields::Unit => format!(".{}", self.name),
+            CodableVariantFields::Newtype(_) => format!("let .{}(_0)", self.name),
+            CodableVariantFields::Tuple(tys) => {
+                let bindings: Vec<String> = (0..tys.len()).map(|idx| format!("_{}", idx)).collect();
+                format!("let .{}({})", self.name, bindings.join(", "))
+            }
+            CodableVariantFields::Named(fields) => {
+                let bindings: Vec<String> = fields
+                    .iter()
+                    .map(|f| format!("{name}: {name}", name = f.name))
+                    .collect();
+                format!("let .{}({})", self.name, bindings.join(", "))
+            }
+        }
+    }
+
+    /// Encode the variant's fields under a key of a keyed container.
+    fn encode_payload(&self, container: &str, key: &str) -> Vec<String> {
+        match &self.fields {
+            CodableVariantFields::Unit => vec![],
+            CodableVariantFields::Newtype(ty) => vec![encode_value(ty, "_0", container, Some(key))],
+            CodableVariantFields::Tuple(tys) => {
+                let mut lines = vec![format!(
+                    "var variantContainer = {}.nestedUnkeyedContainer(forKey: {})",
+                    container, key
+                )];
+                for (idx, ty) in tys.iter().enumerate() {
+                    lines.push(encode_value(
+                        ty,
+                        &format!("_{}", idx),
+                        "variantContainer",
+                        None,
+                    ));
+                }
+                lines
+            }
+            CodableVariantFields::Named(fields) => {
+                let mut lines = vec![format!(
+                    "var variantContainer = {}.nestedContainer(keyedBy: {}.self, forKey: {})",
+                    container,
+                    self.fields_coding_keys_name(),
+                    key
+                )];
+                for field in fields {
+                    lines.push(encode_value(
+                        &field.ty,
+                        &field.name,
+                        "variantContainer",
+                        Some(&format!(".{}", field.name)),
+                    ));
+                }
+                lines
+            }
+        }
+    }
+
+    /// Decode the variant's fields from a key of a keyed container.
+    ///
+    /// Returns the statements that need to run before the variant can be constructed, along with
+    /// the expression that constructs the variant.
+    fn decode_payload(&self, container: &str, key: &str) -> (Vec<String>, String) {
+        match &self.fields {
+            CodableVariantFields::Unit => (vec![], format!(".{}", self.name)),
+            CodableVariantFields::Newtype(ty) => (
+                vec![],
+                format!(".{}({})", self.name, decode_value(ty, container, Some(key))),
+            ),
+            CodableVariantFields::Tuple(_) => (
+                vec![format!(
+                    "var variantContainer = try {}.nestedUnkeyedContainer(forKey: {})",
+                    container, key
+                )],
+                self.construct("variantContainer"),
+            ),
+            CodableVariantFields::Named(_) => (
+                vec![format!(
+                    "let variantContainer = try {}.nestedContainer(keyedBy: {}.self, forKey: {})",
+                    container,
+                    self.fields_coding_keys_name(),
+                    key
+                )],
+                self.construct("variantContainer"),
+            ),
+        }
+    }
+
+    /// Construct the variant from fields that are decoded from the given container.
+    ///
+    /// `.Variant(try container.decode(UInt8.self))`
+    /// `.Variant(field: try container.decode(UInt8.self, forKey: .field))`
+    fn construct(&self, container: &str) -> String {
+        let args: Vec<String> = match &self.fields {
+            CodableVariantFields::Unit => return format!(".{}", self.name),
+            CodableVariantFields::Newtype(ty) => vec![decode_value(ty, container, None)],
+            CodableVariantFields::Tuple(tys) => tys
+                .iter()
+                .map(|ty| decode_value(ty, container, None))
+                .collect(),
+            CodableVariantFields::Named(fields) => fields
+                .iter()
+                .map(|field| {
+                    format!(
+                        "{}: {}",
+                        field.name,
+                        decode_value(&field.ty, container, Some(&format!(".{}", field.name)))
+                    )
+                })
+                .collect(),
+        };
+
+        format!(".{}({})", self.name, args.join(", "))
+    }
 }
@@ -1,5 +1,14 @@
 use crate::bridged_type::shared_struct::StructField;
 use crate::bridged_type::{BridgedType, SharedStruct, StructFields, StructSwiftRepr, TypePosition};
+use crate::codegen::generate_swift::availability::shared_struct_availability;
+use crate::codegen::generate_swift::codable::{
+    codable_protocol, decode_value, encode_value, generate_coding_keys,
+};
+use crate::codegen::generate_swift::comparable::{
+    equal_values, hash_value, lexicographic_less_than,
+};
+use crate::codegen::generate_swift::doc_comment::swift_doc_comment;
+use crate::codegen::generate_swift::operators::generate_swift_operators;
 use crate::SwiftBridgeModule;
 
 impl SwiftBridgeModule {
@@ -13,6 +22,7 @@ impl SwiftBridgeModule {
         }
 
         let struct_name = &shared_struct.swift_name_string();
+        let availability = shared_struct_availability(shared_struct);
         let option_ffi_name = shared_struct.ffi_option_name_string();
 
         match shared_struct.swift_repr {
@@ -50,7 +60,7 @@ impl SwiftBridgeModule {
                 // No need to generate any code. Swift will automatically generate a
                 //  struct from our C header typedef that we generate for this struct.
                 let swift_struct = format!(
-                    r#"public struct {struct_name} {{{fields}
+                    r#"{doc_comment}{availability}public struct {struct_name} {{{fields}
     public init({initializer_params}) {{{initializer_body}}}
 
     @inline(__always)
@@ -58,13 +68,13 @@ impl SwiftBridgeModule {
         {convert_swift_to_ffi_repr}
     }}
 }}
-extension {ffi_repr_name} {{
+{availability}extension {ffi_repr_name} {{
     @inline(__always)
     func intoSwiftRepr() -> {struct_name} {{
         {convert_ffi_repr_to_swift}
     }}
 }}
-extension {option_ffi_name} {{
+{availability}extension {option_ffi_name} {{
     @inline(__always)
     func intoSwiftRepr() -> Optional<{struct_name}> {{
         if self.is_some {{
@@ -82,7 +92,8 @@ extension {option_ffi_name} {{
             return {option_ffi_name}(is_some: false, val: {ffi_repr_name}())
         }}
     }}
-}}"#,
+}}{comparable_impl}{operators}{codable_impl}{vectorizable_impl}"#,
+                    doc_comment = swift_doc_comment(shared_struct.doc_comment.as_ref(), ""),
                     struct_name = struct_name,
                     initializer_params = initializer_params,
                     initializer_body = initializer_body,
@@ -90,7 +101,18 @@ extension {option_ffi_name} {{
                     ffi_repr_name = shared_struct.ffi_name_string(),
                     option_ffi_name = option_ffi_name,
                     convert_swift_to_ffi_repr = convert_swift_to_ffi_repr,
-                    convert_ffi_repr_to_swift = convert_ffi_repr_to_swift
+                    convert_ffi_repr_to_swift = convert_ffi_repr_to_swift,
+                    comparable_impl = self.generate_shared_struct_comparable_string(shared_struct),
+                    operators = generate_swift_operators(
+                        struct_name,
+                        &shared_struct.name,
+                        &shared_struct.operators,
+                        false,
+                        &availability,
+                        &self.types,
+                    ),
+                    codable_impl = self.generate_shared_struct_codable_string(shared_struct),
+                    vectorizable_impl = generate_vectorizable_struct_string(shared_struct),
                 );
 
                 Some(swift_struct)
@@ -158,7 +180,8 @@ extension {option_ffi_name} {{
             let bridged_ty = BridgedType::new_with_type(field.field_type(), &self.types).unwrap();
 
             fields += &format!(
-                "    public var {}: {}\n",
+                "{}    public var {}: {}\n",
+                swift_doc_comment(field.doc_comment(), "    "),
                 field.swift_name_string(),
                 bridged_ty.to_swift_type(TypePosition::SharedStructField, &self.types)
             );
@@ -170,4 +193,275 @@ extension {option_ffi_name} {{
 
         fields
     }
+
+    /// Generate `Equatable`, `Hashable` and `Comparable` conformances that match the struct's
+    /// `PartialEq`, `Hash` and `PartialOrd` derives.
+    ///
+    /// Fields are compared in declaration order, the same way that Rust's derives compare them.
+    fn generate_shared_struct_comparable_string(&self, shared_struct: &SharedStruct) -> String {
+        // Swift's `Hashable` and `Comparable` both require `Equatable`.
+        if !shared_struct.derives.partial_eq {
+            return "".to_string();
+        }
+
+        let struct_name = shared_struct.swift_name_string();
+        let availability = shared_struct_availability(shared_struct);
+        let fields: Vec<(String, String)> = shared_struct
+            .fields
+            .normalized_fields()
+            .iter()
+            .map(|field| {
+                let ty = BridgedType::new_with_type(&field.ty, &self.types)
+                    .unwrap()
+                    .to_swift_type(TypePosition::SharedStructField, &self.types);
+                (ty, field.ffi_field_name())
+            })
+            .collect();
+
+        let equal = if fields.is_empty() {
+            "true".to_string()
+        } else {
+            fields
+                .iter()
+                .map(|(ty, name)| {
+                    equal_values(ty, &format!("lhs.{}", name), &format!("rhs.{}", name))
+                })
+                .collect::<Vec<_>>()
+                .join(" && ")
+        };
+        let mut conformances = format!(
+            r#"
+{availability}extension {struct_name}: Equatable {{
+    public static func == (lhs: {struct_name}, rhs: {struct_name}) -> Bool {{
+        return {equal}
+    }}
+}}"#
+        );
+
+        if shared_struct.derives.hash {
+            let hash: Vec<String> = fields
+                .iter()
+                .map(|(ty, name)| {
+                    format!("\n        {}", hash_value(ty, &format!("self.{}", name)))
+                })
+                .collect();
+            conformances += &format!(
+                r#"
+{availability}extension {struct_name}: Hashable {{
+    public func hash(into hasher: inout Hasher) {{{hash}
+    }}
+}}"#,
+                hash = hash.join("")
+            );
+        }
+
+        if shared_struct.derives.partial_ord {
+            let values: Vec<(String, String, String)> = fields
+                .iter()
+                .map(|(ty, name)| {
+                    (
+                        ty.to_string(),
+                        format!("lhs.{}", name),
+                        format!("rhs.{}", name),
+                    )
+                })
+                .collect();
+            conformances += &format!(
+                r#"
+{availability}extension {struct_name}: Comparable {{
+    public static func < (lhs: {struct_name}, rhs: {struct_name}) -> Bool {{
+{less_than}
+    }}
+}}"#,
+                less_than = lexicographic_less_than(&values, "        ")
+            );
+        }
+
+        conformances
+    }
+
+    /// Generate a `Codable` conformance that matches the struct's serde derives.
+    fn generate_shared_struct_codable_string(&self, shared_struct: &SharedStruct) -> String {
+        let protocol = match codable_protocol(
+            shared_struct.derives.serialize,
+            shared_struct.derives.deserialize,
+        ) {
+            Some(protocol) => protocol,
+            None => return "".to_string(),
+        };
+
+        let struct_name = shared_struct.swift_name_string();
+        let availability = shared_struct_availability(shared_struct);
+        let swift_type = |ty| {
+            BridgedType::new_with_type(ty, &self.types)
+                .unwrap()
+                .to_swift_type(TypePosition::SharedStructField, &self.types)
+        };
+
+        let mut sections = vec![];
+        let mut encode = vec![];
+        let mut init_args = vec![];
+        let decode_container;
+
+        match &shared_struct.fields {
+            StructFields::Named(named) => {
+                let keys: Vec<(String, String)> = named
+                    .iter()
+                    .map(|field| {
+                        let name = field.name.to_string();
+                        let key = field.serde.serialized_name(
+                            &name,
+                            shared_struct.serde.rename_all,
+                            false,
+                        );
+                        (name, key)
+                    })
+                    .collect();
+                sections.push(generate_coding_keys("CodingKeys", &keys));
+
+                encode.push(
+                    "var container = encoder.container(keyedBy: CodingKeys.self)".to_string(),
+                );
+                decode_container =
+                    "let container = try decoder.container(keyedBy: CodingKeys.self)";
+
+                for field in named {
+                    let ty = swift_type(&field.ty);
+                    let key = format!(".{}", field.name);
+                    encode.push(encode_value(
+                        &ty,
+                        &format!("self.{}", field.name),
+                        "container",
+                        Some(&key),
+                    ));
+                    init_args.push(format!(
+                        "{}: {}",
+                        field.name,
+                        decode_value(&ty, "container", Some(&key))
+                    ));
+                }
+            }
+            // Newtype structs are serialized as their inner value.
+            StructFields::Unnamed(unnamed) if unnamed.len() == 1 => {
+                let ty = swift_type(&unnamed[0].ty);
+
+                encode.push("var container = encoder.singleValueContainer()".to_string());
+                encode.push(encode_value(&ty, "self._0", "container", None));
+
+                decode_container = "let container = try decoder.singleValueContainer()";
+                init_args.push(format!("_0: {}", decode_value(&ty, "container", None)));
+            }
+            StructFields::Unnamed(unnamed) => {
+                encode.push("var container = encoder.unkeyedContainer()".to_string());
+                decode_container = "var container = try decoder.unkeyedContainer()";
+
+                for field in unnamed {
+                    let ty = swift_type(&field.ty);
+                    encode.push(encode_value(
+                        &ty,
+                        &format!("self._{}", field.idx),
+                        "container",
+                        None,
+                    ));
+                    init_args.push(format!(
+                        "_{}: {}",
+                        field.idx,
+                        decode_value(&ty, "container", None)
+                    ));
+                }
+            }
+            // Unit structs are serialized as null.
+            StructFields::Unit => {
+                encode.push("var container = encoder.singleValueContainer()".to_string());
+                encode.push("try container.encodeNil()".to_string());
+                decode_container = "_ = try decoder.singleValueContainer()";
+            }
+        };
+
+        // Avoid unused variable warnings for structs such as `struct Foo {}`.
+        let mut decode_container = decode_container.to_string();
+        let is_unit = matches!(shared_struct.fields, StructFields::Unit);
+        if shared_struct.fields.is_empty() && !is_unit {
+            encode[0] = encode[0].replace("var container = ", "_ = ");
+            decode_container = decode_container
+                .replace("let container = ", "_ = ")
+                .replace("var container = ", "_ = ");
+        }
+
+        if shared_struct.derives.serialize {
+            sections.push(format!(
+                r#"    public func encode(to encoder: Encoder) throws {{
+        {encode}
+    }}"#,
+                encode = encode.join("\n        ")
+            ));
+        }
+        if shared_struct.derives.deserialize {
+            sections.push(format!(
+                r#"    public init(from decoder: Decoder) throws {{
+        {decode_container}
+        self.init({init_args})
+    }}"#,
+                init_args = init_args.join(", ")
+            ));
+        }
+
+        format!(
+            r#"
+{availability}extension {struct_name}: {protocol} {{
+{sections}
+}}"#,
+            sections = sections.join("\n\n")
+        )
+    }
+}
+
+/// Lets the struct be stored in a `RustVec`, `RustMap` or `RustSet`.
+fn generate_vectorizable_struct_string(shared_struct: &SharedStruct) -> String {
+    if !shared_struct.is_vectorizable() {
+        return "".to_string();
+    }
+
+    let struct_name = shared_struct.swift_name_string();
+    let availability = shared_struct_availability(shared_struct);
+
+    format!(
+        r#"
+{availability}extension {struct_name}: Vectorizable {{
+    public static func vecOfSelfNew() -> UnsafeMutableRawPointer {{
+        __swift_bridge__$Vec_{struct_name}$new()
+    }}
+
+    public static func vecOfSelfFree(vecPtr: UnsafeMutableRawPointer) {{
+        __swift_bridge__$Vec_{struct_name}$drop(vecPtr)
+    }}
+
+    public static func vecOfSelfPush(vecPtr: UnsafeMutableRawPointer, value: Self) {{
+        __swift_bridge__$Vec_{struct_name}$push(vecPtr, value.intoFfiRepr())
+    }}
+
+    public static func vecOfSelfPop(vecPtr: UnsafeMutableRawPointer) -> Optional<Self> {{
+        let maybeStruct = __swift_bridge__$Vec_{struct_name}$pop(vecPtr)
+        return maybeStruct.intoSwiftRepr()
+    }}
+
+    public static func vecOfSelfGet(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Optional<Self> {{
+        let maybeStruct = __swift_bridge__$Vec_{struct_name}$get(vecPtr, index)
+        return maybeStruct.intoSwiftRepr()
+    }}
+
+    public static func vecOfSelfGetMut(vecPtr: UnsafeMutableRawPointer, index: UInt) -> Optional<Self> {{
+        let maybeStruct = __swift_bridge__$Vec_{struct_name}$get_mut(vecPtr, index)
+        return maybeStruct.intoSwiftRepr()
+    }}
+
+    public static func vecOfSelfAsPtr(vecPtr: UnsafeMutableRawPointer) -> UnsafePointer<Self> {{
+        UnsafePointer<Self>(OpaquePointer(__swift_bridge__$Vec_{struct_name}$as_ptr(vecPtr)))
+    }}
+
+    public static func vecOfSelfLen(vecPtr: UnsafeMutableRawPointer) -> UInt {{
+        __swift_bridge__$Vec_{struct_name}$len(vecPtr)
+    }}
+}}"#
+    )
 }
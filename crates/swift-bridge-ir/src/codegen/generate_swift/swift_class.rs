@@ -1,4 +1,7 @@
-use crate::codegen::generate_swift::{generate_swift_class_methods, ClassProtocols};
+use crate::codegen::generate_swift::availability::opaque_type_availability;
+use crate::codegen::generate_swift::doc_comment::swift_doc_comment;
+use crate::codegen::generate_swift::operators::generate_swift_operators;
+use crate::codegen::generate_swift::{generate_swift_class_methods, ClassMethods, ClassProtocols};
 use crate::parse::OpaqueForeignTypeDeclaration;
 use crate::{ParsedExternFn, TypeDeclarations, SWIFT_BRIDGE_PREFIX};
 use std::collections::HashMap;
@@ -16,33 +19,60 @@ pub(super) fn generate_swift_class(
     let class_methods = generate_swift_class_methods(
         &type_name,
         associated_funcs_and_methods,
+        declares_class(ty),
         types,
         swift_bridge_path,
     );
 
-    create_class_declaration(
-        ty,
-        class_protocols,
-        &class_methods.initializers,
-        &class_methods.owned_self_methods,
-        &class_methods.ref_self_methods,
-        &class_methods.ref_mut_self_methods,
-        types,
+    create_class_declaration(ty, class_protocols, &class_methods, types)
+}
+
+/// Whether or not this module declares the type's classes, as opposed to only extending classes
+/// that were declared elsewhere.
+fn declares_class(ty: &OpaqueForeignTypeDeclaration) -> bool {
+    let is_concrete_generic = !ty.generics.is_empty() && !ty.attributes.declare_generic;
+
+    !ty.attributes.already_declared && !is_concrete_generic
+}
+
+/// Generate the class that holds a strong reference to an `Arc<SomeType>`.
+///
+/// Only `&self` methods are available, since the value might be shared with Rust.
+pub(super) fn generate_swift_arc_class(ty: &OpaqueForeignTypeDeclaration) -> String {
+    let type_name = ty.ty_name_ident();
+    let free_func_name = ty.free_arc_rust_opaque_type_ffi_name();
+    let availability = opaque_type_availability(ty);
+
+    format!(
+        r#"{availability}public class {type_name}Arc: {type_name}Ref {{
+    public override init(ptr: UnsafeMutableRawPointer) {{
+        super.init(ptr: ptr)
+    }}
+
+    deinit {{
+        {free_func_name}(ptr)
+    }}
+}}"#
     )
 }
 
 fn create_class_declaration(
     ty: &OpaqueForeignTypeDeclaration,
     class_protocols: &ClassProtocols,
-    initializers: &[String],
-    owned_self_methods: &[String],
-    ref_self_methods: &[String],
-    ref_mut_self_methods: &[String],
+    class_methods: &ClassMethods,
     types: &TypeDeclarations,
 ) -> String {
+    let initializers = &class_methods.initializers;
+    let owned_self_methods = &class_methods.owned_self_methods;
+    let ref_self_methods = &class_methods.ref_self_methods;
+    let ref_mut_self_methods = &class_methods.ref_mut_self_methods;
+
     let type_name = &ty.ty_name_ident().to_string();
     let generics = ty.generics.angle_bracketed_generic_placeholders_string();
 
+    let doc_comment = swift_doc_comment(ty.attributes.doc_comment.as_ref(), "");
+    let availability = opaque_type_availability(ty);
+
     let mut class_decl = {
         let free_func_call = if ty.generics.len() == 0 {
             format!("{}${}$_free(ptr)", SWIFT_BRIDGE_PREFIX, type_name)
@@ -51,7 +81,7 @@ fn create_class_declaration(
         };
 
         format!(
-            r#"public class {type_name}{generics}: {type_name}RefMut{generics} {{
+            r#"{doc_comment}{availability}public class {type_name}{generics}: {type_name}RefMut{generics} {{
     var isOwned: Bool = true
 
     public override init(ptr: UnsafeMutableRawPointer) {{
@@ -64,33 +94,44 @@ fn create_class_declaration(
         }}
     }}
 }}"#,
+            doc_comment = doc_comment,
+            availability = availability,
             type_name = type_name,
             generics = generics,
             free_func_call = free_func_call
         )
     };
 
+    let class_properties = |properties: &[String]| -> String {
+        properties
+            .iter()
+            .map(|property| format!("\n\n{property}"))
+            .collect()
+    };
+
     let mut class_ref_mut_decl = {
+        let properties = class_properties(&class_methods.ref_mut_self_properties);
         format!(
             r#"
-public class {type_name}RefMut{generics}: {type_name}Ref{generics} {{
+{availability}public class {type_name}RefMut{generics}: {type_name}Ref{generics} {{
     public override init(ptr: UnsafeMutableRawPointer) {{
         super.init(ptr: ptr)
-    }}
+    }}{properties}
 }}"#,
             type_name = type_name,
             generics = generics
         )
     };
     let mut class_ref_decl = {
+        let properties = class_properties(&class_methods.ref_self_properties);
         format!(
             r#"
-public class {type_name}Ref{generics} {{
+{availability}public class {type_name}Ref{generics} {{
     var ptr: UnsafeMutableRawPointer
 
     public init(ptr: UnsafeMutableRawPointer) {{
         self.ptr = ptr
-    }}
+    }}{properties}
 }}"#,
             type_name = type_name,
             generics = generics
@@ -113,7 +154,7 @@ public class {type_name}Ref{generics} {{
 
         class_ref_decl += &format!(
             r#"
-extension {type_name}Ref: Identifiable {{{identifiable_var}}}"#,
+{availability}extension {type_name}Ref: Identifiable {{{identifiable_var}}}"#,
             type_name = type_name,
             identifiable_var = identifiable_var,
         );
@@ -125,7 +166,7 @@ extension {type_name}Ref: Identifiable {{{identifiable_var}}}"#,
         let initializers: String = initializers.join("\n\n");
         format!(
             r#"
-extension {type_name} {{
+{availability}extension {type_name} {{
 {initializers}
 }}"#,
             type_name = type_name,
@@ -139,7 +180,7 @@ extension {type_name} {{
         let owned_instance_methods: String = owned_self_methods.join("\n\n");
         format!(
             r#"
-extension {type_name} {{
+{availability}extension {type_name} {{
 {owned_instance_methods}
 }}"#,
             type_name = type_name,
@@ -153,7 +194,7 @@ extension {type_name} {{
         let ref_instance_methods: String = ref_self_methods.join("\n\n");
         format!(
             r#"
-extension {type_name}Ref {{
+{availability}extension {type_name}Ref {{
 {ref_instance_methods}
 }}"#,
             type_name = type_name,
@@ -167,7 +208,7 @@ extension {type_name}Ref {{
         let ref_mut_instance_methods: String = ref_mut_self_methods.join("\n\n");
         format!(
             r#"
-extension {type_name}RefMut {{
+{availability}extension {type_name}RefMut {{
 {ref_mut_instance_methods}
 }}"#,
             type_name = type_name,
@@ -177,7 +218,7 @@ extension {type_name}RefMut {{
 
     let is_concrete_generic = ty.generics.len() > 0 && !ty.attributes.declare_generic;
 
-    if ty.attributes.already_declared || is_concrete_generic {
+    if !declares_class(ty) {
         class_decl = "".to_string();
         class_ref_decl = "".to_string();
         class_ref_mut_decl = "".to_string();
@@ -187,7 +228,7 @@ extension {type_name}RefMut {{
     if is_concrete_generic {
         generic_freer = format!(
             r#"
-extension {type_name}: SwiftBridgeGenericFreer
+{availability}extension {type_name}: SwiftBridgeGenericFreer
 where {swift_generic_bounds} {{
     public func rust_free() {{
         {free_func_name}(ptr)
@@ -203,7 +244,7 @@ where {swift_generic_bounds} {{
             let ty_name = ty.ty_name_ident();
             format!(
                 r#"
-extension {ty_name}Ref: Equatable {{
+{availability}extension {ty_name}Ref: Equatable {{
     public static func == (lhs: {ty_name}Ref, rhs: {ty_name}Ref) -> Bool {{
         __swift_bridge__${ty_name}$_partial_eq(rhs.ptr, lhs.ptr)
     }}
@@ -218,7 +259,7 @@ extension {ty_name}Ref: Equatable {{
             let ty_name = ty.ty_name_ident();
             format!(
                 r#"
-extension {ty_name}Ref: Hashable{{
+{availability}extension {ty_name}Ref: Hashable{{
     public func hash(into hasher: inout Hasher){{
         hasher.combine(__swift_bridge__${ty_name}$_hash(self.ptr))
     }}
@@ -229,9 +270,84 @@ extension {ty_name}Ref: Hashable{{
             "".to_string()
         }
     };
+    let comparable_method: String = {
+        if ty.attributes.comparable {
+            let ty_name = ty.ty_name_ident();
+            // `__swift_bridge__$T$_partial_cmp` returns -1, 0 or 1 for less, equal or greater,
+            // and 2 if the values cannot be compared.
+            // We implement every comparison operator since the ones that Swift derives from `<`
+            // would treat values that cannot be compared as ordered, unlike Rust's `PartialOrd`.
+            format!(
+                r#"
+{availability}extension {ty_name}Ref: Comparable {{
+    public static func < (lhs: {ty_name}Ref, rhs: {ty_name}Ref) -> Bool {{
+        __swift_bridge__${ty_name}$_partial_cmp(lhs.ptr, rhs.ptr) == -1
+    }}
+
+    public static func <= (lhs: {ty_name}Ref, rhs: {ty_name}Ref) -> Bool {{
+        let ordering = __swift_bridge__${ty_name}$_partial_cmp(lhs.ptr, rhs.ptr)
+        return ordering == -1 || ordering == 0
+    }}
+
+    public static func > (lhs: {ty_name}Ref, rhs: {ty_name}Ref) -> Bool {{
+        __swift_bridge__${ty_name}$_partial_cmp(lhs.ptr, rhs.ptr) == 1
+    }}
+
+    public static func >= (lhs: {ty_name}Ref, rhs: {ty_name}Ref) -> Bool {{
+        let ordering = __swift_bridge__${ty_name}$_partial_cmp(lhs.ptr, rhs.ptr)
+        return ordering == 0 || ordering == 1
+    }}
+}}"#,
+            )
+        } else {
+            "".to_string()
+        }
+    };
+    let operators = if ty.host_lang.is_rust() {
+        generate_swift_operators(
+            &format!("{}Ref", ty.ty_name_ident()),
+            ty.ty_name_ident(),
+            &ty.attributes.operators,
+            true,
+            &availability,
+            types,
+        )
+    } else {
+        "".to_string()
+    };
+    let display_method: String = {
+        if ty.attributes.display {
+            let ty_name = ty.ty_name_ident();
+            format!(
+                r#"
+{availability}extension {ty_name}Ref: CustomStringConvertible {{
+    public var description: String {{
+        RustString(ptr: __swift_bridge__${ty_name}$_display(self.ptr)).toString()
+    }}
+}}"#,
+            )
+        } else {
+            "".to_string()
+        }
+    };
+    let debug_method: String = {
+        if ty.attributes.debug {
+            let ty_name = ty.ty_name_ident();
+            format!(
+                r#"
+{availability}extension {ty_name}Ref: CustomDebugStringConvertible {{
+    public var debugDescription: String {{
+        RustString(ptr: __swift_bridge__${ty_name}$_debug(self.ptr)).toString()
+    }}
+}}"#,
+            )
+        } else {
+            "".to_string()
+        }
+    };
     let class = format!(
         r#"
-{class_decl}{initializers}{owned_instance_methods}{class_ref_decl}{ref_mut_instance_methods}{class_ref_mut_decl}{ref_instance_methods}{generic_freer}{equatable_method}{hashable_method}"#,
+{class_decl}{initializers}{owned_instance_methods}{class_ref_decl}{ref_mut_instance_methods}{class_ref_mut_decl}{ref_instance_methods}{generic_freer}{equatable_method}{hashable_method}{comparable_method}{operators}{display_method}{debug_method}"#,
         class_decl = class_decl,
         class_ref_decl = class_ref_mut_decl,
         class_ref_mut_decl = class_ref_decl,
@@ -241,6 +357,10 @@ extension {ty_name}Ref: Hashable{{
         ref_instance_methods = ref_instance_methods,
         equatable_method = equatable_method,
         hashable_method = hashable_method,
+        comparable_method = comparable_method,
+        operators = operators,
+        display_method = display_method,
+        debug_method = debug_method,
     );
 
     return class;
@@ -0,0 +1,96 @@
+use crate::bridged_type::{pat_type_pat_is_self, BridgeableType, BridgedType, TypePosition};
+use crate::parse::{HostLang, OpaqueForeignTypeDeclaration};
+use crate::{ParsedExternFn, TypeDeclarations};
+use quote::ToTokens;
+use syn::FnArg;
+
+/// Generate the Swift protocol for an `extern "Swift"` type that has a
+/// `#[swift_bridge(protocol)]` attribute.
+///
+/// ```no_rust,ignore
+/// public protocol Storage: AnyObject {
+///     func read(key: RustStr) -> Optional<RustVec<UInt8>>
+/// }
+/// ```
+pub(super) fn generate_swift_protocol(
+    ty: &OpaqueForeignTypeDeclaration,
+    functions: &[ParsedExternFn],
+    types: &TypeDeclarations,
+) -> String {
+    let ty_name = ty.ty.to_string();
+
+    let mut requirements = "".to_string();
+
+    for function in functions {
+        let is_protocol_method = function
+            .associated_type
+            .as_ref()
+            .and_then(|t| t.as_opaque())
+            .map(|associated_ty| associated_ty.ty == ty.ty)
+            .unwrap_or(false);
+        if !is_protocol_method || !function.is_method() {
+            continue;
+        }
+
+        requirements += &format!("    func {}\n", protocol_requirement(function, types));
+    }
+
+    format!(
+        r#"public protocol {ty_name}: AnyObject {{
+{requirements}}}
+"#
+    )
+}
+
+// fn read(&self, key: &str) -> Option<Vec<u8>>
+//  becomes
+// read(key: RustStr) -> Optional<RustVec<UInt8>>
+fn protocol_requirement(function: &ParsedExternFn, types: &TypeDeclarations) -> String {
+    let fn_name = if let Some(swift_name) = function.swift_name_override.as_ref() {
+        swift_name.value()
+    } else {
+        function.func.sig.ident.to_string()
+    };
+
+    let mut params = vec![];
+    for arg in function.func.sig.inputs.iter() {
+        let pat_ty = match arg {
+            FnArg::Typed(pat_ty) => pat_ty,
+            FnArg::Receiver(_) => continue,
+        };
+        if pat_type_pat_is_self(pat_ty) {
+            continue;
+        }
+
+        // Validated by `validate_swift_protocols` while parsing.
+        let bridged_ty = BridgedType::new_with_type(&pat_ty.ty, types).unwrap();
+        if bridged_ty.only_encoding().is_some() {
+            continue;
+        }
+
+        params.push(format!(
+            "{}: {}",
+            pat_ty.pat.to_token_stream(),
+            bridged_ty.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types)
+        ));
+    }
+
+    // Validated by `validate_swift_protocols` while parsing.
+    let ret = BridgedType::new_with_return_type(&function.func.sig.output, types).unwrap();
+    let ret = if ret.can_be_encoded_with_zero_bytes() {
+        "".to_string()
+    } else {
+        format!(
+            " -> {}",
+            ret.to_swift_type(TypePosition::FnReturn(HostLang::Rust), types)
+        )
+    };
+
+    let maybe_async = if function.sig.asyncness.is_some() {
+        " async"
+    } else {
+        ""
+    };
+
+    format!("{}({}){}{}", fn_name, params.join(", "), maybe_async, ret)
+}
@@ -1,9 +1,11 @@
 use proc_macro2::Ident;
 
 /// Generate the `extension MyRustType: Vectorizable {}` for the Swift side.
-pub(super) fn generate_vectorizable_extension(ty: &Ident) -> String {
+///
+/// `availability` holds the type's `@available` attributes, if any.
+pub(super) fn generate_vectorizable_extension(ty: &Ident, availability: &str) -> String {
     format!(
-        r#"extension {ty}: Vectorizable {{
+        r#"{availability}extension {ty}: Vectorizable {{
     public static func vecOfSelfNew() -> UnsafeMutableRawPointer {{
         __swift_bridge__$Vec_{ty}$new()
     }}
@@ -118,7 +120,7 @@ extension ARustType: Vectorizable {
 "#;
 
         assert_trimmed_generated_equals_trimmed_expected(
-            &generate_vectorizable_extension(&Ident::new("ARustType", Span::call_site())),
+            &generate_vectorizable_extension(&Ident::new("ARustType", Span::call_site()), ""),
             &expected,
         );
     }
@@ -1,6 +1,6 @@
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream};
 use quote::ToTokens;
-use syn::{Error, FnArg, Item, Receiver};
+use syn::{Error, Expr, FnArg, Item, ItemStatic, Receiver};
 use syn::{ForeignItemFn, ForeignItemType, LitStr};
 use syn::{Token, Type};
 
@@ -42,6 +42,11 @@ pub(crate) enum ParseError {
     StructUnrecognizedAttribute { attribute: Ident },
     /// An enum was declared with an unrecognized attribute.
     EnumUnrecognizedAttribute { attribute: Ident },
+    /// A `#[serde(...)]` attribute that we cannot mirror in the generated Swift `Codable`
+    /// conformance.
+    SerdeUnsupportedAttribute { attribute: Ident },
+    /// `#[serde(rename_all = "...")]` with a rule that serde does not support.
+    SerdeInvalidRenameRule { rule: LitStr },
     /// There is no reason to use `swift_repr = "class"` on an empty struct.
     /// It's extra overhead with no advantages.
     EmptyStructHasSwiftReprClass {
@@ -54,10 +59,61 @@ pub(crate) enum ParseError {
     /// We do not currently support passing mutable references to Copy opaque types across FFI.
     // Would need to Box the copy type and pass a pointer between languages.
     ArgCopyAndRefMut { arg: FnArg },
+    /// An argument's `#[swift_bridge(default = ...)]` value can't be used for its type.
+    /// Example: `#[swift_bridge(default = 300)] arg: u8`
+    InvalidArgumentDefault { value: Expr, ty: Box<Type> },
+    /// An `extern "Swift"` function's argument uses `#[swift_bridge(default = ...)]`. Rust always
+    /// passes every argument, so the default would never be used.
+    /// Example: `fn some_function(#[swift_bridge(default = 10)] arg: u8);`
+    ArgumentDefaultOnSwiftFn { value: Expr },
     /// There was an unsupported item in the module, such as a `use` statement.
     InvalidModuleItem { item: Item },
+    /// A `static mut` item in a bridge module.
+    /// Example: `static mut COUNTER: u32 = 0;`
+    MutableStatic { item: ItemStatic },
     /// The associated_to attribute is used for only an associated method.
     InvalidAssociatedTo { self_: FnArg },
+    /// `#[swift_bridge(protocol)]` was used on a type that isn't in an `extern "Swift"` block.
+    ProtocolOnNonSwiftType { ty: Ident },
+    /// A method on a `#[swift_bridge(protocol)]` type has an argument or return type that can't
+    /// be used in the Swift protocol.
+    /// Example: `fn messages(&self) -> impl Stream<Item = String>;`
+    UnsupportedProtocolMethodType { ty: Box<Type> },
+    /// `#[swift_bridge(trait_object)]` was used on a type that isn't in an `extern "Rust"` block.
+    TraitObjectOnNonRustType { ty: Ident },
+    /// An async `extern "Swift"` function takes a reference other than `&str`.
+    /// Swift uses the arguments after Rust's borrow has ended, so they must be owned.
+    AsyncSwiftFnBorrowedArg { arg: FnArg },
+    /// Only non-async `extern "Rust"` functions can return an `impl Stream<Item = T>`.
+    UnsupportedStreamFn { func: ForeignItemFn },
+    /// Only non-async `extern "Rust"` functions can return an `impl Iterator<Item = T>` or a
+    /// `Box<dyn Iterator<Item = T>>`.
+    UnsupportedIteratorFn { func: ForeignItemFn },
+    /// `#[swift_bridge(local)]` was used on a function that isn't an async `extern "Rust"`
+    /// function.
+    LocalOnNonAsyncRustFn { func: ForeignItemFn },
+    /// A `#[swift_bridge(swift_property)]` function must be a non-async extern "Rust" method
+    /// that either takes `&self` and returns a value, or takes `&mut self` and the new value.
+    InvalidSwiftPropertyFn { func: ForeignItemFn },
+    /// A `#[swift_bridge(swift_property)]` setter does not have a matching getter.
+    SwiftPropertySetterWithoutGetter { func: ForeignItemFn },
+    /// A map key or value, or a set item, that Swift can't hold a reference to.
+    /// Example: `HashMap<u8, Vec<u8>>`
+    UnsupportedCollectionItem { ty: Type },
+    /// A `#[swift_bridge(trait_object)]` type that Swift gets as an `Arc<dyn Trait>` was used in a
+    /// way that needs to own or mutably borrow the trait object.
+    /// Example: a `fn resize(&mut self)` method when there is also a `fn make() -> Arc<dyn Trait>`.
+    ArcTraitObjectOwnedOrMutated {
+        trait_name: Ident,
+        usage: TokenStream,
+    },
+    /// A `SwiftError` that would need to be passed from Rust to Swift.
+    /// Example: `extern "Rust" { fn some_function() -> SwiftError; }`
+    UnsupportedSwiftError { ty: Type },
+    /// A non-async extern "Swift" function returns a `Result` whose error type is not
+    /// `SwiftError`. Swift functions can throw any `Error`.
+    /// Example: `extern "Swift" { fn some_function() -> Result<u8, String>; }`
+    ExternSwiftResultErrorNotSwiftError { ty: Type },
 }
 
 /// An error while parsing a function attribute.
@@ -181,6 +237,17 @@ struct {struct_name};
                 let message = format!(r#"Did not recognize enum attribute "{}"."#, attribute);
                 Error::new_spanned(attribute, message)
             }
+            ParseError::SerdeUnsupportedAttribute { attribute } => {
+                let message = format!(
+                    r#"swift-bridge does not support the serde attribute "{}"."#,
+                    attribute
+                );
+                Error::new_spanned(attribute, message)
+            }
+            ParseError::SerdeInvalidRenameRule { rule } => {
+                let message = format!(r#"Unknown serde rename_all rule "{}"."#, rule.value());
+                Error::new_spanned(rule, message)
+            }
             ParseError::FunctionAttribute(fn_attrib) => match fn_attrib {
                 FunctionAttributeParseError::Identifiable(identifiable) => match identifiable {
                     IdentifiableParseError::MustBeRefSelf { fn_ident } => {
@@ -204,15 +271,103 @@ struct {struct_name};
                     format!(r#"Mutable references to opaque Copy types are not yet supported."#);
                 Error::new_spanned(arg, message)
             }
+            ParseError::InvalidArgumentDefault { value, ty } => {
+                let message = format!(
+                    r#"Default value `{}` can't be used for an argument of type `{}`. Use a literal that fits the argument's type, `None` for an Option, or a string literal for a String or &str."#,
+                    value.to_token_stream(),
+                    ty.to_token_stream()
+                );
+                Error::new_spanned(value, message)
+            }
+            ParseError::ArgumentDefaultOnSwiftFn { value } => {
+                let message = r#"Default values can only be used on the arguments of extern "Rust" functions."#;
+                Error::new_spanned(value, message)
+            }
             ParseError::InvalidModuleItem { item } => {
-                let message = format!(r#"Only `extern` blocks, structs and enums are supported."#);
+                let message =
+                    "Only `extern` blocks, structs, enums, consts and statics are supported.";
                 Error::new_spanned(item, message)
             }
+            ParseError::MutableStatic { item } => {
+                Error::new_spanned(item, "Mutable statics cannot be bridged.")
+            }
             ParseError::InvalidAssociatedTo { self_ } => {
                 let message =
                     format!(r#"The associated_to attribute can only be used on static methods."#);
                 Error::new_spanned(self_, message)
             }
+            ParseError::ProtocolOnNonSwiftType { ty } => {
+                let message = format!(
+                    r#"Only types in an extern "Swift" block can be a protocol. Type {} is not."#,
+                    ty
+                );
+                Error::new_spanned(ty, message)
+            }
+            ParseError::UnsupportedProtocolMethodType { ty } => {
+                let message = format!(
+                    r#"Type {} can't be used in a method on a protocol."#,
+                    ty.to_token_stream()
+                );
+                Error::new_spanned(ty, message)
+            }
+            ParseError::TraitObjectOnNonRustType { ty } => {
+                let message = format!(
+                    r#"Only types in an extern "Rust" block can be a trait object. Type {} is not."#,
+                    ty
+                );
+                Error::new_spanned(ty, message)
+            }
+            ParseError::AsyncSwiftFnBorrowedArg { arg } => {
+                let message =
+                    r#"Async extern "Swift" functions can only take owned arguments or &str."#;
+                Error::new_spanned(arg, message)
+            }
+            ParseError::UnsupportedStreamFn { func } => {
+                let message =
+                    r#"Only non-async extern "Rust" functions can return an `impl Stream`."#;
+                Error::new_spanned(&func.sig, message)
+            }
+            ParseError::UnsupportedIteratorFn { func } => {
+                let message = r#"Only non-async extern "Rust" functions can return an iterator."#;
+                Error::new_spanned(&func.sig, message)
+            }
+            ParseError::LocalOnNonAsyncRustFn { func } => {
+                let message =
+                    r#"The local attribute can only be used on async extern "Rust" functions."#;
+                Error::new_spanned(&func.sig, message)
+            }
+            ParseError::InvalidSwiftPropertyFn { func } => {
+                let message = r#"A swift_property must be a non-async extern "Rust" getter that takes `&self` and returns a value, or a setter that takes `&mut self` and the new value."#;
+                Error::new_spanned(&func.sig, message)
+            }
+            ParseError::SwiftPropertySetterWithoutGetter { func } => {
+                let message = format!(
+                    r#"The swift_property setter "{}" does not have a matching `&self` getter."#,
+                    func.sig.ident
+                );
+                Error::new_spanned(&func.sig, message)
+            }
+            ParseError::UnsupportedCollectionItem { ty } => {
+                let message = format!(
+                    r#"`{}` can't be used as a map key or value or a set item. Supported items are primitives, String, opaque Rust types, transparent enums without data carrying variants and transparent structs that derive Clone."#,
+                    ty.to_token_stream()
+                );
+                Error::new_spanned(ty, message)
+            }
+            ParseError::ArcTraitObjectOwnedOrMutated { trait_name, usage } => {
+                let message = format!(
+                    r#"`{trait_name}` is passed to Swift as an `Arc<dyn {trait_name}>`, so it can't be moved into a `Box<dyn {trait_name}>` or mutably borrowed. Only `&self` methods, `&dyn {trait_name}` and `Arc<dyn {trait_name}>` can be used."#
+                );
+                Error::new_spanned(usage, message)
+            }
+            ParseError::UnsupportedSwiftError { ty } => {
+                let message = r#"SwiftError can only be passed from Swift to Rust, such as in an argument of an extern "Rust" function or in the Result that an extern "Swift" function returns."#;
+                Error::new_spanned(ty, message)
+            }
+            ParseError::ExternSwiftResultErrorNotSwiftError { ty } => {
+                let message = r#"Swift functions can throw any Error, so the Result that an extern "Swift" function returns must use SwiftError as its error type."#;
+                Error::new_spanned(ty, message)
+            }
         }
     }
 }
@@ -8,10 +8,11 @@
 #![deny(missing_docs)]
 
 use proc_macro2::Ident;
+use quote::ToTokens;
 use syn::Path;
 
 use crate::bridge_module_attributes::CfgAttr;
-use crate::parse::TypeDeclarations;
+use crate::parse::{BridgeConst, TypeDeclarations};
 use crate::parsed_extern_fn::ParsedExternFn;
 
 pub use self::bridge_macro_attributes::{SwiftBridgeModuleAttr, SwiftBridgeModuleAttrs};
@@ -60,16 +61,28 @@ pub struct SwiftBridgeModule {
     name: Ident,
     types: TypeDeclarations,
     functions: Vec<ParsedExternFn>,
+    consts: Vec<BridgeConst>,
     swift_bridge_path: Path,
     cfg_attrs: Vec<CfgAttr>,
+    /// Whether the module uses `#[swift_bridge::bridge(catch_panics)]`.
+    catch_panics: bool,
 }
 
 impl SwiftBridgeModule {
     /// Set the path used for `swift_bridge` types such as `swift_bridge::RustString`.
     /// We set this to `crate` when we're inside of the `swift_bridge` crate.
     pub fn set_swift_bridge_path(&mut self, path: Path) {
+        self.types.set_swift_bridge_path(path.to_token_stream());
         self.swift_bridge_path = path;
     }
+
+    /// Catch panics in the module's Rust functions instead of letting them unwind into Swift.
+    pub fn set_catch_panics(&mut self) {
+        self.catch_panics = true;
+        for function in self.functions.iter_mut() {
+            function.catch_panics = true;
+        }
+    }
 }
 
 #[cfg(test)]
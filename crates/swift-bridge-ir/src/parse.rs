@@ -1,20 +1,37 @@
 use crate::bridge_module_attributes::CfgAttr;
-use crate::bridged_type::BridgedType;
+use crate::bridged_type::shared_struct::NormalizedStructField;
+use crate::bridged_type::{
+    collection_item_ref, BridgedType, OpaqueForeignType, StdLibType, TraitObjectPointer,
+};
 use crate::errors::{ParseError, ParseErrors};
 use crate::parse::parse_enum::SharedEnumDeclarationParser;
 use crate::parse::parse_extern_mod::ForeignModParser;
 use crate::parse::parse_struct::SharedStructDeclarationParser;
-use crate::SwiftBridgeModule;
+use crate::parsed_extern_fn::ParsedExternFn;
+use crate::{
+    SwiftBridgeModule, SwiftBridgeModuleAttr, SwiftBridgeModuleAttrs, SWIFT_BRIDGE_PREFIX,
+};
 use proc_macro2::TokenTree;
 use quote::{quote, ToTokens};
+use std::collections::HashSet;
 use syn::parse::{Parse, ParseStream};
-use syn::{Item, ItemMod, Token};
+use syn::{FnArg, GenericArgument, Item, ItemMod, PathArguments, ReturnType, Token, Type};
 
+mod availability;
+mod bridge_const;
+mod doc_comment;
+mod operators;
 mod parse_enum;
 mod parse_extern_mod;
 mod parse_struct;
+mod serde_attributes;
 
 mod type_declarations;
+pub(crate) use self::availability::{parse_available, Deprecation, PlatformVersion};
+pub(crate) use self::bridge_const::BridgeConst;
+pub(crate) use self::doc_comment::doc_comment_from_attributes;
+pub(crate) use self::operators::OperatorTrait;
+pub(crate) use self::serde_attributes::{derive_path_is, SerdeAttributes};
 pub(crate) use self::type_declarations::*;
 
 impl Parse for SwiftBridgeModule {
@@ -59,16 +76,30 @@ impl Parse for SwiftBridgeModuleAndErrors {
             let module_name = item_mod.ident;
 
             let mut functions = vec![];
+            let mut consts = vec![];
             let mut type_declarations = TypeDeclarations::default();
             let mut unresolved_types = vec![];
             let mut cfg_attrs = vec![];
 
+            let mut catch_panics = false;
+
             for attr in item_mod.attrs {
                 match attr.path.to_token_stream().to_string().as_str() {
                     "cfg" => {
                         let cfg: CfgAttr = syn::parse2(attr.tokens)?;
                         cfg_attrs.push(cfg);
                     }
+                    // The `#[swift_bridge::bridge(...)]` attribute is only still on the module
+                    // when parsing a file from a build script.
+                    "swift_bridge :: bridge" | "swift_bridge_macro :: bridge"
+                        if !attr.tokens.is_empty() =>
+                    {
+                        let module_attrs: SwiftBridgeModuleAttrs = attr.parse_args()?;
+                        catch_panics = module_attrs
+                            .attributes
+                            .iter()
+                            .any(|attr| matches!(attr, SwiftBridgeModuleAttr::CatchPanics));
+                    }
                     _ => {}
                 };
             }
@@ -106,6 +137,17 @@ impl Parse for SwiftBridgeModuleAndErrors {
                             TypeDeclaration::Shared(SharedTypeDeclaration::Enum(shared_enum)),
                         );
                     }
+                    Item::Const(item_const) => {
+                        consts.push(BridgeConst::from_item_const(item_const)?);
+                    }
+                    Item::Static(item_static) => {
+                        if item_static.mutability.is_some() {
+                            errors.push(ParseError::MutableStatic { item: item_static });
+                            continue;
+                        }
+
+                        consts.push(BridgeConst::from_item_static(item_static)?);
+                    }
                     invalid_item => {
                         let error = ParseError::InvalidModuleItem { item: invalid_item };
                         errors.push(error);
@@ -123,13 +165,79 @@ impl Parse for SwiftBridgeModuleAndErrors {
                 });
             }
 
-            let module = SwiftBridgeModule {
+            for bridge_const in consts.iter() {
+                if BridgedType::new_with_type(&bridge_const.ty, &type_declarations).is_none() {
+                    errors.push(ParseError::UndeclaredType {
+                        ty: bridge_const.ty.clone(),
+                    });
+                }
+            }
+
+            for setter in functions.iter().filter(|f| f.is_swift_property_setter()) {
+                let property = setter.swift_property_name();
+                let setter_ty = setter
+                    .associated_type
+                    .as_ref()
+                    .and_then(|ty| ty.as_opaque());
+
+                let has_getter = functions.iter().any(|getter| {
+                    let getter_ty = getter
+                        .associated_type
+                        .as_ref()
+                        .and_then(|ty| ty.as_opaque());
+
+                    getter.is_swift_property
+                        && !getter.is_swift_property_setter()
+                        && getter_ty.map(|ty| ty.to_string()) == setter_ty.map(|ty| ty.to_string())
+                        && getter.swift_property_name() == property
+                });
+
+                if !has_getter {
+                    errors.push(ParseError::SwiftPropertySetterWithoutGetter {
+                        func: setter.func.clone(),
+                    });
+                }
+            }
+
+            for function in functions.iter() {
+                let arg_types = function.func.sig.inputs.iter().filter_map(|arg| match arg {
+                    FnArg::Receiver(_) => None,
+                    FnArg::Typed(pat_ty) => Some(pat_ty.ty.as_ref()),
+                });
+                let return_type = match &function.func.sig.output {
+                    ReturnType::Default => None,
+                    ReturnType::Type(_, ty) => Some(ty.as_ref()),
+                };
+
+                for ty in arg_types.chain(return_type) {
+                    validate_collection_items(ty, &type_declarations, &mut errors);
+                }
+            }
+            for field in shared_type_fields(&type_declarations) {
+                validate_collection_items(&field.ty, &type_declarations, &mut errors);
+            }
+
+            validate_arc_trait_objects(&functions, &type_declarations, &mut errors);
+            validate_swift_errors(&functions, &type_declarations, &mut errors);
+            validate_operators(&type_declarations, &mut errors);
+            validate_swift_protocols(&functions, &type_declarations, &mut errors);
+
+            if let Some(scope) = collection_symbol_scope(&functions, &type_declarations) {
+                type_declarations.set_collection_symbol_scope(scope);
+            }
+
+            let mut module = SwiftBridgeModule {
                 name: module_name,
                 types: type_declarations,
                 functions,
+                consts,
                 swift_bridge_path: syn::parse2(quote! { swift_bridge }).unwrap(),
                 cfg_attrs,
+                catch_panics: false,
             };
+            if catch_panics {
+                module.set_catch_panics();
+            }
             Ok(SwiftBridgeModuleAndErrors { module, errors })
         } else {
             return Err(syn::Error::new_spanned(
@@ -140,6 +248,389 @@ impl Parse for SwiftBridgeModuleAndErrors {
     }
 }
 
+/// Push an error for every map key or value, or set item, in the type that Swift can't hold a
+/// reference to. For example, the `Vec<u8>` in `HashMap<u8, Vec<u8>>`.
+fn validate_collection_items(ty: &Type, types: &TypeDeclarations, errors: &mut ParseErrors) {
+    match ty {
+        Type::Path(path) => {
+            let is_collection = matches!(
+                BridgedType::new_with_type(ty, types),
+                Some(BridgedType::StdLib(StdLibType::Map(_) | StdLibType::Set(_)))
+            );
+
+            for segment in path.path.segments.iter() {
+                let args = match &segment.arguments {
+                    PathArguments::AngleBracketed(args) => &args.args,
+                    _ => continue,
+                };
+
+                for arg in args.iter() {
+                    if let GenericArgument::Type(item_ty) = arg {
+                        if is_collection {
+                            let item = BridgedType::new_with_type(item_ty, types);
+                            if let Some(item) = item {
+                                if collection_item_ref(&item, types).is_none() {
+                                    errors.push(ParseError::UnsupportedCollectionItem {
+                                        ty: item_ty.clone(),
+                                    });
+                                }
+                            }
+                        }
+
+                        validate_collection_items(item_ty, types, errors);
+                    }
+                }
+            }
+        }
+        Type::Reference(reference) => validate_collection_items(&reference.elem, types, errors),
+        Type::Tuple(tuple) => {
+            for elem in tuple.elems.iter() {
+                validate_collection_items(elem, types, errors);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Push an error for every use of a `#[swift_bridge(trait_object)]` type that needs to own or
+/// mutably borrow the trait object, if Rust also passes that trait object to Swift as an
+/// `Arc<dyn Trait>`.
+///
+/// Swift uses the same class for `Box<dyn Trait>` and `Arc<dyn Trait>`, and a trait object that
+/// might be shared can't be moved out of its `Arc` or mutably borrowed.
+fn validate_arc_trait_objects(
+    functions: &[ParsedExternFn],
+    types: &TypeDeclarations,
+    errors: &mut ParseErrors,
+) {
+    let mut passed_to_swift_in_arc = HashSet::new();
+    for function in functions.iter() {
+        let (_, to_swift) = arg_and_return_types_by_direction(function);
+        for ty in to_swift {
+            for (_, trait_object) in trait_object_types(ty, types) {
+                if trait_object.trait_object_pointer == Some(TraitObjectPointer::Arc) {
+                    passed_to_swift_in_arc.insert(trait_object.ty.to_string());
+                }
+            }
+        }
+    }
+    if passed_to_swift_in_arc.is_empty() {
+        return;
+    }
+
+    for function in functions.iter() {
+        let method_of_arc_trait_object = function
+            .associated_type
+            .as_ref()
+            .and_then(|ty| ty.as_opaque())
+            .filter(|ty| {
+                ty.attributes.trait_object && passed_to_swift_in_arc.contains(&ty.ty.to_string())
+            });
+        if let (Some(ty), Some(receiver)) =
+            (method_of_arc_trait_object, function.func.sig.receiver())
+        {
+            if function.self_reference().is_none() || function.self_mutability().is_some() {
+                errors.push(ParseError::ArcTraitObjectOwnedOrMutated {
+                    trait_name: ty.ty.clone(),
+                    usage: receiver.to_token_stream(),
+                });
+            }
+        }
+
+        let (to_rust, _) = arg_and_return_types_by_direction(function);
+        for ty in to_rust {
+            for (usage, trait_object) in trait_object_types(ty, types) {
+                let owned_or_mutated = match trait_object.trait_object_pointer {
+                    Some(TraitObjectPointer::Box) => true,
+                    Some(TraitObjectPointer::Ref) => trait_object.mutable,
+                    _ => false,
+                };
+
+                if owned_or_mutated && passed_to_swift_in_arc.contains(&trait_object.ty.to_string())
+                {
+                    errors.push(ParseError::ArcTraitObjectOwnedOrMutated {
+                        trait_name: trait_object.ty.clone(),
+                        usage: usage.to_token_stream(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// `SwiftError` can only be passed from Swift to Rust, either directly or inside of an `Option` or
+/// `Result`. Non-async extern "Swift" functions that return a `Result` become Swift functions that
+/// `throw`, and since Swift can throw any `Error` their error type must be `SwiftError`.
+fn validate_swift_errors(
+    functions: &[ParsedExternFn],
+    types: &TypeDeclarations,
+    errors: &mut ParseErrors,
+) {
+    for function in functions.iter() {
+        let (to_rust, to_swift) = arg_and_return_types_by_direction(function);
+
+        for ty in to_swift {
+            push_swift_error_errors(ty, types, errors);
+        }
+        for ty in to_rust {
+            if is_swift_error(ty, types) {
+                continue;
+            }
+
+            let is_option_or_result =
+                match ty {
+                    Type::Path(path) => path.path.segments.last().is_some_and(|segment| {
+                        segment.ident == "Option" || segment.ident == "Result"
+                    }),
+                    _ => false,
+                };
+            if !is_option_or_result {
+                push_swift_error_errors(ty, types, errors);
+                continue;
+            }
+
+            for child in child_types(ty) {
+                if !is_swift_error(child, types) {
+                    push_swift_error_errors(child, types, errors);
+                }
+            }
+        }
+
+        if function.host_lang.is_swift() && function.func.sig.asyncness.is_none() {
+            if let ReturnType::Type(_, ty) = &function.func.sig.output {
+                if let Type::Path(path) = ty.as_ref() {
+                    let is_result = path
+                        .path
+                        .segments
+                        .last()
+                        .is_some_and(|segment| segment.ident == "Result");
+                    let err_is_swift_error = child_types(ty)
+                        .get(1)
+                        .is_some_and(|err| is_swift_error(err, types));
+
+                    if is_result && !err_is_swift_error {
+                        errors.push(ParseError::ExternSwiftResultErrorNotSwiftError {
+                            ty: ty.as_ref().clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    for field in shared_type_fields(types) {
+        push_swift_error_errors(&field.ty, types, errors);
+    }
+}
+
+/// The fields of all of the module's shared structs and of all of its shared enums' variants.
+fn shared_type_fields(types: &TypeDeclarations) -> Vec<NormalizedStructField> {
+    types
+        .types()
+        .into_iter()
+        .flat_map(|declaration| match declaration {
+            TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct)) => {
+                shared_struct.fields.normalized_fields()
+            }
+            TypeDeclaration::Shared(SharedTypeDeclaration::Enum(shared_enum)) => shared_enum
+                .variants
+                .iter()
+                .flat_map(|variant| variant.fields.normalized_fields())
+                .collect(),
+            TypeDeclaration::Opaque(_) => vec![],
+        })
+        .collect()
+}
+
+/// See [`TypeDeclarations::collection_symbol_scope`].
+///
+/// A module that uses a map or a set either uses it in one of its functions or in one of its
+/// shared types, so there is always a function or a shared type to take the scope from.
+fn collection_symbol_scope(
+    functions: &[ParsedExternFn],
+    types: &TypeDeclarations,
+) -> Option<String> {
+    if let Some(function) = functions.first() {
+        return Some(function.link_name());
+    }
+
+    types
+        .types()
+        .into_iter()
+        .find_map(|declaration| match declaration {
+            TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct))
+                if !shared_struct.already_declared =>
+            {
+                Some(shared_struct.swift_name_string())
+            }
+            TypeDeclaration::Shared(SharedTypeDeclaration::Enum(shared_enum))
+                if !shared_enum.already_declared =>
+            {
+                Some(shared_enum.swift_name_string())
+            }
+            _ => None,
+        })
+        .map(|name| format!("{}${}", SWIFT_BRIDGE_PREFIX, name))
+}
+
+/// Push an error for every `SwiftError` in the type, such as the `SwiftError` in
+/// `Vec<SwiftError>`.
+fn push_swift_error_errors(ty: &Type, types: &TypeDeclarations, errors: &mut ParseErrors) {
+    if is_swift_error(ty, types) {
+        errors.push(ParseError::UnsupportedSwiftError { ty: ty.clone() });
+        return;
+    }
+
+    for child in child_types(ty) {
+        push_swift_error_errors(child, types, errors);
+    }
+}
+
+pub(crate) fn is_swift_error(ty: &Type, types: &TypeDeclarations) -> bool {
+    ty.to_token_stream().to_string() == "SwiftError" && types.get("SwiftError").is_none()
+}
+
+/// The types directly inside of a type, such as the `u8` and `String` in `Result<u8, String>`.
+pub(crate) fn child_types(ty: &Type) -> Vec<&Type> {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .iter()
+            .filter_map(|segment| match &segment.arguments {
+                PathArguments::AngleBracketed(args) => Some(args.args.iter()),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        Type::Reference(reference) => vec![reference.elem.as_ref()],
+        Type::Tuple(tuple) => tuple.elems.iter().collect(),
+        _ => vec![],
+    }
+}
+
+/// The index and output types of an `Index(IndexType, Output = OutputType)` operator attribute
+/// must be types that can be bridged.
+fn validate_operators(types: &TypeDeclarations, errors: &mut ParseErrors) {
+    for declaration in types.types() {
+        let operators = match declaration {
+            TypeDeclaration::Shared(SharedTypeDeclaration::Struct(shared_struct)) => {
+                &shared_struct.operators
+            }
+            TypeDeclaration::Opaque(opaque) => &opaque.attributes.operators,
+            TypeDeclaration::Shared(SharedTypeDeclaration::Enum(_)) => continue,
+        };
+
+        for operator in operators {
+            if let OperatorTrait::Index { index, output, .. } = operator {
+                for ty in [index, output] {
+                    if BridgedType::new_with_type(ty, types).is_none() {
+                        errors.push(ParseError::UndeclaredType {
+                            ty: ty.as_ref().clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Every argument and return type of a method on a `#[swift_bridge(protocol)]` type must be
+/// bridgeable, since it becomes a requirement of the generated Swift protocol.
+fn validate_swift_protocols(
+    functions: &[ParsedExternFn],
+    types: &TypeDeclarations,
+    errors: &mut ParseErrors,
+) {
+    for function in functions {
+        let is_protocol_method = function
+            .associated_type
+            .as_ref()
+            .and_then(|ty| ty.as_opaque())
+            .is_some_and(|ty| ty.attributes.protocol);
+        if !is_protocol_method || !function.is_method() {
+            continue;
+        }
+
+        let arg_types = function.func.sig.inputs.iter().filter_map(|arg| match arg {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(pat_ty) => Some(pat_ty.ty.as_ref()),
+        });
+        let return_type = match &function.func.sig.output {
+            ReturnType::Default => None,
+            ReturnType::Type(_, ty) => Some(ty.as_ref()),
+        };
+
+        for ty in arg_types.chain(return_type) {
+            if BridgedType::new_with_type(ty, types).is_none() {
+                errors.push(ParseError::UnsupportedProtocolMethodType {
+                    ty: Box::new(ty.clone()),
+                });
+            }
+        }
+    }
+}
+
+/// The types that Swift passes to Rust and the types that Rust passes to Swift when calling the
+/// function, not including `self`.
+fn arg_and_return_types_by_direction(function: &ParsedExternFn) -> (Vec<&Type>, Vec<&Type>) {
+    let arg_types: Vec<&Type> = function
+        .func
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(pat_ty) => Some(pat_ty.ty.as_ref()),
+        })
+        .collect();
+    let return_type: Vec<&Type> = match &function.func.sig.output {
+        ReturnType::Default => vec![],
+        ReturnType::Type(_, ty) => vec![ty.as_ref()],
+    };
+
+    if function.host_lang.is_rust() {
+        (arg_types, return_type)
+    } else {
+        (return_type, arg_types)
+    }
+}
+
+/// Every `Box<dyn Trait>`, `Arc<dyn Trait>`, `&dyn Trait` and `&mut dyn Trait` in the type, such
+/// as the `Box<dyn Trait>` in `Option<Box<dyn Trait>>`.
+fn trait_object_types<'a>(
+    ty: &'a Type,
+    types: &TypeDeclarations,
+) -> Vec<(&'a Type, OpaqueForeignType)> {
+    match ty {
+        Type::Path(_) => {
+            let tokens = ty.to_token_stream().to_string();
+            if let Some(trait_object) =
+                OpaqueForeignType::trait_object_from_str_tokens(&tokens, types)
+            {
+                return vec![(ty, trait_object)];
+            }
+        }
+        Type::Reference(reference) => {
+            if let Some(trait_object) =
+                OpaqueForeignType::trait_object_from_type_reference(reference, types)
+            {
+                return vec![(ty, trait_object)];
+            }
+        }
+        _ => {}
+    }
+
+    child_types(ty)
+        .into_iter()
+        .flat_map(|ty| trait_object_types(ty, types))
+        .collect()
+}
+
 // Used to fast-forward our attribute parsing to the next attribute when we've run into an
 // issue parsing the current attribute.
 fn move_input_cursor_to_next_comma(input: ParseStream) {
@@ -207,4 +698,134 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    /// Verify that we can parse `const` and `static` items.
+    #[test]
+    fn parse_consts_and_statics() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod foo {
+                /// The maximum number of items.
+                const MAX_ITEMS: u32 = 100;
+                static VERSION: &str = "1.2.3";
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert_eq!(module.consts.len(), 2);
+
+        assert_eq!(module.consts[0].name, "MAX_ITEMS");
+        assert!(!module.consts[0].is_static);
+        assert_eq!(
+            module.consts[0].doc_comment.as_deref(),
+            Some(" The maximum number of items.")
+        );
+        assert_eq!(
+            module.consts[0].primitive_literal(&module.types).as_deref(),
+            Some("100")
+        );
+
+        assert_eq!(module.consts[1].name, "VERSION");
+        assert!(module.consts[1].is_static);
+        assert!(module.consts[1].primitive_literal(&module.types).is_none());
+    }
+
+    /// Verify that we get an error when a `static` is mutable.
+    #[test]
+    fn error_if_static_is_mutable() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod foo {
+                static mut COUNTER: u32 = 0;
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::MutableStatic { item } => {
+                assert_eq!(item.ident, "COUNTER")
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we get an error when a `const` has a type that can't be bridged.
+    #[test]
+    fn error_if_const_type_undeclared() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod foo {
+                const SOME_CONST: SomeType = SomeType;
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::UndeclaredType { ty } => {
+                assert_eq!(ty.to_token_stream().to_string(), "SomeType")
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we get an error when a map key or value can't be stored in a collection that
+    /// Swift can see.
+    #[test]
+    fn error_if_map_item_unsupported() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod foo {
+                #[swift_bridge(swift_repr = "struct")]
+                struct NotClone {
+                    field: u8,
+                }
+
+                extern "Rust" {
+                    fn some_function(arg: HashMap<u8, Vec<u8>>) -> Option<HashMap<NotClone, u8>>;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        let unsupported: Vec<String> = errors
+            .iter()
+            .map(|error| match error {
+                ParseError::UnsupportedCollectionItem { ty } => ty.to_token_stream().to_string(),
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(unsupported, vec!["Vec < u8 >", "NotClone"]);
+    }
+
+    /// Verify that we get an error when a set that is used in a shared type's field holds an item
+    /// that can't be stored in a collection that Swift can see.
+    #[test]
+    fn error_if_set_item_in_shared_type_field_unsupported() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod foo {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: Option<HashSet<Vec<u8>>>,
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::UnsupportedCollectionItem { ty } => {
+                assert_eq!(ty.to_token_stream().to_string(), "Vec < u8 >");
+            }
+            _ => panic!(),
+        }
+    }
 }
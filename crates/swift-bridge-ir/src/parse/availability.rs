@@ -0,0 +1,134 @@
+use proc_macro2::Ident;
+use syn::parse::ParseStream;
+use syn::{Attribute, Lit, LitStr, Meta, NestedMeta, Token};
+
+/// The platforms that Swift's `@available` attribute accepts.
+const SWIFT_PLATFORMS: &[&str] = &[
+    "iOS",
+    "iOSApplicationExtension",
+    "macOS",
+    "macOSApplicationExtension",
+    "macCatalyst",
+    "macCatalystApplicationExtension",
+    "tvOS",
+    "tvOSApplicationExtension",
+    "watchOS",
+    "watchOSApplicationExtension",
+    "visionOS",
+    "visionOSApplicationExtension",
+];
+
+/// A platform version from `#[swift_bridge(available(iOS = "15", macOS = "12"))]`.
+#[derive(Clone)]
+pub(crate) struct PlatformVersion {
+    /// iOS
+    pub platform: Ident,
+    /// "15"
+    pub version: LitStr,
+}
+
+/// `#[deprecated]`, `#[deprecated = "..."]` or `#[deprecated(since = "...", note = "...")]`
+#[derive(Default, Clone)]
+pub(crate) struct Deprecation {
+    pub since: Option<LitStr>,
+    pub note: Option<LitStr>,
+}
+
+/// Parse the `(iOS = "15", macOS = "12")` that follows `available` in a `#[swift_bridge(...)]`
+/// attribute.
+pub(crate) fn parse_available(input: ParseStream) -> syn::Result<Vec<PlatformVersion>> {
+    let content;
+    syn::parenthesized!(content in input);
+
+    let mut platforms = vec![];
+    while !content.is_empty() {
+        let platform: Ident = content.parse()?;
+        if !SWIFT_PLATFORMS.contains(&platform.to_string().as_str()) {
+            return Err(syn::Error::new_spanned(
+                &platform,
+                format!(
+                    r#"Unrecognized platform "{}". Expected one of {}."#,
+                    platform,
+                    SWIFT_PLATFORMS.join(", ")
+                ),
+            ));
+        }
+        content.parse::<Token![=]>()?;
+
+        let version: LitStr = content.parse()?;
+        let is_version = version
+            .value()
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+        if !is_version {
+            return Err(syn::Error::new_spanned(
+                &version,
+                r#"Expected a version such as "15" or "15.4"."#,
+            ));
+        }
+
+        platforms.push(PlatformVersion { platform, version });
+
+        if !content.is_empty() {
+            content.parse::<Token![,]>()?;
+        }
+    }
+
+    Ok(platforms)
+}
+
+impl Deprecation {
+    /// Parse the `#[deprecated]` attribute out of a list of attributes, ignoring all other
+    /// attributes.
+    pub(crate) fn from_attributes(attributes: &[Attribute]) -> syn::Result<Option<Self>> {
+        let attr = match attributes
+            .iter()
+            .find(|attr| attr.path.is_ident("deprecated"))
+        {
+            Some(attr) => attr,
+            None => return Ok(None),
+        };
+
+        let mut deprecation = Deprecation::default();
+
+        match attr.parse_meta()? {
+            Meta::Path(_) => {}
+            Meta::NameValue(name_value) => {
+                deprecation.note = Some(expect_lit_str(name_value.lit)?);
+            }
+            Meta::List(list) => {
+                for nested in list.nested {
+                    let name_value = match nested {
+                        NestedMeta::Meta(Meta::NameValue(name_value)) => name_value,
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                other,
+                                r#"Expected `since = "..."` or `note = "..."`."#,
+                            ))
+                        }
+                    };
+
+                    if name_value.path.is_ident("since") {
+                        deprecation.since = Some(expect_lit_str(name_value.lit)?);
+                    } else if name_value.path.is_ident("note") {
+                        deprecation.note = Some(expect_lit_str(name_value.lit)?);
+                    } else {
+                        return Err(syn::Error::new_spanned(
+                            name_value.path,
+                            r#"Expected `since = "..."` or `note = "..."`."#,
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(Some(deprecation))
+    }
+}
+
+fn expect_lit_str(lit: Lit) -> syn::Result<LitStr> {
+    match lit {
+        Lit::Str(lit_str) => Ok(lit_str),
+        other => Err(syn::Error::new_spanned(other, "Expected a string literal.")),
+    }
+}
@@ -0,0 +1,114 @@
+use crate::bridged_type::{BridgedType, StdLibType};
+use crate::parse::doc_comment_from_attributes;
+use crate::{TypeDeclarations, SWIFT_BRIDGE_PREFIX};
+use proc_macro2::Ident;
+use quote::format_ident;
+use syn::{Expr, ExprUnary, Item, ItemConst, ItemStatic, Lit, Type, UnOp};
+
+/// A `const` or `static` item in a bridge module.
+///
+/// ```no_run,ignore
+/// #[swift_bridge::bridge]
+/// mod ffi {
+///     const MAX_ITEMS: u32 = 100;
+///     static VERSION: &str = "1.2.3";
+/// }
+/// ```
+pub(crate) struct BridgeConst {
+    /// The original item, which gets emitted as is into the generated module.
+    pub item: Item,
+    pub name: Ident,
+    pub ty: Type,
+    pub value: Expr,
+    pub is_static: bool,
+    pub doc_comment: Option<String>,
+}
+
+impl BridgeConst {
+    pub(crate) fn from_item_const(item: ItemConst) -> syn::Result<Self> {
+        Ok(BridgeConst {
+            name: item.ident.clone(),
+            ty: item.ty.as_ref().clone(),
+            value: item.expr.as_ref().clone(),
+            is_static: false,
+            doc_comment: doc_comment_from_attributes(&item.attrs)?,
+            item: Item::Const(item),
+        })
+    }
+
+    pub(crate) fn from_item_static(item: ItemStatic) -> syn::Result<Self> {
+        Ok(BridgeConst {
+            name: item.ident.clone(),
+            ty: item.ty.as_ref().clone(),
+            value: item.expr.as_ref().clone(),
+            is_static: true,
+            doc_comment: doc_comment_from_attributes(&item.attrs)?,
+            item: Item::Static(item),
+        })
+    }
+
+    /// The value of a primitive constant that is a literal, such as `"100"` for
+    /// `const MAX_ITEMS: u32 = 100;`.
+    ///
+    /// Swift and C use these values directly. Every other constant gets read from Rust.
+    pub(crate) fn primitive_literal(&self, types: &TypeDeclarations) -> Option<String> {
+        match BridgedType::new_with_type(&self.ty, types)? {
+            BridgedType::StdLib(
+                StdLibType::U8
+                | StdLibType::I8
+                | StdLibType::U16
+                | StdLibType::I16
+                | StdLibType::U32
+                | StdLibType::I32
+                | StdLibType::U64
+                | StdLibType::I64
+                | StdLibType::Usize
+                | StdLibType::Isize
+                | StdLibType::F32
+                | StdLibType::F64
+                | StdLibType::Bool,
+            ) => {}
+            _ => return None,
+        };
+
+        match &self.value {
+            Expr::Lit(lit) => literal_to_string(&lit.lit),
+            Expr::Unary(ExprUnary {
+                op: UnOp::Neg(_),
+                expr,
+                ..
+            }) => match expr.as_ref() {
+                Expr::Lit(lit) => literal_to_string(&lit.lit).map(|lit| format!("-{}", lit)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// __swift_bridge__$MAX_ITEMS
+    pub(crate) fn link_name(&self) -> String {
+        format!("{}${}", SWIFT_BRIDGE_PREFIX, self.name)
+    }
+
+    /// __swift_bridge__MAX_ITEMS
+    pub(crate) fn prefixed_fn_name(&self) -> Ident {
+        format_ident!("{}{}", SWIFT_BRIDGE_PREFIX, self.name)
+    }
+}
+
+fn literal_to_string(lit: &Lit) -> Option<String> {
+    match lit {
+        Lit::Int(int) => Some(int.base10_digits().to_string()),
+        Lit::Float(float) => {
+            let digits = float.base10_digits();
+            // `1.` is a valid Rust float but not a valid Swift float.
+            if digits.ends_with('.') {
+                Some(format!("{}0", digits))
+            } else {
+                Some(digits.to_string())
+            }
+        }
+        Lit::Bool(bool) => Some(bool.value.to_string()),
+        _ => None,
+    }
+}
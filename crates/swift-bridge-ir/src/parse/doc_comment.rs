@@ -0,0 +1,35 @@
+use syn::{Attribute, Lit, Meta};
+
+/// Collect the `///` doc comment on a type, function, field or enum variant, ignoring all other
+/// attributes.
+///
+/// Each line keeps the text that follows its `///`, so `/// Some comment` becomes
+/// `" Some comment"`. Lines are joined with newlines.
+pub(crate) fn doc_comment_from_attributes(attributes: &[Attribute]) -> syn::Result<Option<String>> {
+    let mut lines = vec![];
+
+    for attr in attributes {
+        if !attr.path.is_ident("doc") {
+            continue;
+        }
+
+        // `#[doc(hidden)]` and friends don't contain any documentation.
+        if let Meta::NameValue(name_value) = attr.parse_meta()? {
+            match name_value.lit {
+                Lit::Str(line) => lines.push(line.value()),
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "Expected a doc comment such as #[doc = \"...\"]",
+                    ))
+                }
+            }
+        }
+    }
+
+    if lines.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(lines.join("\n")))
+    }
+}
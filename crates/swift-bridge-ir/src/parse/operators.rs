@@ -0,0 +1,138 @@
+use crate::SWIFT_BRIDGE_PREFIX;
+use proc_macro2::{Ident, Span};
+use quote::format_ident;
+use syn::parse::ParseStream;
+use syn::{Token, Type};
+
+/// A Rust operator trait that gets exposed to Swift as an operator or a subscript.
+///
+/// `#[swift_bridge(Add, Neg, Index(usize, Output = f64))]`
+///
+/// Each variant holds the span of its attribute, which is where we point errors such as a missing
+/// `Clone` implementation.
+#[derive(Clone)]
+pub(crate) enum OperatorTrait {
+    /// `Add`, which becomes Swift's `+`.
+    Add(Span),
+    /// `Sub`, which becomes Swift's `-`.
+    Sub(Span),
+    /// `Mul`, which becomes Swift's `*`.
+    Mul(Span),
+    /// `Div`, which becomes Swift's `/`.
+    Div(Span),
+    /// `Neg`, which becomes Swift's prefix `-`.
+    Neg(Span),
+    /// `Index(usize, Output = f64)`, which becomes a Swift subscript.
+    Index {
+        index: Box<Type>,
+        output: Box<Type>,
+        span: Span,
+    },
+}
+
+impl OperatorTrait {
+    /// Parse the operator trait that an attribute's key refers to.
+    ///
+    /// Returns `None` if the key isn't an operator trait.
+    pub(crate) fn parse_with_key(key: &Ident, input: ParseStream) -> Option<syn::Result<Self>> {
+        let span = key.span();
+        let operator = match key.to_string().as_str() {
+            "Add" => OperatorTrait::Add(span),
+            "Sub" => OperatorTrait::Sub(span),
+            "Mul" => OperatorTrait::Mul(span),
+            "Div" => OperatorTrait::Div(span),
+            "Neg" => OperatorTrait::Neg(span),
+            "Index" => return Some(parse_index(input, span)),
+            _ => return None,
+        };
+
+        Some(Ok(operator))
+    }
+
+    /// The span of the attribute, such as the `Add` in `#[swift_bridge(Add)]`.
+    pub(crate) fn span(&self) -> Span {
+        match self {
+            OperatorTrait::Add(span)
+            | OperatorTrait::Sub(span)
+            | OperatorTrait::Mul(span)
+            | OperatorTrait::Div(span)
+            | OperatorTrait::Neg(span)
+            | OperatorTrait::Index { span, .. } => *span,
+        }
+    }
+
+    /// The name of the trait, such as `Add`.
+    pub(crate) fn trait_name(&self) -> &'static str {
+        match self {
+            OperatorTrait::Add(_) => "Add",
+            OperatorTrait::Sub(_) => "Sub",
+            OperatorTrait::Mul(_) => "Mul",
+            OperatorTrait::Div(_) => "Div",
+            OperatorTrait::Neg(_) => "Neg",
+            OperatorTrait::Index { .. } => "Index",
+        }
+    }
+
+    /// The name of the trait's method, such as `add` for `Add`.
+    pub(crate) fn method_name(&self) -> &'static str {
+        match self {
+            OperatorTrait::Add(_) => "add",
+            OperatorTrait::Sub(_) => "sub",
+            OperatorTrait::Mul(_) => "mul",
+            OperatorTrait::Div(_) => "div",
+            OperatorTrait::Neg(_) => "neg",
+            OperatorTrait::Index { .. } => "index",
+        }
+    }
+
+    /// The Swift operator for a binary operator trait, such as `+` for `Add`.
+    pub(crate) fn swift_binary_operator(&self) -> Option<&'static str> {
+        match self {
+            OperatorTrait::Add(_) => Some("+"),
+            OperatorTrait::Sub(_) => Some("-"),
+            OperatorTrait::Mul(_) => Some("*"),
+            OperatorTrait::Div(_) => Some("/"),
+            OperatorTrait::Neg(_) | OperatorTrait::Index { .. } => None,
+        }
+    }
+
+    /// __swift_bridge__$SomeType$_add
+    pub(crate) fn link_name(&self, ty_name: &Ident) -> String {
+        format!(
+            "{}${}$_{}",
+            SWIFT_BRIDGE_PREFIX,
+            ty_name,
+            self.method_name()
+        )
+    }
+
+    /// __swift_bridge__SomeType__add
+    pub(crate) fn prefixed_fn_name(&self, ty_name: &Ident) -> Ident {
+        format_ident!("{}{}__{}", SWIFT_BRIDGE_PREFIX, ty_name, self.method_name())
+    }
+}
+
+/// Parse the `(usize, Output = f64)` that follows `Index`.
+fn parse_index(input: ParseStream, span: Span) -> syn::Result<OperatorTrait> {
+    let content;
+    syn::parenthesized!(content in input);
+
+    let index: Type = content.parse()?;
+    content.parse::<Token![,]>()?;
+
+    let output_key: Ident = content.parse()?;
+    if output_key != "Output" {
+        return Err(syn::Error::new_spanned(
+            output_key,
+            r#"Expected the index's output type, such as `Index(usize, Output = f64)`."#,
+        ));
+    }
+    content.parse::<Token![=]>()?;
+    let output: Type = content.parse()?;
+
+    Ok(OperatorTrait::Index {
+        index: Box::new(index),
+        output: Box::new(output),
+        span,
+    })
+}
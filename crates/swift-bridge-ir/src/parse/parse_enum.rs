@@ -1,5 +1,6 @@
 use crate::bridged_type::{EnumVariant, SharedEnum, StructFields};
 use crate::errors::ParseErrors;
+use crate::parse::{doc_comment_from_attributes, Deprecation, SerdeAttributes};
 use syn::ItemEnum;
 
 use self::enum_attributes::SharedEnumAllAttributes;
@@ -21,19 +22,33 @@ impl<'a> SharedEnumDeclarationParser<'a> {
         let mut variants = vec![];
 
         for v in item_enum.variants {
+            let mut serde_errors = vec![];
+            let serde = SerdeAttributes::from_attributes(&v.attrs, &mut serde_errors)?;
+            self.errors.append(serde_errors);
+
             let variant = EnumVariant {
                 name: v.ident,
-                fields: StructFields::from_syn_fields(v.fields),
+                doc_comment: doc_comment_from_attributes(&v.attrs)?,
+                deprecated: Deprecation::from_attributes(&v.attrs)?,
+                fields: StructFields::from_syn_fields(v.fields, self.errors)?,
+                serde,
             };
             variants.push(variant);
         }
 
+        let doc_comment = doc_comment_from_attributes(&item_enum.attrs)?;
+        let deprecated = Deprecation::from_attributes(&item_enum.attrs)?;
+
         let shared_enum = SharedEnum {
             name: item_enum.ident,
             variants,
             already_declared: attribs.swift_bridge.already_declared,
             swift_name: attribs.swift_bridge.swift_name,
             derive: attribs.derive,
+            serde: attribs.serde,
+            doc_comment,
+            available: attribs.swift_bridge.available,
+            deprecated,
         };
 
         Ok(shared_enum)
@@ -202,4 +217,118 @@ mod tests {
         let ty = module.types.types()[0].unwrap_shared_enum();
         assert!(ty.derive.debug);
     }
+
+    /// Verify that we can parse the comparison derives on enums.
+    #[test]
+    fn derive_comparison_traits() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+                enum Foo {
+                    Variant1
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.types()[0].unwrap_shared_enum();
+        assert!(ty.derive.partial_eq);
+        assert!(ty.derive.eq);
+        assert!(ty.derive.hash);
+        assert!(ty.derive.partial_ord);
+        assert!(ty.derive.ord);
+    }
+
+    /// Verify that we can parse serde derives along with enum and variant serde attributes.
+    #[test]
+    fn parse_serde_attributes() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[derive(Serialize, Deserialize)]
+                #[serde(tag = "type", content = "value")]
+                enum Foo {
+                    #[serde(rename = "first")]
+                    Variant1,
+                    Variant2 {
+                        #[serde(rename = "renamed")]
+                        field: u8
+                    }
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.types()[0].unwrap_shared_enum();
+        assert!(ty.derive.serialize);
+        assert!(ty.derive.deserialize);
+        assert_eq!(ty.serde.tag.as_ref().unwrap().value(), "type");
+        assert_eq!(ty.serde.content.as_ref().unwrap().value(), "value");
+        assert_eq!(
+            ty.variants[0].serde.rename.as_ref().unwrap().value(),
+            "first"
+        );
+
+        match &ty.variants[1].fields {
+            StructFields::Named(fields) => {
+                assert_eq!(fields[0].serde.rename.as_ref().unwrap().value(), "renamed");
+            }
+            _ => panic!(),
+        };
+    }
+
+    /// Verify that we can parse the doc comments of an enum and its variants.
+    #[test]
+    fn parse_doc_comments() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                /// An enum.
+                enum SomeEnum {
+                    /// A variant.
+                    Variant1,
+                    Variant2,
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.types()[0].unwrap_shared_enum();
+        assert_eq!(ty.doc_comment.as_deref().unwrap(), " An enum.");
+        assert_eq!(
+            ty.variants[0].doc_comment.as_deref().unwrap(),
+            " A variant."
+        );
+        assert!(ty.variants[1].doc_comment.is_none());
+    }
+
+    /// Verify that we push an error for serde enum representations that we cannot mirror in
+    /// Swift.
+    #[test]
+    fn error_if_unsupported_serde_attribute() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[derive(Serialize)]
+                #[serde(untagged)]
+                enum Foo {
+                    Variant1
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 1);
+
+        match &errors[0] {
+            ParseError::SerdeUnsupportedAttribute { attribute } => {
+                assert_eq!(attribute, "untagged");
+            }
+            _ => panic!(),
+        };
+    }
 }
@@ -1,6 +1,9 @@
 use crate::bridged_type::DeriveAttrs;
 use crate::errors::ParseError;
-use crate::parse::move_input_cursor_to_next_comma;
+use crate::parse::{
+    derive_path_is, move_input_cursor_to_next_comma, parse_available, PlatformVersion,
+    SerdeAttributes,
+};
 use proc_macro2::Ident;
 use quote::ToTokens;
 use syn::parse::{Parse, ParseStream};
@@ -11,6 +14,7 @@ pub(super) struct SharedEnumAllAttributes {
     pub errors: Vec<ParseError>,
     pub swift_bridge: SharedEnumSwiftBridgeAttributes,
     pub derive: DeriveAttrs,
+    pub serde: SerdeAttributes,
 }
 
 impl SharedEnumAllAttributes {
@@ -26,8 +30,22 @@ impl SharedEnumAllAttributes {
                         syn::punctuated::Punctuated::<Path, syn::Token![,]>::parse_terminated;
                     let args = attr.parse_args_with(parser)?;
                     for arg in args.into_iter() {
+                        if derive_path_is(&arg, "Serialize") {
+                            attributes.derive.serialize = true;
+                            continue;
+                        }
+                        if derive_path_is(&arg, "Deserialize") {
+                            attributes.derive.deserialize = true;
+                            continue;
+                        }
+
                         match arg.get_ident().unwrap().to_string().as_str() {
                             "Debug" => attributes.derive.debug = true,
+                            "PartialEq" => attributes.derive.partial_eq = true,
+                            "Eq" => attributes.derive.eq = true,
+                            "Hash" => attributes.derive.hash = true,
+                            "PartialOrd" => attributes.derive.partial_ord = true,
+                            "Ord" => attributes.derive.ord = true,
                             _ => todo!("Unsupported derive macro; please see https://github.com/chinedufn/swift-bridge/issues/190#issuecomment-1463234027"),
                         }
                     }
@@ -38,6 +56,12 @@ impl SharedEnumAllAttributes {
                         .errors
                         .append(&mut attributes.swift_bridge.errors);
                 }
+                "doc" | "deprecated" => {}
+                "serde" => {
+                    attributes
+                        .serde
+                        .push_attribute(attr, &mut attributes.errors)?;
+                }
                 _ => todo!("Push unsupported attribute error."),
             };
         }
@@ -50,6 +74,7 @@ pub(super) enum EnumAttr {
     AlreadyDeclared,
     Error(ParseError),
     SwiftName(LitStr),
+    Available(Vec<PlatformVersion>),
 }
 
 #[derive(Default)]
@@ -57,6 +82,7 @@ pub(super) struct SharedEnumSwiftBridgeAttributes {
     pub errors: Vec<ParseError>,
     pub already_declared: bool,
     pub swift_name: Option<LitStr>,
+    pub available: Vec<PlatformVersion>,
 }
 
 impl SharedEnumSwiftBridgeAttributes {
@@ -65,6 +91,7 @@ impl SharedEnumSwiftBridgeAttributes {
             EnumAttr::AlreadyDeclared => self.already_declared = true,
             EnumAttr::Error(error) => self.errors.push(error),
             EnumAttr::SwiftName(name) => self.swift_name = Some(name),
+            EnumAttr::Available(platforms) => self.available = platforms,
         };
         Ok(())
     }
@@ -91,6 +118,7 @@ impl Parse for EnumAttr {
 
         let attr = match key.to_string().as_str() {
             "already_declared" => EnumAttr::AlreadyDeclared,
+            "available" => EnumAttr::Available(parse_available(input)?),
             "swift_name" => {
                 input.parse::<Token![=]>()?;
 
@@ -1,4 +1,4 @@
-use self::argument_attributes::ArgumentAttributes;
+use self::argument_attributes::{swift_default_value, ArgumentAttributes};
 pub(crate) use self::opaque_type_attributes::OpaqueTypeAllAttributes;
 use crate::bridged_type::{
     bridgeable_type_from_fn_arg, pat_type_pat_is_self, BridgeableType, BridgedType,
@@ -9,8 +9,9 @@ use crate::parse::parse_extern_mod::generics::GenericOpaqueType;
 use crate::parse::type_declarations::{
     OpaqueForeignTypeDeclaration, TypeDeclaration, TypeDeclarations,
 };
+use crate::parse::{doc_comment_from_attributes, Deprecation};
 use crate::parse::{HostLang, OpaqueRustTypeGenerics};
-use crate::parsed_extern_fn::fn_arg_is_mutable_reference;
+use crate::parsed_extern_fn::{fn_arg_is_mutable_reference, impl_stream_item_ty, iterator_item_ty};
 use crate::ParsedExternFn;
 use proc_macro2::Ident;
 use quote::{format_ident, ToTokens};
@@ -88,6 +89,16 @@ impl<'a> ForeignModParser<'a> {
                         attributes: OpaqueTypeAllAttributes::from_attributes(&foreign_ty.attrs)?,
                         generics: OpaqueRustTypeGenerics::new(),
                     };
+                    if foreign_type.attributes.protocol && host_lang.is_rust() {
+                        self.errors.push(ParseError::ProtocolOnNonSwiftType {
+                            ty: foreign_ty.ident.clone(),
+                        });
+                    }
+                    if foreign_type.attributes.trait_object && host_lang.is_swift() {
+                        self.errors.push(ParseError::TraitObjectOnNonRustType {
+                            ty: foreign_ty.ident.clone(),
+                        });
+                    }
                     self.type_declarations.insert(
                         ty_name.clone(),
                         TypeDeclaration::Opaque(foreign_type.clone()),
@@ -98,8 +109,13 @@ impl<'a> ForeignModParser<'a> {
                     let mut attributes = FunctionAttributes::default();
 
                     for attr in func.attrs.iter() {
+                        if attr.path.is_ident("doc") || attr.path.is_ident("deprecated") {
+                            continue;
+                        }
                         attributes = attr.parse_args()?;
                     }
+                    let doc_comment = doc_comment_from_attributes(&func.attrs)?;
+                    let deprecated = Deprecation::from_attributes(&func.attrs)?;
 
                     for arg in func.sig.inputs.iter() {
                         if let FnArg::Typed(pat_ty) = arg {
@@ -111,7 +127,23 @@ impl<'a> ForeignModParser<'a> {
                     }
 
                     let return_type = &func.sig.output;
-                    if let ReturnType::Type(_, return_ty) = return_type {
+                    if let Some(item_ty) = impl_stream_item_ty(return_type) {
+                        if host_lang.is_swift() || func.sig.asyncness.is_some() {
+                            self.errors
+                                .push(ParseError::UnsupportedStreamFn { func: func.clone() });
+                        }
+                        if BridgedType::new_with_type(item_ty, self.type_declarations).is_none() {
+                            self.unresolved_types.push(item_ty.clone());
+                        }
+                    } else if let Some(item_ty) = iterator_item_ty(return_type) {
+                        if host_lang.is_swift() || func.sig.asyncness.is_some() {
+                            self.errors
+                                .push(ParseError::UnsupportedIteratorFn { func: func.clone() });
+                        }
+                        if BridgedType::new_with_type(item_ty, self.type_declarations).is_none() {
+                            self.unresolved_types.push(item_ty.clone());
+                        }
+                    } else if let ReturnType::Type(_, return_ty) = return_type {
                         if BridgedType::new_with_type(return_ty.deref(), &self.type_declarations)
                             .is_none()
                         {
@@ -128,6 +160,18 @@ impl<'a> ForeignModParser<'a> {
                         &mut local_type_declarations,
                     )?;
 
+                    if attributes.is_local && (host_lang.is_swift() || func.sig.asyncness.is_none())
+                    {
+                        self.errors
+                            .push(ParseError::LocalOnNonAsyncRustFn { func: func.clone() });
+                    }
+
+                    if attributes.is_swift_property && !is_valid_swift_property_fn(&func, host_lang)
+                    {
+                        self.errors
+                            .push(ParseError::InvalidSwiftPropertyFn { func: func.clone() });
+                    }
+
                     if attributes.is_swift_identifiable {
                         let args = &func.sig.inputs;
 
@@ -166,6 +210,7 @@ impl<'a> ForeignModParser<'a> {
                         }
                     }
                     let mut argument_labels: HashMap<Ident, LitStr> = HashMap::new();
+                    let mut argument_defaults: HashMap<Ident, String> = HashMap::new();
                     for arg in func.sig.inputs.iter() {
                         let is_mutable_ref = fn_arg_is_mutable_reference(arg);
 
@@ -184,6 +229,14 @@ impl<'a> ForeignModParser<'a> {
                             self.errors
                                 .push(ParseError::ArgCopyAndRefMut { arg: arg.clone() });
                         }
+
+                        if func.sig.asyncness.is_some()
+                            && host_lang.is_swift()
+                            && fn_arg_is_borrowed_non_str(arg)
+                        {
+                            self.errors
+                                .push(ParseError::AsyncSwiftFnBorrowedArg { arg: arg.clone() });
+                        }
                         match arg {
                             syn::FnArg::Typed(ty) => {
                                 for attr in ty.attrs.iter() {
@@ -197,6 +250,39 @@ impl<'a> ForeignModParser<'a> {
                                             label,
                                         );
                                     }
+                                    if let Some(default) = attribute.default {
+                                        // Undeclared types get reported elsewhere.
+                                        if host_lang.is_swift() {
+                                            self.errors.push(
+                                                ParseError::ArgumentDefaultOnSwiftFn {
+                                                    value: default,
+                                                },
+                                            );
+                                        } else if let Some(bridged_ty) = BridgedType::new_with_type(
+                                            &ty.ty,
+                                            self.type_declarations,
+                                        ) {
+                                            match swift_default_value(&default, &bridged_ty) {
+                                                Some(swift_default) => {
+                                                    argument_defaults.insert(
+                                                        format_ident!(
+                                                            "{}",
+                                                            ty.pat.to_token_stream().to_string()
+                                                        ),
+                                                        swift_default,
+                                                    );
+                                                }
+                                                None => {
+                                                    self.errors.push(
+                                                        ParseError::InvalidArgumentDefault {
+                                                            value: default,
+                                                            ty: ty.ty.clone(),
+                                                        },
+                                                    );
+                                                }
+                                            }
+                                        }
+                                    }
                                 }
                             }
                             _ => {}
@@ -237,7 +323,15 @@ impl<'a> ForeignModParser<'a> {
                         return_with: attributes.return_with,
                         args_into: attributes.args_into,
                         get_field: attributes.get_field,
+                        set_field: attributes.set_field,
+                        is_swift_property: attributes.is_swift_property,
+                        is_local: attributes.is_local,
+                        catch_panics: false,
                         argument_labels: argument_labels,
+                        argument_defaults,
+                        doc_comment,
+                        available: attributes.available,
+                        deprecated,
                     };
                     self.functions.push(func);
                 }
@@ -389,9 +483,54 @@ Otherwise we use a more general error that says that your argument is invalid.
     }
 }
 
+// Whether or not the argument is a reference, other than `&self` or `&str`.
+fn fn_arg_is_borrowed_non_str(fn_arg: &FnArg) -> bool {
+    match fn_arg {
+        FnArg::Receiver(_) => false,
+        FnArg::Typed(pat_ty) => {
+            if pat_type_pat_is_self(pat_ty) {
+                return false;
+            }
+
+            match pat_ty.ty.deref() {
+                Type::Reference(type_ref) => type_ref.elem.to_token_stream().to_string() != "str",
+                _ => false,
+            }
+        }
+    }
+}
+
+/// Whether a `#[swift_bridge(swift_property)]` function is either a `&self` getter that returns a
+/// value or a `&mut self` setter that takes the new value and returns nothing.
+fn is_valid_swift_property_fn(func: &ForeignItemFn, host_lang: HostLang) -> bool {
+    if host_lang.is_swift() || func.sig.asyncness.is_some() {
+        return false;
+    }
+
+    let is_ref_mut_self = match func.sig.inputs.iter().next() {
+        Some(arg @ FnArg::Receiver(receiver)) if receiver.reference.is_some() => {
+            fn_arg_is_mutable_reference(arg)
+        }
+        Some(arg @ FnArg::Typed(pat_ty))
+            if pat_type_pat_is_self(pat_ty) && matches!(pat_ty.ty.deref(), Type::Reference(_)) =>
+        {
+            fn_arg_is_mutable_reference(arg)
+        }
+        _ => return false,
+    };
+    let returns_value = matches!(&func.sig.output, ReturnType::Type(_, _));
+
+    match func.sig.inputs.len() {
+        1 => returns_value,
+        2 => is_ref_mut_self && !returns_value,
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::errors::ParseError;
+    use crate::parse::OperatorTrait;
     use crate::test_utils::{parse_errors, parse_ok};
     use crate::SwiftBridgeModule;
     use quote::{quote, ToTokens};
@@ -789,6 +928,103 @@ mod tests {
         );
     }
 
+    /// Verify that we can parse the `Comparable` attribute, and that it implies `Equatable`.
+    #[test]
+    fn parse_comparable_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Comparable)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.get("SomeType").unwrap().unwrap_opaque();
+        assert!(ty.attributes.comparable);
+        assert!(ty.attributes.equatable);
+    }
+
+    /// Verify that we can parse operator trait attributes.
+    #[test]
+    fn parse_operator_attributes() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Add, Neg, Index(usize, Output = f64))]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.get("SomeType").unwrap().unwrap_opaque();
+        let operators = &ty.attributes.operators;
+        assert_eq!(operators.len(), 3);
+        assert_eq!(operators[0].trait_name(), "Add");
+        assert_eq!(operators[1].trait_name(), "Neg");
+        match &operators[2] {
+            OperatorTrait::Index { index, output, .. } => {
+                assert_eq!(index.to_token_stream().to_string(), "usize");
+                assert_eq!(output.to_token_stream().to_string(), "f64");
+            }
+            _ => panic!(),
+        };
+    }
+
+    /// Verify that we push errors for `Index` operator types that can't be bridged.
+    #[test]
+    fn error_if_index_operator_type_is_undeclared() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Index(SomeIndex, Output = SomeOutput))]
+                    type SomeType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        let undeclared: Vec<String> = errors
+            .iter()
+            .map(|error| match error {
+                ParseError::UndeclaredType { ty } => ty.to_token_stream().to_string(),
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(undeclared, vec!["SomeIndex", "SomeOutput"]);
+    }
+
+    /// Verify that we can parse the `Display` and `Debug` attributes.
+    #[test]
+    fn parse_display_and_debug_attributes() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(Display, Debug)]
+                    type SomeType;
+
+                    #[swift_bridge(Debug)]
+                    type AnotherType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.get("SomeType").unwrap().unwrap_opaque();
+        assert!(ty.attributes.display);
+        assert!(ty.attributes.debug);
+
+        let ty = module.types.get("AnotherType").unwrap().unwrap_opaque();
+        assert!(!ty.attributes.display);
+        assert!(ty.attributes.debug);
+    }
+
     //Verify that we can parse the `hashable` attribute.
     #[test]
     fn parse_hashable_attribute() {
@@ -841,6 +1077,339 @@ mod tests {
         );
     }
 
+    /// Verify that we can parse the `protocol` attribute.
+    #[test]
+    fn parse_protocol_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Swift" {
+                    #[swift_bridge(protocol)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert!(
+            module
+                .types
+                .get("SomeType")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .protocol
+        );
+    }
+
+    /// Verify that we push an error if an extern "Rust" type uses the `protocol` attribute.
+    #[test]
+    fn error_if_protocol_attribute_on_rust_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(protocol)]
+                    type SomeType;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::ProtocolOnNonSwiftType { ty } => {
+                assert_eq!(ty.to_string(), "SomeType");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we push an error if a method on a protocol uses a type that can't be used in
+    /// the Swift protocol.
+    #[test]
+    fn error_if_protocol_method_type_is_unsupported() {
+        let tokens = quote! {
+            mod foo {
+                extern "Swift" {
+                    #[swift_bridge(protocol)]
+                    type SomeType;
+
+                    fn numbers(&self) -> impl Iterator<Item = u8>;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        match &errors[0] {
+            ParseError::UnsupportedIteratorFn { func } => {
+                assert_eq!(func.sig.ident.to_string(), "numbers");
+            }
+            _ => panic!(),
+        }
+        match &errors[1] {
+            ParseError::UnsupportedProtocolMethodType { ty } => {
+                assert_eq!(
+                    ty.to_token_stream().to_string(),
+                    "impl Iterator < Item = u8 >"
+                );
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we can parse the `trait_object` attribute.
+    #[test]
+    fn parse_trait_object_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(trait_object)]
+                    type SomeTrait;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert!(
+            module
+                .types
+                .get("SomeTrait")
+                .unwrap()
+                .unwrap_opaque()
+                .attributes
+                .trait_object
+        );
+    }
+
+    /// Verify that we push an error if an extern "Swift" type uses the `trait_object` attribute.
+    #[test]
+    fn error_if_trait_object_attribute_on_swift_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Swift" {
+                    #[swift_bridge(trait_object)]
+                    type SomeTrait;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::TraitObjectOnNonRustType { ty } => {
+                assert_eq!(ty.to_string(), "SomeTrait");
+            }
+            _ => panic!(),
+        }
+    }
+
+    /// Verify that we push an error if an async extern "Swift" function takes a reference other
+    /// than `&str`.
+    #[test]
+    fn error_if_async_swift_function_takes_borrowed_arg() {
+        let tokens = quote! {
+            mod foo {
+                extern "Swift" {
+                    type SomeType;
+
+                    async fn a(&self, arg: &str);
+                    async fn b(arg: &[u8]);
+                    async fn c(arg: &SomeType);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        for error in errors.iter() {
+            match error {
+                ParseError::AsyncSwiftFnBorrowedArg { arg: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push an error if an async or extern "Swift" function returns a stream.
+    #[test]
+    fn error_if_unsupported_function_returns_stream() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    fn a() -> impl Stream<Item = u8>;
+                    async fn b() -> impl Stream<Item = u8>;
+                }
+
+                extern "Swift" {
+                    fn c() -> impl Stream<Item = u8>;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        for error in errors.iter() {
+            match error {
+                ParseError::UnsupportedStreamFn { func: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push an error if an async or extern "Swift" function returns an iterator.
+    #[test]
+    fn error_if_unsupported_function_returns_iterator() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    fn a() -> impl Iterator<Item = u8>;
+                    async fn b() -> impl Iterator<Item = u8>;
+                    fn c() -> Box<dyn Iterator<Item = u8>>;
+                }
+
+                extern "Swift" {
+                    fn d() -> Box<dyn Iterator<Item = u8>>;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        for error in errors.iter() {
+            match error {
+                ParseError::UnsupportedIteratorFn { func: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push an error for every use of a trait object that needs to own or mutably
+    /// borrow it, if the trait object is passed to Swift as an `Arc<dyn Trait>`.
+    #[test]
+    fn error_if_arc_trait_object_is_owned_or_mutated() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(trait_object)]
+                    type SomeTrait;
+
+                    fn read(&self) -> u8;
+                    fn write(&mut self, value: u8);
+                }
+
+                extern "Rust" {
+                    fn make_arc() -> Arc<dyn SomeTrait>;
+                    fn take_boxed(arg: Option<Box<dyn SomeTrait>>);
+                    fn take_ref_mut(arg: &mut dyn SomeTrait);
+                    fn take_ref(arg: &dyn SomeTrait);
+                    fn take_arc(arg: Arc<dyn SomeTrait>);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 3);
+        let usages: Vec<String> = errors
+            .iter()
+            .map(|error| match error {
+                ParseError::ArcTraitObjectOwnedOrMutated { trait_name, usage } => {
+                    assert_eq!(trait_name.to_string(), "SomeTrait");
+                    usage.to_string()
+                }
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(
+            usages,
+            vec!["& mut self", "Box < dyn SomeTrait >", "& mut dyn SomeTrait"]
+        );
+    }
+
+    /// Verify that we push errors for `SwiftError`s that would need to be passed from Rust to
+    /// Swift, and for throwing extern "Swift" functions whose error type is not `SwiftError`.
+    #[test]
+    fn error_if_swift_error_is_unsupported() {
+        let tokens = quote! {
+            mod foo {
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    field: SwiftError,
+                }
+
+                extern "Rust" {
+                    fn rust_returns_swift_error() -> Option<SwiftError>;
+                    fn rust_takes_vec(arg: Vec<SwiftError>);
+                    fn rust_takes_swift_error(arg: SwiftError);
+                }
+
+                extern "Swift" {
+                    fn swift_takes_swift_error(arg: SwiftError);
+                    fn swift_returns_result() -> Result<u8, String>;
+                    fn swift_returns_swift_error() -> Result<u8, SwiftError>;
+                    async fn swift_returns_async_result() -> Result<u8, String>;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        let errors: Vec<String> = errors
+            .iter()
+            .map(|error| match error {
+                ParseError::UnsupportedSwiftError { ty } => {
+                    format!("unsupported {}", ty.to_token_stream())
+                }
+                ParseError::ExternSwiftResultErrorNotSwiftError { ty } => {
+                    format!("not swift error {}", ty.to_token_stream())
+                }
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "unsupported SwiftError",
+                "unsupported SwiftError",
+                "unsupported SwiftError",
+                "not swift error Result < u8 , String >",
+                "unsupported SwiftError",
+            ]
+        );
+    }
+
+    /// Verify that a trait object that is only passed to Swift in a `Box<dyn Trait>` can be
+    /// mutably borrowed, and passed back to Rust as a `Box<dyn Trait>` or `Arc<dyn Trait>`.
+    #[test]
+    fn boxed_trait_object_can_be_owned_or_mutated() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(trait_object)]
+                    type SomeTrait;
+
+                    fn write(&mut self, value: u8);
+                }
+
+                extern "Rust" {
+                    fn make_boxed() -> Box<dyn SomeTrait>;
+                    fn take_boxed(arg: Box<dyn SomeTrait>);
+                    fn take_ref_mut(arg: &mut dyn SomeTrait);
+                    fn take_arc(arg: Arc<dyn SomeTrait>);
+                }
+            }
+        };
+
+        parse_ok(tokens);
+    }
+
     /// Verify that we can parse the `copy` attribute.
     #[test]
     fn parse_copy_attribute() {
@@ -916,6 +1485,34 @@ mod tests {
         );
     }
 
+    /// Verify that we can parse a multi-line doc comment from an extern "Rust" function.
+    #[test]
+    fn parse_function_doc_comment() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    /// Line one.
+                    ///
+                    /// Line two.
+                    #[swift_bridge(swift_name = "someFunction")]
+                    fn some_function();
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let func = &module.functions[0];
+        assert_eq!(
+            func.doc_comment.as_deref().unwrap(),
+            " Line one.\n\n Line two."
+        );
+        assert_eq!(
+            func.swift_name_override.as_ref().unwrap().value(),
+            "someFunction"
+        );
+    }
+
     /// Verify that we push errors for unknown arguments in a function
     #[test]
     fn error_args_into_arg_not_found_in_function() {
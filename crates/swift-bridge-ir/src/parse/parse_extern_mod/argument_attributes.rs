@@ -1,16 +1,21 @@
+use crate::bridged_type::{BridgeableType, BridgedType, StdLibType};
 use proc_macro2::Ident;
 use syn::parse::{Parse, ParseStream};
-use syn::{LitStr, Token};
+use syn::{Expr, ExprLit, ExprUnary, Lit, LitStr, Token, UnOp};
 
 #[derive(Default)]
 pub(super) struct ArgumentAttributes {
     /// LitStr: argument_name
     pub label: Option<LitStr>,
+    /// `default = 10`, `default = None` or `default = "text"`
+    pub default: Option<Expr>,
 }
 
 enum ArgumentAttr {
     /// LitStr: argument_name
     ArgumentLabel(LitStr),
+    /// `default = 10`, `default = None` or `default = "text"`
+    Default(Box<Expr>),
 }
 
 impl Parse for ArgumentAttributes {
@@ -23,6 +28,9 @@ impl Parse for ArgumentAttributes {
                 ArgumentAttr::ArgumentLabel(label) => {
                     attributes.label = Some(label);
                 }
+                ArgumentAttr::Default(value) => {
+                    attributes.default = Some(*value);
+                }
             }
         }
         Ok(attributes)
@@ -38,6 +46,11 @@ impl Parse for ArgumentAttr {
                 let value: LitStr = input.parse()?;
                 ArgumentAttr::ArgumentLabel(value)
             }
+            "default" => {
+                input.parse::<Token![=]>()?;
+                let value: Expr = input.parse()?;
+                ArgumentAttr::Default(Box::new(value))
+            }
             _ => {
                 let attrib = key.to_string();
                 Err(syn::Error::new_spanned(
@@ -50,10 +63,109 @@ impl Parse for ArgumentAttr {
     }
 }
 
+/// Convert an argument's default value to Swift, such as `10`, `nil` or `"text"`.
+///
+/// Returns `None` if the value can't be used for the argument's type.
+pub(super) fn swift_default_value(value: &Expr, ty: &BridgedType) -> Option<String> {
+    if let Expr::Lit(ExprLit {
+        lit: Lit::Str(string),
+        ..
+    }) = value
+    {
+        return if ty.is_str() || ty.is_string() {
+            Some(swift_string_literal(&string.value()))
+        } else {
+            None
+        };
+    }
+
+    let ty = match ty {
+        BridgedType::StdLib(ty) => ty,
+        _ => return None,
+    };
+
+    let (is_negative, lit) = match value {
+        Expr::Lit(lit) => (false, &lit.lit),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match expr.as_ref() {
+            Expr::Lit(lit) => (true, &lit.lit),
+            _ => return None,
+        },
+        Expr::Path(path) if path.path.is_ident("None") => {
+            return match ty {
+                StdLibType::Option(_) => Some("nil".to_string()),
+                _ => None,
+            };
+        }
+        _ => return None,
+    };
+    let sign = if is_negative { "-" } else { "" };
+
+    match (ty, lit) {
+        (StdLibType::Option(option), _) => swift_default_value(value, &option.ty),
+        (StdLibType::Bool, Lit::Bool(bool)) if !is_negative => Some(bool.value.to_string()),
+        (StdLibType::F32 | StdLibType::F64, Lit::Float(float)) => {
+            Some(format!("{}{}", sign, float.base10_digits()))
+        }
+        (StdLibType::F32 | StdLibType::F64, Lit::Int(int)) => {
+            Some(format!("{}{}", sign, int.base10_digits()))
+        }
+        (_, Lit::Int(int)) => {
+            let (min, max) = integer_range(ty)?;
+            let value: i128 = int.base10_parse().ok()?;
+            let value = if is_negative { -value } else { value };
+
+            if value < min || value > max {
+                return None;
+            }
+            Some(value.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Write a Rust string as a Swift string literal, such as `"line\n"`.
+fn swift_string_literal(string: &str) -> String {
+    let mut literal = "\"".to_string();
+    for c in string.chars() {
+        match c {
+            '"' => literal += "\\\"",
+            '\\' => literal += "\\\\",
+            '\n' => literal += "\\n",
+            '\r' => literal += "\\r",
+            '\t' => literal += "\\t",
+            '\0' => literal += "\\0",
+            c if c.is_control() => literal += &format!("\\u{{{:x}}}", c as u32),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+fn integer_range(ty: &StdLibType) -> Option<(i128, i128)> {
+    let range = match ty {
+        StdLibType::U8 => (u8::MIN as i128, u8::MAX as i128),
+        StdLibType::I8 => (i8::MIN as i128, i8::MAX as i128),
+        StdLibType::U16 => (u16::MIN as i128, u16::MAX as i128),
+        StdLibType::I16 => (i16::MIN as i128, i16::MAX as i128),
+        StdLibType::U32 => (u32::MIN as i128, u32::MAX as i128),
+        StdLibType::I32 => (i32::MIN as i128, i32::MAX as i128),
+        StdLibType::U64 | StdLibType::Usize => (u64::MIN as i128, u64::MAX as i128),
+        StdLibType::I64 | StdLibType::Isize => (i64::MIN as i128, i64::MAX as i128),
+        _ => return None,
+    };
+    Some(range)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::test_utils::parse_ok;
-    use quote::{format_ident, quote};
+    use crate::errors::ParseError;
+    use crate::test_utils::{parse_errors, parse_ok};
+    use quote::{format_ident, quote, ToTokens};
 
     /// Verify that we can parse a function that has a argument label.
     #[test]
@@ -78,4 +190,107 @@ mod tests {
             .unwrap();
         assert_eq!(argument_label.value().to_string(), "argumentLabel1");
     }
+
+    /// Verify that we can parse argument default values and convert them to Swift.
+    #[test]
+    fn parse_argument_defaults() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    fn some_function(
+                        #[swift_bridge(default = 255)] arg1: u8,
+                        #[swift_bridge(default = -128)] arg2: i8,
+                        #[swift_bridge(default = 2)] arg3: f32,
+                        #[swift_bridge(default = false)] arg4: bool,
+                        #[swift_bridge(default = None)] arg5: Option<u8>,
+                        #[swift_bridge(default = 5)] arg6: Option<u8>,
+                        arg7: u8,
+                        #[swift_bridge(default = "localhost")] arg8: &str,
+                        #[swift_bridge(default = "say \"hi\"\n")] arg9: String,
+                    );
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+        let defaults = &module.functions[0].argument_defaults;
+
+        assert_eq!(defaults.len(), 8);
+        assert_eq!(defaults.get(&format_ident!("arg1")).unwrap(), "255");
+        assert_eq!(defaults.get(&format_ident!("arg2")).unwrap(), "-128");
+        assert_eq!(defaults.get(&format_ident!("arg3")).unwrap(), "2");
+        assert_eq!(defaults.get(&format_ident!("arg4")).unwrap(), "false");
+        assert_eq!(defaults.get(&format_ident!("arg5")).unwrap(), "nil");
+        assert_eq!(defaults.get(&format_ident!("arg6")).unwrap(), "5");
+        assert_eq!(
+            defaults.get(&format_ident!("arg8")).unwrap(),
+            r#""localhost""#
+        );
+        assert_eq!(
+            defaults.get(&format_ident!("arg9")).unwrap(),
+            r#""say \"hi\"\n""#
+        );
+    }
+
+    /// Verify that we get an error if a default value can't be used for its argument's type.
+    #[test]
+    fn error_if_argument_default_does_not_match_type() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    type SomeType;
+
+                    fn some_function(
+                        #[swift_bridge(default = 256)] arg1: u8,
+                        #[swift_bridge(default = -1)] arg2: u32,
+                        #[swift_bridge(default = 1.5)] arg3: i32,
+                        #[swift_bridge(default = None)] arg4: bool,
+                        #[swift_bridge(default = 1)] arg5: SomeType,
+                        #[swift_bridge(default = "None")] arg6: Option<u8>,
+                        #[swift_bridge(default = 1)] arg7: String,
+                    );
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 7);
+        let values: Vec<String> = errors
+            .iter()
+            .map(|error| match error {
+                ParseError::InvalidArgumentDefault { value, .. } => {
+                    value.to_token_stream().to_string()
+                }
+                _ => panic!(),
+            })
+            .collect();
+        assert_eq!(
+            values,
+            vec!["256", "- 1", "1.5", "None", "1", "\"None\"", "1"]
+        );
+    }
+
+    /// Verify that we get an error if an extern "Swift" function's argument has a default value,
+    /// since Rust always passes every argument.
+    #[test]
+    fn error_if_argument_default_on_swift_fn() {
+        let tokens = quote! {
+            mod foo {
+                extern "Swift" {
+                    fn some_function(#[swift_bridge(default = 10)] arg: u8);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::ArgumentDefaultOnSwiftFn { value } => {
+                assert_eq!(value.to_token_stream().to_string(), "10");
+            }
+            _ => panic!(),
+        }
+    }
 }
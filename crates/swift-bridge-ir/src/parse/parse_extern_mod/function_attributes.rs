@@ -1,4 +1,7 @@
-use crate::parsed_extern_fn::{GetField, GetFieldDirect, GetFieldWith};
+use crate::parse::{parse_available, PlatformVersion};
+use crate::parsed_extern_fn::{
+    GetField, GetFieldDirect, GetFieldWith, SetField, SetFieldDirect, SetFieldWith,
+};
 use proc_macro2::Ident;
 use syn::parse::{Parse, ParseStream};
 use syn::{LitStr, Path, Token};
@@ -14,6 +17,10 @@ pub(super) struct FunctionAttributes {
     pub return_with: Option<Path>,
     pub args_into: Option<Vec<Ident>>,
     pub get_field: Option<GetField>,
+    pub set_field: Option<SetField>,
+    pub is_swift_property: bool,
+    pub is_local: bool,
+    pub available: Vec<PlatformVersion>,
 }
 
 impl FunctionAttributes {
@@ -43,6 +50,17 @@ impl FunctionAttributes {
             FunctionAttr::GetFieldWith(get_field) => {
                 self.get_field = Some(GetField::With(get_field))
             }
+            FunctionAttr::SetField(set_field) => self.set_field = Some(SetField::Direct(set_field)),
+            FunctionAttr::SetFieldWith(set_field) => {
+                self.set_field = Some(SetField::With(set_field))
+            }
+            FunctionAttr::SwiftProperty => {
+                self.is_swift_property = true;
+            }
+            FunctionAttr::Local => {
+                self.is_local = true;
+            }
+            FunctionAttr::Available(platforms) => self.available = platforms,
         }
     }
 }
@@ -58,6 +76,11 @@ pub(super) enum FunctionAttr {
     ArgsInto(Vec<Ident>),
     GetField(GetFieldDirect),
     GetFieldWith(GetFieldWith),
+    SetField(SetFieldDirect),
+    SetFieldWith(SetFieldWith),
+    SwiftProperty,
+    Local,
+    Available(Vec<PlatformVersion>),
 }
 
 impl Parse for FunctionAttributes {
@@ -95,6 +118,9 @@ impl Parse for FunctionAttr {
             }
             "init" => FunctionAttr::Init,
             "Identifiable" => FunctionAttr::Identifiable,
+            "local" => FunctionAttr::Local,
+            "swift_property" => FunctionAttr::SwiftProperty,
+            "available" => FunctionAttr::Available(parse_available(input)?),
             // TODO: Right before we release 0.2.0 we should remove this
             //  "into_return_type" variant since it is deprecated.
             //
@@ -149,6 +175,36 @@ impl Parse for FunctionAttr {
                     path,
                 })
             }
+            "get_mut" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                let field_name = content.parse::<Ident>()?;
+
+                FunctionAttr::GetField(GetFieldDirect {
+                    maybe_ref: Some(Token![&](key.span())),
+                    maybe_mut: Some(Token![mut](key.span())),
+                    field_name,
+                })
+            }
+            "set" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                let field_name = content.parse::<Ident>()?;
+
+                FunctionAttr::SetField(SetFieldDirect { field_name })
+            }
+            "set_with" => {
+                let content;
+                syn::parenthesized!(content in input);
+
+                let field_name = content.parse::<Ident>()?;
+                content.parse::<Token![=]>()?;
+                let path = content.parse::<Path>()?;
+
+                FunctionAttr::SetFieldWith(SetFieldWith { field_name, path })
+            }
             _ => {
                 let attrib = key.to_string();
                 Err(syn::Error::new_spanned(
@@ -213,6 +269,52 @@ mod tests {
         );
     }
 
+    /// Verify that we can parse the local attribute from extern "Rust" blocks.
+    #[test]
+    fn parse_extern_rust_local_attribute() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(local)]
+                    async fn some_function ();
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        assert!(module.functions[0].is_local);
+    }
+
+    /// Verify that we push an error if the local attribute is used on a function that isn't an
+    /// async extern "Rust" function.
+    #[test]
+    fn error_if_local_attribute_on_non_async_rust_fn() {
+        let tokens = quote! {
+            mod foo {
+                extern "Rust" {
+                    #[swift_bridge(local)]
+                    fn a ();
+                }
+
+                extern "Swift" {
+                    #[swift_bridge(local)]
+                    async fn b ();
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 2);
+        for error in errors.iter() {
+            match error {
+                ParseError::LocalOnNonAsyncRustFn { func: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
     /// Verify that we can parse an associated function.
     #[test]
     fn parse_associated_function() {
@@ -514,6 +616,171 @@ mod tests {
         }
     }
 
+    /// Verify that we can parse the `get_mut` attribute.
+    #[test]
+    fn parses_get_mut_attribute() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type Foo;
+
+                    #[swift_bridge(get_mut(field))]
+                    fn some_function(&mut self) -> &mut u8;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let field = module.functions[0]
+            .get_field
+            .as_ref()
+            .unwrap()
+            .unwrap_direct();
+        assert!(field.maybe_ref.is_some());
+        assert!(field.maybe_mut.is_some());
+        assert_eq!(field.field_name, "field");
+    }
+
+    /// Verify that we can parse the `set` and `set_with` attributes.
+    #[test]
+    fn parses_set_and_set_with_attributes() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type Foo;
+
+                    #[swift_bridge(set(field))]
+                    fn set_field(&mut self, value: u8);
+
+                    #[swift_bridge(set_with(field = a::b::c))]
+                    fn set_field_with(&mut self, value: u16);
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let funcs = &module.functions;
+
+        let field = funcs[0].set_field.as_ref().unwrap().unwrap_direct();
+        assert_eq!(field.field_name, "field");
+
+        let field = funcs[1].set_field.as_ref().unwrap().unwrap_with();
+        assert_eq!(field.field_name, "field");
+        assert_eq!(
+            field.path.to_token_stream().to_string(),
+            quote! { a::b::c }.to_string()
+        );
+    }
+
+    /// Verify that we can parse the `swift_property` attribute.
+    #[test]
+    fn parses_swift_property_attribute() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type Foo;
+
+                    #[swift_bridge(get(field), swift_property)]
+                    fn field(&self) -> u8;
+
+                    #[swift_bridge(swift_property)]
+                    fn some_property(&self) -> u8;
+
+                    #[swift_bridge(swift_property)]
+                    fn set_some_property(&mut self, value: u8);
+
+                    fn another_function(&self) -> u8;
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let funcs = &module.functions;
+
+        assert!(funcs[0].is_swift_property);
+        assert!(!funcs[0].is_swift_property_setter());
+        assert_eq!(funcs[0].swift_property_name().unwrap(), "field");
+
+        assert!(!funcs[1].is_swift_property_setter());
+        assert_eq!(funcs[1].swift_property_name().unwrap(), "some_property");
+
+        assert!(funcs[2].is_swift_property_setter());
+        assert_eq!(funcs[2].swift_property_name().unwrap(), "some_property");
+
+        assert!(!funcs[3].is_swift_property);
+        assert!(funcs[3].swift_property_name().is_none());
+    }
+
+    /// Verify that we push an error if the swift_property attribute is used on a function that
+    /// isn't a getter or a setter.
+    #[test]
+    fn error_if_invalid_swift_property_fn() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type Foo;
+
+                    #[swift_bridge(swift_property)]
+                    fn no_return_value(&self);
+
+                    #[swift_bridge(swift_property)]
+                    fn not_mutable(&self) -> u8;
+                    #[swift_bridge(swift_property)]
+                    fn set_not_mutable(&self, value: u8);
+
+                    #[swift_bridge(swift_property)]
+                    fn not_a_method() -> u8;
+
+                    #[swift_bridge(swift_property)]
+                    async fn is_async(&self) -> u8;
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 4);
+        for error in errors.iter() {
+            match error {
+                ParseError::InvalidSwiftPropertyFn { func: _ } => {}
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Verify that we push an error if a swift_property setter doesn't have a getter.
+    #[test]
+    fn error_if_swift_property_setter_without_getter() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    type Foo;
+
+                    #[swift_bridge(set(field), swift_property)]
+                    fn set_field(&mut self, value: u8);
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ParseError::SwiftPropertySetterWithoutGetter { func } => {
+                assert_eq!(func.sig.ident, "set_field");
+            }
+            _ => panic!(),
+        }
+    }
+
     /// Verify that we can parse a function that has multiple swift_bridge attributes.
     #[test]
     fn parses_multiple_function_swift_bridge_attributes() {
@@ -534,4 +801,44 @@ mod tests {
         assert_eq!(func.args_into.as_ref().unwrap().len(), 1);
         assert_eq!(func.return_into, true);
     }
+
+    /// Verify that we can parse the `available` attribute and the `#[deprecated]` attribute.
+    #[test]
+    fn parses_available_and_deprecated_attributes() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                extern "Rust" {
+                    #[deprecated(since = "1.0", note = "Some note.")]
+                    #[swift_bridge(available(iOS = "15.4", macOS = "12"), swift_name = "a")]
+                    fn some_function();
+
+                    #[deprecated]
+                    fn another_function();
+
+                    fn third_function();
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let func = &module.functions[0];
+        assert_eq!(func.available.len(), 2);
+        assert_eq!(func.available[0].platform, "iOS");
+        assert_eq!(func.available[0].version.value(), "15.4");
+        assert_eq!(func.available[1].platform, "macOS");
+        assert_eq!(func.available[1].version.value(), "12");
+        let deprecated = func.deprecated.as_ref().unwrap();
+        assert_eq!(deprecated.since.as_ref().unwrap().value(), "1.0");
+        assert_eq!(deprecated.note.as_ref().unwrap().value(), "Some note.");
+        assert_eq!(func.swift_name_override.as_ref().unwrap().value(), "a");
+
+        let deprecated = module.functions[1].deprecated.as_ref().unwrap();
+        assert!(deprecated.since.is_none());
+        assert!(deprecated.note.is_none());
+
+        assert!(module.functions[2].available.is_empty());
+        assert!(module.functions[2].deprecated.is_none());
+    }
 }
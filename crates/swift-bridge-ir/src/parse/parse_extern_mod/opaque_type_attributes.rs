@@ -1,17 +1,20 @@
-use crate::parse::OpaqueCopy;
+use crate::parse::{
+    doc_comment_from_attributes, parse_available, Deprecation, OpaqueCopy, OperatorTrait,
+    PlatformVersion,
+};
 use proc_macro2::Ident;
 use quote::ToTokens;
 use std::ops::Deref;
 use syn::parse::{Parse, ParseStream};
-use syn::{Attribute, LitInt, Meta};
+use syn::{Attribute, LitInt};
 
 #[derive(Default, Clone)]
 pub(crate) struct OpaqueTypeAllAttributes {
     pub swift_bridge: OpaqueTypeSwiftBridgeAttributes,
-    /// A doc comment.
-    // TODO: Use this to generate doc comment for the generated Swift type.
-    #[allow(unused)]
+    /// The type's doc comment, which we copy to the generated Swift class.
     pub doc_comment: Option<String>,
+    /// `#[deprecated]`
+    pub deprecated: Option<Deprecation>,
 }
 
 #[derive(Default, Clone)]
@@ -26,12 +29,37 @@ pub(crate) struct OpaqueTypeSwiftBridgeAttributes {
     /// `#[swift_bridge(declare_generic)]`
     /// Used to declare a generic type.
     pub declare_generic: bool,
+    /// `#[swift_bridge(Comparable)]`
+    /// Used to determine if Comparable need to be implemented.
+    pub comparable: bool,
+    /// `#[swift_bridge(Debug)]`
+    /// Used to determine if CustomDebugStringConvertible need to be implemented.
+    pub debug: bool,
+    /// `#[swift_bridge(Display)]`
+    /// Used to determine if CustomStringConvertible need to be implemented.
+    pub display: bool,
     /// `#[swift_bridge(Equatable)]`
     /// Used to determine if Equatable need to be implemented.
     pub equatable: bool,
     /// `#[swift_bridge(Hashable)]`
     /// Used to determine if Hashable need to be implemented.
     pub hashable: bool,
+    /// `#[swift_bridge(protocol)]`
+    /// Used to generate a Swift protocol for an `extern "Swift"` type, along with an
+    /// implementation of the Rust trait with the same name.
+    pub protocol: bool,
+    /// `#[swift_bridge(trait_object)]`
+    /// Used to expose a Rust trait to Swift, so that `Box<dyn Trait>` and `Arc<dyn Trait>` can
+    /// be passed to Swift.
+    pub trait_object: bool,
+    /// `#[swift_bridge(available(iOS = "15", macOS = "12"))]`
+    /// The platform versions that the generated Swift type is available on.
+    pub available: Vec<PlatformVersion>,
+    /// `#[swift_bridge(Add, Sub, Index(usize, Output = f64))]`
+    /// The Rust operator traits that get exposed as Swift operators and subscripts.
+    /// Non-`Copy` types must implement `Clone`, since their operands are cloned before being
+    /// passed to the operator by value.
+    pub operators: Vec<OperatorTrait>,
 }
 
 impl OpaqueTypeAllAttributes {
@@ -42,28 +70,15 @@ impl OpaqueTypeAllAttributes {
             let attribute_name = attr.path.to_token_stream().to_string();
 
             match attribute_name.as_str() {
-                "doc" => {
-                    let meta = attr.parse_meta()?;
-                    let doc = match meta {
-                        Meta::NameValue(name_val) => match name_val.lit {
-                            syn::Lit::Str(comment) => comment.value(),
-                            _ => {
-                                todo!("Push parse error that doc attribute is in incorrect format")
-                            }
-                        },
-                        _ => {
-                            todo!("Push parse error that doc attribute is in incorrect format")
-                        }
-                    };
-
-                    attributes.doc_comment = Some(doc);
-                }
+                "doc" | "deprecated" => {}
                 "swift_bridge" => {
                     attributes.swift_bridge = attr.parse_args()?;
                 }
                 _ => todo!("Push unsupported attribute error."),
             };
         }
+        attributes.doc_comment = doc_comment_from_attributes(attribs)?;
+        attributes.deprecated = Deprecation::from_attributes(attribs)?;
 
         Ok(attributes)
     }
@@ -73,20 +88,39 @@ impl OpaqueTypeSwiftBridgeAttributes {
     pub(super) fn store_attrib(&mut self, attrib: OpaqueTypeAttr) {
         match attrib {
             OpaqueTypeAttr::AlreadyDeclared => self.already_declared = true,
+            OpaqueTypeAttr::Comparable => {
+                // Swift's `Comparable` requires `Equatable`, and Rust's `PartialOrd` requires
+                // `PartialEq`.
+                self.comparable = true;
+                self.equatable = true;
+            }
             OpaqueTypeAttr::Copy { size } => self.copy = Some(OpaqueCopy { size_bytes: size }),
             OpaqueTypeAttr::DeclareGeneric => self.declare_generic = true,
+            OpaqueTypeAttr::Debug => self.debug = true,
+            OpaqueTypeAttr::Display => self.display = true,
             OpaqueTypeAttr::Equatable => self.equatable = true,
             OpaqueTypeAttr::Hashable => self.hashable = true,
+            OpaqueTypeAttr::Protocol => self.protocol = true,
+            OpaqueTypeAttr::TraitObject => self.trait_object = true,
+            OpaqueTypeAttr::Available(platforms) => self.available = platforms,
+            OpaqueTypeAttr::Operator(operator) => self.operators.push(operator),
         }
     }
 }
 
 pub(crate) enum OpaqueTypeAttr {
     AlreadyDeclared,
+    Comparable,
     Copy { size: usize },
     DeclareGeneric,
+    Debug,
+    Display,
     Equatable,
     Hashable,
+    Protocol,
+    TraitObject,
+    Available(Vec<PlatformVersion>),
+    Operator(OperatorTrait),
 }
 
 impl Parse for OpaqueTypeSwiftBridgeAttributes {
@@ -110,6 +144,8 @@ impl Parse for OpaqueTypeAttr {
 
         let attrib = match key.to_string().as_str() {
             "already_declared" => OpaqueTypeAttr::AlreadyDeclared,
+            "available" => OpaqueTypeAttr::Available(parse_available(input)?),
+            "Comparable" => OpaqueTypeAttr::Comparable,
             // Copy(10)
             "Copy" => {
                 let content;
@@ -122,15 +158,22 @@ impl Parse for OpaqueTypeAttr {
                 }
             }
             "declare_generic" => OpaqueTypeAttr::DeclareGeneric,
+            "Debug" => OpaqueTypeAttr::Debug,
+            "Display" => OpaqueTypeAttr::Display,
             "Equatable" => OpaqueTypeAttr::Equatable,
             "Hashable" => OpaqueTypeAttr::Hashable,
-            _ => {
-                let attrib = key.to_string();
-                Err(syn::Error::new_spanned(
-                    key,
-                    format!(r#"Unrecognized attribute "{}"."#, attrib),
-                ))?
-            }
+            "protocol" => OpaqueTypeAttr::Protocol,
+            "trait_object" => OpaqueTypeAttr::TraitObject,
+            _ => match OperatorTrait::parse_with_key(&key, input) {
+                Some(operator) => OpaqueTypeAttr::Operator(operator?),
+                None => {
+                    let attrib = key.to_string();
+                    Err(syn::Error::new_spanned(
+                        key,
+                        format!(r#"Unrecognized attribute "{}"."#, attrib),
+                    ))?
+                }
+            },
         };
 
         Ok(attrib)
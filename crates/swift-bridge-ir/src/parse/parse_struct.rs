@@ -2,11 +2,14 @@ use crate::bridged_type::{
     shared_struct::StructDerives, SharedStruct, StructFields, StructSwiftRepr,
 };
 use crate::errors::{ParseError, ParseErrors};
-use crate::parse::move_input_cursor_to_next_comma;
+use crate::parse::{
+    derive_path_is, doc_comment_from_attributes, move_input_cursor_to_next_comma, parse_available,
+    Deprecation, OperatorTrait, PlatformVersion, SerdeAttributes,
+};
 use proc_macro2::Ident;
 use quote::ToTokens;
 use syn::parse::{Parse, ParseStream};
-use syn::{ItemStruct, LitStr, Meta, Token};
+use syn::{ItemStruct, LitStr, Meta, NestedMeta, Token};
 
 pub(crate) struct SharedStructDeclarationParser<'a> {
     pub item_struct: ItemStruct,
@@ -18,6 +21,8 @@ enum StructAttr {
     SwiftName(LitStr),
     Error(StructAttrParseError),
     AlreadyDeclared,
+    Available(Vec<PlatformVersion>),
+    Operator(OperatorTrait),
 }
 
 enum StructAttrParseError {
@@ -31,6 +36,9 @@ struct StructAttribs {
     swift_name: Option<LitStr>,
     already_declared: bool,
     derives: StructDerives,
+    serde: SerdeAttributes,
+    available: Vec<PlatformVersion>,
+    operators: Vec<OperatorTrait>,
 }
 
 impl Default for StructDerives {
@@ -38,6 +46,13 @@ impl Default for StructDerives {
         StructDerives {
             copy: false,
             clone: false,
+            partial_eq: false,
+            eq: false,
+            hash: false,
+            partial_ord: false,
+            ord: false,
+            serialize: false,
+            deserialize: false,
         }
     }
 }
@@ -77,10 +92,14 @@ impl Parse for StructAttr {
                 StructAttr::SwiftName(name)
             }
             "already_declared" => StructAttr::AlreadyDeclared,
-            _ => {
-                move_input_cursor_to_next_comma(input);
-                StructAttr::Error(StructAttrParseError::UnrecognizedAttribute(key))
-            }
+            "available" => StructAttr::Available(parse_available(input)?),
+            _ => match OperatorTrait::parse_with_key(&key, input) {
+                Some(operator) => StructAttr::Operator(operator?),
+                None => {
+                    move_input_cursor_to_next_comma(input);
+                    StructAttr::Error(StructAttrParseError::UnrecognizedAttribute(key))
+                }
+            },
         };
 
         Ok(attr)
@@ -92,6 +111,8 @@ impl<'a> SharedStructDeclarationParser<'a> {
         let item_struct = self.item_struct;
 
         let mut attribs = StructAttribs::default();
+        let doc_comment = doc_comment_from_attributes(&item_struct.attrs)?;
+        let deprecated = Deprecation::from_attributes(&item_struct.attrs)?;
 
         for attr in item_struct.attrs {
             let attribute_name = attr.path.to_token_stream().to_string();
@@ -124,6 +145,12 @@ impl<'a> SharedStructDeclarationParser<'a> {
                             StructAttr::AlreadyDeclared => {
                                 attribs.already_declared = true;
                             }
+                            StructAttr::Available(platforms) => {
+                                attribs.available = platforms;
+                            }
+                            StructAttr::Operator(operator) => {
+                                attribs.operators.push(operator);
+                            }
                         };
                     }
                 }
@@ -133,12 +160,31 @@ impl<'a> SharedStructDeclarationParser<'a> {
                             match derive.to_token_stream().to_string().as_str() {
                                 "Copy" => attribs.derives.copy = true,
                                 "Clone" => attribs.derives.clone = true,
-                                _ => {}
+                                "PartialEq" => attribs.derives.partial_eq = true,
+                                "Eq" => attribs.derives.eq = true,
+                                "Hash" => attribs.derives.hash = true,
+                                "PartialOrd" => attribs.derives.partial_ord = true,
+                                "Ord" => attribs.derives.ord = true,
+                                _ => {
+                                    if let NestedMeta::Meta(Meta::Path(path)) = &derive {
+                                        if derive_path_is(path, "Serialize") {
+                                            attribs.derives.serialize = true;
+                                        } else if derive_path_is(path, "Deserialize") {
+                                            attribs.derives.deserialize = true;
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
                     _ => todo!("Push parse error that derive attribute is in incorrect format"),
                 },
+                "doc" | "deprecated" => {}
+                "serde" => {
+                    let mut serde_errors = vec![];
+                    attribs.serde.push_attribute(&attr, &mut serde_errors)?;
+                    self.errors.append(serde_errors);
+                }
                 attr_name => {
                     todo!(
                         "Push unsupported attribute error. Found unsupported attribute \"{}\" on struct \"{}\". Consult the swift-bridge manual for supported struct attributes.",
@@ -173,10 +219,15 @@ impl<'a> SharedStructDeclarationParser<'a> {
         let shared_struct = SharedStruct {
             name: item_struct.ident,
             swift_repr,
-            fields: StructFields::from_syn_fields(item_struct.fields),
+            fields: StructFields::from_syn_fields(item_struct.fields, self.errors)?,
             swift_name: attribs.swift_name,
             already_declared: attribs.already_declared,
             derives: attribs.derives,
+            serde: attribs.serde,
+            doc_comment,
+            available: attribs.available,
+            deprecated,
+            operators: attribs.operators,
         };
 
         Ok(shared_struct)
@@ -186,6 +237,7 @@ impl<'a> SharedStructDeclarationParser<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse::serde_attributes::SerdeRenameRule;
     use crate::test_utils::{parse_errors, parse_ok};
     use quote::{quote, ToTokens};
 
@@ -369,6 +421,102 @@ mod tests {
         assert_eq!(ty2.derives.clone, true);
     }
 
+    /// Verify that we parse the comparison derives.
+    #[test]
+    fn parse_comparison_derives() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+                struct Foo;
+
+                #[derive(PartialEq)]
+                struct Bar;
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.types()[0].unwrap_shared_struct();
+        assert_eq!(ty.derives.partial_eq, true);
+        assert_eq!(ty.derives.eq, true);
+        assert_eq!(ty.derives.hash, true);
+        assert_eq!(ty.derives.partial_ord, true);
+        assert_eq!(ty.derives.ord, true);
+
+        let ty2 = module.types.types()[1].unwrap_shared_struct();
+        assert_eq!(ty2.derives.partial_eq, true);
+        assert_eq!(ty2.derives.eq, false);
+        assert_eq!(ty2.derives.hash, false);
+        assert_eq!(ty2.derives.partial_ord, false);
+        assert_eq!(ty2.derives.ord, false);
+    }
+
+    /// Verify that we parse serde derives along with struct and field serde attributes.
+    #[test]
+    fn parse_serde_attributes() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                #[derive(serde::Serialize, Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                struct Foo {
+                    #[serde(rename = "baz")]
+                    bar: u8
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.types()[0].unwrap_shared_struct();
+        assert_eq!(ty.derives.serialize, true);
+        assert_eq!(ty.derives.deserialize, true);
+        assert_eq!(ty.serde.attributes.len(), 1);
+        assert_eq!(ty.serde.rename_all, Some(SerdeRenameRule::Camel));
+
+        match &ty.fields {
+            StructFields::Named(fields) => {
+                assert_eq!(fields[0].serde.rename.as_ref().unwrap().value(), "baz");
+            }
+            _ => panic!(),
+        };
+    }
+
+    /// Verify that we push an error for serde attributes that we cannot mirror in Swift.
+    #[test]
+    fn error_if_unsupported_serde_attribute() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct")]
+                #[derive(Serialize)]
+                #[serde(rename_all = "not-a-rule")]
+                struct Foo {
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    bar: Option<u8>
+                }
+            }
+        };
+
+        let errors = parse_errors(tokens);
+        assert_eq!(errors.len(), 2);
+
+        match &errors[0] {
+            ParseError::SerdeInvalidRenameRule { rule } => {
+                assert_eq!(rule.value(), "not-a-rule");
+            }
+            _ => panic!(),
+        };
+        match &errors[1] {
+            ParseError::SerdeUnsupportedAttribute { attribute } => {
+                assert_eq!(attribute, "skip_serializing_if");
+            }
+            _ => panic!(),
+        };
+    }
+
     /// Verify that we properly parse multiple comma separated struct attributes.
     #[test]
     fn parses_multiple_struct_attributes() {
@@ -429,6 +577,57 @@ mod tests {
         assert!(ty.already_declared);
     }
 
+    /// Verify that we can parse operator trait attributes on a struct.
+    #[test]
+    fn parses_struct_operator_attributes() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                #[swift_bridge(swift_repr = "struct", Add, Sub, Mul, Div)]
+                struct Point {
+                    x: f64,
+                    y: f64,
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.types()[0].unwrap_shared_struct();
+        let operators: Vec<&str> = ty.operators.iter().map(|op| op.trait_name()).collect();
+        assert_eq!(operators, vec!["Add", "Sub", "Mul", "Div"]);
+    }
+
+    /// Verify that we can parse the doc comments of a struct and its fields.
+    #[test]
+    fn parse_doc_comments() {
+        let tokens = quote! {
+            #[swift_bridge::bridge]
+            mod ffi {
+                /// Line one.
+                /// Line two.
+                #[swift_bridge(swift_repr = "struct")]
+                struct SomeStruct {
+                    /// A field.
+                    field: u8,
+                    undocumented: u8,
+                }
+            }
+        };
+
+        let module = parse_ok(tokens);
+
+        let ty = module.types.types()[0].unwrap_shared_struct();
+        assert_eq!(ty.doc_comment.as_deref().unwrap(), " Line one.\n Line two.");
+        match &ty.fields {
+            StructFields::Named(fields) => {
+                assert_eq!(fields[0].doc_comment.as_deref().unwrap(), " A field.");
+                assert!(fields[1].doc_comment.is_none());
+            }
+            _ => panic!(),
+        };
+    }
+
     /// Verify that we return an error if an attribute isn't recognized.
     #[test]
     fn error_if_attribute_unrecognized() {
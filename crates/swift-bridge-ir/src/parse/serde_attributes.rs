@@ -0,0 +1,266 @@
+use crate::errors::ParseError;
+use syn::{Attribute, Lit, LitStr, Meta, NestedMeta};
+
+/// The `#[serde(...)]` attributes on a shared struct, shared enum, enum variant or field.
+///
+/// We re-emit the attributes on the generated Rust type and use the subset that we understand
+/// to generate matching `Codable` conformances on the Swift side.
+#[derive(Default, Clone)]
+pub(crate) struct SerdeAttributes {
+    /// The raw `#[serde(...)]` attributes.
+    pub attributes: Vec<Attribute>,
+    /// `#[serde(rename = "...")]`
+    pub rename: Option<LitStr>,
+    /// `#[serde(rename_all = "...")]`
+    pub rename_all: Option<SerdeRenameRule>,
+    /// `#[serde(tag = "...")]`
+    pub tag: Option<LitStr>,
+    /// `#[serde(content = "...")]`
+    pub content: Option<LitStr>,
+}
+
+/// The case conversions supported by `#[serde(rename_all = "...")]`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum SerdeRenameRule {
+    /// "lowercase"
+    Lower,
+    /// "UPPERCASE"
+    Upper,
+    /// "PascalCase"
+    Pascal,
+    /// "camelCase"
+    Camel,
+    /// "snake_case"
+    Snake,
+    /// "SCREAMING_SNAKE_CASE"
+    ScreamingSnake,
+    /// "kebab-case"
+    Kebab,
+    /// "SCREAMING-KEBAB-CASE"
+    ScreamingKebab,
+}
+
+impl SerdeAttributes {
+    /// Parse the `#[serde(...)]` attributes out of a list of attributes, ignoring all other
+    /// attributes.
+    pub(crate) fn from_attributes(
+        attributes: &[Attribute],
+        errors: &mut Vec<ParseError>,
+    ) -> syn::Result<Self> {
+        let mut serde = SerdeAttributes::default();
+
+        for attr in attributes {
+            if attr.path.is_ident("serde") {
+                serde.push_attribute(attr, errors)?;
+            }
+        }
+
+        Ok(serde)
+    }
+
+    /// Parse a `#[serde(...)]` attribute.
+    pub(crate) fn push_attribute(
+        &mut self,
+        attr: &Attribute,
+        errors: &mut Vec<ParseError>,
+    ) -> syn::Result<()> {
+        let meta_list = match attr.parse_meta()? {
+            Meta::List(meta_list) => meta_list,
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "Expected a serde attribute such as #[serde(rename = \"...\")]",
+                ))
+            }
+        };
+
+        for nested in meta_list.nested {
+            let (key, value) = match nested {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                    let key = name_value.path.get_ident().cloned();
+                    let value = match name_value.lit {
+                        Lit::Str(value) => Some(value),
+                        _ => None,
+                    };
+                    (key, value)
+                }
+                NestedMeta::Meta(Meta::Path(path)) => (path.get_ident().cloned(), None),
+                NestedMeta::Meta(Meta::List(list)) => (list.path.get_ident().cloned(), None),
+                NestedMeta::Lit(lit) => {
+                    return Err(syn::Error::new_spanned(lit, "Unexpected serde attribute"));
+                }
+            };
+            let key = match key {
+                Some(key) => key,
+                None => continue,
+            };
+
+            match (key.to_string().as_str(), value) {
+                ("rename", Some(value)) => self.rename = Some(value),
+                ("rename_all", Some(value)) => match SerdeRenameRule::from_str(&value.value()) {
+                    Some(rule) => self.rename_all = Some(rule),
+                    None => errors.push(ParseError::SerdeInvalidRenameRule { rule: value }),
+                },
+                ("tag", Some(value)) => self.tag = Some(value),
+                ("content", Some(value)) => self.content = Some(value),
+                ("deny_unknown_fields", None) => {}
+                _ => errors.push(ParseError::SerdeUnsupportedAttribute { attribute: key }),
+            };
+        }
+
+        self.attributes.push(attr.clone());
+
+        Ok(())
+    }
+
+    /// The key that a struct field or enum variant gets serialized as.
+    ///
+    /// `rename_all` is the rule of the struct or enum that contains the field or variant.
+    pub(crate) fn serialized_name(
+        &self,
+        name: &str,
+        rename_all: Option<SerdeRenameRule>,
+        is_variant: bool,
+    ) -> String {
+        if let Some(rename) = self.rename.as_ref() {
+            return rename.value();
+        }
+
+        match rename_all {
+            Some(rule) if is_variant => rule.apply_to_variant(name),
+            Some(rule) => rule.apply_to_field(name),
+            None => name.to_string(),
+        }
+    }
+}
+
+impl SerdeRenameRule {
+    fn from_str(rule: &str) -> Option<Self> {
+        let rule = match rule {
+            "lowercase" => SerdeRenameRule::Lower,
+            "UPPERCASE" => SerdeRenameRule::Upper,
+            "PascalCase" => SerdeRenameRule::Pascal,
+            "camelCase" => SerdeRenameRule::Camel,
+            "snake_case" => SerdeRenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => SerdeRenameRule::ScreamingSnake,
+            "kebab-case" => SerdeRenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => SerdeRenameRule::ScreamingKebab,
+            _ => return None,
+        };
+        Some(rule)
+    }
+
+    /// Apply the rule to a PascalCase enum variant name, the same way that serde does.
+    pub(crate) fn apply_to_variant(&self, variant: &str) -> String {
+        match self {
+            SerdeRenameRule::Pascal => variant.to_string(),
+            SerdeRenameRule::Lower => variant.to_ascii_lowercase(),
+            SerdeRenameRule::Upper => variant.to_ascii_uppercase(),
+            SerdeRenameRule::Camel => variant[..1].to_ascii_lowercase() + &variant[1..],
+            SerdeRenameRule::Snake => {
+                let mut snake = String::new();
+                for (idx, ch) in variant.char_indices() {
+                    if idx > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            SerdeRenameRule::ScreamingSnake => SerdeRenameRule::Snake
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            SerdeRenameRule::Kebab => SerdeRenameRule::Snake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+            SerdeRenameRule::ScreamingKebab => SerdeRenameRule::ScreamingSnake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+
+    /// Apply the rule to a snake_case field name, the same way that serde does.
+    pub(crate) fn apply_to_field(&self, field: &str) -> String {
+        match self {
+            SerdeRenameRule::Lower | SerdeRenameRule::Snake => field.to_string(),
+            SerdeRenameRule::Upper | SerdeRenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            SerdeRenameRule::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            SerdeRenameRule::Camel => {
+                let pascal = SerdeRenameRule::Pascal.apply_to_field(field);
+                pascal[..1].to_ascii_lowercase() + &pascal[1..]
+            }
+            SerdeRenameRule::Kebab => field.replace('_', "-"),
+            SerdeRenameRule::ScreamingKebab => SerdeRenameRule::ScreamingSnake
+                .apply_to_field(field)
+                .replace('_', "-"),
+        }
+    }
+}
+
+/// Returns true if the last segment of a derive path matches the name.
+///
+/// `Serialize`, `serde::Serialize` and `::serde::Serialize` all match "Serialize".
+pub(crate) fn derive_path_is(derive: &syn::Path, name: &str) -> bool {
+    derive
+        .segments
+        .last()
+        .map(|segment| segment.ident == name)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that we apply `rename_all` rules to variant names the same way that serde does.
+    #[test]
+    fn rename_variants() {
+        let tests = [
+            (SerdeRenameRule::Lower, "someenumvariant"),
+            (SerdeRenameRule::Upper, "SOMEENUMVARIANT"),
+            (SerdeRenameRule::Pascal, "SomeEnumVariant"),
+            (SerdeRenameRule::Camel, "someEnumVariant"),
+            (SerdeRenameRule::Snake, "some_enum_variant"),
+            (SerdeRenameRule::ScreamingSnake, "SOME_ENUM_VARIANT"),
+            (SerdeRenameRule::Kebab, "some-enum-variant"),
+            (SerdeRenameRule::ScreamingKebab, "SOME-ENUM-VARIANT"),
+        ];
+
+        for (rule, expected) in tests {
+            assert_eq!(rule.apply_to_variant("SomeEnumVariant"), expected);
+        }
+    }
+
+    /// Verify that we apply `rename_all` rules to field names the same way that serde does.
+    #[test]
+    fn rename_fields() {
+        let tests = [
+            (SerdeRenameRule::Lower, "some_struct_field"),
+            (SerdeRenameRule::Upper, "SOME_STRUCT_FIELD"),
+            (SerdeRenameRule::Pascal, "SomeStructField"),
+            (SerdeRenameRule::Camel, "someStructField"),
+            (SerdeRenameRule::Snake, "some_struct_field"),
+            (SerdeRenameRule::ScreamingSnake, "SOME_STRUCT_FIELD"),
+            (SerdeRenameRule::Kebab, "some-struct-field"),
+            (SerdeRenameRule::ScreamingKebab, "SOME-STRUCT-FIELD"),
+        ];
+
+        for (rule, expected) in tests {
+            assert_eq!(rule.apply_to_field("some_struct_field"), expected);
+        }
+    }
+}
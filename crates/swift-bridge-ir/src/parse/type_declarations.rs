@@ -17,6 +17,17 @@ pub(crate) use self::generics::*;
 pub(crate) struct TypeDeclarations {
     decls: HashMap<String, TypeDeclaration>,
     order: Vec<String>,
+    /// Prepended to the symbols of the `HashMap`, `BTreeMap`, `HashSet` and `BTreeSet` functions
+    /// that the module exports.
+    ///
+    /// Every bridge module that uses a collection exports its own copy of these functions, so
+    /// they need a name that is unique to the module. We use the link name of the module's first
+    /// function, or the FFI name of its first shared type if it has no functions, since those are
+    /// already unique across a Swift module.
+    collection_symbol_scope: Option<String>,
+    /// See [`crate::SwiftBridgeModule::set_swift_bridge_path`]. Used by built-in types such as
+    /// `SwiftError` whose Rust type lives in the `swift_bridge` crate.
+    swift_bridge_path: Option<TokenStream>,
 }
 
 #[derive(Clone)]
@@ -52,14 +63,7 @@ impl TypeDeclaration {
 
     pub fn to_opaque_type(&self, reference: bool, mutable: bool) -> Option<OpaqueForeignType> {
         match self {
-            TypeDeclaration::Opaque(opaque) => Some(OpaqueForeignType {
-                ty: opaque.ty.clone(),
-                host_lang: opaque.host_lang,
-                reference,
-                mutable,
-                has_swift_bridge_copy_annotation: opaque.attributes.copy.is_some(),
-                generics: opaque.generics.clone(),
-            }),
+            TypeDeclaration::Opaque(opaque) => Some(opaque.to_opaque_type(reference, mutable)),
             _ => None,
         }
     }
@@ -74,13 +78,30 @@ pub(crate) struct OpaqueForeignTypeDeclaration {
 }
 
 impl OpaqueForeignTypeDeclaration {
+    pub(crate) fn to_opaque_type(&self, reference: bool, mutable: bool) -> OpaqueForeignType {
+        OpaqueForeignType {
+            ty: self.ty.clone(),
+            host_lang: self.host_lang,
+            reference,
+            mutable,
+            has_swift_bridge_copy_annotation: self.attributes.copy.is_some(),
+            is_swift_protocol: self.attributes.protocol,
+            is_trait_object: self.attributes.trait_object,
+            trait_object_pointer: None,
+            arc: false,
+            generics: self.generics.clone(),
+        }
+    }
+
     pub(crate) fn ffi_repr_type_tokens(&self) -> TokenStream {
         if self.attributes.copy.is_some() {
             self.ffi_copy_repr_ident().to_token_stream()
         } else {
-            let ty_name = &self.ty;
+            let pointee = self
+                .to_opaque_type(false, false)
+                .rust_pointee_type(&quote::quote! {});
             quote::quote! {
-                *mut super::#ty_name
+                *mut #pointee
             }
         }
     }
@@ -125,6 +146,23 @@ impl OpaqueForeignTypeDeclaration {
         )
     }
 
+    /// The C FFI link name of the function used to drop Swift's `Arc<SomeType>`.
+    ///
+    /// "__swift_bridge__$SomeType$_free_arc"
+    pub(crate) fn free_arc_rust_opaque_type_ffi_name(&self) -> String {
+        format!("{}${}$_free_arc", SWIFT_BRIDGE_PREFIX, **self)
+    }
+
+    /// The Rust function used to drop Swift's `Arc<SomeType>`.
+    ///
+    /// "__swift_bridge__SomeType__free_arc"
+    pub(crate) fn free_arc_rust_opaque_type_ident(&self) -> Ident {
+        Ident::new(
+            &format!("{}{}__free_arc", SWIFT_BRIDGE_PREFIX, self.ty),
+            self.ty.span(),
+        )
+    }
+
     /// The identifier for the `#[repr(C)] __swift_bridge__SomeStruct([u8; 123usize])`
     /// type that is generated to pass a Copy type over FFI.
     pub(crate) fn ffi_copy_repr_ident(&self) -> Ident {
@@ -243,6 +281,25 @@ impl TypeDeclarations {
         self.order.push(type_name);
     }
 
+    pub(crate) fn collection_symbol_scope(&self) -> Option<&str> {
+        self.collection_symbol_scope.as_deref()
+    }
+
+    pub(crate) fn set_collection_symbol_scope(&mut self, scope: String) {
+        self.collection_symbol_scope = Some(scope);
+    }
+
+    /// The path to the `swift_bridge` crate, such as `swift_bridge` or `crate`.
+    pub(crate) fn swift_bridge_path(&self) -> TokenStream {
+        self.swift_bridge_path
+            .clone()
+            .unwrap_or_else(|| quote::quote! { swift_bridge })
+    }
+
+    pub(crate) fn set_swift_bridge_path(&mut self, path: TokenStream) {
+        self.swift_bridge_path = Some(path);
+    }
+
     pub fn types(&self) -> Vec<&TypeDeclaration> {
         self.order
             .iter()
@@ -1,13 +1,20 @@
-use crate::bridged_type::boxed_fn::BridgeableBoxedFnOnce;
+use crate::bridged_type::boxed_fn::BridgeableBoxedFn;
 use crate::bridged_type::{pat_type_pat_is_self, BridgeableType, BridgedType, StdLibType};
-use crate::parse::{HostLang, SharedTypeDeclaration, TypeDeclaration, TypeDeclarations};
+use crate::parse::{
+    child_types, is_swift_error, Deprecation, HostLang, PlatformVersion, SharedTypeDeclaration,
+    TypeDeclaration, TypeDeclarations,
+};
 use crate::SWIFT_BRIDGE_PREFIX;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, quote_spanned, ToTokens};
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{FnArg, ForeignItemFn, Lifetime, LitStr, Path, ReturnType, Token, Type};
+use syn::{
+    FnArg, ForeignItemFn, GenericArgument, Lifetime, LitStr, Path, PathArguments, ReturnType,
+    Token, Type, TypeParamBound,
+};
 
 mod to_extern_c_fn;
 mod to_extern_c_param_names_and_types;
@@ -92,7 +99,41 @@ pub(crate) struct ParsedExternFn {
     pub args_into: Option<Vec<Ident>>,
     /// Get one of the associated type's fields
     pub get_field: Option<GetField>,
+    /// Set one of the associated type's fields
+    pub set_field: Option<SetField>,
+    /// Expose this getter or setter as a Swift computed property instead of a method.
+    ///
+    /// ```no_run,ignore
+    /// #[swift_bridge(get(count), swift_property)]
+    /// fn count(&self) -> u32;
+    ///
+    /// #[swift_bridge(set(count), swift_property)]
+    /// fn set_count(&mut self, count: u32);
+    /// ```
+    pub is_swift_property: bool,
+    /// Run this async function on the local executor's thread, so that its future does not need
+    /// to be `Send`.
+    ///
+    /// ```no_run,ignore
+    /// #[swift_bridge(local)]
+    /// async fn some_function(&self) -> u8;
+    /// ```
+    pub is_local: bool,
+    /// Whether the bridge module uses `#[swift_bridge::bridge(catch_panics)]`.
+    ///
+    /// Panics in functions that throw in Swift become a Swift `RustPanic` error. Panics in other
+    /// functions call Swift's panic handler and then abort.
+    pub catch_panics: bool,
     pub argument_labels: HashMap<Ident, LitStr>,
+    /// The Swift default values of arguments that use `#[swift_bridge(default = ...)]`, such as
+    /// `10` or `nil`.
+    pub argument_defaults: HashMap<Ident, String>,
+    /// The function's doc comment, which we copy to the generated Swift function.
+    pub doc_comment: Option<String>,
+    /// `#[swift_bridge(available(iOS = "15"))]`
+    pub available: Vec<PlatformVersion>,
+    /// `#[deprecated]`
+    pub deprecated: Option<Deprecation>,
 }
 
 pub(crate) enum GetField {
@@ -113,6 +154,38 @@ pub struct GetFieldWith {
     pub(crate) path: Path,
 }
 
+pub(crate) enum SetField {
+    Direct(SetFieldDirect),
+    With(SetFieldWith),
+}
+
+pub struct SetFieldDirect {
+    pub(crate) field_name: Ident,
+}
+
+pub struct SetFieldWith {
+    pub(crate) field_name: Ident,
+    pub(crate) path: Path,
+}
+
+impl GetField {
+    pub(crate) fn field_name(&self) -> &Ident {
+        match self {
+            GetField::Direct(d) => &d.field_name,
+            GetField::With(w) => &w.field_name,
+        }
+    }
+}
+
+impl SetField {
+    pub(crate) fn field_name(&self) -> &Ident {
+        match self {
+            SetField::Direct(d) => &d.field_name,
+            SetField::With(w) => &w.field_name,
+        }
+    }
+}
+
 #[cfg(test)]
 impl GetField {
     pub(crate) fn unwrap_direct(&self) -> &GetFieldDirect {
@@ -130,11 +203,98 @@ impl GetField {
     }
 }
 
+#[cfg(test)]
+impl SetField {
+    pub(crate) fn unwrap_direct(&self) -> &SetFieldDirect {
+        match self {
+            SetField::Direct(d) => d,
+            _ => panic!(),
+        }
+    }
+
+    pub(crate) fn unwrap_with(&self) -> &SetFieldWith {
+        match self {
+            SetField::With(d) => d,
+            _ => panic!(),
+        }
+    }
+}
+
 impl ParsedExternFn {
     pub fn is_method(&self) -> bool {
         self.func.sig.receiver().is_some()
     }
 
+    /// Whether or not the function returns a `Result<T, SwiftError>`.
+    ///
+    /// Swift implements `extern "Swift"` functions that return one of these as functions that
+    /// `throw`.
+    pub(crate) fn returns_result_with_swift_error(&self, types: &TypeDeclarations) -> bool {
+        let ty = match &self.func.sig.output {
+            ReturnType::Type(_, ty) => ty.as_ref(),
+            ReturnType::Default => return false,
+        };
+        let is_result = match ty {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Result"),
+            _ => false,
+        };
+
+        is_result
+            && child_types(ty)
+                .get(1)
+                .is_some_and(|err| is_swift_error(err, types))
+    }
+
+    /// Whether this is a `#[swift_bridge(swift_property)]` setter, as opposed to a getter.
+    ///
+    /// Setters take `&mut self` and the new value, getters only take `&self`.
+    pub(crate) fn is_swift_property_setter(&self) -> bool {
+        self.is_swift_property && self.func.sig.inputs.len() == 2
+    }
+
+    /// The name of the Swift computed property that a `#[swift_bridge(swift_property)]` function
+    /// gets or sets.
+    ///
+    /// This is the name of the field when using `get`/`set`, otherwise it is the function's name
+    /// with any `set_` prefix removed.
+    pub(crate) fn swift_property_name(&self) -> Option<String> {
+        if !self.is_swift_property {
+            return None;
+        }
+
+        if let Some(get_field) = self.get_field.as_ref() {
+            return Some(get_field.field_name().to_string());
+        }
+        if let Some(set_field) = self.set_field.as_ref() {
+            return Some(set_field.field_name().to_string());
+        }
+        if let Some(swift_name) = self.swift_name_override.as_ref() {
+            return Some(swift_name.value());
+        }
+
+        let fn_name = self.func.sig.ident.to_string();
+        if self.is_swift_property_setter() {
+            if let Some(name) = fn_name.strip_prefix("set_") {
+                return Some(name.to_string());
+            }
+        }
+        Some(fn_name)
+    }
+
+    /// Whether the Swift function that calls this Rust function throws.
+    /// Async functions always throw, since the Swift `Task` awaiting them can get cancelled.
+    pub(crate) fn throws_in_swift(&self, types: &TypeDeclarations) -> bool {
+        self.sig.asyncness.is_some()
+            || self
+                .return_ty_built_in(types)
+                .map(|ty| ty.is_result())
+                .unwrap_or(false)
+    }
+
     pub fn self_reference(&self) -> Option<(Token![&], Option<Lifetime>)> {
         match self.func.sig.receiver()? {
             FnArg::Receiver(receiver) => receiver.reference.clone(),
@@ -198,6 +358,32 @@ impl ParsedExternFn {
         }
     }
 
+    /// The `T` in a `-> impl Stream<Item = T>` return type.
+    pub(crate) fn stream_item_ty(&self) -> Option<&Type> {
+        impl_stream_item_ty(&self.func.sig.output)
+    }
+
+    /// The `Option<T>` that Swift receives each time it asks a returned
+    /// `impl Stream<Item = T>` for its next item.
+    pub(crate) fn stream_next_ty(&self, types: &TypeDeclarations) -> Option<BridgedType> {
+        let item_ty = self.stream_item_ty()?;
+        let next_ty: Type = syn::parse_quote! { Option<#item_ty> };
+        BridgedType::new_with_type(&next_ty, types)
+    }
+
+    /// The `T` in a `-> impl Iterator<Item = T>` or `-> Box<dyn Iterator<Item = T>>` return type.
+    pub(crate) fn iterator_item_ty(&self) -> Option<&Type> {
+        iterator_item_ty(&self.func.sig.output)
+    }
+
+    /// The `Option<T>` that Swift receives each time it asks a returned iterator for its next
+    /// item.
+    pub(crate) fn iterator_next_ty(&self, types: &TypeDeclarations) -> Option<BridgedType> {
+        let item_ty = self.iterator_item_ty()?;
+        let next_ty: Type = syn::parse_quote! { Option<#item_ty> };
+        BridgedType::new_with_type(&next_ty, types)
+    }
+
     pub fn extern_swift_linked_fn_new(&self) -> Ident {
         let sig = &self.func.sig;
 
@@ -254,9 +440,22 @@ impl ParsedExternFn {
         swift_bridge_path: &Path,
         types: &TypeDeclarations,
     ) -> TokenStream {
+        let args = self.to_call_rust_arg_list(swift_bridge_path, types);
+
+        quote! {
+            #(#args),*
+        }
+    }
+
+    /// The expressions for each of the arguments that get passed when calling the function.
+    pub(crate) fn to_call_rust_arg_list(
+        &self,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> Vec<TokenStream> {
         let mut args = vec![];
         let inputs = &self.func.sig.inputs;
-        for fn_arg in inputs.into_iter() {
+        for (arg_idx, fn_arg) in inputs.into_iter().enumerate() {
             match fn_arg {
                 FnArg::Receiver(_receiver) => {
                     if self.host_lang.is_swift() {
@@ -280,6 +479,16 @@ impl ParsedExternFn {
                         if self.host_lang.is_rust() {
                             arg = if let Some(repr) = built_in.only_encoding() {
                                 repr.rust
+                            } else if let BridgedType::StdLib(StdLibType::BoxedFn(boxed_fn)) =
+                                &built_in
+                            {
+                                boxed_fn.convert_swift_callback_to_rust_boxed_fn(
+                                    &arg,
+                                    &self.call_boxed_fn_link_name(arg_idx),
+                                    &self.free_boxed_fn_link_name(arg_idx),
+                                    swift_bridge_path,
+                                    types,
+                                )
                             } else {
                                 built_in.convert_ffi_expression_to_rust_type(
                                     &arg,
@@ -316,9 +525,7 @@ impl ParsedExternFn {
             };
         }
 
-        quote! {
-            #(#args),*
-        }
+        args
     }
 
     // fn foo (&self, arg1: u8, arg2: u32)
@@ -361,6 +568,10 @@ impl ParsedExternFn {
     }
 
     pub fn to_c_header_return(&self, types: &TypeDeclarations) -> String {
+        if self.stream_item_ty().is_some() || self.iterator_item_ty().is_some() {
+            return "void*".to_string();
+        }
+
         match &self.func.sig.output {
             ReturnType::Default => "void".to_string(),
             ReturnType::Type(_, ty) => {
@@ -409,6 +620,17 @@ impl ParsedExternFn {
             }
         }
 
+        if let Some(next_ty) = self.stream_next_ty(types) {
+            if let Some(include) = next_ty.to_c_include(types) {
+                includes.push(include);
+            }
+        }
+        if let Some(next_ty) = self.iterator_next_ty(types) {
+            if let Some(include) = next_ty.to_c_include(types) {
+                includes.push(include);
+            }
+        }
+
         for param in &self.func.sig.inputs {
             if let FnArg::Typed(pat_ty) = param {
                 if let Some(ty) = BridgedType::new_with_type(&pat_ty.ty, types) {
@@ -473,6 +695,22 @@ impl ParsedExternFn {
         )
     }
 
+    /// `__swift_bridge__$some_function$next`, for getting the next item of a returned stream.
+    pub fn stream_next_link_name(&self) -> String {
+        format!("{}$next", self.link_name())
+    }
+    pub fn stream_free_link_name(&self) -> String {
+        format!("{}$_free", self.link_name())
+    }
+
+    /// `__swift_bridge__$some_function$next`, for getting the next item of a returned iterator.
+    pub fn iterator_next_link_name(&self) -> String {
+        format!("{}$next", self.link_name())
+    }
+    pub fn iterator_free_link_name(&self) -> String {
+        format!("{}$_free", self.link_name())
+    }
+
     pub fn call_boxed_fn_link_name(&self, boxed_fn_idx: usize) -> String {
         format!("{}$param{}", self.link_name(), boxed_fn_idx)
     }
@@ -486,7 +724,7 @@ impl ParsedExternFn {
     pub fn boxed_fn_to_c_header_fns(
         &self,
         idx: usize,
-        boxed_fn: &BridgeableBoxedFnOnce,
+        boxed_fn: &BridgeableBoxedFn,
         types: &TypeDeclarations,
     ) -> String {
         let call_boxed_fn_link_name = self.call_boxed_fn_link_name(idx);
@@ -546,7 +784,7 @@ void {free_boxed_fn_link_name}(void* {boxed_fn_arg_name});"#
     pub fn args_filtered_to_boxed_fns(
         &self,
         type_decls: &TypeDeclarations,
-    ) -> Vec<(usize, BridgeableBoxedFnOnce)> {
+    ) -> Vec<(usize, BridgeableBoxedFn)> {
         self.func
             .sig
             .inputs
@@ -556,7 +794,7 @@ void {free_boxed_fn_link_name}(void* {boxed_fn_arg_name});"#
                 let ty = BridgedType::new_with_fn_arg(arg, type_decls)?;
 
                 match ty {
-                    BridgedType::StdLib(StdLibType::BoxedFnOnce(boxed_fn)) => Some((idx, boxed_fn)),
+                    BridgedType::StdLib(StdLibType::BoxedFn(boxed_fn)) => Some((idx, boxed_fn)),
                     _ => None,
                 }
             })
@@ -564,7 +802,7 @@ void {free_boxed_fn_link_name}(void* {boxed_fn_arg_name});"#
     }
 
     /// `let cb1 = __private__RustFnOnceCallback$some_function$param0(ptr: callback); let cb0 = ...`
-    pub fn fnonce_callback_initializers(
+    pub fn boxed_fn_callback_initializers(
         &self,
         fn_name: &str,
         maybe_associated_ty: &str,
@@ -573,15 +811,16 @@ void {free_boxed_fn_link_name}(void* {boxed_fn_arg_name});"#
         let mut initializers = "".to_string();
         let mut maybe_space = "";
 
-        for (idx, fn_once) in self.args_filtered_to_boxed_fns(types) {
+        for (idx, boxed_fn) in self.args_filtered_to_boxed_fns(types) {
             let arg_name = self.arg_name_at_idx(idx).unwrap();
 
-            if fn_once.params.is_empty() && fn_once.ret.is_null() {
+            if boxed_fn.uses_no_args_no_return_support() {
                 initializers += &format!(
                 "{maybe_space}let cb{idx} = __private__RustFnOnceCallbackNoArgsNoRet(ptr: {arg_name});"
             );
             } else {
-                initializers += &format!("{maybe_space}let cb{idx} = __private__RustFnOnceCallback{maybe_associated_ty}${fn_name}$param{idx}(ptr: {arg_name});");
+                let class_prefix = boxed_fn.swift_callback_class_prefix();
+                initializers += &format!("{maybe_space}let cb{idx} = {class_prefix}{maybe_associated_ty}${fn_name}$param{idx}(ptr: {arg_name});");
             }
 
             maybe_space = " ";
@@ -590,6 +829,17 @@ void {free_boxed_fn_link_name}(void* {boxed_fn_arg_name});"#
         initializers
     }
 
+    /// The Swift class that holds on to a Swift closure that was passed to Rust.
+    ///
+    /// __private__SwiftCallback$some_function$param0
+    pub fn swift_callback_class_name(&self, boxed_fn_idx: usize) -> String {
+        format!(
+            "__private__SwiftCallback{}$param{}",
+            self.link_name().trim_start_matches(SWIFT_BRIDGE_PREFIX),
+            boxed_fn_idx
+        )
+    }
+
     /// Get the name of the argument at the given index.
     ///
     /// So, `fn some_function (foo: u32, bar: u8);`
@@ -670,6 +920,78 @@ pub(crate) fn fn_arg_is_mutable_reference(fn_arg: &FnArg) -> bool {
     }
 }
 
+/// Get the `T` in an `impl Stream<Item = T>` return type.
+pub(crate) fn impl_stream_item_ty(ret: &ReturnType) -> Option<&Type> {
+    let ty = match ret {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => return None,
+    };
+
+    match ty.deref() {
+        Type::ImplTrait(impl_trait) => trait_bounds_item_ty(&impl_trait.bounds, "Stream"),
+        _ => None,
+    }
+}
+
+/// Get the `T` in an `impl Iterator<Item = T>` or `Box<dyn Iterator<Item = T>>` return type.
+pub(crate) fn iterator_item_ty(ret: &ReturnType) -> Option<&Type> {
+    let ty = match ret {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => return None,
+    };
+
+    match ty.deref() {
+        Type::ImplTrait(impl_trait) => trait_bounds_item_ty(&impl_trait.bounds, "Iterator"),
+        Type::Path(path) => {
+            let last_segment = path.path.segments.last()?;
+            if last_segment.ident != "Box" {
+                return None;
+            }
+
+            let args = match &last_segment.arguments {
+                PathArguments::AngleBracketed(args) => args,
+                _ => return None,
+            };
+            match args.args.first()? {
+                GenericArgument::Type(Type::TraitObject(trait_object)) => {
+                    trait_bounds_item_ty(&trait_object.bounds, "Iterator")
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Get the `T` in a `Trait<Item = T>` bound.
+fn trait_bounds_item_ty<'a>(
+    bounds: &'a Punctuated<TypeParamBound, Token![+]>,
+    trait_name: &str,
+) -> Option<&'a Type> {
+    for bound in bounds.iter() {
+        let trait_bound = match bound {
+            TypeParamBound::Trait(trait_bound) => trait_bound,
+            _ => continue,
+        };
+        let last_segment = trait_bound.path.segments.last()?;
+        if last_segment.ident != trait_name {
+            continue;
+        }
+
+        if let PathArguments::AngleBracketed(args) = &last_segment.arguments {
+            for arg in args.args.iter() {
+                if let GenericArgument::Binding(binding) = arg {
+                    if binding.ident == "Item" {
+                        return Some(&binding.ty);
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
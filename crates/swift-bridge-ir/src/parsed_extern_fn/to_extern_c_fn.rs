@@ -1,11 +1,13 @@
-use crate::bridged_type::BridgedType;
+use crate::bridged_type::{pat_type_pat_is_self, BridgeableType, BridgedType};
 use crate::parse::{HostLang, OpaqueCopy, TypeDeclaration, TypeDeclarations};
-use crate::parsed_extern_fn::{GetField, GetFieldDirect, GetFieldWith, ParsedExternFn};
+use crate::parsed_extern_fn::{
+    GetField, GetFieldDirect, GetFieldWith, ParsedExternFn, SetField, SetFieldDirect, SetFieldWith,
+};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use std::collections::HashMap;
 use syn::spanned::Spanned;
-use syn::Path;
+use syn::{FnArg, Path};
 
 impl ParsedExternFn {
     /// Generates:
@@ -40,7 +42,25 @@ impl ParsedExternFn {
 
         let prefixed_fn_name = self.prefixed_fn_name();
 
+        if self.stream_item_ty().is_some() {
+            return self.to_extern_c_stream_fn_tokens(
+                &params,
+                swift_bridge_path,
+                types,
+                custom_type_definitions,
+            );
+        }
+        if self.iterator_item_ty().is_some() {
+            return self.to_extern_c_iterator_fn_tokens(
+                &params,
+                swift_bridge_path,
+                types,
+                custom_type_definitions,
+            );
+        }
+
         let ret = self.rust_fn_sig_return_tokens(swift_bridge_path, types, custom_type_definitions);
+        let maybe_allow_deprecated = self.maybe_allow_deprecated();
 
         match self.host_lang {
             HostLang::Rust => {
@@ -51,74 +71,399 @@ impl ParsedExternFn {
                 let is_async = self.sig.asyncness.is_some();
 
                 if !is_async {
+                    let call_fn = self.maybe_catch_panics(call_fn, swift_bridge_path, types);
+
                     quote! {
+                        #maybe_allow_deprecated
                         #[export_name = #link_name]
                         pub extern "C" fn #prefixed_fn_name ( #params ) #ret {
                             #call_fn
                         }
                     }
                 } else {
-                    let (await_fut, call_callback) = if maybe_return_ty.is_some() {
-                        let return_ty = self.return_ty_built_in(types).unwrap();
-                        let awaited_val = return_ty.convert_rust_expression_to_ffi_type(
-                            &quote! {fut.await},
-                            swift_bridge_path,
-                            types,
-                            // TODO: Add a UI test and then add a better span.
-                            Span::call_site(),
-                        );
-
-                        (
-                            quote! {
-                                let val = #awaited_val;
-                            },
-                            quote! {
-                                (callback)(callback_wrapper, val)
-                            },
-                        )
+                    let return_ty = self.return_ty_built_in(types).unwrap();
+                    let cancellable_task = cancellable_task_tokens(
+                        &return_ty,
+                        self.catch_panics,
+                        swift_bridge_path,
+                        types,
+                    );
+
+                    let body = if self.is_local {
+                        self.spawn_local_task_tokens(&cancellable_task, swift_bridge_path, types)
                     } else {
-                        (
-                            quote! {
-                                fut.await;
-                            },
-                            quote! {
-                                (callback)(callback_wrapper)
-                            },
-                        )
+                        quote! {
+                            let fut = #call_fn;
+                            #cancellable_task
+                            swift_bridge::async_support::spawn_task(Box::pin(task))
+                        }
                     };
 
                     quote! {
+                        #maybe_allow_deprecated
                         #[export_name = #link_name]
                         pub extern "C" fn #prefixed_fn_name (
+                            task_handle: *const std::ffi::c_void,
                             callback_wrapper: *mut std::ffi::c_void,
                             callback: extern "C" fn(*mut std::ffi::c_void #maybe_return_ty) -> (),
+                            on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
                             #params
                         ) {
-                            let callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
-                            let fut = #call_fn;
-                            let task = async move {
-                                #await_fut
-
-                                let callback_wrapper = callback_wrapper;
-                                let callback_wrapper = callback_wrapper.0;
-
-                                #call_callback
-                            };
-                            swift_bridge::async_support::ASYNC_RUNTIME.spawn_task(Box::pin(task))
+                            #body
                         }
                     }
                 }
             }
             HostLang::Swift => {
-                quote! {
-                    #[link_name = #link_name]
-                    fn #prefixed_fn_name ( #params ) #ret;
+                if self.sig.asyncness.is_none() {
+                    quote! {
+                        #[link_name = #link_name]
+                        fn #prefixed_fn_name ( #params ) #ret;
+                    }
+                } else {
+                    let maybe_return_ty =
+                        self.maybe_async_rust_fn_return_ty(swift_bridge_path, types);
+
+                    quote! {
+                        #[link_name = #link_name]
+                        fn #prefixed_fn_name (
+                            callback_wrapper: *mut std::ffi::c_void,
+                            callback: extern "C" fn(*mut std::ffi::c_void #maybe_return_ty) -> (),
+                            #params
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Generates the functions for returning an `impl Stream<Item = T>` to Swift.
+    ///
+    /// ```no_run,ignore
+    /// // Returns a `RustStream<T>` that Swift holds on to.
+    /// #[export_name = "__swift_bridge__$some_function"]
+    /// pub extern "C" fn __swift_bridge__some_function() -> *mut RustStream<T> { ... }
+    ///
+    /// // Spawns a task that passes the stream's next item to Swift's callback.
+    /// #[export_name = "__swift_bridge__$some_function$next"]
+    /// pub extern "C" fn __swift_bridge__some_function__next(this: *mut RustStream<T>, ...) { ... }
+    ///
+    /// // Drops the stream once Swift is done iterating over it.
+    /// #[export_name = "__swift_bridge__$some_function$_free"]
+    /// pub extern "C" fn __swift_bridge__some_function__free(this: *mut RustStream<T>) { ... }
+    /// ```
+    fn to_extern_c_stream_fn_tokens(
+        &self,
+        params: &TokenStream,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+        custom_type_definitions: &mut HashMap<String, TokenStream>,
+    ) -> TokenStream {
+        let link_name = self.link_name();
+        let next_link_name = self.stream_next_link_name();
+        let free_link_name = self.stream_free_link_name();
+
+        let prefixed_fn_name = self.prefixed_fn_name();
+        let next_fn_name = Ident::new(
+            &format!("{}__next", prefixed_fn_name),
+            prefixed_fn_name.span(),
+        );
+        let free_fn_name = Ident::new(
+            &format!("{}__free", prefixed_fn_name),
+            prefixed_fn_name.span(),
+        );
+
+        let item_ty = BridgedType::new_with_type(self.stream_item_ty().unwrap(), types).unwrap();
+        let item_ty = item_ty.to_rust_type_path(types);
+        let stream_ty = quote! { #swift_bridge_path::async_support::RustStream<#item_ty> };
+
+        let next_ty = self.stream_next_ty(types).unwrap();
+        if let Some(tokens) = next_ty.generate_custom_rust_ffi_types(swift_bridge_path, types) {
+            for token in tokens.into_iter() {
+                custom_type_definitions.insert(token.to_string(), token);
+            }
+        }
+        let next_ffi_ty = next_ty.to_ffi_compatible_rust_type(swift_bridge_path, types);
+
+        let call_fn = self.call_fn_tokens(swift_bridge_path, types);
+        let new_stream = self.maybe_catch_panics(
+            quote! {
+                #swift_bridge_path::async_support::RustStream::new(#call_fn).box_into_raw()
+            },
+            swift_bridge_path,
+            types,
+        );
+        let free_stream = self.maybe_catch_panics(
+            quote! {
+                let _ = unsafe { Box::from_raw(this) };
+            },
+            swift_bridge_path,
+            types,
+        );
+        let cancellable_task =
+            cancellable_task_tokens(&next_ty, self.catch_panics, swift_bridge_path, types);
+
+        let maybe_allow_deprecated = self.maybe_allow_deprecated();
+
+        quote! {
+            #maybe_allow_deprecated
+            #[export_name = #link_name]
+            pub extern "C" fn #prefixed_fn_name ( #params ) -> *mut #stream_ty {
+                #new_stream
+            }
+
+            #[export_name = #next_link_name]
+            pub extern "C" fn #next_fn_name (
+                this: *mut #stream_ty,
+                task_handle: *const std::ffi::c_void,
+                callback_wrapper: *mut std::ffi::c_void,
+                callback: extern "C" fn(*mut std::ffi::c_void, #next_ffi_ty) -> (),
+                on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
+            ) {
+                let fut = unsafe { #swift_bridge_path::async_support::RustStream::next(this) };
+                #cancellable_task
+                swift_bridge::async_support::spawn_task(Box::pin(task))
+            }
+
+            #[export_name = #free_link_name]
+            pub extern "C" fn #free_fn_name (this: *mut #stream_ty) {
+                #free_stream
+            }
+        }
+    }
+
+    /// Generates the functions for returning an `impl Iterator<Item = T>` or a
+    /// `Box<dyn Iterator<Item = T>>` to Swift.
+    ///
+    /// ```no_run,ignore
+    /// // Returns a `RustIterator<T>` that Swift holds on to.
+    /// #[export_name = "__swift_bridge__$some_function"]
+    /// pub extern "C" fn __swift_bridge__some_function() -> *mut RustIterator<T> { ... }
+    ///
+    /// // Advances the iterator and returns its next item.
+    /// #[export_name = "__swift_bridge__$some_function$next"]
+    /// pub extern "C" fn __swift_bridge__some_function__next(this: *mut RustIterator<T>) -> ... { ... }
+    ///
+    /// // Drops the iterator once Swift is done iterating over it.
+    /// #[export_name = "__swift_bridge__$some_function$_free"]
+    /// pub extern "C" fn __swift_bridge__some_function__free(this: *mut RustIterator<T>) { ... }
+    /// ```
+    fn to_extern_c_iterator_fn_tokens(
+        &self,
+        params: &TokenStream,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+        custom_type_definitions: &mut HashMap<String, TokenStream>,
+    ) -> TokenStream {
+        let link_name = self.link_name();
+        let next_link_name = self.iterator_next_link_name();
+        let free_link_name = self.iterator_free_link_name();
+
+        let prefixed_fn_name = self.prefixed_fn_name();
+        let next_fn_name = Ident::new(
+            &format!("{}__next", prefixed_fn_name),
+            prefixed_fn_name.span(),
+        );
+        let free_fn_name = Ident::new(
+            &format!("{}__free", prefixed_fn_name),
+            prefixed_fn_name.span(),
+        );
+
+        let item_ty = BridgedType::new_with_type(self.iterator_item_ty().unwrap(), types).unwrap();
+        let item_ty = item_ty.to_rust_type_path(types);
+        let iterator_ty = quote! { #swift_bridge_path::iterator_support::RustIterator<#item_ty> };
+
+        let next_ty = self.iterator_next_ty(types).unwrap();
+        if let Some(tokens) = next_ty.generate_custom_rust_ffi_types(swift_bridge_path, types) {
+            for token in tokens.into_iter() {
+                custom_type_definitions.insert(token.to_string(), token);
+            }
+        }
+        let next_ffi_ty = next_ty.to_ffi_compatible_rust_type(swift_bridge_path, types);
+
+        let call_fn = self.call_fn_tokens(swift_bridge_path, types);
+        let new_iterator = self.maybe_catch_panics(
+            quote! {
+                #swift_bridge_path::iterator_support::RustIterator::new(#call_fn).box_into_raw()
+            },
+            swift_bridge_path,
+            types,
+        );
+        let next_item = next_ty.convert_rust_expression_to_ffi_type(
+            &quote! { unsafe { (&mut *this).next() } },
+            swift_bridge_path,
+            types,
+            self.func.span(),
+        );
+        let next_item = self.maybe_catch_panics(next_item, swift_bridge_path, types);
+        let free_iterator = self.maybe_catch_panics(
+            quote! {
+                let _ = unsafe { Box::from_raw(this) };
+            },
+            swift_bridge_path,
+            types,
+        );
+
+        let maybe_allow_deprecated = self.maybe_allow_deprecated();
+
+        quote! {
+            #maybe_allow_deprecated
+            #[export_name = #link_name]
+            pub extern "C" fn #prefixed_fn_name ( #params ) -> *mut #iterator_ty {
+                #new_iterator
+            }
+
+            #[export_name = #next_link_name]
+            pub extern "C" fn #next_fn_name (this: *mut #iterator_ty) -> #next_ffi_ty {
+                #next_item
+            }
+
+            #[export_name = #free_link_name]
+            pub extern "C" fn #free_fn_name (this: *mut #iterator_ty) {
+                #free_iterator
+            }
+        }
+    }
+
+    /// Generates the body of a `#[swift_bridge(local)]` async function, which creates its future
+    /// on the local executor's thread.
+    ///
+    /// The arguments are converted to their Rust types before being moved to the local executor's
+    /// thread, so the compiler verifies that they are `Send`. Methods assert that their `self` type
+    /// is `Sync` for `&self` or `Send` for `&mut self` and `self`. Types can hold non thread-safe
+    /// state in a `swift_bridge::async_support::LocalState` to meet these requirements.
+    ///
+    /// ```no_run,ignore
+    /// let arg = /* convert the FFI argument */;
+    /// let task_handle = swift_bridge::async_support::AssertSend(task_handle);
+    /// let callback_wrapper = swift_bridge::async_support::AssertSend(callback_wrapper);
+    /// swift_bridge::async_support::spawn_local_task(move || {
+    ///     let task_handle = task_handle.into_inner();
+    ///     let callback_wrapper = callback_wrapper.into_inner();
+    ///     let fut = super::some_function(arg);
+    ///     // ...
+    ///     task
+    /// })
+    /// ```
+    fn spawn_local_task_tokens(
+        &self,
+        cancellable_task: &TokenStream,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        let arg_names: Vec<TokenStream> = self
+            .func
+            .sig
+            .inputs
+            .iter()
+            .filter_map(|arg| match arg {
+                FnArg::Typed(pat_ty) if !pat_type_pat_is_self(pat_ty) => {
+                    let pat = &pat_ty.pat;
+                    Some(quote! { #pat })
                 }
+                _ => None,
+            })
+            .collect();
+        let arg_conversions = self.to_call_rust_arg_list(swift_bridge_path, types);
+
+        let call_fn =
+            self.call_fn_tokens_with_args(quote! { #(#arg_names),* }, swift_bridge_path, types);
+
+        let (send_this, receive_this) = if self.is_method() {
+            let assert_this = if self.is_copy_method_on_opaque_type() {
+                quote! {}
+            } else if self.self_reference().is_some() && self.self_mutability().is_none() {
+                quote! { #swift_bridge_path::async_support::assert_self_is_sync(this); }
+            } else {
+                quote! { #swift_bridge_path::async_support::assert_self_is_send(this); }
+            };
+
+            (
+                quote! {
+                    #assert_this
+                    let this = #swift_bridge_path::async_support::AssertSend(this);
+                },
+                quote! {
+                    let this = this.into_inner();
+                },
+            )
+        } else {
+            (quote! {}, quote! {})
+        };
+
+        quote! {
+            #(let #arg_names = #arg_conversions;)*
+            #send_this
+            let task_handle = #swift_bridge_path::async_support::AssertSend(task_handle);
+            let callback_wrapper = #swift_bridge_path::async_support::AssertSend(callback_wrapper);
+            #swift_bridge_path::async_support::spawn_local_task(move || {
+                #receive_this
+                let task_handle = task_handle.into_inner();
+                let callback_wrapper = callback_wrapper.into_inner();
+                let fut = #call_fn;
+                #cancellable_task
+                task
+            })
+        }
+    }
+
+    /// When catching panics, wraps a function's body so that panics don't unwind into Swift.
+    ///
+    /// ```no_run,ignore
+    /// // Functions that throw in Swift, such as those that return a `Result`.
+    /// unsafe { swift_bridge::panic_support::call_or_store_panic(|| { /* ... */ }) }
+    ///
+    /// // All other functions.
+    /// swift_bridge::panic_support::call_or_abort(|| { /* ... */ })
+    /// ```
+    fn maybe_catch_panics(
+        &self,
+        body: TokenStream,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        if !self.catch_panics {
+            return body;
+        }
+
+        if self.throws_in_swift(types) {
+            quote! {
+                unsafe { #swift_bridge_path::panic_support::call_or_store_panic(|| { #body }) }
+            }
+        } else {
+            quote! {
+                #swift_bridge_path::panic_support::call_or_abort(|| { #body })
             }
         }
     }
 
+    /// `#[allow(deprecated)]` if the function or the type that it belongs to is `#[deprecated]`,
+    /// since the function that we export calls it.
+    fn maybe_allow_deprecated(&self) -> TokenStream {
+        let associated_type_is_deprecated = self
+            .associated_type
+            .as_ref()
+            .and_then(|ty| ty.as_opaque())
+            .is_some_and(|ty| ty.attributes.deprecated.is_some());
+
+        if self.deprecated.is_some() || associated_type_is_deprecated {
+            quote! { #[allow(deprecated)] }
+        } else {
+            quote! {}
+        }
+    }
+
     fn call_fn_tokens(&self, swift_bridge_path: &Path, types: &TypeDeclarations) -> TokenStream {
+        let call_args = self.to_call_rust_args(swift_bridge_path, types);
+        self.call_fn_tokens_with_args(call_args, swift_bridge_path, types)
+    }
+
+    fn call_fn_tokens_with_args(
+        &self,
+        call_args: TokenStream,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
         let sig = &self.func.sig;
         let fn_name = if let Some(fn_name) = self.rust_name_override.as_ref() {
             let span = fn_name.span();
@@ -132,18 +477,21 @@ impl ParsedExternFn {
             }
         };
 
-        let call_args = self.to_call_rust_args(swift_bridge_path, types);
-
         let call_fn = quote! {
             #fn_name ( #call_args )
         };
 
         let mut call_fn = if self.is_method() {
-            self.call_method_tokens(&call_fn)
+            self.call_method_tokens(&call_fn, &call_args)
         } else {
             self.call_function_tokens(&call_fn)
         };
 
+        // Each of the stream's or iterator's items gets converted as Swift asks for it.
+        if self.stream_item_ty().is_some() || self.iterator_item_ty().is_some() {
+            return call_fn;
+        }
+
         let return_ty = self.return_ty_built_in(types).unwrap();
 
         if self.return_into {
@@ -171,7 +519,7 @@ impl ParsedExternFn {
     }
 
     /// Generate tokens for calling a method.
-    fn call_method_tokens(&self, call_fn: &TokenStream) -> TokenStream {
+    fn call_method_tokens(&self, call_fn: &TokenStream, call_args: &TokenStream) -> TokenStream {
         let this = if self.is_copy_method_on_opaque_type() {
             quote! {
                 this.into_rust_repr()
@@ -191,6 +539,21 @@ impl ParsedExternFn {
             }
         };
 
+        if let Some(set_field) = &self.set_field {
+            return match set_field {
+                SetField::Direct(SetFieldDirect { field_name }) => {
+                    quote! {
+                        #this . #field_name = #call_args
+                    }
+                }
+                SetField::With(SetFieldWith { field_name, path }) => {
+                    quote! {
+                        #this . #field_name = super::#path ( #call_args )
+                    }
+                }
+            };
+        }
+
         match &self.get_field {
             Some(GetField::Direct(get_direct)) => {
                 let GetFieldDirect {
@@ -260,6 +623,95 @@ impl ParsedExternFn {
     }
 }
 
+/// Creates a `task` that awaits `fut` and passes its output to Swift's `callback`, unless Swift
+/// cancels the task first.
+///
+/// When catching panics, a panic gets stored for Swift to throw as a `RustPanic`, and Swift's
+/// `callback` receives a zeroed value that it discards.
+fn cancellable_task_tokens(
+    return_ty: &BridgedType,
+    catch_panics: bool,
+    swift_bridge_path: &Path,
+    types: &TypeDeclarations,
+) -> TokenStream {
+    let returns_null = return_ty
+        .to_ffi_compatible_rust_type(swift_bridge_path, types)
+        .to_string()
+        == "()";
+
+    let fut_await = if catch_panics {
+        let call_callback_zeroed = if returns_null {
+            quote! { (callback)(callback_wrapper) }
+        } else {
+            quote! { (callback)(callback_wrapper, unsafe { std::mem::zeroed() }) }
+        };
+
+        quote! {
+            match #swift_bridge_path::async_support::CatchUnwind::new(fut).await {
+                Ok(val) => val,
+                Err(payload) => {
+                    #swift_bridge_path::panic_support::store_panic(payload);
+
+                    let callback_wrapper = task_callback_wrapper;
+                    let callback_wrapper = callback_wrapper.0;
+
+                    return #call_callback_zeroed;
+                }
+            }
+        }
+    } else {
+        quote! { fut.await }
+    };
+
+    let (await_fut, call_callback) = if !returns_null {
+        let awaited_val = return_ty.convert_rust_expression_to_ffi_type(
+            &fut_await,
+            swift_bridge_path,
+            types,
+            // TODO: Add a UI test and then add a better span.
+            Span::call_site(),
+        );
+
+        (
+            quote! {
+                let val = #awaited_val;
+            },
+            quote! {
+                (callback)(callback_wrapper, val)
+            },
+        )
+    } else {
+        (
+            quote! {
+                #fut_await;
+            },
+            quote! {
+                (callback)(callback_wrapper)
+            },
+        )
+    };
+
+    quote! {
+        let task_callback_wrapper = swift_bridge::async_support::SwiftCallbackWrapper(callback_wrapper);
+        let task = async move {
+            #await_fut
+
+            let callback_wrapper = task_callback_wrapper;
+            let callback_wrapper = callback_wrapper.0;
+
+            #call_callback
+        };
+        let task = unsafe {
+            swift_bridge::async_support::CancellableTask::new(
+                task_handle as *const swift_bridge::async_support::AsyncTaskHandle,
+                Box::pin(task),
+                callback_wrapper,
+                on_cancel
+            )
+        };
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
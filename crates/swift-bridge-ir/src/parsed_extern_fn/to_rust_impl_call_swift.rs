@@ -37,39 +37,47 @@ impl ParsedExternFn {
         let sig = &self.func.sig;
         let fn_name = &sig.ident;
 
-        let ret = &sig.output;
-
-        let ret = match &ret {
-            ReturnType::Default => {
-                quote! {#ret}
-            }
-            ReturnType::Type(arrow, _ty) => {
-                if let Some(built_in) = BridgedType::new_with_return_type(&sig.output, types) {
-                    let ty = built_in.maybe_convert_pointer_to_super_pointer(types);
-                    let return_ty_span = sig.output.span();
-
-                    quote_spanned! {return_ty_span=> #arrow #ty}
-                } else {
-                    quote! { #ret }
-                }
-            }
-        };
+        let ret = self.rust_fn_that_calls_a_swift_extern_return_type(types);
 
         let params = self.params_with_explicit_self_types_removed(types);
         let call_args = self.to_call_rust_args(swift_bridge_path, types);
         let linked_fn_name = self.extern_swift_linked_fn_new();
 
+        if sig.asyncness.is_some() {
+            return self.to_async_rust_fn_that_calls_a_swift_extern(
+                &params,
+                &call_args,
+                swift_bridge_path,
+                types,
+            );
+        }
+
         let mut inner = quote! {
             unsafe { #linked_fn_name(#call_args) }
         };
 
         if let Some(built_in) = BridgedType::new_with_return_type(&sig.output, types) {
-            inner = built_in.convert_ffi_expression_to_rust_type(
-                &inner,
-                sig.output.span(),
-                swift_bridge_path,
-                types,
-            );
+            if built_in.is_result() {
+                // The `Result` conversion uses its FFI value more than once, so we only call
+                // Swift once.
+                let convert = built_in.convert_ffi_expression_to_rust_type(
+                    &quote! { val },
+                    sig.output.span(),
+                    swift_bridge_path,
+                    types,
+                );
+                inner = quote! {
+                    let val = #inner;
+                    #convert
+                };
+            } else {
+                inner = built_in.convert_ffi_expression_to_rust_type(
+                    &inner,
+                    sig.output.span(),
+                    swift_bridge_path,
+                    types,
+                );
+            }
         } else {
             todo!("Push to ParsedErrors")
         }
@@ -81,6 +89,130 @@ impl ParsedExternFn {
         }
     }
 
+    /// Generates a function that calls an async Swift function and returns a future that
+    /// resolves when Swift calls back with the function's output.
+    ///
+    /// ```no_run,ignore
+    /// pub fn some_function(arg: u8) -> impl std::future::Future<Output = u16> {
+    ///     extern "C" fn on_complete(callback_wrapper: *mut std::ffi::c_void, val: u16) {
+    ///         let val = val;
+    ///         unsafe { swift_bridge::async_support::SwiftFuture::<u16>::complete(callback_wrapper, val) }
+    ///     }
+    ///
+    ///     let (future, callback_wrapper) = swift_bridge::async_support::SwiftFuture::<u16>::new();
+    ///     unsafe { __swift_bridge__some_function(callback_wrapper, on_complete, arg) };
+    ///     future
+    /// }
+    /// ```
+    ///
+    /// Swift is called right away, so the returned future does not borrow any of the arguments.
+    fn to_async_rust_fn_that_calls_a_swift_extern(
+        &self,
+        params: &TokenStream,
+        call_args: &TokenStream,
+        swift_bridge_path: &Path,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        let sig = &self.func.sig;
+        let fn_name = &sig.ident;
+        let linked_fn_name = self.extern_swift_linked_fn_new();
+
+        let return_ty = self.return_ty_built_in(types).unwrap();
+        let output = return_ty.maybe_convert_pointer_to_super_pointer(types);
+
+        let (maybe_val_param, output_val) = if return_ty.is_null() {
+            (quote! {}, quote! { let val = (); })
+        } else {
+            let ffi_ty = return_ty.to_ffi_compatible_rust_type(swift_bridge_path, types);
+            let output_val = return_ty.convert_ffi_expression_to_rust_type(
+                &quote! { val },
+                sig.output.span(),
+                swift_bridge_path,
+                types,
+            );
+
+            (quote! { , val: #ffi_ty }, quote! { let val = #output_val; })
+        };
+
+        let swift_future = quote! { #swift_bridge_path::async_support::SwiftFuture::<#output> };
+
+        quote! {
+            pub fn #fn_name(#params) -> impl std::future::Future<Output = #output> {
+                extern "C" fn on_complete(callback_wrapper: *mut std::ffi::c_void #maybe_val_param) {
+                    #output_val
+                    unsafe { #swift_future::complete(callback_wrapper, val) }
+                }
+
+                let (future, callback_wrapper) = #swift_future::new();
+                unsafe { #linked_fn_name(callback_wrapper, on_complete, #call_args) };
+                future
+            }
+        }
+    }
+
+    /// Generates a trait method that forwards to the method that calls the Swift extern.
+    ///
+    /// Used when implementing the Rust trait for a `#[swift_bridge(protocol)]` type.
+    ///
+    /// ```no_run,ignore
+    /// impl super::Storage for Storage {
+    ///     // We're generating this function.
+    ///     fn read(&self, key: &str) -> Option<Vec<u8>> {
+    ///         Storage::read(self, key)
+    ///     }
+    /// }
+    /// ```
+    pub fn to_rust_trait_method_that_calls_a_swift_extern(
+        &self,
+        ty_name: &Ident,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        let fn_name = &self.func.sig.ident;
+
+        let ret = self.rust_fn_that_calls_a_swift_extern_return_type(types);
+        let params = self.params_with_explicit_self_types_removed(types);
+
+        let mut args = vec![];
+        for arg in self.func.sig.inputs.iter() {
+            match arg {
+                FnArg::Receiver(_) => args.push(quote! { self }),
+                FnArg::Typed(pat_ty) => {
+                    let pat = &pat_ty.pat;
+                    args.push(quote! { #pat });
+                }
+            }
+        }
+
+        quote! {
+            fn #fn_name(#params) #ret {
+                #ty_name::#fn_name(#(#args),*)
+            }
+        }
+    }
+
+    fn rust_fn_that_calls_a_swift_extern_return_type(
+        &self,
+        types: &TypeDeclarations,
+    ) -> TokenStream {
+        let ret = &self.func.sig.output;
+
+        match &ret {
+            ReturnType::Default => {
+                quote! {#ret}
+            }
+            ReturnType::Type(arrow, _ty) => {
+                if let Some(built_in) = BridgedType::new_with_return_type(ret, types) {
+                    let ty = built_in.maybe_convert_pointer_to_super_pointer(types);
+                    let return_ty_span = ret.span();
+
+                    quote_spanned! {return_ty_span=> #arrow #ty}
+                } else {
+                    quote! { #ret }
+                }
+            }
+        }
+    }
+
     /// #\[export_name = "__swift_bridge__$SomeType$some_method$param1"]
     /// pub extern "C" fn SomeType_some_method_param1(boxed_fn: *mut dyn FnOnce(u8) -> (), arg0: u8) {
     ///     unsafe { Box::from_raw(boxed_fn) }(arg0)
@@ -99,7 +231,7 @@ impl ParsedExternFn {
 
         let mut boxed_fn_support = vec![];
         for (idx, boxed_fn) in self.args_filtered_to_boxed_fns(types) {
-            if boxed_fn.does_not_have_params_or_return() {
+            if boxed_fn.uses_no_args_no_return_support() {
                 continue;
             }
 
@@ -144,9 +276,7 @@ impl ParsedExternFn {
             let arg_name = self.arg_name_tokens_at_idx(idx).unwrap();
             let arg_name = Ident::new(&format!("{}_{}", fn_name, arg_name), arg_name.span());
 
-            let call_boxed_fn = quote! {
-                unsafe { Box::from_raw(#arg_name)(#(#call_args),*) }
-            };
+            let call_boxed_fn = boxed_fn.call_boxed_fn_ptr(&arg_name.to_token_stream(), &call_args);
             let call_boxed_fn = boxed_fn.ret.convert_rust_expression_to_ffi_type(
                 &call_boxed_fn,
                 swift_bridge_path,
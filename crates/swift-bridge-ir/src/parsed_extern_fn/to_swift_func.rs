@@ -1,9 +1,11 @@
-use crate::bridged_type::{pat_type_pat_is_self, BridgeableType, BridgedType, TypePosition};
-use crate::parse::TypeDeclarations;
+use crate::bridged_type::{
+    pat_type_pat_is_self, BridgeableType, BridgedType, StdLibType, TypePosition,
+};
+use crate::parse::{TypeDeclaration, TypeDeclarations};
 use crate::parsed_extern_fn::ParsedExternFn;
 use quote::{format_ident, ToTokens};
 use std::ops::Deref;
-use syn::{FnArg, Path, ReturnType, Type};
+use syn::{FnArg, PatType, Path, ReturnType, Type};
 
 impl ParsedExternFn {
     pub fn to_swift_param_names_and_types(
@@ -45,12 +47,18 @@ impl ParsedExternFn {
                         todo!("Push to ParsedErrors")
                     };
 
-                    if let Some(argument_label) =
+                    let param = if let Some(argument_label) =
                         self.argument_labels.get(&format_ident!("{}", arg_name))
                     {
                         format!("{} {}: {}", argument_label.value().as_str(), arg_name, ty)
                     } else {
                         format!("_ {}: {}", arg_name, ty)
+                    };
+
+                    // Only extern "Rust" functions can have default values.
+                    match self.argument_defaults.get(&format_ident!("{}", arg_name)) {
+                        Some(default) => format!("{} = {}", param, default),
+                        None => param,
                     }
                 }
             };
@@ -96,36 +104,12 @@ impl ParsedExternFn {
                         continue;
                     }
 
-                    let pat = &pat_ty.pat;
-                    let arg = pat.to_token_stream().to_string();
-                    let arg_name = arg.clone();
-
-                    let arg =
-                        if let Some(bridged_ty) = BridgedType::new_with_type(&pat_ty.ty, types) {
-                            if self.host_lang.is_rust() {
-                                if bridged_ty.can_be_encoded_with_zero_bytes() {
-                                    continue;
-                                }
-
-                                bridged_ty.convert_swift_expression_to_ffi_type(
-                                    &arg,
-                                    types,
-                                    TypePosition::FnArg(self.host_lang, arg_idx),
-                                )
-                            } else {
-                                if let Some(only) = bridged_ty.only_encoding() {
-                                    only.swift
-                                } else {
-                                    bridged_ty.convert_ffi_value_to_swift_value(
-                                        &arg,
-                                        TypePosition::FnArg(self.host_lang, arg_idx),
-                                        types,
-                                    )
-                                }
-                            }
-                        } else {
-                            todo!("Push to ParsedErrors")
-                        };
+                    let arg_name = pat_ty.pat.to_token_stream().to_string();
+                    let arg = match self.to_swift_call_arg(arg_idx, pat_ty, types) {
+                        Some(arg) => arg,
+                        None => continue,
+                    };
+
                     let arg = if include_var_name {
                         format!("{}: {}", arg_name, arg)
                     } else {
@@ -139,7 +123,112 @@ impl ParsedExternFn {
         args.join(", ")
     }
 
+    /// Converts the arguments that Rust passed to an async Swift function before the Swift
+    /// `Task` gets spawned, since Rust is free to drop the arguments as soon as the call returns.
+    ///
+    /// fn foo (&self, arg1: &str)
+    ///  becomes
+    ///  - ["let arg1 = arg1.toString()"] and "arg1: arg1"
+    pub fn to_swift_async_call_arg_bindings(
+        &self,
+        types: &TypeDeclarations,
+    ) -> (Vec<String>, String) {
+        let mut bindings = vec![];
+        let mut args = vec![];
+
+        for (arg_idx, arg) in self.func.sig.inputs.iter().enumerate() {
+            let pat_ty = match arg {
+                FnArg::Typed(pat_ty) if !pat_type_pat_is_self(pat_ty) => pat_ty,
+                _ => continue,
+            };
+
+            let arg_name = pat_ty.pat.to_token_stream().to_string();
+            let mut arg = match self.to_swift_call_arg(arg_idx, pat_ty, types) {
+                Some(arg) => arg,
+                None => continue,
+            };
+
+            // The `RustStr` points to Rust memory, so we copy it into a Swift `String`.
+            if let Some(BridgedType::StdLib(StdLibType::Str)) =
+                BridgedType::new_with_type(&pat_ty.ty, types)
+            {
+                arg = format!("{}.toString()", arg);
+            }
+
+            if arg != arg_name {
+                bindings.push(format!("let {} = {}", arg_name, arg));
+            }
+            args.push(format!("{}: {}", arg_name, arg_name));
+        }
+
+        (bindings, args.join(", "))
+    }
+
+    // Converts an argument to the type that the function being called expects.
+    //
+    // Returns `None` if the argument does not need to be passed.
+    fn to_swift_call_arg(
+        &self,
+        arg_idx: usize,
+        pat_ty: &PatType,
+        types: &TypeDeclarations,
+    ) -> Option<String> {
+        let arg = pat_ty.pat.to_token_stream().to_string();
+
+        let arg = if let Some(bridged_ty) = BridgedType::new_with_type(&pat_ty.ty, types) {
+            if self.host_lang.is_rust() {
+                if bridged_ty.can_be_encoded_with_zero_bytes() {
+                    return None;
+                }
+
+                if let BridgedType::StdLib(StdLibType::BoxedFn(_)) = bridged_ty {
+                    let class_name = self.swift_callback_class_name(arg_idx);
+                    format!("Unmanaged.passRetained({class_name}({arg})).toOpaque()")
+                } else {
+                    bridged_ty.convert_swift_expression_to_ffi_type(
+                        &arg,
+                        types,
+                        TypePosition::FnArg(self.host_lang, arg_idx),
+                    )
+                }
+            } else {
+                if let Some(only) = bridged_ty.only_encoding() {
+                    only.swift
+                } else {
+                    bridged_ty.convert_ffi_value_to_swift_value(
+                        &arg,
+                        TypePosition::FnArg(self.host_lang, arg_idx),
+                        types,
+                    )
+                }
+            }
+        } else {
+            todo!("Push to ParsedErrors")
+        };
+
+        Some(arg)
+    }
+
     pub fn to_swift_return_type(&self, types: &TypeDeclarations) -> String {
+        if let Some(item_ty) = self.stream_item_ty() {
+            let item_ty = BridgedType::new_with_type(item_ty, types).unwrap();
+            return format!(
+                " -> AsyncThrowingStream<{}, Error>",
+                item_ty.to_swift_type(TypePosition::FnReturn(self.host_lang), types)
+            );
+        }
+
+        if self.iterator_item_ty().is_some() {
+            let maybe_type_name_segment = match self.associated_type.as_ref() {
+                Some(TypeDeclaration::Opaque(ty)) => format!("${}", ty.ty),
+                _ => "".to_string(),
+            };
+            return format!(
+                " -> RustIterator{}${}",
+                maybe_type_name_segment, self.func.sig.ident
+            );
+        }
+
         match &self.func.sig.output {
             ReturnType::Default => "".to_string(),
             ReturnType::Type(_, ty) => {
@@ -148,6 +237,18 @@ impl ParsedExternFn {
                         if built_in.can_be_encoded_with_zero_bytes() {
                             return "".to_string();
                         }
+
+                        // Swift catches the error that its function throws and passes the
+                        // `Result` to Rust.
+                        match built_in.as_result() {
+                            Some(result) if self.sig.asyncness.is_none() => {
+                                return format!(
+                                    " -> {}",
+                                    result.to_ffi_compatible_swift_type(types)
+                                );
+                            }
+                            _ => {}
+                        }
                     }
 
                     let maybe_throws = if built_in.is_result() { "throws " } else { "" };
@@ -15,6 +15,9 @@ pub fn bridge(
             SwiftBridgeModuleAttr::SwiftBridgePath(path) => {
                 module.set_swift_bridge_path(path);
             }
+            SwiftBridgeModuleAttr::CatchPanics => {
+                module.set_catch_panics();
+            }
         }
     }
 
@@ -0,0 +1,13 @@
+//! # To Run
+//! cargo test -p swift-bridge-macro -- ui trybuild=invalid-argument-default.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        fn some_function(#[swift_bridge(default = 300)] arg: u8);
+    }
+}
+
+fn some_function(_arg: u8) {}
+
+fn main() {}
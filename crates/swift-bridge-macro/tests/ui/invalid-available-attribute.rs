@@ -0,0 +1,12 @@
+//! # To Run
+//! cargo test -p swift-bridge-macro -- ui trybuild=invalid-available-attribute.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(available(iPhoneOS = "15"))]
+        fn some_function();
+    }
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+//! # To Run
+//! cargo test -p swift-bridge-macro -- ui trybuild=local-async-method-self-not-sync.rs
+
+// Swift can keep using `self` while the local executor's thread borrows it, so `&self` methods
+// require the type to be `Sync`. Non thread-safe state can be wrapped in a
+// `swift_bridge::async_support::LocalState`.
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type NotSync;
+
+        #[swift_bridge(local)]
+        async fn increment(&self, amount: u32) -> u32;
+    }
+}
+
+pub struct NotSync {
+    count: Rc<RefCell<u32>>,
+}
+
+impl NotSync {
+    async fn increment(&self, amount: u32) -> u32 {
+        *self.count.borrow_mut() += amount;
+        let count = *self.count.borrow();
+        count
+    }
+}
+
+fn main() {}
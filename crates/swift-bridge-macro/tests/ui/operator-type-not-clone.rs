@@ -0,0 +1,22 @@
+//! # To Run
+//! cargo test -p swift-bridge-macro -- ui trybuild=operator-type-not-clone.rs
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(Add)]
+        type DoesNotImplementClone;
+    }
+}
+
+pub struct DoesNotImplementClone(u8);
+
+impl std::ops::Add for DoesNotImplementClone {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        DoesNotImplementClone(self.0 + rhs.0)
+    }
+}
+
+fn main() {}
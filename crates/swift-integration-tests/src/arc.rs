@@ -0,0 +1,77 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type ArcCounter;
+
+        fn increment(&self) -> u32;
+        fn value(&self) -> u32;
+
+        fn make_arc_counter() -> Arc<ArcCounter>;
+        fn make_optional_arc_counter(make: bool) -> Option<Arc<ArcCounter>>;
+        fn arc_counter_strong_count(counter: Arc<ArcCounter>) -> usize;
+        fn arc_counter_value_by_ref(counter: &ArcCounter) -> u32;
+    }
+
+    extern "Rust" {
+        type ArcCounterHolder;
+
+        #[swift_bridge(init)]
+        fn new(counter: Arc<ArcCounter>) -> ArcCounterHolder;
+        fn counter(&self) -> Arc<ArcCounter>;
+        fn increment_from_rust(&self);
+    }
+}
+
+pub struct ArcCounter(AtomicU32);
+
+impl ArcCounter {
+    fn increment(&self) -> u32 {
+        self.0.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    fn value(&self) -> u32 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+fn make_arc_counter() -> Arc<ArcCounter> {
+    Arc::new(ArcCounter(AtomicU32::new(0)))
+}
+
+fn make_optional_arc_counter(make: bool) -> Option<Arc<ArcCounter>> {
+    if make {
+        Some(make_arc_counter())
+    } else {
+        None
+    }
+}
+
+fn arc_counter_strong_count(counter: Arc<ArcCounter>) -> usize {
+    Arc::strong_count(&counter)
+}
+
+fn arc_counter_value_by_ref(counter: &ArcCounter) -> u32 {
+    counter.value()
+}
+
+/// Holds on to an `Arc<ArcCounter>` that is shared with Swift.
+pub struct ArcCounterHolder {
+    counter: Arc<ArcCounter>,
+}
+
+impl ArcCounterHolder {
+    fn new(counter: Arc<ArcCounter>) -> Self {
+        ArcCounterHolder { counter }
+    }
+
+    fn counter(&self) -> Arc<ArcCounter> {
+        self.counter.clone()
+    }
+
+    fn increment_from_rust(&self) {
+        self.counter.increment();
+    }
+}
@@ -1 +1,2 @@
+mod argument_default;
 mod argument_label;
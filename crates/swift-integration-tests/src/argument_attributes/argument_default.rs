@@ -0,0 +1,23 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        fn test_argument_default(
+            value: i32,
+            #[swift_bridge(default = 10)] multiplier: i32,
+            #[swift_bridge(label = "offset", default = None)] offset: Option<i32>,
+        ) -> i32;
+
+        fn test_argument_default_string(
+            #[swift_bridge(default = "Hello")] greeting: &str,
+            #[swift_bridge(default = "world")] name: String,
+        ) -> String;
+    }
+}
+
+fn test_argument_default(value: i32, multiplier: i32, offset: Option<i32>) -> i32 {
+    value * multiplier + offset.unwrap_or(0)
+}
+
+fn test_argument_default_string(greeting: &str, name: String) -> String {
+    format!("{greeting}, {name}")
+}
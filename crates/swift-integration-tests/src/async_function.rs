@@ -1,3 +1,9 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use swift_bridge::async_support::LocalState;
+
 #[swift_bridge::bridge]
 mod ffi {
     #[swift_bridge(swift_repr = "struct")]
@@ -16,6 +22,8 @@ mod ffi {
         async fn rust_async_func_return_result_null_opaque_rust(
             succeed: bool,
         ) -> Result<(), AsyncResultOpaqueRustType2>;
+        async fn rust_async_pending_until_cancelled();
+        fn rust_async_pending_future_was_dropped() -> bool;
     }
 
     extern "Rust" {
@@ -26,6 +34,20 @@ mod ffi {
         async fn reflect_u16(&self, arg: u16) -> u16;
     }
 
+    extern "Rust" {
+        #[swift_bridge(local)]
+        async fn rust_local_async_reflect_string(string: String) -> String;
+    }
+
+    extern "Rust" {
+        type AsyncRustLocalCounter;
+
+        #[swift_bridge(init)]
+        fn new() -> AsyncRustLocalCounter;
+        #[swift_bridge(local)]
+        async fn increment(&self, amount: u32) -> u32;
+    }
+
     extern "Rust" {
         type AsyncResultOpaqueRustType1;
 
@@ -95,6 +117,38 @@ impl TestRustAsyncSelf {
     }
 }
 
+/// Holds an `Rc` across an await point, so that the future is not `Send`.
+async fn rust_local_async_reflect_string(string: String) -> String {
+    let string = Rc::new(string);
+    std::future::ready(()).await;
+    string.as_ref().clone()
+}
+
+/// Uses `Rc<RefCell<_>>` state, so its futures can only run on the local executor.
+///
+/// The state is wrapped in a `LocalState`, which makes the counter `Sync` even though the
+/// state itself isn't.
+pub struct AsyncRustLocalCounter {
+    count: LocalState<Rc<RefCell<u32>>>,
+}
+
+impl AsyncRustLocalCounter {
+    fn new() -> Self {
+        AsyncRustLocalCounter {
+            count: LocalState::new(|| Rc::new(RefCell::new(0))),
+        }
+    }
+
+    async fn increment(&self, amount: u32) -> u32 {
+        let count = self.count.get().clone();
+        std::future::ready(()).await;
+
+        *count.borrow_mut() += amount;
+        let count = *count.borrow();
+        count
+    }
+}
+
 pub struct AsyncResultOpaqueRustType1(u32);
 
 impl AsyncResultOpaqueRustType1 {
@@ -189,3 +243,25 @@ async fn rust_async_func_return_result_null_opaque_rust(
         Err(AsyncResultOpaqueRustType2(111))
     }
 }
+
+static PENDING_FUTURE_DROPPED: AtomicBool = AtomicBool::new(false);
+
+/// Never completes, so that Swift can verify that cancelling its `Task` drops the future.
+fn rust_async_pending_until_cancelled() -> impl Future<Output = ()> {
+    struct SetDroppedOnDrop;
+    impl Drop for SetDroppedOnDrop {
+        fn drop(&mut self) {
+            PENDING_FUTURE_DROPPED.store(true, Ordering::SeqCst);
+        }
+    }
+
+    let on_drop = SetDroppedOnDrop;
+    async move {
+        let _on_drop = on_drop;
+        std::future::pending::<()>().await
+    }
+}
+
+fn rust_async_pending_future_was_dropped() -> bool {
+    PENDING_FUTURE_DROPPED.load(Ordering::SeqCst)
+}
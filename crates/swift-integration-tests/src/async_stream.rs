@@ -0,0 +1,124 @@
+use futures_core::Stream;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        fn rust_stream_count_to(end: u8) -> impl Stream<Item = u8>;
+        fn rust_stream_strings() -> impl Stream<Item = String>;
+        fn rust_stream_endless() -> impl Stream<Item = u32>;
+        fn rust_stream_endless_was_dropped() -> bool;
+        fn rust_stream_pending() -> impl Stream<Item = u8>;
+    }
+
+    extern "Rust" {
+        type RustStreamSource;
+
+        #[swift_bridge(init)]
+        fn new(items: u8) -> RustStreamSource;
+        fn items(&self) -> impl Stream<Item = u8>;
+    }
+}
+
+struct CountTo {
+    next: u8,
+    end: u8,
+}
+
+impl Stream for CountTo {
+    type Item = u8;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u8>> {
+        if self.next > self.end {
+            return Poll::Ready(None);
+        }
+
+        let item = self.next;
+        self.next += 1;
+        Poll::Ready(Some(item))
+    }
+}
+
+fn rust_stream_count_to(end: u8) -> impl Stream<Item = u8> {
+    CountTo { next: 1, end }
+}
+
+struct Strings(Vec<String>);
+
+impl Stream for Strings {
+    type Item = String;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<String>> {
+        if self.0.is_empty() {
+            Poll::Ready(None)
+        } else {
+            Poll::Ready(Some(self.0.remove(0)))
+        }
+    }
+}
+
+fn rust_stream_strings() -> impl Stream<Item = String> {
+    Strings(vec!["hello".to_string(), "world".to_string()])
+}
+
+static ENDLESS_STREAM_DROPPED: AtomicBool = AtomicBool::new(false);
+
+/// Never ends, so that Swift can verify that the stream gets dropped when it stops iterating.
+struct Endless(u32);
+
+impl Stream for Endless {
+    type Item = u32;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u32>> {
+        self.0 += 1;
+        Poll::Ready(Some(self.0))
+    }
+}
+
+impl Drop for Endless {
+    fn drop(&mut self) {
+        ENDLESS_STREAM_DROPPED.store(true, Ordering::SeqCst);
+    }
+}
+
+fn rust_stream_endless() -> impl Stream<Item = u32> {
+    Endless(0)
+}
+
+fn rust_stream_endless_was_dropped() -> bool {
+    ENDLESS_STREAM_DROPPED.load(Ordering::SeqCst)
+}
+
+/// Never yields an item, so that Swift can verify that cancelling its iteration stops waiting.
+struct Pending;
+
+impl Stream for Pending {
+    type Item = u8;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u8>> {
+        Poll::Pending
+    }
+}
+
+fn rust_stream_pending() -> impl Stream<Item = u8> {
+    Pending
+}
+
+pub struct RustStreamSource {
+    items: u8,
+}
+
+impl RustStreamSource {
+    fn new(items: u8) -> Self {
+        RustStreamSource { items }
+    }
+
+    fn items(&self) -> impl Stream<Item = u8> {
+        CountTo {
+            next: 1,
+            end: self.items,
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
 #[swift_bridge::bridge]
 mod ffi {
     extern "Swift" {
@@ -21,6 +24,15 @@ mod ffi {
         );
     }
 
+    extern "Swift" {
+        fn swift_calls_rust_fn_callback_three_times(arg: Box<dyn Fn(u8) -> u8 + Send>) -> u8;
+        fn swift_calls_rust_fnmut_callback_three_times(arg: Box<dyn FnMut() -> u32>) -> u32;
+
+        fn swift_stores_rust_fn_callback(arg: Box<dyn Fn() + Send + Sync>);
+        fn swift_calls_stored_rust_fn_callback();
+        fn swift_drops_stored_rust_fn_callback();
+    }
+
     extern "Swift" {
         type SwiftMethodCallbackTester;
 
@@ -34,23 +46,36 @@ mod ffi {
         ) -> u16;
     }
 
-    // TODO
-    // extern "Rust" {
-    //     fn rust_takes_callback_fnonce_no_args_no_return(arg: Box<dyn FnOnce() -> ()>);
-    //     fn rust_takes_callback_fnonce_primitive(doubling_fn: Box<dyn FnOnce(u8) -> u8>);
-    //     fn rust_takes_callback_fnonce_opaque_rust(
-    //         doubling_fn: Box<dyn FnOnce(CallbackTestOpaqueRustType) -> CallbackTestOpaqueRustType>,
-    //     );
-    //
-    //     fn rust_takes_callback_fnonce_two_params(
-    //         arg: Box<dyn FnOnce(i16, CallbackTestOpaqueRustType)>,
-    //     );
-    //
-    //     fn rust_takes_two_callbacks_fnonce_noop(
-    //         arg1: Box<dyn FnOnce()>,
-    //         arg2: Box<dyn FnOnce() -> ()>,
-    //     );
-    // }
+    extern "Rust" {
+        fn rust_takes_callback_fnonce_no_args_no_return(arg: Box<dyn FnOnce() -> ()>);
+        fn rust_takes_callback_fnonce_primitive(doubling_fn: Box<dyn FnOnce(u8) -> u8>);
+        fn rust_takes_callback_fnonce_opaque_rust(
+            doubling_fn: Box<dyn FnOnce(CallbackTestOpaqueRustType) -> CallbackTestOpaqueRustType>,
+        );
+
+        fn rust_takes_callback_fnonce_two_params(
+            arg: Box<dyn FnOnce(i16, CallbackTestOpaqueRustType)>,
+        );
+
+        fn rust_takes_two_callbacks_fnonce_noop(
+            arg1: Box<dyn FnOnce()>,
+            arg2: Box<dyn FnOnce() -> ()>,
+        );
+
+        fn rust_calls_callback_fn_three_times(callback: Box<dyn Fn(u8) -> u8>) -> u8;
+    }
+
+    extern "Rust" {
+        type CallbackTestChangeNotifier;
+
+        #[swift_bridge(init)]
+        fn new() -> CallbackTestChangeNotifier;
+
+        fn set_on_change(&mut self, callback: Box<dyn Fn(u32) + Send>);
+        fn clear_on_change(&mut self);
+        fn notify_change(&self, value: u32);
+        fn notify_change_from_another_thread(&mut self, value: u32);
+    }
 
     extern "Rust" {
         type CallbackTestOpaqueRustType;
@@ -66,30 +91,74 @@ mod ffi {
     }
 }
 
-// TODO
-// fn rust_takes_callback_fnonce_no_args_no_return(arg: Box<dyn FnOnce() -> ()>) {
-//     (arg)()
-// }
-// fn rust_takes_callback_fnonce_primitive(doubling_fn: Box<dyn FnOnce(u8) -> u8>) {
-//     let doubling_fn = (doubling_fn)(2);
-//     assert_eq!(doubled, 4)
-// }
-//
-// fn rust_takes_callback_fnonce_opaque_rust(
-//     doubling_fn: Box<dyn FnOnce(CallbackTestOpaqueRustType) -> CallbackTestOpaqueRustType>,
-// ) {
-//     let start = CallbackTestOpaqueRustType { val: 100 };
-//
-//     let doubled = (doubling_fn)(start);
-//     assert_eq!(doubled.val(), 200);
-// }
-//
-// fn rust_takes_callback_fnonce_two_params(arg: Box<dyn FnOnce(i16, CallbackTestOpaqueRustType)>) {
-//     (arg)(123, CallbackTestOpaqueRustType { val: 222 })
-// }
-// fn rust_takes_two_callbacks_fnonce_noop(arg: Box<dyn FnOnce(i16, CallbackTestOpaqueRustType)>) {
-//     (arg)(123, CallbackTestOpaqueRustType { val: 222 })
-// }
+fn rust_takes_callback_fnonce_no_args_no_return(arg: Box<dyn FnOnce() -> ()>) {
+    (arg)()
+}
+
+fn rust_takes_callback_fnonce_primitive(doubling_fn: Box<dyn FnOnce(u8) -> u8>) {
+    let doubled = (doubling_fn)(2);
+    assert_eq!(doubled, 4)
+}
+
+fn rust_takes_callback_fnonce_opaque_rust(
+    doubling_fn: Box<dyn FnOnce(CallbackTestOpaqueRustType) -> CallbackTestOpaqueRustType>,
+) {
+    let start = CallbackTestOpaqueRustType { val: 100 };
+
+    let doubled = (doubling_fn)(start);
+    assert_eq!(doubled.val(), 200);
+}
+
+fn rust_takes_callback_fnonce_two_params(arg: Box<dyn FnOnce(i16, CallbackTestOpaqueRustType)>) {
+    (arg)(123, CallbackTestOpaqueRustType { val: 222 })
+}
+
+fn rust_takes_two_callbacks_fnonce_noop(arg1: Box<dyn FnOnce()>, arg2: Box<dyn FnOnce() -> ()>) {
+    (arg1)();
+    (arg2)();
+}
+
+fn rust_calls_callback_fn_three_times(callback: Box<dyn Fn(u8) -> u8>) -> u8 {
+    callback(1) + callback(2) + callback(3)
+}
+
+/// Holds on to a Swift closure and calls it whenever there is a change.
+pub struct CallbackTestChangeNotifier {
+    on_change: Option<Box<dyn Fn(u32) + Send>>,
+}
+
+impl CallbackTestChangeNotifier {
+    fn new() -> Self {
+        Self { on_change: None }
+    }
+
+    fn set_on_change(&mut self, callback: Box<dyn Fn(u32) + Send>) {
+        self.on_change = Some(callback);
+    }
+
+    fn clear_on_change(&mut self) {
+        self.on_change = None;
+    }
+
+    fn notify_change(&self, value: u32) {
+        if let Some(on_change) = self.on_change.as_ref() {
+            on_change(value);
+        }
+    }
+
+    fn notify_change_from_another_thread(&mut self, value: u32) {
+        if let Some(on_change) = self.on_change.take() {
+            let on_change = std::thread::spawn(move || {
+                on_change(value);
+                on_change
+            })
+            .join()
+            .unwrap();
+
+            self.on_change = Some(on_change);
+        }
+    }
+}
 
 pub struct CallbackTestOpaqueRustType {
     val: u32,
@@ -136,4 +205,34 @@ fn test_callbacks_rust_calls_swift() {
     ffi::swift_func_takes_callback_with_result_arg(Box::new(|result| {
         assert_eq!(result.unwrap().val(), 555)
     }));
+
+    test_multi_call_callbacks_rust_calls_swift();
+}
+
+/// Verify that Swift can call `Box<dyn Fn>` and `Box<dyn FnMut>` callbacks many times, and that
+/// Swift frees them once it no longer holds on to them.
+fn test_multi_call_callbacks_rust_calls_swift() {
+    let sum = ffi::swift_calls_rust_fn_callback_three_times(Box::new(|num| num * 2));
+    assert_eq!(sum, 2 + 4 + 6);
+
+    let mut count = 0;
+    let last = ffi::swift_calls_rust_fnmut_callback_three_times(Box::new(move || {
+        count += 1;
+        count
+    }));
+    assert_eq!(last, 3);
+
+    let calls = Arc::new(AtomicU32::new(0));
+    let calls_clone = calls.clone();
+    ffi::swift_stores_rust_fn_callback(Box::new(move || {
+        calls_clone.fetch_add(1, Ordering::SeqCst);
+    }));
+
+    ffi::swift_calls_stored_rust_fn_callback();
+    ffi::swift_calls_stored_rust_fn_callback();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(Arc::strong_count(&calls), 2);
+
+    ffi::swift_drops_stored_rust_fn_callback();
+    assert_eq!(Arc::strong_count(&calls), 1);
 }
@@ -0,0 +1,34 @@
+//! See also: crates/swift-bridge-ir/src/codegen/codegen_tests/catch_panics_codegen_tests.rs
+
+#[swift_bridge::bridge(catch_panics)]
+mod ffi {
+    extern "Rust" {
+        type CatchPanicsError;
+
+        fn rust_func_result_may_panic(should_panic: bool) -> Result<(), CatchPanicsError>;
+        async fn rust_async_func_may_panic(should_panic: bool) -> u8;
+        fn rust_func_reflect_u8_catching_panics(arg: u8) -> u8;
+    }
+}
+
+pub struct CatchPanicsError;
+
+fn rust_func_result_may_panic(should_panic: bool) -> Result<(), CatchPanicsError> {
+    if should_panic {
+        panic!("rust_func_result_may_panic panicked");
+    }
+
+    Ok(())
+}
+
+async fn rust_async_func_may_panic(should_panic: bool) -> u8 {
+    if should_panic {
+        panic!("rust_async_func_may_panic panicked");
+    }
+
+    123
+}
+
+fn rust_func_reflect_u8_catching_panics(arg: u8) -> u8 {
+    arg
+}
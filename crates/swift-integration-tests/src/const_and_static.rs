@@ -0,0 +1,12 @@
+#[swift_bridge::bridge]
+mod ffi {
+    /// The maximum number of items.
+    pub const CONST_MAX_ITEMS: u32 = 100;
+    pub const CONST_MIN_OFFSET: i64 = -5;
+    pub const CONST_RATIO: f64 = 0.25;
+    pub static STATIC_ENABLED: bool = true;
+
+    pub const CONST_BUFFER_SIZE: usize = 4 * 1024;
+    pub static STATIC_VERSION: &str = "1.2.3";
+    pub const CONST_MAYBE_NUMBER: Option<u8> = Some(7);
+}
@@ -4,4 +4,19 @@ mod ffi {
     enum DeriveDebugEnum {
         Variant,
     }
+
+    #[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+    enum EnumDeriveOrd {
+        Unit,
+        Unnamed(u8, String),
+        Named { flag: bool },
+    }
+
+    extern "Rust" {
+        fn rust_cmp_enum_derive_ord(lhs: EnumDeriveOrd, rhs: EnumDeriveOrd) -> i8;
+    }
+}
+
+fn rust_cmp_enum_derive_ord(lhs: ffi::EnumDeriveOrd, rhs: ffi::EnumDeriveOrd) -> i8 {
+    lhs.cmp(&rhs) as i8
 }
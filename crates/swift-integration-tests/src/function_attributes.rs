@@ -5,4 +5,6 @@ mod identifiable;
 mod return_into;
 mod return_with;
 mod rust_name;
+mod set;
 mod swift_name;
+mod swift_property;
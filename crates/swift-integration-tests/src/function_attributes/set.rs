@@ -0,0 +1,73 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type SomeTypeSet;
+
+        #[swift_bridge(init)]
+        fn new() -> SomeTypeSet;
+
+        #[swift_bridge(get(my_u8))]
+        fn my_u8(&self) -> u8;
+
+        #[swift_bridge(set(my_u8))]
+        fn set_my_u8(&mut self, value: u8);
+
+        #[swift_bridge(get(&my_string))]
+        fn my_string(&self) -> &str;
+
+        #[swift_bridge(set(my_string))]
+        fn set_my_string(&mut self, value: String);
+
+        #[swift_bridge(set_with(my_u16 = u8_to_u16))]
+        fn set_my_u16_from_u8(&mut self, value: u8);
+
+        #[swift_bridge(get(my_u16))]
+        fn my_u16(&self) -> u16;
+
+        #[swift_bridge(get_mut(my_nested))]
+        fn my_nested_mut(&mut self) -> &mut NestedTypeSet;
+    }
+
+    extern "Rust" {
+        type NestedTypeSet;
+
+        fn increment(&mut self);
+        fn value(&self) -> u32;
+    }
+}
+
+pub struct SomeTypeSet {
+    my_u8: u8,
+    my_string: String,
+    my_u16: u16,
+    my_nested: NestedTypeSet,
+}
+
+pub struct NestedTypeSet {
+    value: u32,
+}
+
+impl SomeTypeSet {
+    fn new() -> SomeTypeSet {
+        SomeTypeSet {
+            my_u8: 123,
+            my_string: "Hello".to_string(),
+            my_u16: 0,
+            my_nested: NestedTypeSet { value: 0 },
+        }
+    }
+}
+
+impl NestedTypeSet {
+    fn increment(&mut self) {
+        self.value += 1;
+    }
+
+    fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+fn u8_to_u16(num: u8) -> u16 {
+    num as u16 * 2
+}
@@ -0,0 +1,50 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type SomeTypeSwiftProperty;
+
+        #[swift_bridge(init)]
+        fn new() -> SomeTypeSwiftProperty;
+
+        #[swift_bridge(get(count), swift_property)]
+        fn count(&self) -> u32;
+
+        #[swift_bridge(set(count), swift_property)]
+        fn set_count(&mut self, count: u32);
+
+        #[swift_bridge(swift_property)]
+        fn name(&self) -> String;
+
+        #[swift_bridge(swift_property)]
+        fn set_name(&mut self, name: String);
+
+        #[swift_bridge(swift_property)]
+        fn name_length(&self) -> usize;
+    }
+}
+
+pub struct SomeTypeSwiftProperty {
+    count: u32,
+    name: String,
+}
+
+impl SomeTypeSwiftProperty {
+    fn new() -> Self {
+        SomeTypeSwiftProperty {
+            count: 0,
+            name: "Hello".to_string(),
+        }
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    fn name_length(&self) -> usize {
+        self.name.len()
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        /// Counts from 1 up to and including `end`.
+        ///
+        /// # Arguments
+        ///
+        /// * `end` - The last number.
+        fn rust_iterator_count_to(end: u8) -> impl Iterator<Item = u8>;
+        fn rust_iterator_strings() -> Box<dyn Iterator<Item = String>>;
+        fn rust_iterator_endless() -> impl Iterator<Item = u32>;
+        fn rust_iterator_endless_was_dropped() -> bool;
+    }
+
+    extern "Rust" {
+        type RustIteratorSource;
+
+        #[swift_bridge(init)]
+        fn new(items: u8) -> RustIteratorSource;
+        fn items(&self) -> Box<dyn Iterator<Item = u8>>;
+    }
+}
+
+fn rust_iterator_count_to(end: u8) -> impl Iterator<Item = u8> {
+    1..=end
+}
+
+fn rust_iterator_strings() -> Box<dyn Iterator<Item = String>> {
+    Box::new(vec!["hello".to_string(), "world".to_string()].into_iter())
+}
+
+static ENDLESS_ITERATOR_DROPPED: AtomicBool = AtomicBool::new(false);
+
+/// Never ends, so that Swift can verify that the iterator gets dropped when it stops iterating.
+struct Endless(u32);
+
+impl Iterator for Endless {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.0 += 1;
+        Some(self.0)
+    }
+}
+
+impl Drop for Endless {
+    fn drop(&mut self) {
+        ENDLESS_ITERATOR_DROPPED.store(true, Ordering::SeqCst);
+    }
+}
+
+fn rust_iterator_endless() -> impl Iterator<Item = u32> {
+    Endless(0)
+}
+
+fn rust_iterator_endless_was_dropped() -> bool {
+    ENDLESS_ITERATOR_DROPPED.load(Ordering::SeqCst)
+}
+
+pub struct RustIteratorSource {
+    items: u8,
+}
+
+impl RustIteratorSource {
+    fn new(items: u8) -> Self {
+        RustIteratorSource { items }
+    }
+
+    fn items(&self) -> Box<dyn Iterator<Item = u8>> {
+        Box::new(1..=self.items)
+    }
+}
@@ -1,21 +1,32 @@
 mod expose_opaque_rust_type;
 mod import_opaque_swift_class;
 
+mod arc;
 mod async_function;
+mod async_stream;
 mod boxed_functions;
+mod catch_panics;
 mod conditional_compilation;
+mod const_and_static;
 mod generics;
+mod iterator;
+mod map;
 mod option;
 mod pointer;
 mod primitive;
 mod result;
 mod rust_function_uses_opaque_swift_type;
+mod serde_codable;
+mod set;
 mod shared_types;
 mod single_representation_type_elision;
 mod slice;
 mod string;
+mod swift_async_function;
 mod swift_function_uses_opaque_rust_type;
 mod swift_function_uses_opaque_swift_type;
+mod swift_protocol;
+mod trait_object;
 mod tuple;
 mod vec;
 
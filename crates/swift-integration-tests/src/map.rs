@@ -0,0 +1,128 @@
+use std::collections::{BTreeMap, HashMap};
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        type ARustTypeInsideMap;
+
+        #[swift_bridge(init)]
+        fn new(text: &str) -> ARustTypeInsideMap;
+
+        fn text(&self) -> &str;
+    }
+
+    extern "Rust" {
+        fn rust_reflect_hash_map_u32_string(arg: HashMap<u32, String>) -> HashMap<u32, String>;
+
+        fn rust_reflect_hash_map_string_opaque_rust_type(
+            arg: HashMap<String, ARustTypeInsideMap>,
+        ) -> HashMap<String, ARustTypeInsideMap>;
+
+        fn rust_reflect_btree_map_u8_u16(arg: BTreeMap<u8, u16>) -> BTreeMap<u8, u16>;
+    }
+
+    extern "Rust" {
+        fn run_map_tests();
+    }
+
+    extern "Swift" {
+        fn swift_return_hash_map_u8_u16() -> HashMap<u8, u16>;
+        fn swift_arg_hash_map_u8_u16(map: HashMap<u8, u16>);
+    }
+}
+
+/// A second bridge module that uses one of the same maps as `ffi`, to verify that each module
+/// can export its own copy of the map's functions.
+#[swift_bridge::bridge]
+mod ffi_second_module {
+    #[swift_bridge(swift_repr = "struct")]
+    #[derive(Clone)]
+    struct StructInsideMap {
+        value: u32,
+    }
+
+    #[swift_bridge(swift_repr = "struct")]
+    struct StructWithMapField {
+        map: HashMap<u8, u16>,
+    }
+
+    extern "Rust" {
+        fn rust_reflect_hash_map_u32_string_from_second_module(
+            arg: HashMap<u32, String>,
+        ) -> HashMap<u32, String>;
+
+        fn rust_reflect_hash_map_u8_transparent_struct(
+            arg: HashMap<u8, StructInsideMap>,
+        ) -> HashMap<u8, StructInsideMap>;
+
+        fn rust_reflect_optional_btree_map_u8_u16(
+            arg: Option<BTreeMap<u8, u16>>,
+        ) -> Option<BTreeMap<u8, u16>>;
+
+        fn rust_reflect_struct_with_map_field(arg: StructWithMapField) -> StructWithMapField;
+    }
+}
+
+fn run_map_tests() {
+    let map = ffi::swift_return_hash_map_u8_u16();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[&1], 10);
+    assert_eq!(map[&2], 20);
+
+    let map: HashMap<u8, u16> = [(1, 10), (2, 20)].into_iter().collect();
+    ffi::swift_arg_hash_map_u8_u16(map);
+}
+
+pub struct ARustTypeInsideMap {
+    text: String,
+}
+
+impl ARustTypeInsideMap {
+    fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+        }
+    }
+
+    fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+fn rust_reflect_hash_map_u32_string(arg: HashMap<u32, String>) -> HashMap<u32, String> {
+    arg
+}
+
+fn rust_reflect_hash_map_string_opaque_rust_type(
+    arg: HashMap<String, ARustTypeInsideMap>,
+) -> HashMap<String, ARustTypeInsideMap> {
+    arg
+}
+
+fn rust_reflect_btree_map_u8_u16(arg: BTreeMap<u8, u16>) -> BTreeMap<u8, u16> {
+    arg
+}
+
+fn rust_reflect_hash_map_u32_string_from_second_module(
+    arg: HashMap<u32, String>,
+) -> HashMap<u32, String> {
+    arg
+}
+
+fn rust_reflect_hash_map_u8_transparent_struct(
+    arg: HashMap<u8, ffi_second_module::StructInsideMap>,
+) -> HashMap<u8, ffi_second_module::StructInsideMap> {
+    arg
+}
+
+fn rust_reflect_optional_btree_map_u8_u16(
+    arg: Option<BTreeMap<u8, u16>>,
+) -> Option<BTreeMap<u8, u16>> {
+    arg
+}
+
+fn rust_reflect_struct_with_map_field(
+    arg: ffi_second_module::StructWithMapField,
+) -> ffi_second_module::StructWithMapField {
+    arg
+}
@@ -1,4 +1,8 @@
 mod already_declared;
+mod comparable;
 mod copy;
+mod debug;
+mod display;
 mod equatable;
 mod hashable;
+mod operators;
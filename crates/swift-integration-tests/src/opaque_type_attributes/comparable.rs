@@ -0,0 +1,43 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(Comparable)]
+        type RustComparableType;
+
+        #[swift_bridge(init)]
+        fn new(num: i32) -> RustComparableType;
+
+        fn value(&self) -> i32;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(Comparable)]
+        type RustPartiallyComparableType;
+
+        #[swift_bridge(init)]
+        fn new(num: f64) -> RustPartiallyComparableType;
+    }
+}
+
+#[derive(PartialEq, PartialOrd)]
+pub struct RustComparableType(i32);
+
+impl RustComparableType {
+    fn new(num: i32) -> Self {
+        RustComparableType(num)
+    }
+
+    fn value(&self) -> i32 {
+        self.0
+    }
+}
+
+/// A type with values that cannot be compared, such as `NaN`.
+#[derive(PartialEq, PartialOrd)]
+pub struct RustPartiallyComparableType(f64);
+
+impl RustPartiallyComparableType {
+    fn new(num: f64) -> Self {
+        RustPartiallyComparableType(num)
+    }
+}
@@ -0,0 +1,30 @@
+use std::fmt::{Debug, Formatter};
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(Debug)]
+        type RustDebugType;
+
+        #[swift_bridge(init)]
+        fn new(num: i32) -> RustDebugType;
+    }
+}
+
+pub struct RustDebugType {
+    num: i32,
+}
+
+impl RustDebugType {
+    fn new(num: i32) -> Self {
+        RustDebugType { num }
+    }
+}
+
+impl Debug for RustDebugType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RustDebugType")
+            .field("num", &self.num)
+            .finish()
+    }
+}
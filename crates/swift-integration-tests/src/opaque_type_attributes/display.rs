@@ -0,0 +1,26 @@
+use std::fmt::{Display, Formatter};
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(Display)]
+        type RustDisplayType;
+
+        #[swift_bridge(init)]
+        fn new(num: i32) -> RustDisplayType;
+    }
+}
+
+pub struct RustDisplayType(i32);
+
+impl RustDisplayType {
+    fn new(num: i32) -> Self {
+        RustDisplayType(num)
+    }
+}
+
+impl Display for RustDisplayType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RustDisplayType with value {}", self.0)
+    }
+}
@@ -0,0 +1,69 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(Add, Sub, Neg, Index(usize, Output = i32))]
+        type RustOperatorsType;
+
+        #[swift_bridge(init)]
+        fn new(values: Vec<i32>) -> RustOperatorsType;
+
+        fn len(&self) -> usize;
+    }
+}
+
+#[derive(Clone)]
+pub struct RustOperatorsType(Vec<i32>);
+
+impl RustOperatorsType {
+    fn new(values: Vec<i32>) -> Self {
+        RustOperatorsType(values)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl std::ops::Add for RustOperatorsType {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        RustOperatorsType(
+            self.0
+                .iter()
+                .zip(rhs.0.iter())
+                .map(|(a, b)| a + b)
+                .collect(),
+        )
+    }
+}
+
+impl std::ops::Sub for RustOperatorsType {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        RustOperatorsType(
+            self.0
+                .iter()
+                .zip(rhs.0.iter())
+                .map(|(a, b)| a - b)
+                .collect(),
+        )
+    }
+}
+
+impl std::ops::Neg for RustOperatorsType {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        RustOperatorsType(self.0.iter().map(|a| -a).collect())
+    }
+}
+
+impl std::ops::Index<usize> for RustOperatorsType {
+    type Output = i32;
+
+    fn index(&self, index: usize) -> &i32 {
+        &self.0[index]
+    }
+}
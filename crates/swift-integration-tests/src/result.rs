@@ -78,6 +78,16 @@ mod ffi {
             succeed: bool,
         ) -> Result<(i32, ResultTestOpaqueRustType, String), ResultTransparentEnum>;
     }
+
+    extern "Swift" {
+        fn swift_func_throws_string(succeed: bool) -> Result<String, SwiftError>;
+        fn swift_func_throws_u32(succeed: bool) -> Result<u32, SwiftError>;
+        fn swift_func_throws_swift_error(succeed: bool) -> Result<(), SwiftError>;
+    }
+
+    extern "Rust" {
+        fn rust_func_calls_throwing_swift_functions();
+    }
 }
 
 fn rust_func_takes_result_string(arg: Result<String, String>) {
@@ -210,3 +220,29 @@ fn rust_func_return_result_tuple_transparent_enum(
         Err(ffi::ResultTransparentEnum::NamedField { data: -123 })
     }
 }
+
+fn rust_func_calls_throwing_swift_functions() {
+    assert_eq!(ffi::swift_func_throws_string(true).unwrap(), "Success");
+    assert_eq!(
+        ffi::swift_func_throws_string(false)
+            .unwrap_err()
+            .localized_description(),
+        "Swift function failed"
+    );
+
+    assert_eq!(ffi::swift_func_throws_u32(true).unwrap(), 123);
+    assert_eq!(
+        ffi::swift_func_throws_u32(false)
+            .unwrap_err()
+            .localized_description(),
+        "Swift function failed"
+    );
+
+    assert!(ffi::swift_func_throws_swift_error(true).is_ok());
+    assert_eq!(
+        ffi::swift_func_throws_swift_error(false)
+            .unwrap_err()
+            .localized_description(),
+        "Swift function failed"
+    );
+}
@@ -0,0 +1,104 @@
+//! Tests for the Swift `Codable` conformances that we generate for shared types that derive
+//! serde's `Serialize` and `Deserialize`.
+//!
+//! Swift encodes values using a `JSONEncoder` and Rust decodes them using `serde_json`, and
+//! vice versa.
+
+#[swift_bridge::bridge]
+mod ffi {
+    #[swift_bridge(swift_repr = "struct")]
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct CodableStruct {
+        some_number: u32,
+        #[serde(rename = "label")]
+        text: String,
+        maybe_number: Option<u8>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum CodableExternallyTaggedEnum {
+        UnitVariant,
+        NewtypeVariant(u16),
+        TupleVariant(u8, String),
+        StructVariant {
+            #[serde(rename = "renamed")]
+            field: i32,
+        },
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "type")]
+    enum CodableInternallyTaggedEnum {
+        UnitVariant,
+        StructVariant { field: u8 },
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "t", content = "c")]
+    enum CodableAdjacentlyTaggedEnum {
+        UnitVariant,
+        NewtypeVariant(String),
+    }
+
+    extern "Rust" {
+        fn rust_codable_struct_to_json(value: CodableStruct) -> String;
+        fn rust_codable_struct_from_json(json: &str) -> CodableStruct;
+
+        fn rust_codable_externally_tagged_enum_to_json(
+            value: CodableExternallyTaggedEnum,
+        ) -> String;
+        fn rust_codable_externally_tagged_enum_from_json(json: &str)
+            -> CodableExternallyTaggedEnum;
+
+        fn rust_codable_internally_tagged_enum_to_json(
+            value: CodableInternallyTaggedEnum,
+        ) -> String;
+        fn rust_codable_internally_tagged_enum_from_json(json: &str)
+            -> CodableInternallyTaggedEnum;
+
+        fn rust_codable_adjacently_tagged_enum_to_json(
+            value: CodableAdjacentlyTaggedEnum,
+        ) -> String;
+        fn rust_codable_adjacently_tagged_enum_from_json(json: &str)
+            -> CodableAdjacentlyTaggedEnum;
+    }
+}
+
+use ffi::{
+    CodableAdjacentlyTaggedEnum, CodableExternallyTaggedEnum, CodableInternallyTaggedEnum,
+    CodableStruct,
+};
+
+fn rust_codable_struct_to_json(value: CodableStruct) -> String {
+    serde_json::to_string(&value).unwrap()
+}
+
+fn rust_codable_struct_from_json(json: &str) -> CodableStruct {
+    serde_json::from_str(json).unwrap()
+}
+
+fn rust_codable_externally_tagged_enum_to_json(value: CodableExternallyTaggedEnum) -> String {
+    serde_json::to_string(&value).unwrap()
+}
+
+fn rust_codable_externally_tagged_enum_from_json(json: &str) -> CodableExternallyTaggedEnum {
+    serde_json::from_str(json).unwrap()
+}
+
+fn rust_codable_internally_tagged_enum_to_json(value: CodableInternallyTaggedEnum) -> String {
+    serde_json::to_string(&value).unwrap()
+}
+
+fn rust_codable_internally_tagged_enum_from_json(json: &str) -> CodableInternallyTaggedEnum {
+    serde_json::from_str(json).unwrap()
+}
+
+fn rust_codable_adjacently_tagged_enum_to_json(value: CodableAdjacentlyTaggedEnum) -> String {
+    serde_json::to_string(&value).unwrap()
+}
+
+fn rust_codable_adjacently_tagged_enum_from_json(json: &str) -> CodableAdjacentlyTaggedEnum {
+    serde_json::from_str(json).unwrap()
+}
@@ -0,0 +1,70 @@
+use std::collections::{BTreeSet, HashSet};
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        fn rust_reflect_hash_set_u32(arg: HashSet<u32>) -> HashSet<u32>;
+
+        fn rust_reflect_hash_set_string(arg: HashSet<String>) -> HashSet<String>;
+
+        fn rust_reflect_btree_set_i64(arg: BTreeSet<i64>) -> BTreeSet<i64>;
+    }
+
+    extern "Rust" {
+        fn run_set_tests();
+    }
+
+    extern "Swift" {
+        fn swift_return_hash_set_u8() -> HashSet<u8>;
+        fn swift_arg_hash_set_u8(set: HashSet<u8>);
+    }
+}
+
+/// A second bridge module that uses the same set as `ffi`, to verify that the set's functions
+/// don't collide at link time.
+#[swift_bridge::bridge]
+mod ffi_second_module {
+    extern "Rust" {
+        fn rust_reflect_hash_set_u32_from_second_module(arg: HashSet<u32>) -> HashSet<u32>;
+
+        fn rust_hash_set_u32_or_error(
+            arg: HashSet<u32>,
+            succeed: bool,
+        ) -> Result<HashSet<u32>, String>;
+    }
+}
+
+fn run_set_tests() {
+    let set = ffi::swift_return_hash_set_u8();
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(&1));
+    assert!(set.contains(&2));
+    assert!(set.contains(&3));
+
+    let set: HashSet<u8> = [1, 2, 3].into_iter().collect();
+    ffi::swift_arg_hash_set_u8(set);
+}
+
+fn rust_reflect_hash_set_u32(arg: HashSet<u32>) -> HashSet<u32> {
+    arg
+}
+
+fn rust_reflect_hash_set_string(arg: HashSet<String>) -> HashSet<String> {
+    arg
+}
+
+fn rust_reflect_btree_set_i64(arg: BTreeSet<i64>) -> BTreeSet<i64> {
+    arg
+}
+
+fn rust_reflect_hash_set_u32_from_second_module(arg: HashSet<u32>) -> HashSet<u32> {
+    arg
+}
+
+fn rust_hash_set_u32_or_error(arg: HashSet<u32>, succeed: bool) -> Result<HashSet<u32>, String> {
+    if succeed {
+        Ok(arg)
+    } else {
+        Err("failed".to_string())
+    }
+}
@@ -1,3 +1,4 @@
 mod already_declared;
 mod derive;
+mod operators;
 mod swift_name;
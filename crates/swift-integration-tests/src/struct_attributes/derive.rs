@@ -25,4 +25,21 @@ mod ffi {
     struct StructDeriveClone3 {
         field: String,
     }
+
+    #[swift_bridge(swift_repr = "struct")]
+    #[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+    struct StructDeriveOrd {
+        number: u8,
+        flag: bool,
+        text: String,
+        maybe_number: Option<u8>,
+    }
+
+    extern "Rust" {
+        fn rust_cmp_struct_derive_ord(lhs: StructDeriveOrd, rhs: StructDeriveOrd) -> i8;
+    }
+}
+
+fn rust_cmp_struct_derive_ord(lhs: ffi::StructDeriveOrd, rhs: ffi::StructDeriveOrd) -> i8 {
+    lhs.cmp(&rhs) as i8
 }
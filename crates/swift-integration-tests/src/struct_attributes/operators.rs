@@ -0,0 +1,54 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+#[swift_bridge::bridge]
+mod ffi {
+    #[swift_bridge(swift_repr = "struct", Add, Sub, Mul, Div)]
+    struct StructOperators {
+        x: f64,
+        y: f64,
+    }
+}
+
+impl Add for ffi::StructOperators {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        ffi::StructOperators {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl Sub for ffi::StructOperators {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        ffi::StructOperators {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl Mul for ffi::StructOperators {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        ffi::StructOperators {
+            x: self.x * rhs.x,
+            y: self.y * rhs.y,
+        }
+    }
+}
+
+impl Div for ffi::StructOperators {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        ffi::StructOperators {
+            x: self.x / rhs.x,
+            y: self.y / rhs.y,
+        }
+    }
+}
@@ -0,0 +1,54 @@
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        async fn rust_calls_swift_async_return_null();
+        async fn rust_calls_swift_async_reflect_u8(arg: u8) -> u8;
+        async fn rust_calls_swift_async_reflect_str(arg: String) -> String;
+        async fn rust_calls_swift_async_return_result(succeed: bool) -> String;
+        async fn rust_calls_swift_async_method() -> u32;
+    }
+
+    extern "Swift" {
+        async fn swift_async_return_null();
+        async fn swift_async_reflect_u8(arg: u8) -> u8;
+        async fn swift_async_reflect_str(arg: &str) -> String;
+        async fn swift_async_return_result(succeed: bool) -> Result<String, String>;
+    }
+
+    extern "Swift" {
+        type SwiftAsyncCounter;
+
+        #[swift_bridge(init)]
+        fn new() -> SwiftAsyncCounter;
+        async fn increment(&self) -> u32;
+    }
+}
+
+async fn rust_calls_swift_async_return_null() {
+    ffi::swift_async_return_null().await
+}
+
+async fn rust_calls_swift_async_reflect_u8(arg: u8) -> u8 {
+    ffi::swift_async_reflect_u8(arg).await
+}
+
+async fn rust_calls_swift_async_reflect_str(arg: String) -> String {
+    ffi::swift_async_reflect_str(&arg).await
+}
+
+async fn rust_calls_swift_async_return_result(succeed: bool) -> String {
+    match ffi::swift_async_return_result(succeed).await {
+        Ok(ok) => format!("ok: {}", ok),
+        Err(err) => format!("err: {}", err),
+    }
+}
+
+async fn rust_calls_swift_async_method() -> u32 {
+    // The futures don't borrow the Swift type, so we don't need to hold on to it while awaiting.
+    let (first, second) = {
+        let counter = ffi::SwiftAsyncCounter::new();
+        (counter.increment(), counter.increment())
+    };
+
+    first.await + second.await
+}
@@ -0,0 +1,49 @@
+/// A key-value store that is implemented in Swift.
+pub trait KeyValueStore {
+    fn contains(&self, key: &str) -> bool;
+    fn get(&self, key: &str) -> String;
+    fn set(&mut self, key: String, value: String);
+    fn count(&self) -> u32;
+}
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        fn rust_reads_from_swift_key_value_store(store: KeyValueStore, key: &str)
+            -> Option<String>;
+        fn rust_writes_to_swift_key_value_store(
+            store: KeyValueStore,
+            key: String,
+            value: String,
+        ) -> u32;
+    }
+
+    extern "Swift" {
+        #[swift_bridge(protocol)]
+        type KeyValueStore;
+
+        fn contains(&self, key: &str) -> bool;
+        fn get(&self, key: &str) -> String;
+        fn set(&mut self, key: String, value: String);
+        fn count(&self) -> u32;
+    }
+}
+
+fn rust_reads_from_swift_key_value_store(store: ffi::KeyValueStore, key: &str) -> Option<String> {
+    let store: Box<dyn KeyValueStore> = Box::new(store);
+    if store.contains(key) {
+        Some(store.get(key))
+    } else {
+        None
+    }
+}
+
+fn rust_writes_to_swift_key_value_store(
+    store: ffi::KeyValueStore,
+    key: String,
+    value: String,
+) -> u32 {
+    let mut store: Box<dyn KeyValueStore> = Box::new(store);
+    store.set(key, value);
+    store.count()
+}
@@ -0,0 +1,137 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// Draws shapes. Exposed to Swift as a `Renderer` class.
+pub trait Renderer {
+    fn name(&self) -> String;
+    fn render(&self, width: u32, height: u32) -> u32;
+    fn resize(&mut self, scale: u32);
+    fn render_count(&self) -> u32;
+}
+
+/// A renderer that is shared with Swift through an `Arc<dyn SharedRenderer>`.
+///
+/// Shared trait objects can't be mutably borrowed, so this only has `&self` methods.
+pub trait SharedRenderer {
+    fn name(&self) -> String;
+    fn render(&self, width: u32, height: u32) -> u32;
+    fn render_count(&self) -> u32;
+}
+
+#[swift_bridge::bridge]
+mod ffi {
+    extern "Rust" {
+        #[swift_bridge(trait_object)]
+        type Renderer;
+
+        fn name(&self) -> String;
+        fn render(&self, width: u32, height: u32) -> u32;
+        fn resize(&mut self, scale: u32);
+        fn render_count(&self) -> u32;
+    }
+
+    extern "Rust" {
+        #[swift_bridge(trait_object)]
+        type SharedRenderer;
+
+        fn name(&self) -> String;
+        fn render(&self, width: u32, height: u32) -> u32;
+        fn render_count(&self) -> u32;
+    }
+
+    extern "Rust" {
+        fn make_boxed_renderer(name: String) -> Box<dyn Renderer>;
+        fn make_arc_renderer(name: String) -> Arc<dyn SharedRenderer>;
+        fn make_optional_renderer(make: bool) -> Option<Box<dyn Renderer>>;
+        fn rust_renders_with_ref(renderer: &dyn Renderer) -> u32;
+        fn rust_resizes_with_ref_mut(renderer: &mut dyn Renderer, scale: u32);
+        fn rust_takes_boxed_renderer(renderer: Box<dyn Renderer>) -> String;
+        fn rust_takes_arc_renderer(renderer: Arc<dyn Renderer>) -> u32;
+        fn rust_takes_arc_shared_renderer(renderer: Arc<dyn SharedRenderer>) -> u32;
+    }
+}
+
+struct AreaRenderer {
+    name: String,
+    scale: u32,
+    render_count: AtomicU32,
+}
+
+impl Renderer for AreaRenderer {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn render(&self, width: u32, height: u32) -> u32 {
+        self.render_count.fetch_add(1, Ordering::SeqCst);
+        width * height * self.scale
+    }
+
+    fn resize(&mut self, scale: u32) {
+        self.scale = scale;
+    }
+
+    fn render_count(&self) -> u32 {
+        self.render_count.load(Ordering::SeqCst)
+    }
+}
+
+impl SharedRenderer for AreaRenderer {
+    fn name(&self) -> String {
+        Renderer::name(self)
+    }
+
+    fn render(&self, width: u32, height: u32) -> u32 {
+        Renderer::render(self, width, height)
+    }
+
+    fn render_count(&self) -> u32 {
+        Renderer::render_count(self)
+    }
+}
+
+impl AreaRenderer {
+    fn new(name: String) -> Self {
+        AreaRenderer {
+            name,
+            scale: 1,
+            render_count: AtomicU32::new(0),
+        }
+    }
+}
+
+fn make_boxed_renderer(name: String) -> Box<dyn Renderer> {
+    Box::new(AreaRenderer::new(name))
+}
+
+fn make_arc_renderer(name: String) -> Arc<dyn SharedRenderer> {
+    Arc::new(AreaRenderer::new(name))
+}
+
+fn make_optional_renderer(make: bool) -> Option<Box<dyn Renderer>> {
+    if make {
+        Some(make_boxed_renderer("optional".to_string()))
+    } else {
+        None
+    }
+}
+
+fn rust_renders_with_ref(renderer: &dyn Renderer) -> u32 {
+    renderer.render(2, 3)
+}
+
+fn rust_resizes_with_ref_mut(renderer: &mut dyn Renderer, scale: u32) {
+    renderer.resize(scale);
+}
+
+fn rust_takes_boxed_renderer(renderer: Box<dyn Renderer>) -> String {
+    renderer.name()
+}
+
+fn rust_takes_arc_renderer(renderer: Arc<dyn Renderer>) -> u32 {
+    renderer.render_count()
+}
+
+fn rust_takes_arc_shared_renderer(renderer: Arc<dyn SharedRenderer>) -> u32 {
+    renderer.render_count()
+}
@@ -1,25 +1,392 @@
-use once_cell::sync::Lazy;
+use futures_core::Stream;
+use once_cell::sync::OnceCell;
+use std::any::Any;
 use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::pin::Pin;
-use std::sync::mpsc::{Receiver, SyncSender};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
 
+type AsyncFnToSpawn = Pin<Box<dyn Future<Output = ()> + 'static + Send>>;
+
+/// Runs the tasks that drive async Rust functions that are called from Swift.
+///
+/// By default these tasks run on a multi-threaded Tokio runtime that `swift-bridge` starts the
+/// first time that Swift calls an async Rust function. Use [`set_executor`] to run them on your
+/// own executor instead.
+///
+/// ```no_run,ignore
+/// struct SmolExecutor;
+///
+/// impl swift_bridge::async_support::AsyncExecutor for SmolExecutor {
+///     fn spawn(&self, task: Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
+///         smol::spawn(task).detach();
+///     }
+/// }
+///
+/// swift_bridge::async_support::set_executor(SmolExecutor).unwrap();
+/// ```
+pub trait AsyncExecutor: Send + Sync + 'static {
+    /// Run the task to completion in the background.
+    ///
+    /// If the executor drops the task before it completes, the Swift caller gets a
+    /// `CancellationError`.
+    fn spawn(&self, task: Pin<Box<dyn Future<Output = ()> + Send + 'static>>);
+}
+
+/// Run async Rust functions on an existing Tokio runtime.
+///
+/// ```no_run,ignore
+/// swift_bridge::async_support::set_executor(tokio::runtime::Handle::current()).unwrap();
+/// ```
+impl AsyncExecutor for tokio::runtime::Handle {
+    fn spawn(&self, task: Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
+        tokio::runtime::Handle::spawn(self, task);
+    }
+}
+
+/// Returned when trying to configure the executor after it has already been set or used.
+#[derive(Debug)]
+pub struct ExecutorAlreadySet;
+
+impl std::fmt::Display for ExecutorAlreadySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("The async executor has already been set or used.")
+    }
+}
+
+impl std::error::Error for ExecutorAlreadySet {}
+
+static EXECUTOR: OnceCell<Box<dyn AsyncExecutor>> = OnceCell::new();
+static DEFAULT_RUNTIME: OnceCell<DefaultRuntime> = OnceCell::new();
+
+/// Use a custom executor to run async Rust functions that are called from Swift.
+///
+/// This must be called before Swift calls any async Rust functions, typically when your
+/// application starts.
+pub fn set_executor(executor: impl AsyncExecutor) -> Result<(), ExecutorAlreadySet> {
+    EXECUTOR
+        .set(Box::new(executor))
+        .map_err(|_| ExecutorAlreadySet)
+}
+
+/// Configure the Tokio runtime that runs async Rust functions when no custom executor has been
+/// set.
+///
+/// This must be called before Swift calls any async Rust functions.
+pub fn configure_default_runtime(config: DefaultRuntimeConfig) -> Result<(), ExecutorAlreadySet> {
+    if EXECUTOR.get().is_some() {
+        return Err(ExecutorAlreadySet);
+    }
+
+    DEFAULT_RUNTIME
+        .set(DefaultRuntime::new(config))
+        .map_err(|_| ExecutorAlreadySet)
+}
+
+/// Shut down the default Tokio runtime, waiting up to `timeout` for its blocking tasks to finish.
+///
+/// Async Rust functions that are still running get dropped, and their Swift callers get a
+/// `CancellationError`. So do any async Rust functions that Swift calls after the shutdown.
+///
+/// This also stops the thread that runs `#[swift_bridge(local)]` async functions, which happens
+/// even if a custom executor was set using [`set_executor`].
+pub fn shutdown_default_runtime(timeout: Duration) {
+    if let Some(default_runtime) = DEFAULT_RUNTIME.get() {
+        let runtime = default_runtime.runtime.lock().unwrap().take();
+        if let Some(runtime) = runtime {
+            runtime.shutdown_timeout(timeout);
+        }
+    }
+
+    if let Some(local_executor) = LOCAL_EXECUTOR.get() {
+        local_executor.sender.lock().unwrap().take();
+    }
+}
+
+/// Configuration for the Tokio runtime that runs async Rust functions when no custom executor
+/// has been set.
+#[derive(Debug, Clone)]
+pub struct DefaultRuntimeConfig {
+    worker_threads: Option<usize>,
+    thread_name: String,
+}
+
+impl DefaultRuntimeConfig {
+    pub fn new() -> Self {
+        DefaultRuntimeConfig {
+            worker_threads: None,
+            thread_name: "swift-bridge-async".to_string(),
+        }
+    }
+
+    /// The number of worker threads. Defaults to the number of CPU cores.
+    pub fn worker_threads(mut self, worker_threads: usize) -> Self {
+        self.worker_threads = Some(worker_threads);
+        self
+    }
+
+    /// The name of the runtime's threads. Defaults to "swift-bridge-async".
+    pub fn thread_name(mut self, thread_name: impl Into<String>) -> Self {
+        self.thread_name = thread_name.into();
+        self
+    }
+}
+
+impl Default for DefaultRuntimeConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct DefaultRuntime {
+    runtime: Mutex<Option<tokio::runtime::Runtime>>,
+}
+
+impl DefaultRuntime {
+    fn new(config: DefaultRuntimeConfig) -> Self {
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        builder.enable_all().thread_name(config.thread_name);
+        if let Some(worker_threads) = config.worker_threads {
+            builder.worker_threads(worker_threads);
+        }
+
+        DefaultRuntime {
+            runtime: Mutex::new(Some(builder.build().unwrap())),
+        }
+    }
+}
+
+impl AsyncExecutor for DefaultRuntime {
+    fn spawn(&self, task: Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
+        let runtime = self.runtime.lock().unwrap();
+
+        // After a shutdown the task gets dropped, which cancels it.
+        if let Some(runtime) = runtime.as_ref() {
+            runtime.spawn(task);
+        }
+    }
+}
+
+struct DefaultExecutor;
+
+impl AsyncExecutor for DefaultExecutor {
+    fn spawn(&self, task: Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
+        DEFAULT_RUNTIME
+            .get_or_init(|| DefaultRuntime::new(DefaultRuntimeConfig::default()))
+            .spawn(task)
+    }
+}
+
+/// Spawn a task on the executor that was set using [`set_executor`], falling back to the default
+/// Tokio runtime.
 #[doc(hidden)]
-pub static ASYNC_RUNTIME: Lazy<TokioRuntime> = Lazy::new(|| {
-    let (sender, receiver) = std::sync::mpsc::sync_channel(10_000);
+pub fn spawn_task(task: AsyncFnToSpawn) {
+    EXECUTOR
+        .get_or_init(|| Box::new(DefaultExecutor))
+        .spawn(task)
+}
 
-    let runtime = TokioRuntime { sender };
+type LocalTaskToSpawn = Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()>>> + Send>;
 
-    runtime.start_runtime(receiver);
+static LOCAL_EXECUTOR: OnceCell<LocalExecutor> = OnceCell::new();
 
-    runtime
-});
-type AsyncFnToSpawn = Pin<Box<dyn Future<Output = ()> + 'static + Send>>;
+/// Runs `#[swift_bridge(local)]` async functions, whose futures do not need to be `Send`.
+///
+/// Every local task runs on the same dedicated thread, using a Tokio `LocalSet`.
+struct LocalExecutor {
+    sender: Mutex<Option<tokio::sync::mpsc::UnboundedSender<LocalTaskToSpawn>>>,
+    thread_id: std::thread::ThreadId,
+}
+
+impl LocalExecutor {
+    fn start() -> Self {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<LocalTaskToSpawn>();
+
+        let thread = std::thread::Builder::new()
+            .name("swift-bridge-local".to_string())
+            .spawn(move || {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .unwrap();
+                let local_set = tokio::task::LocalSet::new();
+
+                // Once the executor shuts down, dropping the `LocalSet` drops (and so cancels)
+                // any tasks that are still running.
+                local_set.block_on(&runtime, async move {
+                    while let Some(make_task) = receiver.recv().await {
+                        tokio::task::spawn_local(make_task());
+                    }
+                });
+            })
+            .unwrap();
+
+        LocalExecutor {
+            sender: Mutex::new(Some(sender)),
+            thread_id: thread.thread().id(),
+        }
+    }
+
+    /// Whether the current thread is the one that runs the local tasks.
+    fn is_current_thread() -> bool {
+        LOCAL_EXECUTOR
+            .get()
+            .is_some_and(|local_executor| local_executor.thread_id == std::thread::current().id())
+    }
+
+    /// Send a task to the local executor's thread, handing it back if the local executor has been
+    /// shut down.
+    fn send(make_task: LocalTaskToSpawn) -> Result<(), LocalTaskToSpawn> {
+        let local_executor = LOCAL_EXECUTOR.get_or_init(LocalExecutor::start);
+        let sender = local_executor.sender.lock().unwrap();
+
+        match sender.as_ref() {
+            Some(sender) => sender.send(make_task).map_err(|err| err.0),
+            None => Err(make_task),
+        }
+    }
+}
+
+/// Spawn a task that does not need to be `Send` on the local executor's thread.
+///
+/// `make_task` gets called on the local executor's thread, so only the values that it captures
+/// need to be `Send`.
+#[doc(hidden)]
+pub fn spawn_local_task<F, Fut>(make_task: F)
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + 'static,
+{
+    let make_task: LocalTaskToSpawn = Box::new(move || Box::pin(make_task()));
+
+    if let Err(make_task) = LocalExecutor::send(make_task) {
+        // The local executor has been shut down. Dropping the task lets Swift know that it was
+        // cancelled.
+        drop(make_task());
+    }
+}
+
+/// State that is only ever used on the thread that runs `#[swift_bridge(local)]` async functions,
+/// so it does not need to be thread-safe.
+///
+/// `LocalState` is `Send` and `Sync` whether or not its value is, which lets types that hold
+/// `Rc` or `RefCell` state have `#[swift_bridge(local)]` `&self` methods.
+///
+/// The value gets created the first time that it is used. Using it on any thread other than the
+/// local executor's thread panics.
+///
+/// ```no_run,ignore
+/// pub struct Counter {
+///     count: LocalState<Rc<RefCell<u32>>>,
+/// }
+///
+/// impl Counter {
+///     fn new() -> Self {
+///         Counter {
+///             count: LocalState::new(|| Rc::new(RefCell::new(0))),
+///         }
+///     }
+///
+///     // #[swift_bridge(local)]
+///     async fn increment(&self, amount: u32) -> u32 {
+///         let count = self.count.get().clone();
+///         *count.borrow_mut() += amount;
+///         let count = *count.borrow();
+///         count
+///     }
+/// }
+/// ```
+pub struct LocalState<T: 'static> {
+    init: Mutex<Option<Box<dyn FnOnce() -> T + Send>>>,
+    value: once_cell::unsync::OnceCell<T>,
+}
+
+// Safety: The value only gets created, borrowed and dropped on the local executor's thread.
+unsafe impl<T> Send for LocalState<T> {}
+unsafe impl<T> Sync for LocalState<T> {}
+
+impl<T> LocalState<T> {
+    /// Create the state, using `init` to create its value on the local executor's thread the
+    /// first time that it is used.
+    pub fn new(init: impl FnOnce() -> T + Send + 'static) -> Self {
+        LocalState {
+            init: Mutex::new(Some(Box::new(init))),
+            value: once_cell::unsync::OnceCell::new(),
+        }
+    }
+
+    /// Borrow the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from any thread other than the one that runs
+    /// `#[swift_bridge(local)]` async functions.
+    pub fn get(&self) -> &T {
+        assert!(
+            LocalExecutor::is_current_thread(),
+            "LocalState can only be used by #[swift_bridge(local)] async functions."
+        );
+
+        self.value.get_or_init(|| {
+            let init = self.init.lock().unwrap().take().unwrap();
+            init()
+        })
+    }
+}
+
+impl<T> Drop for LocalState<T> {
+    fn drop(&mut self) {
+        if LocalExecutor::is_current_thread() {
+            return;
+        }
+
+        // The value can only be dropped on the local executor's thread. If the local executor has
+        // been shut down we leak it instead.
+        if let Some(value) = self.value.take() {
+            let value = AssertSend(value);
+            let drop_value: LocalTaskToSpawn = Box::new(move || {
+                drop(value.into_inner());
+                Box::pin(async {})
+            });
+            if let Err(drop_value) = LocalExecutor::send(drop_value) {
+                std::mem::forget(drop_value);
+            }
+        }
+    }
+}
+
+/// Lets a value that is not `Send` be moved to the local executor's thread.
+///
+/// Used for the pointers that Swift passes to `#[swift_bridge(local)]` async functions, which
+/// only get used on the local executor's thread.
+#[doc(hidden)]
+pub struct AssertSend<T>(pub T);
+unsafe impl<T> Send for AssertSend<T> {}
 
 #[doc(hidden)]
-pub struct TokioRuntime {
-    sender: SyncSender<AsyncFnToSpawn>,
+impl<T> AssertSend<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
 }
 
+/// Fails to compile unless the type that a `#[swift_bridge(local)]` `&self` method is called on is
+/// `Sync`, since the method borrows it on the local executor's thread while Swift can keep using
+/// it on other threads.
+///
+/// Types can wrap their non thread-safe state in a [`LocalState`] to become `Sync`.
+#[doc(hidden)]
+pub fn assert_self_is_sync<T: Sync + ?Sized>(_this: *const T) {}
+
+/// Fails to compile unless the type that a `#[swift_bridge(local)]` `&mut self` or `self` method
+/// is called on is `Send`, since the method mutably borrows or takes it on the local executor's
+/// thread.
+#[doc(hidden)]
+pub fn assert_self_is_send<T: Send + ?Sized>(_this: *const T) {}
+
 // TODO: Audit to make sure that this is safe to be Send/Sync.
 //  Need to research Swift class thread safety. If there are cases where this can be unsafe then
 //  we can just have one tokio runtime per thread (lazily initialized) and then run async functions
@@ -32,21 +399,266 @@ pub struct SwiftCallbackWrapper(pub *mut std::ffi::c_void);
 unsafe impl Send for SwiftCallbackWrapper {}
 unsafe impl Sync for SwiftCallbackWrapper {}
 
+/// Lets Swift cancel the task that is running an async Rust function.
+///
+/// Swift creates a handle before calling the async Rust function, and cancels it when the Swift
+/// `Task` that is awaiting the function gets cancelled.
 #[doc(hidden)]
-impl TokioRuntime {
-    pub fn spawn_task(&self, task: AsyncFnToSpawn) {
-        self.sender.send(task).unwrap();
+pub struct AsyncTaskHandle {
+    cancelled: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl AsyncTaskHandle {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+
+        let waker = self.waker.lock().unwrap().take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
     }
 
-    fn start_runtime(&self, receiver: Receiver<AsyncFnToSpawn>) {
-        std::thread::spawn(move || {
-            tokio::runtime::Runtime::new()
-                .unwrap()
-                .block_on(async move {
-                    while let Ok(task) = receiver.recv() {
-                        tokio::spawn(task);
-                    }
-                })
-        });
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+#[doc(hidden)]
+#[allow(non_snake_case)]
+#[export_name = "__swift_bridge__$AsyncTaskHandle$new"]
+pub extern "C" fn __swift_bridge__AsyncTaskHandle_new() -> *const AsyncTaskHandle {
+    Arc::into_raw(Arc::new(AsyncTaskHandle {
+        cancelled: AtomicBool::new(false),
+        waker: Mutex::new(None),
+    }))
+}
+
+#[doc(hidden)]
+#[allow(non_snake_case)]
+#[export_name = "__swift_bridge__$AsyncTaskHandle$cancel"]
+pub unsafe extern "C" fn __swift_bridge__AsyncTaskHandle_cancel(handle: *const AsyncTaskHandle) {
+    (*handle).cancel()
+}
+
+#[doc(hidden)]
+#[allow(non_snake_case)]
+#[export_name = "__swift_bridge__$AsyncTaskHandle$_free"]
+pub unsafe extern "C" fn __swift_bridge__AsyncTaskHandle__free(handle: *const AsyncTaskHandle) {
+    drop(Arc::from_raw(handle))
+}
+
+/// Runs an async Rust function's task until it completes or Swift cancels it.
+///
+/// When cancelled, the task gets dropped and Swift's `on_cancel` callback gets called instead of
+/// the task's completion callback.
+#[doc(hidden)]
+pub struct CancellableTask<F = AsyncFnToSpawn> {
+    task: Option<F>,
+    handle: Arc<AsyncTaskHandle>,
+    callback_wrapper: SwiftCallbackWrapper,
+    on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
+}
+
+#[doc(hidden)]
+impl<F> CancellableTask<F> {
+    /// # Safety
+    ///
+    /// `handle` must have been created by `__swift_bridge__$AsyncTaskHandle$new` and not yet
+    /// freed.
+    pub unsafe fn new(
+        handle: *const AsyncTaskHandle,
+        task: F,
+        callback_wrapper: *mut std::ffi::c_void,
+        on_cancel: extern "C" fn(*mut std::ffi::c_void) -> (),
+    ) -> Self {
+        // Swift holds on to its own reference to the handle until the async function returns.
+        Arc::increment_strong_count(handle);
+        let handle = Arc::from_raw(handle);
+
+        CancellableTask {
+            task: Some(task),
+            handle,
+            callback_wrapper: SwiftCallbackWrapper(callback_wrapper),
+            on_cancel,
+        }
+    }
+}
+
+impl<F: Future<Output = ()> + Unpin> Future for CancellableTask<F> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        *self.handle.waker.lock().unwrap() = Some(cx.waker().clone());
+
+        if self.handle.is_cancelled() {
+            if self.task.take().is_some() {
+                (self.on_cancel)(self.callback_wrapper.0);
+            }
+            return Poll::Ready(());
+        }
+
+        let poll = match self.task.as_mut() {
+            Some(task) => Pin::new(task).poll(cx),
+            None => Poll::Ready(()),
+        };
+        if poll.is_ready() {
+            self.task = None;
+        }
+
+        poll
+    }
+}
+
+impl<F> Drop for CancellableTask<F> {
+    fn drop(&mut self) {
+        // The executor dropped the task before it completed, such as when the runtime was shut
+        // down, so Swift would otherwise wait forever.
+        if self.task.take().is_some() {
+            (self.on_cancel)(self.callback_wrapper.0);
+        }
+    }
+}
+
+/// Resolves to the panic's payload if the future panics, for bridge modules that use
+/// `#[swift_bridge::bridge(catch_panics)]`.
+#[doc(hidden)]
+pub struct CatchUnwind<F> {
+    fut: Pin<Box<F>>,
+}
+
+#[doc(hidden)]
+impl<F: Future> CatchUnwind<F> {
+    pub fn new(fut: F) -> Self {
+        CatchUnwind { fut: Box::pin(fut) }
+    }
+}
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match catch_unwind(AssertUnwindSafe(|| self.fut.as_mut().poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
+
+/// A future that resolves when Swift calls back with the output of an async Swift function.
+#[doc(hidden)]
+pub struct SwiftFuture<T> {
+    state: Arc<Mutex<SwiftFutureState<T>>>,
+}
+
+struct SwiftFutureState<T> {
+    output: Option<T>,
+    waker: Option<Waker>,
+}
+
+#[doc(hidden)]
+impl<T> SwiftFuture<T> {
+    /// Create a future along with the callback wrapper pointer that gets passed to Swift.
+    ///
+    /// Swift hands the pointer back to Rust when the async function completes, at which point
+    /// it gets passed to [`SwiftFuture::complete`].
+    pub fn new() -> (Self, *mut std::ffi::c_void) {
+        let state = Arc::new(Mutex::new(SwiftFutureState {
+            output: None,
+            waker: None,
+        }));
+        let callback_wrapper = Arc::into_raw(state.clone()) as *mut std::ffi::c_void;
+
+        (SwiftFuture { state }, callback_wrapper)
+    }
+
+    /// Resolve the future that the callback wrapper belongs to.
+    ///
+    /// # Safety
+    ///
+    /// The callback wrapper must have been created by `SwiftFuture::<T>::new`, and can only be
+    /// completed once.
+    pub unsafe fn complete(callback_wrapper: *mut std::ffi::c_void, output: T) {
+        let state = Arc::from_raw(callback_wrapper as *const Mutex<SwiftFutureState<T>>);
+
+        let waker = {
+            let mut state = state.lock().unwrap();
+            state.output = Some(output);
+            state.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Future for SwiftFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+
+        match state.output.take() {
+            Some(output) => Poll::Ready(output),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// A stream returned by an extern "Rust" function, which Swift consumes as an
+/// `AsyncThrowingStream`.
+///
+/// Swift requests one item at a time, so the stream only gets polled while Swift is waiting for
+/// its next item.
+#[doc(hidden)]
+pub struct RustStream<T> {
+    stream: Pin<Box<dyn Stream<Item = T> + Send>>,
+}
+
+#[doc(hidden)]
+impl<T> RustStream<T> {
+    pub fn new(stream: impl Stream<Item = T> + Send + 'static) -> Self {
+        RustStream {
+            stream: Box::pin(stream),
+        }
+    }
+
+    pub fn box_into_raw(self) -> *mut Self {
+        Box::into_raw(Box::new(self))
+    }
+
+    /// Get a future that resolves to the stream's next item.
+    ///
+    /// # Safety
+    ///
+    /// The stream must not be freed or polled for another item until the returned future has
+    /// completed or been dropped.
+    pub unsafe fn next(this: *mut Self) -> RustStreamNext<T> {
+        RustStreamNext { stream: this }
+    }
+}
+
+/// Resolves to the next item of a [`RustStream`].
+#[doc(hidden)]
+pub struct RustStreamNext<T> {
+    stream: *mut RustStream<T>,
+}
+
+// Safety: `RustStream::next` requires that the stream outlives the future and is not accessed
+// elsewhere in the meantime, the stream itself is `Send`, and the items that the future resolves
+// to are `Send`.
+unsafe impl<T> Send for RustStreamNext<T> where T: Send {}
+
+impl<T> Future for RustStreamNext<T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = self.stream;
+        unsafe { &mut *stream }.stream.as_mut().poll_next(cx)
     }
 }
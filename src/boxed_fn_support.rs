@@ -15,3 +15,69 @@ pub extern "C" fn __swift_bridge__free_boxed_fn_once_no_args_no_return(
         let _ = Box::from_raw(boxed_fn);
     }
 }
+
+/// A Swift closure that was passed to Rust as a `Box<dyn Fn(A, B) -> C>`.
+///
+/// Swift retains the closure and passes us a pointer to it. We release the closure when this
+/// is dropped.
+///
+/// Swift closures are not necessarily safe to call from other threads, so this is neither `Send`
+/// nor `Sync`. See [`SendableSwiftCallback`] for closures that are.
+pub struct SwiftCallback {
+    ptr: *mut std::ffi::c_void,
+    free: unsafe extern "C" fn(*mut std::ffi::c_void),
+}
+
+impl SwiftCallback {
+    /// Take ownership of a retained Swift closure. `free` releases the closure.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a retained Swift closure, and `free` must release it. The closure is
+    /// released when this is dropped, so nothing else may release it.
+    pub unsafe fn new(
+        ptr: *mut std::ffi::c_void,
+        free: unsafe extern "C" fn(*mut std::ffi::c_void),
+    ) -> Self {
+        SwiftCallback { ptr, free }
+    }
+
+    /// A pointer to the retained Swift closure.
+    pub fn as_ptr(&self) -> *mut std::ffi::c_void {
+        self.ptr
+    }
+}
+
+impl Drop for SwiftCallback {
+    fn drop(&mut self) {
+        unsafe { (self.free)(self.ptr) }
+    }
+}
+
+/// A `@Sendable` Swift closure that was passed to Rust as a `Box<dyn Fn(A, B) -> C + Send>`.
+///
+/// Swift checks that `@Sendable` closures are safe to call from any thread, so this is `Send`
+/// and `Sync`.
+pub struct SendableSwiftCallback(SwiftCallback);
+
+impl SendableSwiftCallback {
+    /// Take ownership of a retained `@Sendable` Swift closure. `free` releases the closure.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as [`SwiftCallback::new`], and the closure must be `@Sendable`.
+    pub unsafe fn new(
+        ptr: *mut std::ffi::c_void,
+        free: unsafe extern "C" fn(*mut std::ffi::c_void),
+    ) -> Self {
+        SendableSwiftCallback(SwiftCallback::new(ptr, free))
+    }
+
+    /// A pointer to the retained Swift closure.
+    pub fn as_ptr(&self) -> *mut std::ffi::c_void {
+        self.0.as_ptr()
+    }
+}
+
+unsafe impl Send for SendableSwiftCallback {}
+unsafe impl Sync for SendableSwiftCallback {}
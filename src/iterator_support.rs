@@ -0,0 +1,29 @@
+/// An iterator returned by an extern "Rust" function, which Swift consumes as a `Sequence`.
+///
+/// Swift requests one item at a time, so the iterator only advances when Swift asks for its next
+/// item.
+#[doc(hidden)]
+pub struct RustIterator<T> {
+    iter: Box<dyn Iterator<Item = T>>,
+}
+
+#[doc(hidden)]
+impl<T> RustIterator<T> {
+    pub fn new(iter: impl Iterator<Item = T> + 'static) -> Self {
+        RustIterator {
+            iter: Box::new(iter),
+        }
+    }
+
+    pub fn box_into_raw(self) -> *mut Self {
+        Box::into_raw(Box::new(self))
+    }
+}
+
+impl<T> Iterator for RustIterator<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
+    }
+}
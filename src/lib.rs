@@ -18,6 +18,15 @@ pub mod boxed_fn_support;
 #[doc(hidden)]
 pub mod copy_support;
 
+#[doc(hidden)]
+pub mod iterator_support;
+
+#[doc(hidden)]
+pub mod panic_support;
+
+#[doc(hidden)]
+pub mod trait_object_support;
+
 #[doc(hidden)]
 #[repr(C)]
 pub struct FfiSlice<T> {
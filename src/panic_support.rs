@@ -0,0 +1,89 @@
+//! Catches panics in bridge modules that use `#[swift_bridge::bridge(catch_panics)]`, so that
+//! they don't unwind into Swift.
+//!
+//! The corresponding C and Swift code can be found in
+//! crates/swift-bridge-build/src/generate_core/panic_support.rs
+
+use crate::string::RustString;
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Mutex;
+
+thread_local! {
+    /// The message of a panic that was caught in a function that throws in Swift.
+    /// Swift takes it right after calling the function and throws it as a `RustPanic`.
+    static CAUGHT_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Swift's panic handler, registered using `RustPanic.setHandler`.
+static PANIC_HANDLER: Mutex<Option<extern "C" fn(*mut RustString)>> = Mutex::new(None);
+
+/// Call a function that can't throw in Swift, aborting if it panics.
+///
+/// The panic message gets passed to Swift's panic handler before aborting.
+pub fn call_or_abort<T>(f: impl FnOnce() -> T) -> T {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(val) => val,
+        Err(payload) => {
+            let message = panic_message(payload);
+
+            let handler = *PANIC_HANDLER.lock().unwrap();
+            if let Some(handler) = handler {
+                handler(RustString(message).box_into_raw());
+            }
+
+            std::process::abort()
+        }
+    }
+}
+
+/// Call a function that throws in Swift, storing the panic message if it panics so that Swift
+/// can throw it as a `RustPanic`.
+///
+/// # Safety
+///
+/// `T` must be an FFI type that is valid when zeroed. Swift receives a zeroed value when the
+/// function panics, which it discards.
+pub unsafe fn call_or_store_panic<T>(f: impl FnOnce() -> T) -> T {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(val) => val,
+        Err(payload) => {
+            store_panic(payload);
+            std::mem::zeroed()
+        }
+    }
+}
+
+/// Store a caught panic until Swift takes it.
+pub fn store_panic(payload: Box<dyn Any + Send>) {
+    let message = panic_message(payload);
+    CAUGHT_PANIC.with(|caught| *caught.borrow_mut() = Some(message));
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+#[doc(hidden)]
+#[allow(non_snake_case)]
+#[export_name = "__swift_bridge__$RustPanic$take"]
+pub extern "C" fn __swift_bridge__RustPanic_take() -> *mut RustString {
+    match CAUGHT_PANIC.with(|caught| caught.borrow_mut().take()) {
+        Some(message) => RustString(message).box_into_raw(),
+        None => std::ptr::null_mut(),
+    }
+}
+
+#[doc(hidden)]
+#[allow(non_snake_case)]
+#[export_name = "__swift_bridge__$RustPanic$set_handler"]
+pub extern "C" fn __swift_bridge__RustPanic_set_handler(handler: extern "C" fn(*mut RustString)) {
+    *PANIC_HANDLER.lock().unwrap() = Some(handler);
+}
@@ -44,3 +44,43 @@ pub struct ResultPtrAndPtr {
     pub is_ok: bool,
     pub ok_or_err: *mut std::ffi::c_void,
 }
+
+/// An error that was thrown by a Swift function.
+///
+/// Use `SwiftError` as the error type of an `extern "Swift"` function's `Result` in order to
+/// receive any Swift `Error`.
+///
+/// ```no_run,ignore
+/// #[swift_bridge::bridge]
+/// mod ffi {
+///     extern "Swift" {
+///         fn load_config(path: &str) -> Result<String, SwiftError>;
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwiftError {
+    localized_description: String,
+}
+
+impl SwiftError {
+    #[doc(hidden)]
+    pub fn new(localized_description: String) -> Self {
+        SwiftError {
+            localized_description,
+        }
+    }
+
+    /// The Swift error's `localizedDescription`.
+    pub fn localized_description(&self) -> &str {
+        &self.localized_description
+    }
+}
+
+impl std::fmt::Display for SwiftError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.localized_description)
+    }
+}
+
+impl std::error::Error for SwiftError {}
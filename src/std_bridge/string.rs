@@ -22,6 +22,9 @@ mod ffi {
 }
 
 #[doc(hidden)]
+// `repr(transparent)` lets us hand out a `&String` that lives inside of a Rust collection
+// (such as a `HashMap<String, V>`) to Swift as a `RustStringRef`.
+#[repr(transparent)]
 pub struct RustString(pub String);
 
 #[doc(hidden)]
@@ -0,0 +1,80 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+/// A `Box<dyn Trait>` or `Arc<dyn Trait>` that was passed to Swift.
+///
+/// Trait objects are fat pointers, so we can't pass them over FFI directly. Instead we box a
+/// `TraitObject` and pass Swift a thin pointer to it.
+pub enum TraitObject<T: ?Sized> {
+    /// A `Box<dyn Trait>`.
+    Boxed(Box<T>),
+    /// An `Arc<dyn Trait>`.
+    Arc(Arc<T>),
+}
+
+impl<T: ?Sized> TraitObject<T> {
+    /// Get back the `Box<dyn Trait>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this trait object was created from an `Arc<dyn Trait>`, since we can't move
+    /// a value out of an `Arc`.
+    ///
+    /// The bridge macro rejects modules that take a `Box<dyn Trait>` from Swift when they also
+    /// pass Swift an `Arc<dyn Trait>`, so generated code never hits this.
+    pub fn into_box(self) -> Box<T> {
+        match self {
+            TraitObject::Boxed(boxed) => boxed,
+            TraitObject::Arc(_) => {
+                panic!("An Arc<dyn Trait> cannot be converted into a Box<dyn Trait>.")
+            }
+        }
+    }
+
+    /// Get back the `Arc<dyn Trait>`. A `Box<dyn Trait>` gets moved into a new `Arc`.
+    pub fn into_arc(self) -> Arc<T> {
+        match self {
+            TraitObject::Boxed(boxed) => Arc::from(boxed),
+            TraitObject::Arc(arc) => arc,
+        }
+    }
+}
+
+impl<T: ?Sized> From<Box<T>> for TraitObject<T> {
+    fn from(boxed: Box<T>) -> Self {
+        TraitObject::Boxed(boxed)
+    }
+}
+
+impl<T: ?Sized> From<Arc<T>> for TraitObject<T> {
+    fn from(arc: Arc<T>) -> Self {
+        TraitObject::Arc(arc)
+    }
+}
+
+impl<T: ?Sized> Deref for TraitObject<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            TraitObject::Boxed(boxed) => boxed,
+            TraitObject::Arc(arc) => arc,
+        }
+    }
+}
+
+impl<T: ?Sized> DerefMut for TraitObject<T> {
+    /// # Panics
+    ///
+    /// Panics if this is an `Arc<dyn Trait>` that has other strong or weak references.
+    ///
+    /// The bridge macro rejects `&mut self` methods and `&mut dyn Trait` arguments for trait
+    /// objects that are passed to Swift as an `Arc<dyn Trait>`, so generated code never hits this.
+    fn deref_mut(&mut self) -> &mut T {
+        match self {
+            TraitObject::Boxed(boxed) => boxed,
+            TraitObject::Arc(arc) => Arc::get_mut(arc)
+                .expect("Cannot mutably borrow an Arc<dyn Trait> that has other references."),
+        }
+    }
+}